@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+/// The options that control how rsx is laid out.
+///
+/// This is read from the `[fmt]` section of `Dioxus.toml` or from a `dxfmt.toml` file:
+///
+/// ```toml
+/// [fmt]
+/// max_width = 100
+/// attribute_order = "class-id-first"
+/// trailing_comma = "always"
+/// shorthand_placement = "first"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FmtConfig {
+    /// The maximum width of a line of attributes and of an rsx block that is collapsed onto a single line
+    pub max_width: usize,
+
+    /// The maximum width of an element that is written on a single line along with its children
+    pub single_line_width: usize,
+
+    /// The maximum number of attributes an element can have before each attribute is put on its own line
+    pub single_line_attributes: usize,
+
+    /// The order attributes are written in
+    pub attribute_order: AttributeOrder,
+
+    /// When to write a comma after the last attribute of an element
+    pub trailing_comma: TrailingComma,
+
+    /// Where shorthand attributes like `class` in `div { class }` are placed among the other attributes
+    pub shorthand_placement: ShorthandPlacement,
+}
+
+impl Default for FmtConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 80,
+            single_line_width: 100,
+            single_line_attributes: 3,
+            attribute_order: AttributeOrder::default(),
+            trailing_comma: TrailingComma::default(),
+            shorthand_placement: ShorthandPlacement::default(),
+        }
+    }
+}
+
+/// The order attributes are written in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AttributeOrder {
+    /// Keep attributes in the order they were written
+    #[default]
+    Preserve,
+
+    /// Sort attributes by name
+    Alphabetical,
+
+    /// Move `class` and `id` to the front and keep the rest in the order they were written
+    ClassIdFirst,
+}
+
+/// When to write a comma after the last attribute of an element.
+///
+/// The last attribute always needs a comma if the element has children.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrailingComma {
+    /// Only write the comma when the attributes are split across several lines
+    #[default]
+    Vertical,
+
+    /// Always write the comma
+    Always,
+
+    /// Never write the comma
+    Never,
+}
+
+/// Where shorthand attributes are placed among the other attributes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ShorthandPlacement {
+    /// Keep shorthand attributes where they were written
+    #[default]
+    Preserve,
+
+    /// Move shorthand attributes before the other attributes
+    First,
+
+    /// Move shorthand attributes after the other attributes
+    Last,
+}
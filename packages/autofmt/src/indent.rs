@@ -1,3 +1,5 @@
+use crate::FmtConfig;
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum IndentType {
     Spaces,
@@ -9,6 +11,7 @@ pub struct IndentOptions {
     width: usize,
     indent_string: String,
     split_line_attributes: bool,
+    config: FmtConfig,
 }
 
 impl IndentOptions {
@@ -21,9 +24,16 @@ impl IndentOptions {
                 IndentType::Spaces => " ".repeat(width),
             },
             split_line_attributes,
+            config: FmtConfig::default(),
         }
     }
 
+    /// Use the line widths, attribute order and comma style from a `[fmt]` config
+    pub fn with_config(mut self, config: FmtConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Gets a string containing one indent worth of whitespace
     pub fn indent_str(&self) -> &str {
         &self.indent_string
@@ -68,6 +78,10 @@ impl IndentOptions {
     pub fn split_line_attributes(&self) -> bool {
         self.split_line_attributes
     }
+
+    pub fn config(&self) -> &FmtConfig {
+        &self.config
+    }
 }
 
 impl Default for IndentOptions {
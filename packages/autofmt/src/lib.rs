@@ -9,10 +9,12 @@ use syn::parse::Parser;
 
 mod buffer;
 mod collect_macros;
+mod config;
 mod indent;
 mod prettier_please;
 mod writer;
 
+pub use config::{AttributeOrder, FmtConfig, ShorthandPlacement, TrailingComma};
 pub use indent::{IndentOptions, IndentType};
 
 /// A modification to the original file to be applied by an IDE
@@ -119,7 +121,7 @@ pub fn try_fmt_file(
             && matches!(body.body.roots[0], BodyNode::RawExpr(_) | BodyNode::Text(_));
 
         // If it's short, and it's not a single expression, and it's not empty, then we can collapse it
-        if formatted.len() <= 80
+        if formatted.len() <= writer.out.indent.config().max_width
             && !formatted.contains('\n')
             && !body_is_solo_expr
             && !formatted.trim().is_empty()
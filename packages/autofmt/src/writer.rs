@@ -1,4 +1,4 @@
-use crate::{buffer::Buffer, IndentOptions};
+use crate::{buffer::Buffer, AttributeOrder, IndentOptions, ShorthandPlacement, TrailingComma};
 use dioxus_rsx::*;
 use proc_macro2::{LineColumn, Span};
 use quote::ToTokens;
@@ -313,7 +313,8 @@ impl<'a> Writer<'a> {
 
         // check if we have a lot of attributes
         let attr_len = self.is_short_attrs(attributes, spreads);
-        let is_short_attr_list = (attr_len + self.out.indent_level * 4) < 80;
+        let is_short_attr_list =
+            (attr_len + self.out.indent_level * 4) < self.out.indent.config().max_width;
         let children_len = self
             .is_short_children(children)
             .map_err(|_| std::fmt::Error)?;
@@ -341,7 +342,9 @@ impl<'a> Writer<'a> {
 
         // if we have few children and few attributes, make it a one-liner
         if is_short_attr_list && is_small_children {
-            if children_len.unwrap() + attr_len + self.out.indent_level * 4 < 100 {
+            if children_len.unwrap() + attr_len + self.out.indent_level * 4
+                < self.out.indent.config().single_line_width
+            {
                 opt_level = ShortOptimization::Oneliner;
             } else {
                 opt_level = ShortOptimization::PropsOnTop;
@@ -450,14 +453,13 @@ impl<'a> Writer<'a> {
             Spread(&'a Spread),
         }
 
-        let mut attr_iter = attributes
-            .iter()
+        let mut attr_iter = self
+            .ordered_attributes(attributes)
+            .into_iter()
             .map(AttrType::Attr)
             .chain(spreads.iter().map(AttrType::Spread))
             .peekable();
 
-        let has_attributes = !attributes.is_empty() || !spreads.is_empty();
-
         while let Some(attr) = attr_iter.next() {
             self.out.indent_level += 1;
 
@@ -492,7 +494,13 @@ impl<'a> Writer<'a> {
             };
 
             let has_more = attr_iter.peek().is_some();
-            let should_finish_comma = has_attributes && has_children || !props_same_line;
+            // The last attribute always needs a comma to separate it from the children
+            let should_finish_comma = has_children
+                || match self.out.indent.config().trailing_comma {
+                    TrailingComma::Vertical => !props_same_line,
+                    TrailingComma::Always => true,
+                    TrailingComma::Never => false,
+                };
 
             if has_more || should_finish_comma {
                 write!(self.out, ",")?;
@@ -518,6 +526,34 @@ impl<'a> Writer<'a> {
         Ok(())
     }
 
+    /// Sort the attributes by the attribute order and shorthand placement in the config
+    ///
+    /// The sort is stable, so attributes that compare equal stay in the order they were written
+    fn ordered_attributes<'b>(&self, attributes: &'b [Attribute]) -> Vec<&'b Attribute> {
+        let config = self.out.indent.config();
+        let mut ordered = attributes.iter().collect::<Vec<_>>();
+
+        match config.attribute_order {
+            AttributeOrder::Preserve => {}
+            AttributeOrder::Alphabetical => ordered.sort_by_cached_key(|attr| attr_name(attr)),
+            AttributeOrder::ClassIdFirst => {
+                ordered.sort_by_key(|attr| match attr_name(attr).as_str() {
+                    "class" => 0,
+                    "id" => 1,
+                    _ => 2,
+                })
+            }
+        }
+
+        match config.shorthand_placement {
+            ShorthandPlacement::Preserve => {}
+            ShorthandPlacement::First => ordered.sort_by_key(|attr| !attr.can_be_shorthand()),
+            ShorthandPlacement::Last => ordered.sort_by_key(|attr| attr.can_be_shorthand()),
+        }
+
+        ordered
+    }
+
     fn write_attribute(&mut self, attr: &Attribute) -> Result {
         self.write_attribute_name(&attr.name)?;
 
@@ -717,8 +753,8 @@ impl<'a> Writer<'a> {
     fn is_short_attrs(&mut self, attributes: &[Attribute], spreads: &[Spread]) -> usize {
         let mut total = 0;
 
-        // No more than 3 attributes before breaking the line
-        if attributes.len() > 3 {
+        // Only a few attributes before breaking the line
+        if attributes.len() > self.out.indent.config().single_line_attributes {
             return 100000;
         }
 
@@ -1124,3 +1160,12 @@ impl<'a> Writer<'a> {
         false
     }
 }
+
+/// The name of an attribute as it is written in rsx
+fn attr_name(attr: &Attribute) -> String {
+    match &attr.name {
+        AttributeName::BuiltIn(name) => name.to_string(),
+        AttributeName::Custom(name) => name.value(),
+        AttributeName::Spread(_) => String::new(),
+    }
+}
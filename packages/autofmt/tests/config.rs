@@ -0,0 +1,49 @@
+use dioxus_autofmt::{AttributeOrder, FmtConfig, IndentOptions, ShorthandPlacement, TrailingComma};
+
+macro_rules! twoway {
+    ($val:literal => $name:ident ($config:expr)) => {
+        #[test]
+        fn $name() {
+            let src_right = include_str!(concat!("./config/", $val, ".rsx"));
+            let src_wrong = include_str!(concat!("./config/", $val, ".wrong.rsx"));
+
+            let parsed = syn::parse_file(src_wrong)
+                .expect("fmt_file should only be called on valid syn::File files");
+
+            let indent = IndentOptions::default().with_config($config);
+            let formatted =
+                dioxus_autofmt::try_fmt_file(src_wrong, &parsed, indent).unwrap_or_default();
+            let out = dioxus_autofmt::apply_formats(src_wrong, formatted);
+
+            // normalize line endings
+            let out = out.replace("\r", "");
+            let src_right = src_right.replace("\r", "");
+
+            pretty_assertions::assert_eq!(&src_right, &out);
+        }
+    };
+}
+
+twoway!("alphabetical" => alphabetical (FmtConfig {
+    attribute_order: AttributeOrder::Alphabetical,
+    ..Default::default()
+}));
+
+twoway!("class-id-first" => class_id_first (FmtConfig {
+    attribute_order: AttributeOrder::ClassIdFirst,
+    shorthand_placement: ShorthandPlacement::Last,
+    trailing_comma: TrailingComma::Never,
+    ..Default::default()
+}));
+
+twoway!("trailing-comma" => trailing_comma (FmtConfig {
+    single_line_attributes: 5,
+    trailing_comma: TrailingComma::Always,
+    ..Default::default()
+}));
+
+twoway!("max-width" => max_width (FmtConfig {
+    max_width: 120,
+    single_line_width: 140,
+    ..Default::default()
+}));
@@ -1,18 +1,27 @@
 use std::path::PathBuf;
 
+use dioxus_rsx::{BodyNode, CallBody, IfChain};
+use proc_macro2::TokenTree;
+use quote::ToTokens;
 use syn::{spanned::Spanned, visit::Visit, Pat};
 
 use crate::{
+    config::CheckConfig,
     issues::{Issue, IssueReport},
     metadata::{
-        AnyLoopInfo, AsyncInfo, ClosureInfo, ComponentInfo, ConditionalInfo, FnInfo, ForInfo,
-        HookInfo, IfInfo, LoopInfo, MatchInfo, Span, WhileInfo,
+        AnyLoopInfo, AsyncInfo, AwaitInfo, ClosureInfo, ComponentInfo, ConditionalInfo,
+        ElementInfo, FnInfo, ForInfo, GuardInfo, HookInfo, IfInfo, LoopInfo, MatchInfo, ReadInfo,
+        Span, SpawnInfo, WhileInfo,
     },
 };
 
 struct VisitHooks {
     issues: Vec<Issue>,
     context: Vec<Node>,
+    /// Whether each function in the context is async
+    async_fns: Vec<bool>,
+    /// The non-reactive props of the component we are currently in
+    props: Vec<String>,
 }
 
 impl VisitHooks {
@@ -20,12 +29,23 @@ impl VisitHooks {
         Self {
             issues: vec![],
             context: vec![],
+            async_fns: vec![],
+            props: vec![],
         }
     }
 }
 
 /// Checks a Dioxus file for issues.
 pub fn check_file(path: PathBuf, file_content: &str) -> IssueReport {
+    check_file_with_config(path, file_content, &CheckConfig::default())
+}
+
+/// Checks a Dioxus file for issues, only reporting the rules that are not allowed in the config.
+pub fn check_file_with_config(
+    path: PathBuf,
+    file_content: &str,
+    config: &CheckConfig,
+) -> IssueReport {
     let file = syn::parse_file(file_content).unwrap();
     let mut visit_hooks = VisitHooks::new();
     visit_hooks.visit_file(&file);
@@ -35,6 +55,7 @@ pub fn check_file(path: PathBuf, file_content: &str) -> IssueReport {
         file_content.to_string(),
         visit_hooks.issues,
     )
+    .with_config(config.clone())
 }
 
 #[allow(unused)]
@@ -96,6 +117,481 @@ fn fn_name_and_name_span(item_fn: &syn::ItemFn) -> (String, Span) {
     (name, name_span)
 }
 
+/// Hooks that rerun when the signals they read change, but not when non-reactive values change
+fn is_reactive_hook_ident(ident: &syn::Ident) -> bool {
+    ident == "use_effect" || ident == "use_memo" || ident == "use_resource"
+}
+
+/// Props of these types are already reactive or don't need to trigger reactive hooks when they change
+fn is_reactive_type(ty: &syn::Type) -> bool {
+    const REACTIVE_TYPES: &[&str] = &[
+        "Signal",
+        "ReadOnlySignal",
+        "ReadSignal",
+        "WriteSignal",
+        "SyncSignal",
+        "Memo",
+        "Resource",
+        "Store",
+        "EventHandler",
+        "Callback",
+        "Coroutine",
+        "Element",
+    ];
+    let syn::Type::Path(path) = ty else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+    if segment.ident == "Option" {
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                return is_reactive_type(inner);
+            }
+        }
+    }
+    REACTIVE_TYPES.iter().any(|name| segment.ident == name)
+}
+
+/// The props of a component that are not reactive
+fn non_reactive_props(item_fn: &syn::ItemFn) -> Vec<String> {
+    item_fn
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            syn::FnArg::Typed(pat_type) if !is_reactive_type(&pat_type.ty) => {
+                match pat_type.pat.as_ref() {
+                    Pat::Ident(ident) => Some(ident.ident.to_string()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Finds every read of a prop inside a closure
+struct FindReads<'a> {
+    props: &'a [String],
+    /// The variables bound inside the closure that are currently in scope. They shadow props with the same name
+    locals: Vec<String>,
+    reads: Vec<ReadInfo>,
+}
+
+impl FindReads<'_> {
+    fn push(&mut self, ident: &syn::Ident) {
+        if self.props.iter().any(|prop| ident == prop)
+            && !self.locals.iter().any(|local| ident == local)
+            && !self.reads.iter().any(|read| ident == &read.name)
+        {
+            self.reads
+                .push(ReadInfo::new(ident.span().into(), ident.to_string()));
+        }
+    }
+
+    fn bind(&mut self, pat: &syn::Pat) {
+        self.locals.extend(bindings(pat));
+    }
+
+    /// Visit code in a new scope, so the variables it binds are forgotten afterwards
+    fn scoped(&mut self, visit: impl FnOnce(&mut Self)) {
+        let locals = self.locals.len();
+        visit(self);
+        self.locals.truncate(locals);
+    }
+}
+
+impl<'ast> syn::visit::Visit<'ast> for FindReads<'_> {
+    fn visit_expr_path(&mut self, i: &'ast syn::ExprPath) {
+        if i.qself.is_none() {
+            if let Some(ident) = i.path.get_ident() {
+                self.push(ident);
+            }
+        }
+        syn::visit::visit_expr_path(self, i);
+    }
+
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        for ident in idents(i.tokens.clone()) {
+            self.push(&ident);
+        }
+    }
+
+    fn visit_block(&mut self, i: &'ast syn::Block) {
+        self.scoped(|this| {
+            for stmt in &i.stmts {
+                match stmt {
+                    // The variable is only in scope after its initializer
+                    syn::Stmt::Local(local) => {
+                        if let Some(init) = &local.init {
+                            this.visit_local_init(init);
+                        }
+                        this.bind(&local.pat);
+                    }
+                    stmt => this.visit_stmt(stmt),
+                }
+            }
+        });
+    }
+
+    fn visit_expr_closure(&mut self, i: &'ast syn::ExprClosure) {
+        self.scoped(|this| {
+            for input in &i.inputs {
+                this.bind(input);
+            }
+            this.visit_expr(&i.body);
+        });
+    }
+
+    fn visit_expr_for_loop(&mut self, i: &'ast syn::ExprForLoop) {
+        self.visit_expr(&i.expr);
+        self.scoped(|this| {
+            this.bind(&i.pat);
+            this.visit_block(&i.body);
+        });
+    }
+
+    fn visit_arm(&mut self, i: &'ast syn::Arm) {
+        self.scoped(|this| {
+            this.bind(&i.pat);
+            if let Some((_, guard)) = &i.guard {
+                this.visit_expr(guard);
+            }
+            this.visit_expr(&i.body);
+        });
+    }
+
+    fn visit_expr_if(&mut self, i: &'ast syn::ExprIf) {
+        self.scoped(|this| {
+            // The bindings of `if let` are only in scope in the then branch
+            match i.cond.as_ref() {
+                syn::Expr::Let(cond) => {
+                    this.visit_expr(&cond.expr);
+                    this.bind(&cond.pat);
+                }
+                cond => this.visit_expr(cond),
+            }
+            this.visit_block(&i.then_branch);
+        });
+        if let Some((_, else_branch)) = &i.else_branch {
+            self.visit_expr(else_branch);
+        }
+    }
+
+    fn visit_expr_while(&mut self, i: &'ast syn::ExprWhile) {
+        self.scoped(|this| {
+            match i.cond.as_ref() {
+                syn::Expr::Let(cond) => {
+                    this.visit_expr(&cond.expr);
+                    this.bind(&cond.pat);
+                }
+                cond => this.visit_expr(cond),
+            }
+            this.visit_block(&i.body);
+        });
+    }
+}
+
+/// The names of the variables bound by a pattern
+fn bindings(pat: &syn::Pat) -> Vec<String> {
+    #[derive(Default)]
+    struct FindBindings(Vec<String>);
+
+    impl<'ast> syn::visit::Visit<'ast> for FindBindings {
+        fn visit_pat_ident(&mut self, i: &'ast syn::PatIdent) {
+            self.0.push(i.ident.to_string());
+            syn::visit::visit_pat_ident(self, i);
+        }
+    }
+
+    let mut find = FindBindings::default();
+    find.visit_pat(pat);
+    find.0
+}
+
+/// Every identifier in a token stream, including the ones inside groups
+fn idents(tokens: proc_macro2::TokenStream) -> Vec<syn::Ident> {
+    let mut found = vec![];
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => found.push(ident),
+            TokenTree::Group(group) => found.extend(idents(group.stream())),
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Check if a statement mentions a variable, including inside macros and inline format arguments
+fn mentions(stmt: &syn::Stmt, name: &str) -> bool {
+    fn mentions_tokens(tokens: proc_macro2::TokenStream, name: &str) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == name,
+            TokenTree::Group(group) => mentions_tokens(group.stream(), name),
+            TokenTree::Literal(literal) => captures(&literal.to_string(), name),
+            TokenTree::Punct(_) => false,
+        })
+    }
+    mentions_tokens(stmt.to_token_stream(), name)
+}
+
+/// Check if a format string captures a variable as an inline argument, like `{name}` or `{name:?}`
+fn captures(literal: &str, name: &str) -> bool {
+    let mut rest = literal;
+    while let Some(open) = rest.find('{') {
+        rest = &rest[open + 1..];
+        // `{{` is an escaped brace, not an argument
+        if let Some(after) = rest.strip_prefix('{') {
+            rest = after;
+            continue;
+        }
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        if &rest[..end] == name && matches!(rest[end..].chars().next(), Some('}' | ':')) {
+            return true;
+        }
+    }
+    false
+}
+
+/// The name of the variable a write guard is bound to, e.g. `count` in `let mut count = signal.write();`
+fn write_guard_binding(stmt: &syn::Stmt) -> Option<&syn::Ident> {
+    let syn::Stmt::Local(local) = stmt else {
+        return None;
+    };
+    let pat = match &local.pat {
+        Pat::Type(pat_type) => pat_type.pat.as_ref(),
+        pat => pat,
+    };
+    let Pat::Ident(ident) = pat else {
+        return None;
+    };
+    let init = local.init.as_ref()?;
+    let syn::Expr::MethodCall(call) = init.expr.as_ref() else {
+        return None;
+    };
+    let is_write =
+        (call.method == "write" || call.method == "write_unchecked") && call.args.is_empty();
+    is_write.then_some(&ident.ident)
+}
+
+/// Check if a statement is `drop(name);`
+fn is_drop_of(stmt: &syn::Stmt, name: &syn::Ident) -> bool {
+    let syn::Stmt::Expr(syn::Expr::Call(call), _) = stmt else {
+        return false;
+    };
+    let syn::Expr::Path(func) = call.func.as_ref() else {
+        return false;
+    };
+    func.path.is_ident("drop")
+        && matches!(call.args.first(), Some(syn::Expr::Path(arg)) if arg.path.is_ident(name))
+}
+
+/// Finds the first `.await` that runs as part of a statement. Closures and async blocks run later, so their awaits are
+/// skipped
+#[derive(Default)]
+struct FindAwait {
+    found: Option<syn::ExprAwait>,
+}
+
+impl<'ast> syn::visit::Visit<'ast> for FindAwait {
+    fn visit_expr_await(&mut self, i: &'ast syn::ExprAwait) {
+        if self.found.is_none() {
+            self.found = Some(i.clone());
+        }
+    }
+
+    fn visit_expr_closure(&mut self, _: &'ast syn::ExprClosure) {}
+
+    fn visit_expr_async(&mut self, _: &'ast syn::ExprAsync) {}
+
+    fn visit_item(&mut self, _: &'ast syn::Item) {}
+}
+
+/// The `spawn` call of a statement that throws away the task, e.g. `spawn(async {});` or `let _ = spawn(async {});`
+fn discarded_spawn(stmt: &syn::Stmt) -> Option<&syn::ExprCall> {
+    let expr = match stmt {
+        syn::Stmt::Expr(expr, Some(_)) => expr,
+        syn::Stmt::Local(local) if matches!(local.pat, Pat::Wild(_)) => {
+            local.init.as_ref()?.expr.as_ref()
+        }
+        _ => return None,
+    };
+    let syn::Expr::Call(call) = expr else {
+        return None;
+    };
+    let syn::Expr::Path(func) = call.func.as_ref() else {
+        return None;
+    };
+    let is_spawn = func.path.segments.last()?.ident == "spawn";
+    is_spawn.then_some(call)
+}
+
+impl VisitHooks {
+    /// Check if the code we are visiting runs inside of a future
+    fn in_async(&self) -> bool {
+        for node in self.context.iter().rev() {
+            match node {
+                Node::Async(_) => return true,
+                Node::Closure(_) => return false,
+                Node::ComponentFn(_) | Node::HookFn(_) | Node::OtherFn(_) => {
+                    return self.async_fns.last().copied().unwrap_or_default()
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Check if the code we are visiting runs while a component renders. Returns whether the code runs unconditionally
+    fn in_render(&self) -> Option<bool> {
+        let mut top_level = true;
+        for node in self.context.iter().rev() {
+            match node {
+                Node::ComponentFn(_) => return Some(top_level),
+                Node::If(_) | Node::Match(_) | Node::For(_) | Node::While(_) | Node::Loop(_) => {
+                    top_level = false
+                }
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    /// Check if the innermost function we are in is a component
+    fn in_component(&self) -> bool {
+        self.context
+            .iter()
+            .rev()
+            .find(|node| {
+                matches!(
+                    node,
+                    Node::ComponentFn(_) | Node::HookFn(_) | Node::OtherFn(_)
+                )
+            })
+            .is_some_and(|node| matches!(node, Node::ComponentFn(_)))
+    }
+
+    fn check_reactive_hook(&mut self, hook_info: &HookInfo, call: &syn::ExprCall) {
+        if self.props.is_empty() || !self.in_component() {
+            return;
+        }
+        let Some(syn::Expr::Closure(closure)) = call.args.first() else {
+            return;
+        };
+        if !closure.inputs.is_empty() {
+            return;
+        }
+        let mut find_reads = FindReads {
+            props: &self.props,
+            locals: vec![],
+            reads: vec![],
+        };
+        find_reads.visit_expr(&closure.body);
+        if !find_reads.reads.is_empty() {
+            self.issues.push(Issue::EffectMissingDependency(
+                hook_info.clone(),
+                ClosureInfo::new(closure.span().into()),
+                find_reads.reads,
+            ));
+        }
+    }
+
+    fn check_write_guards(&mut self, block: &syn::Block) {
+        for (i, stmt) in block.stmts.iter().enumerate() {
+            let Some(name) = write_guard_binding(stmt) else {
+                continue;
+            };
+            for (j, later) in block.stmts.iter().enumerate().skip(i + 1) {
+                if is_drop_of(later, name) {
+                    break;
+                }
+                let mut find_await = FindAwait::default();
+                find_await.visit_stmt(later);
+                let Some(await_expr) = find_await.found else {
+                    continue;
+                };
+                // The guard can only be dropped early if nothing uses it from the await onwards
+                let name_string = name.to_string();
+                let used_later = block.stmts[j..]
+                    .iter()
+                    .any(|stmt| mentions(stmt, &name_string));
+                let drop_at = (!used_later).then(|| later.span().start().into());
+                self.issues.push(Issue::WriteHeldAcrossAwait(
+                    GuardInfo::new(stmt.span().into(), name_string, name.span().into()),
+                    AwaitInfo::new(await_expr.span().into(), drop_at),
+                ));
+                break;
+            }
+        }
+    }
+
+    fn check_rsx_nodes(&mut self, nodes: &[BodyNode]) {
+        for node in nodes {
+            match node {
+                BodyNode::Element(element) => self.check_rsx_nodes(&element.children),
+                BodyNode::Component(component) => self.check_rsx_nodes(&component.children.roots),
+                BodyNode::ForLoop(for_loop) => {
+                    let for_info = ForInfo::new(
+                        for_loop.span().into(),
+                        for_loop
+                            .for_token
+                            .span()
+                            .join(for_loop.expr.span())
+                            .unwrap_or_else(|| for_loop.span())
+                            .into(),
+                    );
+                    let element_info = match for_loop.body.roots.first() {
+                        Some(BodyNode::Element(element))
+                            if !element
+                                .raw_attributes
+                                .iter()
+                                .any(|attr| attr.name.is_likely_key()) =>
+                        {
+                            Some(ElementInfo::new(
+                                element.name.span().into(),
+                                element.name.to_string(),
+                                element.brace.map(|brace| brace.span.open().into()),
+                            ))
+                        }
+                        Some(BodyNode::Component(component)) if component.get_key().is_none() => {
+                            Some(ElementInfo::new(
+                                component.name.span().into(),
+                                component
+                                    .name
+                                    .to_token_stream()
+                                    .to_string()
+                                    .replace(' ', ""),
+                                component.brace.map(|brace| brace.span.open().into()),
+                            ))
+                        }
+                        _ => None,
+                    };
+                    if let Some(element_info) = element_info {
+                        self.issues.push(Issue::MissingKey(element_info, for_info));
+                    }
+                    self.check_rsx_nodes(&for_loop.body.roots);
+                }
+                BodyNode::IfChain(if_chain) => self.check_rsx_if_chain(if_chain),
+                BodyNode::Text(_) | BodyNode::RawExpr(_) => {}
+            }
+        }
+    }
+
+    fn check_rsx_if_chain(&mut self, if_chain: &IfChain) {
+        self.check_rsx_nodes(&if_chain.then_branch.roots);
+        if let Some(else_if) = &if_chain.else_if_branch {
+            self.check_rsx_if_chain(else_if);
+        }
+        if let Some(else_branch) = &if_chain.else_branch {
+            self.check_rsx_nodes(&else_branch.roots);
+        }
+    }
+}
+
 impl<'ast> syn::visit::Visit<'ast> for VisitHooks {
     fn visit_expr_call(&mut self, i: &'ast syn::ExprCall) {
         if let syn::Expr::Path(ref path) = *i.func {
@@ -106,6 +602,9 @@ impl<'ast> syn::visit::Visit<'ast> for VisitHooks {
                         segment.ident.span().into(),
                         segment.ident.to_string(),
                     );
+                    if is_reactive_hook_ident(&segment.ident) {
+                        self.check_reactive_hook(&hook_info, i);
+                    }
                     let mut container_fn: Option<Node> = None;
                     for node in self.context.iter().rev() {
                         match &node {
@@ -175,6 +674,12 @@ impl<'ast> syn::visit::Visit<'ast> for VisitHooks {
 
     fn visit_item_fn(&mut self, i: &'ast syn::ItemFn) {
         let (name, name_span) = fn_name_and_name_span(i);
+        let props = match is_component_fn(i) {
+            true => non_reactive_props(i),
+            false => vec![],
+        };
+        let outer_props = std::mem::replace(&mut self.props, props);
+        self.async_fns.push(i.sig.asyncness.is_some());
         if is_component_fn(i) {
             self.context.push(Node::ComponentFn(ComponentInfo::new(
                 i.span().into(),
@@ -193,6 +698,50 @@ impl<'ast> syn::visit::Visit<'ast> for VisitHooks {
         }
         syn::visit::visit_item_fn(self, i);
         self.context.pop();
+        self.async_fns.pop();
+        self.props = outer_props;
+    }
+
+    fn visit_block(&mut self, i: &'ast syn::Block) {
+        if self.in_async() {
+            self.check_write_guards(i);
+        }
+        syn::visit::visit_block(self, i);
+    }
+
+    fn visit_stmt(&mut self, i: &'ast syn::Stmt) {
+        if let Some(call) = discarded_spawn(i) {
+            if let Some(top_level) = self.in_render() {
+                let name_span = match call.func.as_ref() {
+                    syn::Expr::Path(path) => path
+                        .path
+                        .segments
+                        .last()
+                        .map(|segment| segment.ident.span()),
+                    _ => None,
+                }
+                .unwrap_or_else(|| call.func.span());
+                self.issues.push(Issue::UnusedSpawn(SpawnInfo::new(
+                    call.span().into(),
+                    name_span.into(),
+                    top_level,
+                )));
+            }
+        }
+        syn::visit::visit_stmt(self, i);
+    }
+
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        if i.path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "rsx")
+        {
+            if let Ok(body) = syn::parse2::<CallBody>(i.tokens.clone()) {
+                self.check_rsx_nodes(&body.body.roots);
+            }
+        }
+        syn::visit::visit_macro(self, i);
     }
 
     fn visit_local(&mut self, i: &'ast syn::Local) {
@@ -293,6 +842,7 @@ mod tests {
         AnyLoopInfo, ClosureInfo, ConditionalInfo, ForInfo, HookInfo, IfInfo, LineColumn, LoopInfo,
         MatchInfo, Span, WhileInfo,
     };
+    use crate::{apply_fixes, check_file_with_config, Applicability, CheckConfig, Level, Rule};
     use indoc::indoc;
     use pretty_assertions::assert_eq;
 
@@ -840,4 +1390,237 @@ mod tests {
             ),]
         );
     }
+
+    #[test]
+    fn test_effect_missing_dependency() {
+        let contents = indoc! {r#"
+            fn Counter(count: i32) -> Element {
+                use_effect(move || println!("{}", count));
+                rsx! {}
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(
+            report.issues.iter().map(Issue::rule).collect::<Vec<_>>(),
+            vec![Rule::EffectMissingDependency]
+        );
+        let fix = report.issues[0].fix().unwrap();
+        assert_eq!(fix.applicability, Applicability::MaybeIncorrect);
+        assert_eq!(report.fixed_content(Applicability::MachineApplicable), None);
+        assert_eq!(
+            report.fixed_content(Applicability::MaybeIncorrect).unwrap(),
+            apply_fixes(contents, [&fix])
+        );
+        assert_eq!(
+            apply_fixes(contents, [&fix]),
+            indoc! {r#"
+                fn Counter(count: i32) -> Element {
+                    use_effect(use_reactive!(|count| println!("{}", count)));
+                    rsx! {}
+                }
+            "#}
+        );
+    }
+
+    #[test]
+    fn test_effect_reactive_prop_okay() {
+        let contents = indoc! {r#"
+            fn Counter(count: ReadOnlySignal<i32>) -> Element {
+                use_effect(move || println!("{}", count));
+                rsx! {}
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(report.issues, vec![]);
+    }
+
+    #[test]
+    fn test_effect_shadowed_prop_okay() {
+        let contents = indoc! {r#"
+            fn Counter(count: i32, items: Vec<i32>) -> Element {
+                use_effect(move || {
+                    let count = 1;
+                    println!("{}", count);
+                });
+                use_memo(move || ITEMS().iter().map(|items| items.len()).sum::<usize>());
+                use_effect(move || {
+                    if let Some(count) = LAST() {
+                        println!("{}", count);
+                    }
+                });
+                rsx! {}
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(report.issues, vec![]);
+    }
+
+    #[test]
+    fn test_effect_read_before_shadow() {
+        let contents = indoc! {r#"
+            fn Counter(count: i32) -> Element {
+                use_effect(move || {
+                    let count = count + 1;
+                    println!("{}", count);
+                });
+                rsx! {}
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        let [Issue::EffectMissingDependency(_, _, reads)] = &report.issues[..] else {
+            panic!("expected a missing dependency, found {:?}", report.issues);
+        };
+        assert_eq!(reads.len(), 1);
+        assert_eq!(reads[0].span.start.line, 3);
+    }
+
+    #[test]
+    fn test_write_held_across_await() {
+        let contents = indoc! {r#"
+            fn App() -> Element {
+                let mut count = use_signal(|| 0);
+                let increment = move |_| async move {
+                    let mut value = count.write();
+                    *value += 1;
+                    fetch().await;
+                };
+                rsx! {}
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(
+            report.issues.iter().map(Issue::rule).collect::<Vec<_>>(),
+            vec![Rule::WriteHeldAcrossAwait]
+        );
+        assert_eq!(
+            report
+                .fixed_content(Applicability::MachineApplicable)
+                .unwrap(),
+            indoc! {r#"
+                fn App() -> Element {
+                    let mut count = use_signal(|| 0);
+                    let increment = move |_| async move {
+                        let mut value = count.write();
+                        *value += 1;
+                        drop(value);
+                        fetch().await;
+                    };
+                    rsx! {}
+                }
+            "#}
+        );
+    }
+
+    #[test]
+    fn test_format_captures() {
+        assert!(captures(r#""{value}""#, "value"));
+        assert!(captures(r#""total: {value:?}""#, "value"));
+        assert!(!captures(r#""{value_total}""#, "value"));
+        assert!(!captures(r#""{{value}}""#, "value"));
+        assert!(!captures(r#""{}""#, "value"));
+    }
+
+    #[test]
+    fn test_write_dropped_before_await_okay() {
+        let contents = indoc! {r#"
+            async fn increment(mut count: Signal<i32>) {
+                let mut value = count.write();
+                *value += 1;
+                drop(value);
+                fetch().await;
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(report.issues, vec![]);
+    }
+
+    #[test]
+    fn test_missing_key() {
+        let contents = indoc! {r#"
+            fn App() -> Element {
+                rsx! {
+                    ul {
+                        for item in items {
+                            li { "{item}" }
+                        }
+                        for item in items {
+                            li { key: "{item}", "{item}" }
+                        }
+                    }
+                }
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(
+            report.issues.iter().map(Issue::rule).collect::<Vec<_>>(),
+            vec![Rule::MissingKey]
+        );
+        let fix = report.issues[0].fix().unwrap();
+        assert_eq!(fix.applicability, Applicability::MaybeIncorrect);
+        assert!(apply_fixes(contents, [&fix]).contains(r#"li { key: "{item}", "{item}" }"#));
+    }
+
+    #[test]
+    fn test_unused_spawn() {
+        let contents = indoc! {r#"
+            fn App() -> Element {
+                spawn(async move {
+                    println!("spawned");
+                });
+                let onclick = move |_| {
+                    spawn(async move {});
+                };
+                rsx! {}
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(
+            report.issues.iter().map(Issue::rule).collect::<Vec<_>>(),
+            vec![Rule::UnusedSpawn]
+        );
+
+        let config = CheckConfig {
+            rules: [(Rule::UnusedSpawn, Level::Allow)].into_iter().collect(),
+        };
+        let report = check_file_with_config("app.rs".into(), contents, &config);
+
+        assert_eq!(report.issues, vec![]);
+    }
+
+    #[test]
+    fn test_signal_outside_component_fix() {
+        let contents = indoc! {r#"
+            fn not_component_or_hook() {
+                let _a = use_signal(|| 0);
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+        let fix = report.issues[0].fix().unwrap();
+
+        assert_eq!(
+            apply_fixes(contents, [&fix]),
+            indoc! {r#"
+                fn not_component_or_hook() {
+                    let _a = Signal::new(0);
+                }
+            "#}
+        );
+    }
 }
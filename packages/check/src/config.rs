@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// A rule that `dioxus-check` can report issues for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum Rule {
+    /// A hook is called inside an `if` or `match`
+    HookInsideConditional,
+    /// A hook is called inside a loop
+    HookInsideLoop,
+    /// A hook is called inside a closure
+    HookInsideClosure,
+    /// A hook is called inside an async block
+    HookInsideAsync,
+    /// A hook is called outside of a component or hook
+    HookOutsideComponent,
+    /// A prop is read inside `use_effect`, `use_memo` or `use_resource` without being reactive
+    EffectMissingDependency,
+    /// A `.write()` guard is held across an `.await`
+    WriteHeldAcrossAwait,
+    /// The first node in an rsx `for` loop doesn't have a `key`
+    MissingKey,
+    /// A task is spawned while the component renders and the result is thrown away
+    UnusedSpawn,
+}
+
+impl Rule {
+    /// Every rule `dioxus-check` knows about
+    pub const ALL: [Rule; 9] = [
+        Rule::HookInsideConditional,
+        Rule::HookInsideLoop,
+        Rule::HookInsideClosure,
+        Rule::HookInsideAsync,
+        Rule::HookOutsideComponent,
+        Rule::EffectMissingDependency,
+        Rule::WriteHeldAcrossAwait,
+        Rule::MissingKey,
+        Rule::UnusedSpawn,
+    ];
+
+    /// The id of the rule used in the config and in reports, e.g. `missing-key`
+    pub const fn id(&self) -> &'static str {
+        match self {
+            Rule::HookInsideConditional => "hook-inside-conditional",
+            Rule::HookInsideLoop => "hook-inside-loop",
+            Rule::HookInsideClosure => "hook-inside-closure",
+            Rule::HookInsideAsync => "hook-inside-async",
+            Rule::HookOutsideComponent => "hook-outside-component",
+            Rule::EffectMissingDependency => "effect-missing-dependency",
+            Rule::WriteHeldAcrossAwait => "write-held-across-await",
+            Rule::MissingKey => "missing-key",
+            Rule::UnusedSpawn => "unused-spawn",
+        }
+    }
+
+    /// A short description of what the rule checks
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Rule::HookInsideConditional => "Hooks must not be called conditionally",
+            Rule::HookInsideLoop => "Hooks must not be called in loops",
+            Rule::HookInsideClosure => "Hooks must not be called in closures",
+            Rule::HookInsideAsync => "Hooks must not be called in async blocks",
+            Rule::HookOutsideComponent => {
+                "Hooks must only be called from components or other hooks"
+            }
+            Rule::EffectMissingDependency => {
+                "Props read in reactive hooks must be passed through use_reactive"
+            }
+            Rule::WriteHeldAcrossAwait => "Write guards must be dropped before awaiting",
+            Rule::MissingKey => "Nodes created in a for loop should have a key",
+            Rule::UnusedSpawn => "Tasks should not be spawned while the component renders",
+        }
+    }
+
+    /// A link to the documentation for the rule, if there is one
+    pub const fn help_uri(&self) -> Option<&'static str> {
+        match self {
+            Rule::HookInsideConditional => {
+                Some("https://dioxuslabs.com/learn/0.6/reference/hooks#no-hooks-in-conditionals")
+            }
+            Rule::HookInsideLoop => {
+                Some("https://dioxuslabs.com/learn/0.6/reference/hooks#no-hooks-in-loops")
+            }
+            Rule::HookInsideClosure | Rule::HookInsideAsync => {
+                Some("https://dioxuslabs.com/learn/0.6/reference/hooks#no-hooks-in-closures")
+            }
+            Rule::HookOutsideComponent => {
+                Some("https://dioxuslabs.com/learn/0.6/reference/hooks#rules-of-hooks")
+            }
+            Rule::EffectMissingDependency
+            | Rule::WriteHeldAcrossAwait
+            | Rule::MissingKey
+            | Rule::UnusedSpawn => None,
+        }
+    }
+
+    /// The level the rule is reported at if the config doesn't override it
+    pub const fn default_level(&self) -> Level {
+        match self {
+            Rule::HookInsideConditional
+            | Rule::HookInsideLoop
+            | Rule::HookInsideClosure
+            | Rule::HookInsideAsync
+            | Rule::HookOutsideComponent
+            | Rule::WriteHeldAcrossAwait => Level::Deny,
+            Rule::EffectMissingDependency | Rule::MissingKey | Rule::UnusedSpawn => Level::Warn,
+        }
+    }
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// How an issue for a rule is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// Don't report the issue
+    Allow,
+    /// Report the issue as a warning
+    Warn,
+    /// Report the issue as an error
+    Deny,
+}
+
+impl Level {
+    /// The label the issue is printed with
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Level::Allow => "allowed",
+            Level::Warn => "warning",
+            Level::Deny => "error",
+        }
+    }
+}
+
+/// The per project configuration for `dioxus-check`, read from the `[check]` section of `Dioxus.toml`.
+///
+/// ```toml
+/// [check.rules]
+/// missing-key = "deny"
+/// unused-spawn = "allow"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckConfig {
+    /// Overrides for the level of each rule
+    #[serde(default)]
+    pub rules: BTreeMap<Rule, Level>,
+}
+
+impl CheckConfig {
+    /// Get the level a rule is reported at
+    pub fn level(&self, rule: Rule) -> Level {
+        self.rules
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_level())
+    }
+}
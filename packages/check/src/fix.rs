@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+use crate::metadata::LineColumn;
+
+/// How confident `dioxus-check` is that a fix is correct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Applicability {
+    /// The fix is definitely what the user wants and can be applied automatically
+    MachineApplicable,
+    /// The fix may not compile or may change behavior. It should be reviewed before it is applied
+    MaybeIncorrect,
+}
+
+/// A single replacement of the text between two locations in a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Edit {
+    /// The start of the replaced text
+    pub start: LineColumn,
+    /// The end of the replaced text. If this is the same as the start, the replacement is inserted
+    pub end: LineColumn,
+    /// The text to replace the range with
+    pub replacement: String,
+}
+
+impl Edit {
+    pub fn replace(start: LineColumn, end: LineColumn, replacement: impl ToString) -> Self {
+        Self {
+            start,
+            end,
+            replacement: replacement.to_string(),
+        }
+    }
+
+    pub fn insert(at: LineColumn, text: impl ToString) -> Self {
+        Self::replace(at.clone(), at, text)
+    }
+}
+
+/// A suggested fix for an issue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fix {
+    /// A description of what the fix does
+    pub message: String,
+    /// How confident we are that the fix is correct
+    pub applicability: Applicability,
+    /// The edits that make up the fix
+    pub edits: Vec<Edit>,
+}
+
+/// Apply fixes to the contents of a file.
+///
+/// Edits that overlap an edit from an earlier fix are skipped along with the rest of their fix, so running the check
+/// again after applying fixes may find more fixes.
+pub fn apply_fixes<'a>(file_content: &str, fixes: impl IntoIterator<Item = &'a Fix>) -> String {
+    let mut applied: Vec<(usize, usize, &str)> = vec![];
+    for fix in fixes {
+        let Some(ranges) = fix
+            .edits
+            .iter()
+            .map(|edit| {
+                let start = byte_offset(file_content, &edit.start)?;
+                let end = byte_offset(file_content, &edit.end)?;
+                Some((start, end, edit.replacement.as_str()))
+            })
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+
+        let overlaps = ranges.iter().any(|(start, end, _)| {
+            applied.iter().any(|(other_start, other_end, _)| {
+                start == other_start || (start < other_end && other_start < end)
+            })
+        });
+        if !overlaps {
+            applied.extend(ranges);
+        }
+    }
+
+    // Apply the edits back to front so the earlier offsets stay valid
+    applied.sort_by_key(|(start, end, _)| (*start, *end));
+    let mut output = file_content.to_string();
+    for (start, end, replacement) in applied.into_iter().rev() {
+        output.replace_range(start..end, replacement);
+    }
+    output
+}
+
+/// Convert a line (1-indexed) and column (0-indexed, in chars) into a byte offset
+fn byte_offset(file_content: &str, location: &LineColumn) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 1..location.line {
+        line_start += file_content[line_start..].find('\n')? + 1;
+    }
+    let line = &file_content[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    let column = match line.char_indices().nth(location.column) {
+        Some((offset, _)) => offset,
+        None if line.chars().count() == location.column => line.len(),
+        None => return None,
+    };
+    Some(line_start + column)
+}
@@ -1,5 +1,5 @@
 use owo_colors::{
-    colors::{css::LightBlue, BrightRed},
+    colors::{css::LightBlue, BrightRed, BrightYellow},
     OwoColorize, Stream,
 };
 use std::{
@@ -7,9 +7,13 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::metadata::{
-    AnyLoopInfo, AsyncInfo, ClosureInfo, ConditionalInfo, ForInfo, HookInfo, IfInfo, MatchInfo,
-    WhileInfo,
+use crate::{
+    config::{CheckConfig, Level, Rule},
+    fix::{apply_fixes, Applicability, Edit, Fix},
+    metadata::{
+        AnyLoopInfo, AsyncInfo, AwaitInfo, ClosureInfo, ConditionalInfo, ElementInfo, ForInfo,
+        GuardInfo, HookInfo, IfInfo, MatchInfo, ReadInfo, Span, SpawnInfo, WhileInfo,
+    },
 };
 
 /// The result of checking a Dioxus file for issues.
@@ -18,6 +22,7 @@ pub struct IssueReport {
     pub crate_root: PathBuf,
     pub file_content: String,
     pub issues: Vec<Issue>,
+    pub config: CheckConfig,
 }
 
 impl IssueReport {
@@ -32,8 +37,54 @@ impl IssueReport {
             crate_root,
             file_content: file_content.to_string(),
             issues,
+            config: CheckConfig::default(),
         }
     }
+
+    /// Set the config the issues were checked with. Issues for rules that are allowed are removed
+    pub fn with_config(mut self, config: CheckConfig) -> Self {
+        self.issues
+            .retain(|issue| config.level(issue.rule()) != Level::Allow);
+        self.config = config;
+        self
+    }
+
+    /// Get the level an issue is reported at
+    pub fn level(&self, issue: &Issue) -> Level {
+        self.config.level(issue.rule())
+    }
+
+    /// Check if any of the issues are errors
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| self.level(issue) == Level::Deny)
+    }
+
+    /// The path of the file relative to the crate root
+    pub fn relative_path(&self) -> &Path {
+        self.path
+            .strip_prefix(&self.crate_root)
+            .unwrap_or(&self.path)
+    }
+
+    /// Apply the fixes that are at least as confident as `applicability` to the file. Pass
+    /// [`Applicability::MaybeIncorrect`] to apply every fix. Returns `None` if there is nothing to fix
+    pub fn fixed_content(&self, applicability: Applicability) -> Option<String> {
+        let fixes = self
+            .issues
+            .iter()
+            .filter_map(Issue::fix)
+            .filter(|fix| {
+                fix.applicability == Applicability::MachineApplicable
+                    || applicability == Applicability::MaybeIncorrect
+            })
+            .collect::<Vec<_>>();
+        if fixes.is_empty() {
+            return None;
+        }
+        Some(apply_fixes(&self.file_content, &fixes))
+    }
 }
 
 fn lightblue(text: &str) -> String {
@@ -46,6 +97,11 @@ fn brightred(text: &str) -> String {
         .to_string()
 }
 
+fn brightyellow(text: &str) -> String {
+    text.if_supports_color(Stream::Stderr, |text| text.fg::<BrightYellow>())
+        .to_string()
+}
+
 fn bold(text: &str) -> String {
     text.if_supports_color(Stream::Stderr, |text| text.bold())
         .to_string()
@@ -53,32 +109,32 @@ fn bold(text: &str) -> String {
 
 impl Display for IssueReport {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let relative_file = Path::new(&self.path)
-            .strip_prefix(&self.crate_root)
-            .unwrap_or(Path::new(&self.path))
-            .display();
+        let relative_file = self.relative_path().display();
 
         let pipe_char = lightblue("|");
 
         for (i, issue) in self.issues.iter().enumerate() {
-            let hook_info = issue.hook_info();
-            let hook_span = hook_info.span;
-            let hook_name_span = hook_info.name_span;
-            let error_line = format!("{}: {}", brightred("error"), issue);
+            let span = issue.span();
+            let highlight = issue.highlight();
+            let color: fn(&str) -> String = match self.level(issue) {
+                Level::Deny => brightred,
+                _ => brightyellow,
+            };
+            let error_line = format!("{}: {}", color(self.level(issue).label()), issue);
             writeln!(f, "{}", bold(&error_line))?;
             writeln!(
                 f,
                 "  {} {}:{}:{}",
                 lightblue("-->"),
                 relative_file,
-                hook_span.start.line,
-                hook_span.start.column + 1
+                span.start.line,
+                span.start.column + 1
             )?;
-            let max_line_num_len = hook_span.end.line.to_string().len();
+            let max_line_num_len = span.end.line.to_string().len();
             writeln!(f, "{:>max_line_num_len$} {}", "", pipe_char)?;
             for (i, line) in self.file_content.lines().enumerate() {
                 let line_num = i + 1;
-                if line_num >= hook_span.start.line && line_num <= hook_span.end.line {
+                if line_num >= span.start.line && line_num <= span.end.line {
                     writeln!(
                         f,
                         "{:>max_line_num_len$} {} {}",
@@ -86,12 +142,17 @@ impl Display for IssueReport {
                         pipe_char,
                         line,
                     )?;
-                    if line_num == hook_span.start.line {
+                    if line_num == highlight.start.line {
+                        // Highlights that span multiple lines are underlined until the end of the first line
+                        let end_column = match highlight.end.line == highlight.start.line {
+                            true => highlight.end.column,
+                            false => line.chars().count(),
+                        };
                         let mut caret = String::new();
-                        for _ in 0..hook_name_span.start.column {
+                        for _ in 0..highlight.start.column {
                             caret.push(' ');
                         }
-                        for _ in hook_name_span.start.column..hook_name_span.end.column {
+                        for _ in highlight.start.column..end_column {
                             caret.push('^');
                         }
                         writeln!(
@@ -99,7 +160,7 @@ impl Display for IssueReport {
                             "{:>max_line_num_len$} {} {}",
                             "",
                             pipe_char,
-                            brightred(&caret),
+                            color(&caret),
                         )?;
                     }
                 }
@@ -131,7 +192,8 @@ impl Display for IssueReport {
                     }
                 }
                 Issue::HookInsideLoop(_, AnyLoopInfo::For(ForInfo { span: _, head_span }))
-                | Issue::HookInsideLoop(_, AnyLoopInfo::While(WhileInfo { span: _, head_span })) => {
+                | Issue::HookInsideLoop(_, AnyLoopInfo::While(WhileInfo { span: _, head_span }))
+                | Issue::MissingKey(_, ForInfo { span: _, head_span }) => {
                     if let Some(source_text) = &head_span.source_text {
                         writeln!(
                             f,
@@ -143,9 +205,41 @@ impl Display for IssueReport {
                 Issue::HookInsideLoop(_, AnyLoopInfo::Loop(_)) => {
                     writeln!(f, "{} `loop {{ … }}` is the loop", note_text_prefix,)?;
                 }
+                Issue::EffectMissingDependency(hook_info, _, reads) => {
+                    writeln!(
+                        f,
+                        "{} `{}` won't rerun when {} {}",
+                        note_text_prefix,
+                        hook_info.name,
+                        quoted_list(reads.iter().map(|read| read.name.as_str())),
+                        if reads.len() == 1 {
+                            "changes"
+                        } else {
+                            "change"
+                        },
+                    )?;
+                }
+                Issue::WriteHeldAcrossAwait(_, AwaitInfo { span, .. }) => {
+                    if let Some(source_text) = &span.source_text {
+                        writeln!(f, "{} `{}` is the await", note_text_prefix, source_text)?;
+                    }
+                }
                 Issue::HookOutsideComponent(_)
                 | Issue::HookInsideClosure(_, _)
-                | Issue::HookInsideAsync(_, _) => {}
+                | Issue::HookInsideAsync(_, _)
+                | Issue::UnusedSpawn(_) => {}
+            }
+
+            if let Some(fix) = issue.fix() {
+                writeln!(
+                    f,
+                    "{:>max_line_num_len$} {}\n{:>max_line_num_len$} {} help: {}",
+                    "",
+                    pipe_char,
+                    "",
+                    lightblue("="),
+                    fix.message
+                )?;
             }
 
             if i < self.issues.len() - 1 {
@@ -157,9 +251,18 @@ impl Display for IssueReport {
     }
 }
 
+/// Format a list of names like "`a`, `b` and `c`"
+fn quoted_list<'a>(names: impl Iterator<Item = &'a str>) -> String {
+    let names = names.map(|name| format!("`{name}`")).collect::<Vec<_>>();
+    match names.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
-#[allow(clippy::enum_variant_names)] // we'll add non-hook ones in the future
 /// Issues that might be found via static analysis of a Dioxus file.
 pub enum Issue {
     /// <https://dioxuslabs.com/learn/0.6/reference/hooks#no-hooks-in-conditionals>
@@ -170,20 +273,191 @@ pub enum Issue {
     HookInsideClosure(HookInfo, ClosureInfo),
     HookInsideAsync(HookInfo, AsyncInfo),
     HookOutsideComponent(HookInfo),
+    /// Props that are read in the closure of `use_effect`, `use_memo` or `use_resource` are not reactive. The hook
+    /// won't rerun when they change unless they are passed through `use_reactive!`
+    EffectMissingDependency(HookInfo, ClosureInfo, Vec<ReadInfo>),
+    /// A write guard that is alive at an `.await` keeps the signal borrowed while the future is suspended. Any other
+    /// read or write of the signal in that time will panic
+    WriteHeldAcrossAwait(GuardInfo, AwaitInfo),
+    /// Nodes created in a `for` loop need a `key` so they can be diffed efficiently when the list changes
+    MissingKey(ElementInfo, ForInfo),
+    /// Spawning a task while the component renders spawns a new task every render. The task can't be cancelled
+    /// because the result is thrown away
+    UnusedSpawn(SpawnInfo),
 }
 
 impl Issue {
-    pub fn hook_info(&self) -> HookInfo {
+    /// The rule the issue breaks
+    pub fn rule(&self) -> Rule {
+        match self {
+            Issue::HookInsideConditional(_, _) => Rule::HookInsideConditional,
+            Issue::HookInsideLoop(_, _) => Rule::HookInsideLoop,
+            Issue::HookInsideClosure(_, _) => Rule::HookInsideClosure,
+            Issue::HookInsideAsync(_, _) => Rule::HookInsideAsync,
+            Issue::HookOutsideComponent(_) => Rule::HookOutsideComponent,
+            Issue::EffectMissingDependency(_, _, _) => Rule::EffectMissingDependency,
+            Issue::WriteHeldAcrossAwait(_, _) => Rule::WriteHeldAcrossAwait,
+            Issue::MissingKey(_, _) => Rule::MissingKey,
+            Issue::UnusedSpawn(_) => Rule::UnusedSpawn,
+        }
+    }
+
+    /// The hook the issue is about, if there is one
+    pub fn hook_info(&self) -> Option<HookInfo> {
         match self {
             Issue::HookInsideConditional(hook_info, _)
             | Issue::HookInsideLoop(hook_info, _)
             | Issue::HookInsideClosure(hook_info, _)
             | Issue::HookInsideAsync(hook_info, _)
-            | Issue::HookOutsideComponent(hook_info) => hook_info.clone(),
+            | Issue::HookOutsideComponent(hook_info)
+            | Issue::EffectMissingDependency(hook_info, _, _) => Some(hook_info.clone()),
+            Issue::WriteHeldAcrossAwait(_, _) | Issue::MissingKey(_, _) | Issue::UnusedSpawn(_) => {
+                None
+            }
+        }
+    }
+
+    /// The span of the code the issue is reported for
+    pub fn span(&self) -> &Span {
+        match self {
+            Issue::HookInsideConditional(hook_info, _)
+            | Issue::HookInsideLoop(hook_info, _)
+            | Issue::HookInsideClosure(hook_info, _)
+            | Issue::HookInsideAsync(hook_info, _)
+            | Issue::HookOutsideComponent(hook_info) => &hook_info.span,
+            Issue::EffectMissingDependency(hook_info, _, reads) => reads
+                .first()
+                .map(|read| &read.span)
+                .unwrap_or(&hook_info.span),
+            Issue::WriteHeldAcrossAwait(guard_info, _) => &guard_info.span,
+            Issue::MissingKey(element_info, _) => &element_info.span,
+            Issue::UnusedSpawn(spawn_info) => &spawn_info.span,
+        }
+    }
+
+    /// The part of the span that is underlined in the report
+    pub fn highlight(&self) -> &Span {
+        match self {
+            Issue::HookInsideConditional(hook_info, _)
+            | Issue::HookInsideLoop(hook_info, _)
+            | Issue::HookInsideClosure(hook_info, _)
+            | Issue::HookInsideAsync(hook_info, _)
+            | Issue::HookOutsideComponent(hook_info) => &hook_info.name_span,
+            Issue::WriteHeldAcrossAwait(guard_info, _) => &guard_info.name_span,
+            Issue::UnusedSpawn(spawn_info) => &spawn_info.name_span,
+            Issue::EffectMissingDependency(_, _, _) | Issue::MissingKey(_, _) => self.span(),
+        }
+    }
+
+    /// A suggested fix for the issue, if there is one
+    pub fn fix(&self) -> Option<Fix> {
+        match self {
+            // Signals outside of components can be created directly instead of with a hook
+            Issue::HookOutsideComponent(hook_info) if hook_info.name == "use_signal" => {
+                let call =
+                    syn::parse_str::<syn::ExprCall>(hook_info.span.source_text.as_ref()?).ok()?;
+                let Some(syn::Expr::Closure(init)) = call.args.first() else {
+                    return None;
+                };
+                if !init.inputs.is_empty() {
+                    return None;
+                }
+                let init = syn::spanned::Spanned::span(&init.body).source_text()?;
+                Some(Fix {
+                    message: "create the signal with `Signal::new` instead".to_string(),
+                    applicability: Applicability::MaybeIncorrect,
+                    edits: vec![Edit::replace(
+                        hook_info.span.start.clone(),
+                        hook_info.span.end.clone(),
+                        format!("Signal::new({init})"),
+                    )],
+                })
+            }
+            Issue::EffectMissingDependency(_, closure_info, reads) => {
+                let closure =
+                    syn::parse_str::<syn::ExprClosure>(closure_info.span.source_text.as_ref()?)
+                        .ok()?;
+                let body = syn::spanned::Spanned::span(&closure.body).source_text()?;
+                let names = reads
+                    .iter()
+                    .map(|read| read.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                // The closure may need clones of the values it moves, and `use_reactive!` may not be imported
+                Some(Fix {
+                    message: format!(
+                        "pass {} through `use_reactive!`",
+                        quoted_list(reads.iter().map(|read| read.name.as_str()))
+                    ),
+                    applicability: Applicability::MaybeIncorrect,
+                    edits: vec![Edit::replace(
+                        closure_info.span.start.clone(),
+                        closure_info.span.end.clone(),
+                        format!("use_reactive!(|{names}| {body})"),
+                    )],
+                })
+            }
+            Issue::WriteHeldAcrossAwait(guard_info, AwaitInfo { drop_at, .. }) => {
+                let drop_at = drop_at.clone()?;
+                Some(Fix {
+                    message: format!("drop `{}` before awaiting", guard_info.name),
+                    applicability: Applicability::MachineApplicable,
+                    edits: vec![Edit::insert(
+                        drop_at.clone(),
+                        format!("drop({});\n{}", guard_info.name, " ".repeat(drop_at.column)),
+                    )],
+                })
+            }
+            Issue::MissingKey(element_info, for_info) => {
+                let brace = element_info.brace.as_ref()?;
+                let key = loop_binding(for_info)?;
+                Some(Fix {
+                    message: format!("add a key to `{}`", element_info.name),
+                    // The binding may not be unique or implement Display
+                    applicability: Applicability::MaybeIncorrect,
+                    edits: vec![Edit::insert(
+                        brace.end.clone(),
+                        format!(" key: \"{{{key}}}\","),
+                    )],
+                })
+            }
+            Issue::UnusedSpawn(spawn_info) if spawn_info.top_level => {
+                let call = spawn_info.span.source_text.as_ref()?;
+                Some(Fix {
+                    message: "only spawn the task when the component is created with `use_hook`"
+                        .to_string(),
+                    // The future may capture values that are moved into the closure
+                    applicability: Applicability::MaybeIncorrect,
+                    edits: vec![Edit::replace(
+                        spawn_info.span.start.clone(),
+                        spawn_info.span.end.clone(),
+                        format!("use_hook(|| {call})"),
+                    )],
+                })
+            }
+            _ => None,
         }
     }
 }
 
+/// The first variable bound by the pattern of a `for` loop, e.g. `item` in `for item in items`
+fn loop_binding(for_info: &ForInfo) -> Option<String> {
+    let head = for_info.head_span.source_text.as_ref()?;
+    let pattern = head.strip_prefix("for")?.split(" in ").next()?;
+    let pattern = syn::parse::Parser::parse_str(syn::Pat::parse_single, pattern).ok()?;
+    first_binding(&pattern)
+}
+
+fn first_binding(pattern: &syn::Pat) -> Option<String> {
+    match pattern {
+        syn::Pat::Ident(ident) => Some(ident.ident.to_string()),
+        syn::Pat::Tuple(tuple) => tuple.elems.iter().find_map(first_binding),
+        syn::Pat::Reference(reference) => first_binding(&reference.pat),
+        syn::Pat::Paren(paren) => first_binding(&paren.pat),
+        _ => None,
+    }
+}
+
 impl std::fmt::Display for Issue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -223,6 +497,31 @@ impl std::fmt::Display for Issue {
                     hook_info.name
                 )
             }
+            Issue::EffectMissingDependency(hook_info, _, reads) => {
+                write!(
+                    f,
+                    "non-reactive prop read in `{}`: {}",
+                    hook_info.name,
+                    reads
+                        .iter()
+                        .map(|read| format!("`{}`", read.name))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+            Issue::WriteHeldAcrossAwait(guard_info, _) => {
+                write!(f, "write guard held across an await: `{}`", guard_info.name)
+            }
+            Issue::MissingKey(element_info, _) => {
+                write!(
+                    f,
+                    "missing key on node in a `for` loop: `{}`",
+                    element_info.name
+                )
+            }
+            Issue::UnusedSpawn(_) => {
+                write!(f, "task spawned while rendering is never used: `spawn`")
+            }
         }
     }
 }
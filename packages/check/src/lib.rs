@@ -3,8 +3,14 @@
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 
 mod check;
+mod config;
+mod fix;
 mod issues;
 mod metadata;
+mod sarif;
 
-pub use check::check_file;
+pub use check::{check_file, check_file_with_config};
+pub use config::{CheckConfig, Level, Rule};
+pub use fix::{apply_fixes, Applicability, Edit, Fix};
 pub use issues::{Issue, IssueReport};
+pub use sarif::{json_report, sarif_report};
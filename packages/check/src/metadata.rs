@@ -159,6 +159,95 @@ impl FnInfo {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about a prop that is read inside a reactive hook.
+pub struct ReadInfo {
+    /// The name of the prop, e.g. `count`.
+    pub name: String,
+    /// The span of the first read of the prop.
+    pub span: Span,
+}
+
+impl ReadInfo {
+    pub const fn new(span: Span, name: String) -> Self {
+        Self { span, name }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about a write guard bound to a variable, e.g. `let mut count = count.write();`.
+pub struct GuardInfo {
+    /// The span of the `let` statement.
+    pub span: Span,
+    /// The name of the variable the guard is bound to.
+    pub name: String,
+    /// The span of the name of the variable.
+    pub name_span: Span,
+}
+
+impl GuardInfo {
+    pub const fn new(span: Span, name: String, name_span: Span) -> Self {
+        Self {
+            span,
+            name,
+            name_span,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about an `.await` expression.
+pub struct AwaitInfo {
+    /// The span of the `.await` expression, e.g. `fetch().await`.
+    pub span: Span,
+    /// The start of the statement that contains the `.await`, if the guard can be dropped before it.
+    pub drop_at: Option<LineColumn>,
+}
+
+impl AwaitInfo {
+    pub const fn new(span: Span, drop_at: Option<LineColumn>) -> Self {
+        Self { span, drop_at }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about an element or component in rsx.
+pub struct ElementInfo {
+    /// The name of the element, e.g. `div` or `MyComponent`.
+    pub name: String,
+    /// The span of the name of the element.
+    pub span: Span,
+    /// The span of the opening brace of the element, if it has one.
+    pub brace: Option<Span>,
+}
+
+impl ElementInfo {
+    pub const fn new(span: Span, name: String, brace: Option<Span>) -> Self {
+        Self { span, name, brace }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about a call to `spawn`.
+pub struct SpawnInfo {
+    /// The span of the call, e.g. `spawn(async move { ... })`.
+    pub span: Span,
+    /// The span of the name, e.g. `spawn`.
+    pub name_span: Span,
+    /// Whether the call is directly in the body of the component, outside of any conditionals or loops.
+    pub top_level: bool,
+}
+
+impl SpawnInfo {
+    pub const fn new(span: Span, name_span: Span, top_level: bool) -> Self {
+        Self {
+            span,
+            name_span,
+            top_level,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// A span of text in a source code file.
 pub struct Span {
@@ -197,8 +286,8 @@ impl From<proc_macro2::Span> for Span {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-/// A location in a source code file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+/// A location in a source code file. Lines start at 1 and columns start at 0.
 pub struct LineColumn {
     pub line: usize,
     pub column: usize,
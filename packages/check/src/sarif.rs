@@ -0,0 +1,135 @@
+use serde_json::{json, Value};
+
+use crate::{
+    config::{Level, Rule},
+    fix::Fix,
+    issues::IssueReport,
+    metadata::{LineColumn, Span},
+};
+
+/// Serialize the issues in the reports as a JSON array for other tools to consume.
+///
+/// Lines start at 1 and columns start at 0, matching the spans in the issues.
+pub fn json_report(reports: &[IssueReport]) -> Value {
+    let issues = reports
+        .iter()
+        .flat_map(|report| {
+            report.issues.iter().map(move |issue| {
+                let span = issue.span();
+                json!({
+                    "rule": issue.rule(),
+                    "level": report.level(issue),
+                    "message": issue.to_string(),
+                    "path": report.relative_path(),
+                    "start": span.start,
+                    "end": span.end,
+                    "fix": issue.fix(),
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+    Value::Array(issues)
+}
+
+/// Create a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log from the reports.
+///
+/// SARIF logs can be uploaded to GitHub code scanning and most other CI systems to annotate the issues in pull requests.
+pub fn sarif_report(reports: &[IssueReport]) -> Value {
+    let rules = Rule::ALL
+        .iter()
+        .map(|rule| {
+            let mut descriptor = json!({
+                "id": rule.id(),
+                "shortDescription": { "text": rule.description() },
+                "defaultConfiguration": { "level": sarif_level(rule.default_level()) },
+            });
+            if let Some(help_uri) = rule.help_uri() {
+                descriptor["helpUri"] = json!(help_uri);
+            }
+            descriptor
+        })
+        .collect::<Vec<_>>();
+
+    let results = reports
+        .iter()
+        .flat_map(|report| {
+            let uri = report.relative_path().to_string_lossy().replace('\\', "/");
+            report.issues.iter().map(move |issue| {
+                let mut result = json!({
+                    "ruleId": issue.rule().id(),
+                    "ruleIndex": Rule::ALL.iter().position(|rule| *rule == issue.rule()),
+                    "level": sarif_level(report.level(issue)),
+                    "message": { "text": issue.to_string() },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": uri },
+                            "region": sarif_region(issue.span()),
+                        }
+                    }],
+                });
+                if let Some(fix) = issue.fix() {
+                    result["fixes"] = json!([sarif_fix(&fix, &uri)]);
+                }
+                result
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "dx check",
+                    "informationUri": "https://dioxuslabs.com",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Allow => "none",
+        Level::Warn => "warning",
+        Level::Deny => "error",
+    }
+}
+
+/// SARIF regions are 1-indexed and the end column is exclusive
+fn sarif_region(span: &Span) -> Value {
+    sarif_range(&span.start, &span.end)
+}
+
+fn sarif_range(start: &LineColumn, end: &LineColumn) -> Value {
+    json!({
+        "startLine": start.line,
+        "startColumn": start.column + 1,
+        "endLine": end.line,
+        "endColumn": end.column + 1,
+    })
+}
+
+fn sarif_fix(fix: &Fix, uri: &str) -> Value {
+    let replacements = fix
+        .edits
+        .iter()
+        .map(|edit| {
+            json!({
+                "deletedRegion": sarif_range(&edit.start, &edit.end),
+                "insertedContent": { "text": edit.replacement },
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "description": { "text": fix.message },
+        "artifactChanges": [{
+            "artifactLocation": { "uri": uri },
+            "replacements": replacements,
+        }],
+    })
+}
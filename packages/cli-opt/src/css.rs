@@ -1,4 +1,10 @@
-use std::{hash::Hasher, path::Path};
+use std::{
+    collections::BTreeMap,
+    hash::Hasher,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{Mutex, RwLock},
+};
 
 use anyhow::{anyhow, Context};
 use codemap::SpanLoc;
@@ -10,16 +16,19 @@ use lightningcss::{
 };
 use manganis_core::{CssAssetOptions, CssModuleAssetOptions};
 
+use crate::source_map::{source_maps, write_source_map, SourceMapComment, SourceMapMode};
+
 pub(crate) fn process_css(
     css_options: &CssAssetOptions,
     source: &Path,
+    final_path: &Path,
     output_path: &Path,
 ) -> anyhow::Result<()> {
-    let css = std::fs::read_to_string(source)?;
+    let css = preprocess_css(css_options, source)?;
 
     let css = if css_options.minified() {
         // Try to minify the css. If we fail, log the error and use the unminified css
-        match minify_css(&css) {
+        match minify_css(&css, source, final_path) {
             Ok(minified) => minified,
             Err(err) => {
                 tracing::error!(
@@ -43,6 +52,229 @@ pub(crate) fn process_css(
     Ok(())
 }
 
+/// The tailwind CLI used to compile tailwind assets
+static TAILWIND_CLI: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Set the tailwind CLI used to compile assets with [`CssAssetOptions::with_tailwind`](manganis_core::CssAssetOptions).
+///
+/// If this is not set, `tailwindcss` is run from the `PATH`.
+pub fn set_tailwind_cli(path: PathBuf) {
+    *TAILWIND_CLI.write().unwrap() = Some(path);
+}
+
+/// Read the css, running it through tailwind or postcss if the options ask for it
+pub(crate) fn preprocess_css(
+    css_options: &CssAssetOptions,
+    source: &Path,
+) -> anyhow::Result<String> {
+    let mut css = match css_options.tailwind() {
+        true => compile_tailwind(source)?,
+        false => std::fs::read_to_string(source)?,
+    };
+
+    if css_options.postcss() {
+        css = run_postcss(source, &css)?;
+    }
+
+    Ok(css)
+}
+
+/// Compile a tailwind entrypoint. Tailwind scans the crate the stylesheet belongs to for class names.
+fn compile_tailwind(source: &Path) -> anyhow::Result<String> {
+    let binary = TAILWIND_CLI
+        .read()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(|| PathBuf::from("tailwindcss"));
+
+    let output = Command::new(&binary)
+        .arg("--input")
+        .arg(source)
+        .current_dir(crate_root(source))
+        .output()
+        .with_context(|| format!("Failed to run tailwind at {}", binary.display()))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to compile {} with tailwind: {}",
+            source.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Run css through postcss with the config closest to the source file
+fn run_postcss(source: &Path, css: &str) -> anyhow::Result<String> {
+    use std::io::Write;
+
+    let npx = if cfg!(windows) { "npx.cmd" } else { "npx" };
+    let config_dir = postcss_config(source)
+        .and_then(|config| config.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| crate_root(source));
+
+    let mut child = Command::new(npx)
+        .args(["--no-install", "postcss", "--from"])
+        .arg(source)
+        .current_dir(config_dir)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .context("Failed to run postcss. Make sure npm is installed.")?;
+
+    child.stdin.take().unwrap().write_all(css.as_bytes())?;
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to process {} with postcss: {}",
+            source.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// The dependencies of preprocessed css assets keyed by the source and whether it uses tailwind and postcss.
+///
+/// Walking the crate for tailwind sources is slow, so the result is reused for every hash and hot reload until the
+/// cache is cleared by the next build.
+static CSS_DEPENDENCIES: Mutex<BTreeMap<(PathBuf, bool, bool), Vec<PathBuf>>> =
+    Mutex::new(BTreeMap::new());
+
+/// Forget the cached dependencies of css assets compiled with tailwind or postcss.
+///
+/// This should be called at the start of every build and whenever a stylesheet changes so new imports and source files
+/// are picked up.
+pub fn clear_css_dependencies() {
+    CSS_DEPENDENCIES.lock().unwrap().clear();
+}
+
+/// The files that the output of a preprocessed css asset depends on besides the source itself.
+///
+/// Tailwind depends on every file it scans for class names in the crate. Postcss depends on its config. Both depend on
+/// the stylesheets the source imports.
+pub(crate) fn css_dependencies(css_options: &CssAssetOptions, source: &Path) -> Vec<PathBuf> {
+    if !css_options.tailwind() && !css_options.postcss() {
+        return vec![];
+    }
+
+    let key = (
+        source.to_path_buf(),
+        css_options.tailwind(),
+        css_options.postcss(),
+    );
+    if let Some(dependencies) = CSS_DEPENDENCIES.lock().unwrap().get(&key) {
+        return dependencies.clone();
+    }
+
+    let dependencies = find_css_dependencies(css_options, source);
+    CSS_DEPENDENCIES
+        .lock()
+        .unwrap()
+        .insert(key, dependencies.clone());
+    dependencies
+}
+
+fn find_css_dependencies(css_options: &CssAssetOptions, source: &Path) -> Vec<PathBuf> {
+    let mut dependencies = vec![];
+
+    if let Ok(css) = std::fs::read_to_string(source) {
+        dependencies.extend(css_imports(source, &css));
+    }
+
+    if css_options.tailwind() {
+        collect_tailwind_sources(&crate_root(source), &mut dependencies);
+    }
+
+    if css_options.postcss() {
+        dependencies.extend(postcss_config(source));
+    }
+
+    dependencies.sort();
+    dependencies.dedup();
+    dependencies.retain(|path| path != source);
+    dependencies
+}
+
+/// The local stylesheets imported with `@import "./file.css"`
+fn css_imports(source: &Path, css: &str) -> Vec<PathBuf> {
+    let dir = source.parent().unwrap_or(Path::new("."));
+    css.lines()
+        .filter_map(|line| line.trim().strip_prefix("@import"))
+        .filter_map(|import| {
+            let import = import.trim().trim_start_matches("url(");
+            let quote = import.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let path = import[1..].split(quote).next()?;
+            path.starts_with('.').then(|| dir.join(path))
+        })
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Collect the files tailwind scans for class names
+fn collect_tailwind_sources(dir: &Path, files: &mut Vec<PathBuf>) {
+    const EXTENSIONS: &[&str] = &["rs", "html", "js", "ts", "jsx", "tsx", "css"];
+    collect_sources(dir, EXTENSIONS, files);
+}
+
+/// Collect the files with one of the extensions in the directory, skipping build output and dependencies
+pub(crate) fn collect_sources(dir: &Path, extensions: &[&str], files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || name == "target" || name == "node_modules" {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_sources(&path, extensions, files);
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| extensions.contains(&ext))
+        {
+            files.push(path);
+        }
+    }
+}
+
+/// The root of the crate the file belongs to, or the directory of the file if it isn't in a crate
+pub(crate) fn crate_root(source: &Path) -> PathBuf {
+    let dir = source.parent().unwrap_or(Path::new("."));
+    dir.ancestors()
+        .find(|dir| dir.join("Cargo.toml").exists())
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// The postcss config closest to the source file
+fn postcss_config(source: &Path) -> Option<PathBuf> {
+    const CONFIGS: &[&str] = &[
+        "postcss.config.js",
+        "postcss.config.cjs",
+        "postcss.config.mjs",
+        "postcss.config.ts",
+        ".postcssrc",
+        ".postcssrc.json",
+    ];
+
+    source.parent()?.ancestors().find_map(|dir| {
+        CONFIGS
+            .iter()
+            .map(|config| dir.join(config))
+            .find(|config| config.exists())
+    })
+}
+
 pub(crate) fn process_css_module(
     css_options: &CssModuleAssetOptions,
     source: &Path,
@@ -87,7 +319,7 @@ pub(crate) fn process_css_module(
     // Minify CSS
     let css = if css_options.minified() {
         // Try to minify the css. If we fail, log the error and use the unminified css
-        match minify_css(&css) {
+        match minify_css(&css, source, final_path) {
             Ok(minified) => minified,
             Err(err) => {
                 tracing::error!(
@@ -111,8 +343,12 @@ pub(crate) fn process_css_module(
     Ok(())
 }
 
-pub(crate) fn minify_css(css: &str) -> anyhow::Result<String> {
+/// Minify css with lightningcss. If source maps are enabled, this also writes the source map for the asset that will end
+/// up at `final_path` and links it from the minified css.
+pub(crate) fn minify_css(css: &str, source: &Path, final_path: &Path) -> anyhow::Result<String> {
+    let filename = source.display().to_string();
     let options = ParserOptions {
+        filename: filename.clone(),
         error_recovery: true,
         ..Default::default()
     };
@@ -137,13 +373,43 @@ pub(crate) fn minify_css(css: &str) -> anyhow::Result<String> {
         targets,
         ..Default::default()
     })?;
+
+    // The map points at the css lightningcss parsed, which is the source file unless it was preprocessed
+    let source_map_mode = source_maps();
+    let mut source_map = match source_map_mode {
+        SourceMapMode::Disabled => None,
+        _ => {
+            let mut source_map = parcel_sourcemap::SourceMap::new("/");
+            let index = source_map.add_source(&filename);
+            source_map
+                .set_source_content(index as usize, css)
+                .map_err(|err| anyhow!("Failed to create css source map: {err:?}"))?;
+            Some(source_map)
+        }
+    };
+
     let printer = PrinterOptions {
         targets,
         minify: true,
+        source_map: source_map.as_mut(),
         ..Default::default()
     };
-    let res = stylesheet.to_css(printer)?;
-    Ok(res.code)
+    let mut code = stylesheet.to_css(printer)?.code;
+
+    if let Some(mut source_map) = source_map {
+        let map = source_map
+            .to_json(None)
+            .map_err(|err| anyhow!("Failed to serialize css source map: {err:?}"))?;
+        let link = write_source_map(
+            map.as_bytes(),
+            final_path,
+            SourceMapComment::Css,
+            source_map_mode,
+        )?;
+        code.extend(link);
+    }
+
+    Ok(code)
 }
 
 /// Compile scss with grass
@@ -167,13 +433,17 @@ pub(crate) fn compile_scss(
 }
 
 /// Process an scss/sass file into css.
+///
+/// Grass doesn't emit source maps, so the source map of an scss asset points at the css grass compiled rather than the
+/// scss itself.
 pub(crate) fn process_scss(
     scss_options: &CssAssetOptions,
     source: &Path,
+    final_path: &Path,
     output_path: &Path,
 ) -> anyhow::Result<()> {
     let css = compile_scss(scss_options, source)?;
-    let minified = minify_css(&css)?;
+    let minified = minify_css(&css, source, final_path)?;
 
     std::fs::write(output_path, minified).with_context(|| {
         format!(
@@ -225,3 +495,96 @@ pub(crate) fn hash_scss(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use manganis_core::{AssetOptionsBuilder, AssetVariant};
+
+    fn css_options(builder: AssetOptionsBuilder<CssAssetOptions>) -> CssAssetOptions {
+        match builder.into_asset_options().variant() {
+            AssetVariant::Css(options) => *options,
+            _ => unreachable!(),
+        }
+    }
+
+    fn write(path: &Path, contents: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn local_imports_are_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("assets/main.css");
+        write(&dir.path().join("assets/theme.css"), "");
+        write(&dir.path().join("assets/parts/buttons.css"), "");
+        let css = r#"
+            @import "./theme.css";
+            @import url('./parts/buttons.css');
+            @import "./missing.css";
+            @import "https://fonts.googleapis.com/css?family=Inter";
+            @import "tailwindcss";
+        "#;
+
+        let imports = css_imports(&source, css);
+        assert_eq!(
+            imports,
+            [
+                dir.path().join("assets/./theme.css"),
+                dir.path().join("assets/./parts/buttons.css"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tailwind_depends_on_crate_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let source = root.join("assets/tailwind.css");
+        write(&root.join("Cargo.toml"), "");
+        write(
+            &source,
+            "@import \"tailwindcss\";\n@import \"./theme.css\";",
+        );
+        write(&root.join("assets/theme.css"), "");
+        write(&root.join("src/main.rs"), "");
+        write(&root.join("src/index.html"), "");
+        write(&root.join("src/notes.txt"), "");
+        write(&root.join("target/debug/build.rs"), "");
+        write(&root.join("node_modules/pkg/index.js"), "");
+        write(&root.join(".git/hook.js"), "");
+
+        let options = css_options(CssAssetOptions::new().with_tailwind());
+        let dependencies = find_css_dependencies(&options, &source);
+        assert_eq!(
+            dependencies,
+            [
+                root.join("assets/theme.css"),
+                root.join("src/index.html"),
+                root.join("src/main.rs"),
+            ]
+        );
+
+        // Plain css doesn't depend on anything
+        let options = css_options(CssAssetOptions::new());
+        assert!(css_dependencies(&options, &source).is_empty());
+    }
+
+    #[test]
+    fn dependencies_are_cached_until_cleared() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("main.css");
+        write(&source, "@import \"./theme.css\";");
+        write(&dir.path().join("theme.css"), "");
+
+        let options = css_options(CssAssetOptions::new().with_postcss());
+        assert_eq!(css_dependencies(&options, &source).len(), 1);
+
+        write(&dir.path().join("postcss.config.js"), "");
+        assert_eq!(css_dependencies(&options, &source).len(), 1);
+
+        clear_css_dependencies();
+        assert_eq!(css_dependencies(&options, &source).len(), 2);
+    }
+}
@@ -1,9 +1,14 @@
 use anyhow::Context;
 use manganis::{AssetOptions, CssModuleAssetOptions, FolderAssetOptions};
-use manganis_core::{AssetVariant, CssAssetOptions, ImageAssetOptions, JsAssetOptions};
-use std::path::Path;
+use manganis_core::{
+    AssetVariant, CssAssetOptions, FontAssetOptions, ImageAssetOptions, JsAssetOptions,
+};
+use std::path::{Path, PathBuf};
 
-use crate::css::{process_css_module, process_scss};
+use crate::css::{css_dependencies, process_css_module, process_scss};
+use crate::font::{font_dependencies, process_font};
+use crate::image::image_width_paths;
+use crate::source_map::source_map_path;
 
 use super::{
     css::process_css, folder::process_folder, image::process_image, js::process_js,
@@ -19,6 +24,36 @@ pub fn process_file_to(
     process_file_to_with_options(options, source, output_path, false)
 }
 
+/// Get the files the processed output of an asset depends on, besides the source file itself.
+///
+/// Changes to any of these files should reprocess the asset. Most assets only depend on their source, but css compiled
+/// with tailwind depends on every file tailwind scans for class names.
+pub fn asset_dependencies(options: &AssetOptions, source: &Path) -> Vec<PathBuf> {
+    match resolve_asset_options(source, options.variant()) {
+        ResolvedAssetType::Css(css_options) => css_dependencies(&css_options, source),
+        ResolvedAssetType::Font(font_options) => font_dependencies(&font_options, source),
+        _ => vec![],
+    }
+}
+
+/// Get the files processing an asset writes next to its output, like source maps or the resized copies of an image.
+///
+/// These files are only written for some options, so they may not exist after processing.
+pub fn asset_extra_outputs(
+    options: &AssetOptions,
+    source: &Path,
+    output_path: &Path,
+) -> Vec<PathBuf> {
+    match resolve_asset_options(source, options.variant()) {
+        ResolvedAssetType::Css(_)
+        | ResolvedAssetType::CssModule(_)
+        | ResolvedAssetType::Scss(_)
+        | ResolvedAssetType::Js(_) => vec![source_map_path(output_path)],
+        ResolvedAssetType::Image(image_options) => image_width_paths(&image_options, output_path),
+        _ => vec![],
+    }
+}
+
 /// Process a specific file asset with additional options
 pub(crate) fn process_file_to_with_options(
     options: &AssetOptions,
@@ -52,19 +87,22 @@ pub(crate) fn process_file_to_with_options(
 
     match &resolved_options {
         ResolvedAssetType::Css(options) => {
-            process_css(options, source, &temp_path)?;
+            process_css(options, source, output_path, &temp_path)?;
         }
         ResolvedAssetType::CssModule(options) => {
             process_css_module(options, source, output_path, &temp_path)?;
         }
         ResolvedAssetType::Scss(options) => {
-            process_scss(options, source, &temp_path)?;
+            process_scss(options, source, output_path, &temp_path)?;
         }
         ResolvedAssetType::Js(options) => {
-            process_js(options, source, &temp_path, !in_folder)?;
+            process_js(options, source, output_path, &temp_path, !in_folder)?;
         }
         ResolvedAssetType::Image(options) => {
-            process_image(options, source, &temp_path)?;
+            process_image(options, source, output_path, &temp_path)?;
+        }
+        ResolvedAssetType::Font(options) => {
+            process_font(options, source, &temp_path)?;
         }
         ResolvedAssetType::Json => {
             process_json(source, &temp_path)?;
@@ -114,6 +152,8 @@ pub(crate) enum ResolvedAssetType {
     Scss(CssAssetOptions),
     /// A javascript asset
     Js(JsAssetOptions),
+    /// A font asset
+    Font(FontAssetOptions),
     /// A json asset
     Json,
     /// A folder asset
@@ -128,6 +168,7 @@ pub(crate) fn resolve_asset_options(source: &Path, options: &AssetVariant) -> Re
         AssetVariant::Css(css) => ResolvedAssetType::Css(*css),
         AssetVariant::CssModule(css) => ResolvedAssetType::CssModule(*css),
         AssetVariant::Js(js) => ResolvedAssetType::Js(*js),
+        AssetVariant::Font(font) => ResolvedAssetType::Font(*font),
         AssetVariant::Folder(folder) => ResolvedAssetType::Folder(*folder),
         AssetVariant::Unknown => resolve_unknown_asset_options(source),
         _ => {
@@ -141,7 +182,9 @@ fn resolve_unknown_asset_options(source: &Path) -> ResolvedAssetType {
     match source.extension().map(|e| e.to_string_lossy()).as_deref() {
         Some("scss" | "sass") => ResolvedAssetType::Scss(CssAssetOptions::default()),
         Some("css") => ResolvedAssetType::Css(CssAssetOptions::default()),
-        Some("js") => ResolvedAssetType::Js(JsAssetOptions::default()),
+        Some("js" | "mjs" | "ts" | "mts" | "cts" | "tsx" | "jsx") => {
+            ResolvedAssetType::Js(JsAssetOptions::default())
+        }
         Some("json") => ResolvedAssetType::Json,
         Some("jpg" | "jpeg" | "png" | "webp" | "avif") => {
             ResolvedAssetType::Image(ImageAssetOptions::default())
@@ -0,0 +1,199 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::Context;
+use manganis_core::FontAssetOptions;
+
+use crate::css::{collect_sources, crate_root};
+
+/// Subset a font and convert it to woff2 with `pyftsubset` from [fonttools](https://github.com/fonttools/fonttools).
+///
+/// Fonts that don't need either step are copied as is. If fonttools isn't installed, fonts that are only subset are
+/// copied with a warning so the app still works, just with a larger font. Fonts converted to woff2 fail to build instead,
+/// since the copy would be served as woff2 without being one.
+pub(crate) fn process_font(
+    font_options: &FontAssetOptions,
+    source: &Path,
+    output_path: &Path,
+) -> anyhow::Result<()> {
+    if !font_options.subset() && !font_options.woff2() {
+        return copy_font(source, output_path);
+    }
+
+    let mut command = Command::new("pyftsubset");
+    command
+        .arg(source)
+        .arg(format!("--output-file={}", output_path.display()))
+        // Keep kerning, ligatures and the other opentype features of the glyphs we keep
+        .arg("--layout-features=*");
+
+    if font_options.woff2() {
+        command.arg("--flavor=woff2");
+    }
+
+    // Without any subset, fonttools only converts the font
+    let ranges = font_options.unicode_ranges();
+    if !font_options.subset() {
+        command.args(["--unicodes=*", "--glyphs=*", "--name-IDs=*"]);
+    }
+    if !ranges.is_empty() {
+        let unicodes = ranges
+            .iter()
+            .map(|range| format!("{:X}-{:X}", range.start(), range.end()))
+            .collect::<Vec<_>>()
+            .join(",");
+        command.arg(format!("--unicodes={unicodes}"));
+    }
+
+    let text_file = output_path.with_extension("subset.txt");
+    if font_options.subset_rsx() {
+        std::fs::write(&text_file, rsx_characters(source))?;
+        command.arg(format!("--text-file={}", text_file.display()));
+    }
+
+    let output = command.output();
+    _ = std::fs::remove_file(&text_file);
+
+    match output {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => anyhow::bail!(
+            "Failed to subset font {}: {}",
+            source.display(),
+            String::from_utf8_lossy(&output.stderr)
+        ),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            fonttools_missing(font_options, source, output_path)
+        }
+        Err(err) => Err(err).context("Failed to run pyftsubset"),
+    }
+}
+
+fn fonttools_missing(
+    font_options: &FontAssetOptions,
+    source: &Path,
+    output_path: &Path,
+) -> anyhow::Result<()> {
+    if font_options.woff2() {
+        anyhow::bail!(
+            "Failed to convert {} to woff2 because fonttools is not installed. Install it with `pip install fonttools brotli`.",
+            source.display()
+        );
+    }
+
+    tracing::warn!(
+        "Failed to subset {} because fonttools is not installed. Install it with `pip install fonttools brotli`. Copying the font without changes.",
+        source.display()
+    );
+    copy_font(source, output_path)
+}
+
+fn copy_font(source: &Path, output_path: &Path) -> anyhow::Result<()> {
+    std::fs::copy(source, output_path).with_context(|| {
+        format!(
+            "Failed to write font to output location: {}",
+            output_path.display()
+        )
+    })?;
+    Ok(())
+}
+
+/// The files a font subset to the rsx depends on: every rust source in the crate of the font
+pub(crate) fn font_dependencies(font_options: &FontAssetOptions, source: &Path) -> Vec<PathBuf> {
+    let mut dependencies = vec![];
+    if font_options.subset_rsx() {
+        collect_sources(&crate_root(source), &["rs"], &mut dependencies);
+        dependencies.sort();
+    }
+    dependencies
+}
+
+/// Every character used in the rust sources of the crate, plus the printable ascii characters so numbers and other
+/// dynamic text always render with the font.
+fn rsx_characters(source: &Path) -> String {
+    let mut characters: BTreeSet<char> = (' '..='~').collect();
+
+    let mut files = vec![];
+    collect_sources(&crate_root(source), &["rs"], &mut files);
+    for file in files {
+        if let Ok(contents) = std::fs::read_to_string(&file) {
+            characters.extend(contents.chars().filter(|c| !c.is_control()));
+        }
+    }
+
+    characters.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use manganis_core::{AssetOptions, AssetOptionsBuilder, AssetVariant};
+
+    fn font_options(builder: AssetOptionsBuilder<FontAssetOptions>) -> FontAssetOptions {
+        match builder.into_asset_options().variant() {
+            AssetVariant::Font(options) => *options,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn fonts_without_fonttools_are_only_copied_if_they_stay_in_their_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("font.ttf");
+        std::fs::write(&source, "ttf").unwrap();
+
+        let subset = font_options(AssetOptions::font().with_latin());
+        let output = dir.path().join("font-dxh123.ttf");
+        fonttools_missing(&subset, &source, &output).unwrap();
+        assert_eq!(std::fs::read(&output).unwrap(), b"ttf");
+
+        let woff2 = font_options(AssetOptions::font().with_latin().with_woff2(true));
+        let output = dir.path().join("font-dxh123.woff2");
+        assert!(fonttools_missing(&woff2, &source, &output).is_err());
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn unicode_ranges_can_start_at_zero() {
+        let options = font_options(
+            AssetOptions::font()
+                .with_unicode_range(0, 0)
+                .with_unicode_range(0x30, 0x39),
+        );
+        assert_eq!(options.unicode_ranges().len(), 2);
+        assert_eq!(options.unicode_ranges()[0].to_string(), "U+0000");
+        assert_eq!(options.unicode_ranges()[1].to_string(), "U+0030-0039");
+        assert!(options.subset());
+
+        let options = font_options(AssetOptions::font());
+        assert!(options.unicode_ranges().is_empty());
+        assert!(!options.subset());
+    }
+
+    #[test]
+    fn rsx_subsets_depend_on_the_crate_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let source = root.join("assets/font.ttf");
+        std::fs::create_dir_all(root.join("assets")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "").unwrap();
+        std::fs::write(&source, "").unwrap();
+        std::fs::write(root.join("src/main.rs"), "rsx! { h1 { \"Grüße\" } }").unwrap();
+
+        let options = font_options(AssetOptions::font().with_rsx_subset(true));
+        assert_eq!(
+            font_dependencies(&options, &source),
+            [root.join("src/main.rs")]
+        );
+        assert!(font_dependencies(&font_options(AssetOptions::font()), &source).is_empty());
+
+        let characters = rsx_characters(&source);
+        assert!(characters.contains('ü'));
+        assert!(characters.contains('ß'));
+        assert!(characters.contains('7'));
+        assert!(!characters.contains('\n'));
+    }
+}
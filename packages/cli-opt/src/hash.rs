@@ -7,11 +7,13 @@ use std::{
 };
 
 use crate::{
-    css::hash_scss,
+    css::{css_dependencies, hash_scss},
     file::{resolve_asset_options, ResolvedAssetType},
+    font::font_dependencies,
+    image::bundle_image_options,
     js::hash_js,
 };
-use manganis::{AssetOptions, BundledAsset};
+use manganis::{AssetOptions, AssetVariant, BundledAsset};
 
 /// The opaque hash type manganis uses to identify assets. Each time an asset or asset options change, this hash will
 /// change. This hash is included in the URL of the bundled asset for cache busting.
@@ -74,10 +76,27 @@ pub(crate) fn hash_file_with_options(
             hash_js(options, source, hasher, !in_folder)?;
         }
 
+        // Tailwind and postcss output depends on other files. Hash the contents of the source and every dependency
+        ResolvedAssetType::Css(css_options) if css_options.tailwind() || css_options.postcss() => {
+            hash_file_contents(source, hasher)?;
+            for dependency in css_dependencies(css_options, source) {
+                hash_file_contents(&dependency, hasher)?;
+            }
+        }
+
+        // Fonts subset to the rsx depend on every rust source in the crate
+        ResolvedAssetType::Font(font_options) if font_options.subset_rsx() => {
+            hash_file_contents(source, hasher)?;
+            for dependency in font_dependencies(font_options, source) {
+                hash_file_contents(&dependency, hasher)?;
+            }
+        }
+
         // Otherwise, we can just hash the file contents
         ResolvedAssetType::CssModule(_)
         | ResolvedAssetType::Css(_)
         | ResolvedAssetType::Image(_)
+        | ResolvedAssetType::Font(_)
         | ResolvedAssetType::Json
         | ResolvedAssetType::File => {
             hash_file_contents(source, hasher)?;
@@ -113,7 +132,8 @@ pub(crate) fn hash_file_contents(source: &Path, hasher: &mut impl Hasher) -> any
     Ok(())
 }
 
-/// Add a hash to the asset, or log an error if it fails
+/// Add a hash to the asset, or log an error if it fails. This also adds the low quality placeholder to image assets
+/// that ask for one.
 pub fn add_hash_to_asset(asset: &mut BundledAsset) {
     let source = asset.absolute_source_path();
     match AssetHash::hash_file_contents(asset.options(), source) {
@@ -134,9 +154,11 @@ pub fn add_hash_to_asset(asset: &mut BundledAsset) {
                     .map(|ext| ext.to_string_lossy().to_string())
             });
 
-            // Rewrite scss as css
-            if let Some("scss" | "sass") = ext.as_deref() {
-                ext = Some("css".to_string());
+            // Rewrite scss as css and typescript or jsx as js
+            match ext.as_deref() {
+                Some("scss" | "sass") => ext = Some("css".to_string()),
+                Some("ts" | "mts" | "cts" | "tsx" | "jsx") => ext = Some("js".to_string()),
+                _ => {}
             }
 
             let hash = hash.bytes();
@@ -160,6 +182,14 @@ pub fn add_hash_to_asset(asset: &mut BundledAsset) {
 
             let bundled_path = bundled_path.to_string_lossy().to_string();
 
+            // The srcset widths and placeholder of images depend on the image, so they are stored in the options
+            let options = match options.variant() {
+                AssetVariant::Image(image) => options.with_variant(AssetVariant::Image(
+                    bundle_image_options(image, &source_path, &hash),
+                )),
+                _ => options,
+            };
+
             *asset = BundledAsset::new(source, &bundled_path, options);
         }
         Err(err) => {
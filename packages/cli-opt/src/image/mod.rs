@@ -1,8 +1,14 @@
-use std::path::Path;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
 
 use anyhow::Context;
+use base64::Engine;
+use image::DynamicImage;
 use jpg::compress_jpg;
-use manganis_core::{ImageAssetOptions, ImageFormat, ImageSize};
+use manganis_core::{image_width_variant, ImageAssetOptions, ImageFormat, ImageSize};
 use png::compress_png;
 
 mod jpg;
@@ -11,12 +17,10 @@ mod png;
 pub(crate) fn process_image(
     image_options: &ImageAssetOptions,
     source: &Path,
+    final_path: &Path,
     output_path: &Path,
 ) -> anyhow::Result<()> {
-    let mut image = image::ImageReader::new(std::io::Cursor::new(&*std::fs::read(source)?))
-        .with_guessed_format()
-        .context("Failed to guess image format")?
-        .decode();
+    let mut image = decode_image(source);
 
     if let Ok(image) = &mut image {
         if let ImageSize::Manual { width, height } = image_options.size() {
@@ -24,7 +28,172 @@ pub(crate) fn process_image(
         }
     }
 
-    match (image, image_options.format()) {
+    // Write the copies for the srcset next to where the image will end up. Widths larger than the image were already
+    // dropped from the options by [`bundle_image_options`], since images are never scaled up.
+    if !image_options.sizes().is_empty() {
+        let image = image
+            .as_ref()
+            .map_err(|err| anyhow::anyhow!("Failed to decode image for resizing: {err}"))?;
+        for &width in image_options.sizes() {
+            let resized = image.resize(
+                width.min(image.width()),
+                u32::MAX,
+                image::imageops::FilterType::Lanczos3,
+            );
+            save_image(
+                Ok(resized),
+                image_options.format(),
+                source,
+                &image_width_path(final_path, width),
+            )?;
+        }
+    }
+
+    save_image(image, image_options.format(), source, output_path)
+}
+
+/// The paths of the resized copies [`process_image`] writes next to the image at `output_path`
+pub(crate) fn image_width_paths(
+    image_options: &ImageAssetOptions,
+    output_path: &Path,
+) -> Vec<PathBuf> {
+    image_options
+        .sizes()
+        .iter()
+        .map(|width| image_width_path(output_path, *width))
+        .collect()
+}
+
+fn image_width_path(output_path: &Path, width: u32) -> PathBuf {
+    let file_name = output_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    output_path.with_file_name(image_width_variant(&file_name, width))
+}
+
+fn decode_image(source: &Path) -> anyhow::Result<DynamicImage> {
+    let image = image::ImageReader::new(std::io::Cursor::new(&*std::fs::read(source)?))
+        .with_guessed_format()
+        .context("Failed to guess image format")?
+        .decode()?;
+    Ok(image)
+}
+
+/// Placeholders keyed by the source and hash of the image. Creating a placeholder decodes the whole image, so it is
+/// only done once for every version of the image instead of on every rebuild.
+static PLACEHOLDERS: Mutex<BTreeMap<(PathBuf, String), Option<String>>> =
+    Mutex::new(BTreeMap::new());
+
+/// Fill in the parts of the image options that depend on the image itself before they are stored in the bundle.
+///
+/// Widths larger than the image are dropped from the `srcset` and the low quality placeholder is generated if the
+/// options ask for one.
+pub(crate) fn bundle_image_options(
+    image_options: &ImageAssetOptions,
+    source: &Path,
+    hash: &str,
+) -> ImageAssetOptions {
+    let mut options = *image_options;
+
+    if !options.sizes().is_empty() {
+        let width = match options.size() {
+            ImageSize::Manual { width, .. } => Some(width),
+            ImageSize::Automatic => image_dimensions(source).map(|(width, _)| width),
+        };
+        if let Some(width) = width {
+            options = options.without_sizes_above(width);
+        }
+    }
+
+    if options.low_quality_preview() {
+        let key = (source.to_path_buf(), hash.to_string());
+        let cached = PLACEHOLDERS.lock().unwrap().get(&key).cloned();
+        let placeholder = cached.unwrap_or_else(|| {
+            let placeholder = image_placeholder(source)
+                .inspect_err(|err| {
+                    tracing::warn!(
+                        "Failed to create placeholder for {}: {err}",
+                        source.display()
+                    )
+                })
+                .ok();
+            PLACEHOLDERS
+                .lock()
+                .unwrap()
+                .insert(key, placeholder.clone());
+            placeholder
+        });
+        if let Some(placeholder) = placeholder {
+            options = options.with_placeholder(&placeholder);
+        }
+    }
+
+    options
+}
+
+/// Read the width and height of an image from its header without decoding it
+fn image_dimensions(source: &Path) -> Option<(u32, u32)> {
+    image::ImageReader::open(source)
+        .ok()?
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+/// The largest side of a placeholder in pixels. Placeholders are stored in the binary with a fixed size, so smaller
+/// placeholders are tried until one fits.
+const PLACEHOLDER_SIZES: [u32; 4] = [16, 12, 8, 4];
+
+/// The maximum length of the base64 encoded placeholder that fits in an [`ImageAssetOptions`]
+const MAX_PLACEHOLDER_LEN: usize = 256;
+
+/// Create a tiny blurred png of the image, encoded as base64
+pub(crate) fn image_placeholder(source: &Path) -> anyhow::Result<String> {
+    let image = decode_image(source)?;
+    let has_alpha = image.color().has_alpha();
+
+    for size in PLACEHOLDER_SIZES {
+        let tiny = image.thumbnail(size, size).blur(0.5);
+
+        let mut png = vec![];
+        let mut encoder = ::png::Encoder::new(&mut png, tiny.width(), tiny.height());
+        encoder.set_depth(::png::BitDepth::Eight);
+        encoder.set_compression(::png::Compression::Best);
+        let pixels = match has_alpha {
+            true => {
+                encoder.set_color(::png::ColorType::Rgba);
+                tiny.into_rgba8().into_raw()
+            }
+            false => {
+                encoder.set_color(::png::ColorType::Rgb);
+                tiny.into_rgb8().into_raw()
+            }
+        };
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+        writer.finish()?;
+
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&png);
+        if encoded.len() <= MAX_PLACEHOLDER_LEN {
+            return Ok(encoded);
+        }
+    }
+
+    anyhow::bail!(
+        "Failed to create a small enough placeholder for {}",
+        source.display()
+    )
+}
+
+fn save_image(
+    image: anyhow::Result<DynamicImage>,
+    format: ImageFormat,
+    source: &Path,
+    output_path: &Path,
+) -> anyhow::Result<()> {
+    match (image, format) {
         (image, ImageFormat::Png) => {
             compress_png(image.context("Failed to decode image")?, output_path);
         }
@@ -71,3 +240,99 @@ pub(crate) fn process_image(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use manganis_core::{AssetOptions, AssetOptionsBuilder, AssetVariant};
+
+    fn image_options(builder: AssetOptionsBuilder<ImageAssetOptions>) -> ImageAssetOptions {
+        match builder.into_asset_options().variant() {
+            AssetVariant::Image(options) => *options,
+            _ => unreachable!(),
+        }
+    }
+
+    fn write_image(path: &Path, width: u32, height: u32) {
+        image::RgbImage::from_fn(width, height, |x, y| {
+            image::Rgb([(x * 255 / width) as u8, (y * 255 / height) as u8, 128])
+        })
+        .save(path)
+        .unwrap();
+    }
+
+    #[test]
+    fn width_variants_are_added_to_the_file_stem() {
+        assert_eq!(
+            image_width_variant("/assets/image-dxh123.webp", 640),
+            "/assets/image-dxh123-640w.webp"
+        );
+        assert_eq!(
+            image_width_variant("/assets/v1.2/image.tar.gz", 320),
+            "/assets/v1.2/image-320w.tar.gz"
+        );
+        assert_eq!(image_width_variant("image", 320), "image-320w");
+    }
+
+    #[test]
+    fn sizes_are_the_set_widths() {
+        let options = image_options(AssetOptions::image());
+        assert!(options.sizes().is_empty());
+
+        let options = image_options(AssetOptions::image().with_sizes([320, 640, 1280]));
+        assert_eq!(options.sizes(), [320, 640, 1280]);
+        assert_eq!(options.without_sizes_above(640).sizes(), [320, 640]);
+        assert!(options.without_sizes_above(100).sizes().is_empty());
+    }
+
+    #[test]
+    fn widths_larger_than_the_image_are_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("image.png");
+        write_image(&source, 100, 50);
+
+        let options = image_options(AssetOptions::image().with_sizes([50, 100, 200]));
+        let bundled = bundle_image_options(&options, &source, "hash");
+        assert_eq!(bundled.sizes(), [50, 100]);
+        assert_eq!(image_width_paths(&bundled, &source).len(), 2);
+
+        // Manually sized images are resized before the widths are written
+        let options = image_options(
+            AssetOptions::image()
+                .with_size(ImageSize::Manual {
+                    width: 60,
+                    height: 30,
+                })
+                .with_sizes([50, 100]),
+        );
+        assert_eq!(
+            bundle_image_options(&options, &source, "hash").sizes(),
+            [50]
+        );
+    }
+
+    #[test]
+    fn placeholders_are_tiny_pngs() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("image.png");
+        write_image(&source, 640, 320);
+
+        let placeholder = image_placeholder(&source).unwrap();
+        assert!(placeholder.len() <= MAX_PLACEHOLDER_LEN);
+
+        let png = base64::engine::general_purpose::STANDARD
+            .decode(&placeholder)
+            .unwrap();
+        let placeholder = image::load_from_memory(&png).unwrap();
+        assert!(placeholder.width() <= PLACEHOLDER_SIZES[0]);
+        assert_eq!(placeholder.width(), placeholder.height() * 2);
+
+        let options = image_options(AssetOptions::image().with_low_quality_preview(true));
+        let bundled = bundle_image_options(&options, &source, "hash");
+        assert!(bundled.placeholder().is_some());
+
+        let options = image_options(AssetOptions::image());
+        let bundled = bundle_image_options(&options, &source, "hash");
+        assert_eq!(bundled.placeholder(), None);
+    }
+}
@@ -1,16 +1,20 @@
 use std::hash::Hasher;
+use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::Context;
-use manganis_core::JsAssetOptions;
+use manganis_core::{EsTarget, JsAssetOptions};
+use swc_common::comments::SingleThreadedComments;
 use swc_common::errors::Emitter;
 use swc_common::errors::Handler;
-use swc_common::input::SourceFileInput;
 use swc_ecma_minifier::option::{ExtraOptions, MinifyOptions};
-use swc_ecma_parser::lexer::Lexer;
-use swc_ecma_parser::Parser;
 use swc_ecma_transforms_base::fixer::fixer;
+use swc_ecma_transforms_base::helpers::{inject_helpers, Helpers, HELPERS};
+use swc_ecma_transforms_base::resolver;
+use swc_ecma_transforms_compat::{es2015, es2016, es2017, es2018, es2019, es2020, es2021, es2022};
+use swc_ecma_transforms_react::{react, Runtime};
+use swc_ecma_transforms_typescript::strip;
 use swc_ecma_visit::VisitMutWith;
 
 use std::collections::HashMap;
@@ -18,14 +22,16 @@ use std::collections::HashMap;
 use anyhow::Error;
 use swc_bundler::{Bundler, Config, Load, ModuleData, ModuleRecord};
 use swc_common::{
-    errors::HANDLER, sync::Lrc, FileName, FilePathMapping, Globals, Mark, SourceMap, Span, GLOBALS,
+    errors::HANDLER, source_map::DefaultSourceMapGenConfig, sync::Lrc, FileName, FilePathMapping,
+    Globals, Mark, SourceFile, SourceMap, Span, GLOBALS,
 };
 use swc_ecma_ast::*;
 use swc_ecma_codegen::text_writer::JsWriter;
 use swc_ecma_loader::{resolvers::node::NodeModulesResolver, TargetEnv};
-use swc_ecma_parser::{parse_file_as_module, Syntax};
+use swc_ecma_parser::{parse_file_as_module, EsSyntax, Syntax, TsSyntax};
 
 use crate::hash::hash_file_contents;
+use crate::source_map::{source_maps, write_source_map, SourceMapComment, SourceMapMode};
 
 struct TracingEmitter;
 
@@ -46,29 +52,137 @@ impl Emitter for TracingEmitter {
     }
 }
 
-/// Run a closure with the swc globals and handler set up
+/// Run a closure with the swc globals, handler and helpers set up
 fn inside_handler<O>(f: impl FnOnce(&Globals, Lrc<SourceMap>) -> O) -> O {
     let globals = Globals::new();
     let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
     let handler = Handler::with_emitter_and_flags(Box::new(TracingEmitter), Default::default());
-    GLOBALS.set(&globals, || HANDLER.set(&handler, || f(&globals, cm)))
+    GLOBALS.set(&globals, || {
+        // Compiling can inject helpers like `_class_call_check`. They are inlined into the output
+        let helpers = Helpers::new(false);
+        HANDLER.set(&handler, || HELPERS.set(&helpers, || f(&globals, cm)))
+    })
 }
 
+/// Bundle, compile and optionally minify a js file into the writer. If `source_map` is true, this returns the source
+/// map of the output as json.
 fn bundle_js_to_writer(
     file: PathBuf,
     bundle: bool,
-    minify: bool,
+    js_options: &JsAssetOptions,
+    source_map: bool,
     write_to: &mut impl std::io::Write,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Option<Vec<u8>>> {
     inside_handler(|globals, cm| {
-        bundle_js_to_writer_inside_handler(globals, cm, file, bundle, minify, write_to)
+        bundle_js_to_writer_inside_handler(
+            globals, cm, file, bundle, js_options, source_map, write_to,
+        )
     })
 }
 
+/// Check if a file needs to be compiled to javascript before the browser can run it
+fn is_typescript_or_jsx(file: &Path) -> bool {
+    let syntax = syntax_for(file);
+    syntax.typescript() || syntax.jsx()
+}
+
+/// Pick the syntax to parse a file with from its extension
+fn syntax_for(file: &Path) -> Syntax {
+    match file.extension().and_then(|ext| ext.to_str()) {
+        Some("ts" | "mts" | "cts") => Syntax::Typescript(TsSyntax::default()),
+        Some("tsx") => Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        }),
+        Some("jsx") => Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+        _ => Syntax::Es(Default::default()),
+    }
+}
+
+/// Parse a js, ts or jsx file and compile it to plain javascript
+fn parse_module(cm: &Lrc<SourceMap>, file: &Path) -> anyhow::Result<(Lrc<SourceFile>, Module)> {
+    let fm = cm.load_file(file)?;
+    let syntax = syntax_for(file);
+
+    let module = HANDLER.with(|handler| {
+        parse_file_as_module(&fm, syntax, Default::default(), None, &mut Vec::new()).map_err(
+            |err| {
+                let mut error = err.into_diagnostic(handler);
+                // swc errors panic on drop if you don't cancel them
+                error.cancel();
+                anyhow::anyhow!("{}", error.message())
+            },
+        )
+    })?;
+
+    if !syntax.typescript() && !syntax.jsx() {
+        return Ok((fm, module));
+    }
+
+    // Strip the types and compile jsx without type checking, the same way esbuild does
+    let unresolved_mark = Mark::new();
+    let top_level_mark = Mark::new();
+    let module = Program::Module(module)
+        .apply((
+            resolver(unresolved_mark, top_level_mark, syntax.typescript()),
+            syntax
+                .typescript()
+                .then(|| strip(unresolved_mark, top_level_mark)),
+            syntax.jsx().then(|| {
+                react::<SingleThreadedComments>(
+                    cm.clone(),
+                    None,
+                    swc_ecma_transforms_react::Options {
+                        runtime: Some(Runtime::Classic),
+                        ..Default::default()
+                    },
+                    top_level_mark,
+                    unresolved_mark,
+                )
+            }),
+        ))
+        .expect_module();
+
+    Ok((fm, module))
+}
+
+/// Rewrite syntax newer than the target into syntax the target supports
+fn downlevel(module: Module, target: EsTarget) -> Module {
+    if target == EsTarget::EsNext {
+        return module;
+    }
+
+    let unresolved_mark = Mark::new();
+    let top_level_mark = Mark::new();
+    Program::Module(module)
+        .apply((
+            resolver(unresolved_mark, top_level_mark, false),
+            (target < EsTarget::Es2022)
+                .then(|| es2022::es2022(Default::default(), unresolved_mark)),
+            (target < EsTarget::Es2021).then(es2021::es2021),
+            (target < EsTarget::Es2020)
+                .then(|| es2020::es2020(Default::default(), unresolved_mark)),
+            (target < EsTarget::Es2019).then(es2019::es2019),
+            (target < EsTarget::Es2018).then(|| es2018::es2018(Default::default())),
+            (target < EsTarget::Es2017)
+                .then(|| es2017::es2017(Default::default(), unresolved_mark)),
+            (target < EsTarget::Es2016).then(es2016::es2016),
+            (target < EsTarget::Es2015).then(|| {
+                es2015::es2015::<SingleThreadedComments>(unresolved_mark, None, Default::default())
+            }),
+            inject_helpers(unresolved_mark),
+        ))
+        .expect_module()
+}
+
 fn resolve_js_inside_handler(
     globals: &Globals,
     file: PathBuf,
     bundle: bool,
+    js_options: &JsAssetOptions,
     cm: &Lrc<SourceMap>,
 ) -> anyhow::Result<Module> {
     if bundle {
@@ -80,6 +194,7 @@ fn resolve_js_inside_handler(
             node_resolver,
             Config {
                 require: true,
+                external_modules: js_options.external().map(Into::into).collect(),
                 ..Default::default()
             },
             Box::new(Hook),
@@ -96,24 +211,8 @@ fn resolve_js_inside_handler(
             .ok_or_else(|| anyhow::anyhow!("swc did not output any bundles"))?;
         Ok(bundle.module)
     } else {
-        let fm = cm.load_file(Path::new(&file)).expect("Failed to load file");
-
-        let lexer = Lexer::new(
-            Default::default(),
-            Default::default(),
-            SourceFileInput::from(&*fm),
-            None,
-        );
-        let mut parser = Parser::new_from(lexer);
-
-        parser.parse_module().map_err(|err| {
-            HANDLER.with(|handler| {
-                let mut error = err.into_diagnostic(handler);
-                // swc errors panic on drop if you don't cancel them
-                error.cancel();
-                anyhow::anyhow!("{}", error.message())
-            })
-        })
+        let (_, module) = parse_module(cm, &file)?;
+        Ok(module)
     }
 }
 
@@ -122,10 +221,13 @@ fn bundle_js_to_writer_inside_handler(
     cm: Lrc<SourceMap>,
     file: PathBuf,
     bundle: bool,
-    minify: bool,
+    js_options: &JsAssetOptions,
+    source_map: bool,
     write_to: &mut impl std::io::Write,
-) -> anyhow::Result<()> {
-    let mut module = resolve_js_inside_handler(globals, file, bundle, &cm)?;
+) -> anyhow::Result<Option<Vec<u8>>> {
+    let minify = js_options.minified();
+    let mut module = resolve_js_inside_handler(globals, file, bundle, js_options, &cm)?;
+    module = downlevel(module, js_options.target());
 
     if minify {
         module = swc_ecma_minifier::optimize(
@@ -146,19 +248,35 @@ fn bundle_js_to_writer_inside_handler(
             },
         )
         .expect_module();
-        module.visit_mut_with(&mut fixer(None));
+    }
+    module.visit_mut_with(&mut fixer(None));
+
+    let mut mappings = vec![];
+    {
+        let mut emitter = swc_ecma_codegen::Emitter {
+            cfg: swc_ecma_codegen::Config::default().with_minify(minify),
+            cm: cm.clone(),
+            comments: None,
+            wr: Box::new(JsWriter::new(
+                cm.clone(),
+                "\n",
+                &mut *write_to,
+                source_map.then_some(&mut mappings),
+            )),
+        };
+
+        emitter.emit_module(&module)?;
     }
 
-    let mut emitter = swc_ecma_codegen::Emitter {
-        cfg: swc_ecma_codegen::Config::default().with_minify(minify),
-        cm: cm.clone(),
-        comments: None,
-        wr: Box::new(JsWriter::new(cm, "\n", write_to, None)),
-    };
+    if !source_map {
+        return Ok(None);
+    }
 
-    emitter.emit_module(&module)?;
+    let mut map = vec![];
+    cm.build_source_map(&mappings, None, DefaultSourceMapGenConfig)
+        .to_writer(&mut map)?;
 
-    Ok(())
+    Ok(Some(map))
 }
 
 struct PathLoader {
@@ -172,24 +290,8 @@ impl Load for PathLoader {
             _ => anyhow::bail!("Only real files are supported"),
         };
 
-        let fm = self.cm.load_file(file)?;
-
-        let module = HANDLER.with(|handler| {
-            parse_file_as_module(
-                &fm,
-                Syntax::Es(Default::default()),
-                Default::default(),
-                None,
-                &mut Vec::new(),
-            )
-            .map_err(|err| {
-                let mut error = err.into_diagnostic(handler);
-                // swc errors panic on drop if you don't cancel them
-                error.cancel();
-                anyhow::anyhow!("{}", error.message())
-            })
-            .context("Failed to parse javascript")
-        })?;
+        let (fm, module) = parse_module(&self.cm, file)
+            .with_context(|| format!("Failed to parse {}", file.display()))?;
 
         Ok(ModuleData {
             fm,
@@ -241,15 +343,40 @@ impl swc_bundler::Hook for Hook {
 pub(crate) fn process_js(
     js_options: &JsAssetOptions,
     source: &Path,
+    final_path: &Path,
     output_path: &Path,
     bundle: bool,
 ) -> anyhow::Result<()> {
     let mut writer = std::io::BufWriter::new(std::fs::File::create(output_path)?);
-    if js_options.minified() {
-        if let Err(err) = bundle_js_to_writer(source.to_path_buf(), bundle, true, &mut writer) {
-            tracing::error!("Failed to minify js. Falling back to non-minified: {err}");
-        } else {
-            return Ok(());
+    if needs_swc(js_options, source) {
+        let source_map_mode = source_maps();
+        let source_map = source_map_mode != SourceMapMode::Disabled;
+        match bundle_js_to_writer(
+            source.to_path_buf(),
+            bundle,
+            js_options,
+            source_map,
+            &mut writer,
+        ) {
+            Ok(map) => {
+                if let Some(map) = map {
+                    let link =
+                        write_source_map(&map, final_path, SourceMapComment::Js, source_map_mode)?;
+                    if let Some(link) = link {
+                        writer.write_all(link.as_bytes())?;
+                    }
+                }
+                return Ok(());
+            }
+            // Typescript and jsx can't run in the browser as is, so there is nothing to fall back to
+            Err(err) if is_typescript_or_jsx(source) => {
+                return Err(err.context(format!("Failed to compile {}", source.display())));
+            }
+            Err(err) => {
+                tracing::error!("Failed to minify js. Falling back to non-minified: {err}");
+                // Start over so the fallback doesn't append to partially written output
+                writer = std::io::BufWriter::new(std::fs::File::create(output_path)?);
+            }
         }
     }
     let mut source_file = std::fs::File::open(source)?;
@@ -263,9 +390,19 @@ pub(crate) fn process_js(
     Ok(())
 }
 
-fn hash_js_module(file: PathBuf, hasher: &mut impl Hasher, bundle: bool) -> anyhow::Result<()> {
+/// Check if the js needs to go through swc, or if it can be copied as is
+fn needs_swc(js_options: &JsAssetOptions, source: &Path) -> bool {
+    js_options.minified() || js_options.target() != EsTarget::EsNext || is_typescript_or_jsx(source)
+}
+
+fn hash_js_module(
+    file: PathBuf,
+    hasher: &mut impl Hasher,
+    bundle: bool,
+    js_options: &JsAssetOptions,
+) -> anyhow::Result<()> {
     inside_handler(|globals, cm| {
-        _ = resolve_js_inside_handler(globals, file, bundle, &cm)?;
+        _ = resolve_js_inside_handler(globals, file, bundle, js_options, &cm)?;
 
         for file in cm.files().iter() {
             let hash = file.src_hash;
@@ -282,8 +419,8 @@ pub(crate) fn hash_js(
     hasher: &mut impl Hasher,
     bundle: bool,
 ) -> anyhow::Result<()> {
-    if js_options.minified() {
-        if let Err(err) = hash_js_module(source.to_path_buf(), hasher, bundle) {
+    if needs_swc(js_options, source) {
+        if let Err(err) = hash_js_module(source.to_path_buf(), hasher, bundle, js_options) {
             tracing::error!("Failed to minify js. Falling back to non-minified: {err}");
             hash_file_contents(source, hasher)?;
         }
@@ -293,3 +430,102 @@ pub(crate) fn hash_js(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use manganis_core::{AssetOptions, AssetOptionsBuilder, AssetVariant};
+
+    fn js_options(builder: AssetOptionsBuilder<JsAssetOptions>) -> JsAssetOptions {
+        match builder.into_asset_options().variant() {
+            AssetVariant::Js(options) => *options,
+            _ => unreachable!(),
+        }
+    }
+
+    fn compile(source: &str, target: EsTarget) -> String {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("script.js");
+        std::fs::write(&file, source).unwrap();
+
+        let options = js_options(AssetOptions::js().with_minify(false).with_target(target));
+        let mut output = vec![];
+        bundle_js_to_writer(file, false, &options, false, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn targets_are_ordered_by_version() {
+        let targets = [
+            EsTarget::Es5,
+            EsTarget::Es2015,
+            EsTarget::Es2016,
+            EsTarget::Es2017,
+            EsTarget::Es2018,
+            EsTarget::Es2019,
+            EsTarget::Es2020,
+            EsTarget::Es2021,
+            EsTarget::Es2022,
+            EsTarget::EsNext,
+        ];
+        assert!(targets.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn newer_syntax_is_downleveled() {
+        let source = "const value = input?.field ?? 1;\nconst add = (a, b) => a + b;\n";
+
+        let output = compile(source, EsTarget::EsNext);
+        assert!(output.contains("?."));
+        assert!(output.contains("??"));
+
+        let output = compile(source, EsTarget::Es2019);
+        assert!(!output.contains("?."));
+        assert!(!output.contains("??"));
+        assert!(output.contains("=>"));
+
+        let output = compile(source, EsTarget::Es5);
+        assert!(!output.contains("=>"));
+        assert!(!output.contains("const "));
+    }
+
+    #[test]
+    fn plain_js_skips_swc() {
+        let js = Path::new("script.js");
+        let unminified = js_options(AssetOptions::js().with_minify(false));
+        assert!(!needs_swc(&unminified, js));
+        assert!(needs_swc(&js_options(AssetOptions::js()), js));
+        assert!(needs_swc(
+            &js_options(
+                AssetOptions::js()
+                    .with_minify(false)
+                    .with_target(EsTarget::Es2017)
+            ),
+            js
+        ));
+
+        for file in [
+            "script.ts",
+            "script.mts",
+            "script.cts",
+            "script.tsx",
+            "script.jsx",
+        ] {
+            assert!(needs_swc(&unminified, Path::new(file)), "{file}");
+        }
+    }
+
+    #[test]
+    fn external_imports_are_kept_in_order() {
+        let options = js_options(
+            AssetOptions::js()
+                .with_external("three")
+                .with_external("@scope/pkg/sub"),
+        );
+        assert_eq!(
+            options.external().collect::<Vec<_>>(),
+            ["three", "@scope/pkg/sub"]
+        );
+        assert_eq!(js_options(AssetOptions::js()).external().count(), 0);
+    }
+}
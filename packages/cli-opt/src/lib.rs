@@ -11,13 +11,19 @@ mod build_info;
 mod css;
 mod file;
 mod folder;
+mod font;
 mod hash;
 mod image;
 mod js;
 mod json;
+mod source_map;
 
-pub use file::process_file_to;
+pub use css::{clear_css_dependencies, set_tailwind_cli};
+pub use file::{asset_dependencies, asset_extra_outputs, process_file_to};
 pub use hash::add_hash_to_asset;
+pub use source_map::{
+    move_source_maps, set_source_maps, source_map_path, source_maps, SourceMapMode,
+};
 
 /// A manifest of all assets collected from dependencies
 ///
@@ -0,0 +1,170 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use anyhow::Context;
+
+/// How source maps are emitted for processed css, scss and js assets.
+///
+/// Maps are only written for assets whose output differs from their source: minified css, scss, and js that is
+/// minified, bundled or compiled from typescript or jsx. Other assets are copied as is, so devtools already show the
+/// original source.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SourceMapMode {
+    /// Don't emit source maps
+    #[default]
+    Disabled,
+    /// Write a `.map` file next to each asset and link it from the asset with a `sourceMappingURL` comment
+    Linked,
+    /// Write a `.map` file next to each asset without linking it. Browsers won't load the map, but it can still be
+    /// uploaded to an error tracker.
+    Hidden,
+}
+
+/// The source map mode used when processing assets
+static SOURCE_MAPS: RwLock<SourceMapMode> = RwLock::new(SourceMapMode::Disabled);
+
+/// Set how source maps are emitted for processed css, scss and js assets.
+///
+/// Source maps are disabled by default.
+pub fn set_source_maps(mode: SourceMapMode) {
+    *SOURCE_MAPS.write().unwrap() = mode;
+}
+
+/// Get how source maps are emitted for processed css, scss and js assets
+pub fn source_maps() -> SourceMapMode {
+    *SOURCE_MAPS.read().unwrap()
+}
+
+/// The path of the source map for an asset written to `output_path`
+pub fn source_map_path(output_path: &Path) -> PathBuf {
+    let mut file_name = output_path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".map");
+    output_path.with_file_name(file_name)
+}
+
+/// The syntax of the comment that links an asset to its source map
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SourceMapComment {
+    Css,
+    Js,
+}
+
+/// Write the source map for the asset that will end up at `final_path`. Returns the comment that should be appended
+/// to the asset to link the source map, if the mode asks for it.
+pub(crate) fn write_source_map(
+    map: &[u8],
+    final_path: &Path,
+    comment: SourceMapComment,
+    mode: SourceMapMode,
+) -> anyhow::Result<Option<String>> {
+    let map_path = source_map_path(final_path);
+    std::fs::write(&map_path, map).with_context(|| {
+        format!(
+            "Failed to write source map to output location: {}",
+            map_path.display()
+        )
+    })?;
+
+    if mode != SourceMapMode::Linked {
+        return Ok(None);
+    }
+
+    let url = map_path.file_name().unwrap_or_default().to_string_lossy();
+    Ok(Some(match comment {
+        SourceMapComment::Css => format!("\n/*# sourceMappingURL={url} */"),
+        SourceMapComment::Js => format!("\n//# sourceMappingURL={url}"),
+    }))
+}
+
+/// Move the source maps written next to the assets in `asset_dir` into `target_dir`, keeping their relative paths.
+///
+/// Only `.map` files that belong to an asset are moved. Returns the number of source maps that were moved.
+pub fn move_source_maps(asset_dir: &Path, target_dir: &Path) -> anyhow::Result<usize> {
+    let mut moved = 0;
+    let Ok(entries) = std::fs::read_dir(asset_dir) else {
+        return Ok(moved);
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            moved += move_source_maps(&path, &target_dir.join(entry.file_name()))?;
+            continue;
+        }
+
+        let is_source_map =
+            path.extension().is_some_and(|ext| ext == "map") && path.with_extension("").is_file();
+        if !is_source_map {
+            continue;
+        }
+
+        std::fs::create_dir_all(target_dir)?;
+        std::fs::rename(&path, target_dir.join(entry.file_name()))
+            .with_context(|| format!("Failed to move source map {}", path.display()))?;
+        moved += 1;
+    }
+
+    Ok(moved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_maps_are_linked_with_a_comment() {
+        let dir = tempfile::tempdir().unwrap();
+        let final_path = dir.path().join("main-dxh123.js");
+
+        let linked = SourceMapMode::Linked;
+        let js = write_source_map(b"{}", &final_path, SourceMapComment::Js, linked).unwrap();
+        let css = write_source_map(b"{}", &final_path, SourceMapComment::Css, linked).unwrap();
+        assert_eq!(
+            js.as_deref(),
+            Some("\n//# sourceMappingURL=main-dxh123.js.map")
+        );
+        assert_eq!(
+            css.as_deref(),
+            Some("\n/*# sourceMappingURL=main-dxh123.js.map */")
+        );
+
+        // Hidden maps are still written, but not linked
+        std::fs::remove_file(source_map_path(&final_path)).unwrap();
+        let hidden = SourceMapMode::Hidden;
+        let js = write_source_map(b"{}", &final_path, SourceMapComment::Js, hidden).unwrap();
+        assert_eq!(js, None);
+        assert_eq!(
+            std::fs::read(dir.path().join("main-dxh123.js.map")).unwrap(),
+            b"{}"
+        );
+    }
+
+    #[test]
+    fn source_maps_are_moved_out_of_the_assets() {
+        let assets = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let write = |path: &str| {
+            let path = assets.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        };
+        write("main.js");
+        write("main.js.map");
+        write("nested/style.css");
+        write("nested/style.css.map");
+        // A map that doesn't belong to an asset is part of the app
+        write("data.map");
+
+        let moved = move_source_maps(assets.path(), target.path()).unwrap();
+        assert_eq!(moved, 2);
+        assert!(target.path().join("main.js.map").is_file());
+        assert!(target.path().join("nested/style.css.map").is_file());
+        assert!(!assets.path().join("main.js.map").exists());
+        assert!(!assets.path().join("nested/style.css.map").exists());
+        assert!(assets.path().join("main.js").is_file());
+        assert!(assets.path().join("data.map").is_file());
+        assert!(!target.path().join("data.map").exists());
+    }
+}
@@ -0,0 +1,114 @@
+// Generated by dx from the [web.pwa] section of Dioxus.toml. Do not edit by hand.
+const CACHE = "{cache_name}";
+const RUNTIME_CACHE = "{cache_name}-runtime";
+const SCOPE = "{scope}";
+const PRECACHE = {precache};
+const RUNTIME_CACHING = {runtime_caching};
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    caches
+      .open(CACHE)
+      // Skip the http cache so assets without a hash in their name aren't precached from a previous build
+      .then((cache) => cache.addAll(PRECACHE.map((url) => new Request(url, { cache: "reload" }))))
+      .then(() => self.skipWaiting())
+  );
+});
+
+// Remove the caches of previous builds
+self.addEventListener("activate", (event) => {
+  event.waitUntil(
+    caches
+      .keys()
+      .then((keys) =>
+        Promise.all(
+          keys
+            .filter((key) => key !== CACHE && key !== RUNTIME_CACHE)
+            .map((key) => caches.delete(key))
+        )
+      )
+      .then(() => self.clients.claim())
+  );
+});
+
+// The cache API only stores GET requests, so POST requests like server functions are cached under a
+// GET request for the same url with a hash of their body in the query
+async function cacheKey(request) {
+  if (request.method === "GET") return request;
+  const body = await request.clone().arrayBuffer();
+  const digest = await crypto.subtle.digest("SHA-256", body);
+  const hash = Array.from(new Uint8Array(digest), (byte) => byte.toString(16).padStart(2, "0")).join("");
+  const url = new URL(request.url);
+  url.searchParams.set("dx-body", hash);
+  return new Request(url);
+}
+
+async function networkFirst(request) {
+  const key = await cacheKey(request);
+  try {
+    const response = await fetch(request);
+    if (response.ok) {
+      const cache = await caches.open(RUNTIME_CACHE);
+      cache.put(key, response.clone());
+    }
+    return response;
+  } catch (error) {
+    const cached = await caches.match(key);
+    if (cached) return cached;
+    throw error;
+  }
+}
+
+async function cacheFirst(request) {
+  const key = await cacheKey(request);
+  const cached = await caches.match(key);
+  if (cached) return cached;
+  const response = await fetch(request);
+  if (response.ok) {
+    const cache = await caches.open(RUNTIME_CACHE);
+    cache.put(key, response.clone());
+  }
+  return response;
+}
+
+async function staleWhileRevalidate(request) {
+  const key = await cacheKey(request);
+  const cached = await caches.match(key);
+  const update = fetch(request).then(async (response) => {
+    if (response.ok) {
+      const cache = await caches.open(RUNTIME_CACHE);
+      cache.put(key, response.clone());
+    }
+    return response;
+  });
+  return cached || update;
+}
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+  const url = new URL(request.url);
+  if (url.origin !== self.location.origin) return;
+
+  // Server functions and other configured routes
+  const rule = RUNTIME_CACHING.find((rule) => url.pathname.startsWith(rule.path));
+  if (rule) {
+    // Server functions are GET or POST requests. Other methods change state on the server, so they aren't cached
+    if (!["GET", "POST"].includes(request.method) || rule.strategy === "network-only") return;
+    if (rule.strategy === "network-first") event.respondWith(networkFirst(request));
+    if (rule.strategy === "cache-first") event.respondWith(cacheFirst(request));
+    if (rule.strategy === "stale-while-revalidate") event.respondWith(staleWhileRevalidate(request));
+    return;
+  }
+
+  if (request.method !== "GET") return;
+
+  // Navigations render the app from the cached index.html when offline
+  if (request.mode === "navigate") {
+    event.respondWith(fetch(request).catch(() => caches.match(SCOPE)));
+    return;
+  }
+
+  // The cache is named after the contents of the precached files, so a build that changes any of
+  // them installs a new service worker with a fresh cache
+  event.respondWith(caches.match(request).then((cached) => cached || fetch(request)));
+});
@@ -0,0 +1,545 @@
+//! Bundle size analysis for web builds.
+//!
+//! `dx build --analyze` breaks the size of a web bundle down into the crates and functions that make
+//! up the wasm binary, the wasm-split chunks it was split into, and the assets that ship with it. The
+//! wasm is analyzed using the name section of the binary cargo produced, before wasm-bindgen and
+//! wasm-opt strip it.
+//!
+//! The report is printed as a summary and written to `analyze.json` and `analyze.html` next to the
+//! `public` folder. If a `[web.budget]` is set in the Dioxus.toml, the build fails when the bundle
+//! exceeds it.
+
+use crate::{BuildArtifacts, BuildRequest, Result, WebBudgetConfig};
+use anyhow::{bail, Context};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+use wasmparser::{KnownCustom, Name, Payload, TypeRef};
+
+/// The number of symbols kept in the report. The full list can have hundreds of thousands of entries.
+const MAX_SYMBOLS: usize = 1000;
+
+/// The number of rows printed per table in the summary
+const SUMMARY_ROWS: usize = 10;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct BundleReport {
+    /// The breakdown of the wasm binary, if it still had a name section
+    pub(crate) wasm: Option<WasmReport>,
+
+    /// The wasm files in the bundle. This is the main module and every wasm-split chunk.
+    pub(crate) chunks: Vec<ChunkSize>,
+
+    /// Every asset in the bundle, largest first
+    pub(crate) assets: Vec<AssetSize>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct WasmReport {
+    /// The size of the wasm binary before wasm-bindgen and wasm-opt
+    pub(crate) total_size: u64,
+    /// The size of the code section
+    pub(crate) code_size: u64,
+    /// The size of the data section
+    pub(crate) data_size: u64,
+    /// The code size contributed by each crate, largest first
+    pub(crate) crates: Vec<CrateSize>,
+    /// The largest functions in the binary, largest first
+    pub(crate) symbols: Vec<SymbolSize>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct CrateSize {
+    pub(crate) name: String,
+    pub(crate) size: u64,
+    pub(crate) functions: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct SymbolSize {
+    pub(crate) name: String,
+    pub(crate) krate: String,
+    pub(crate) size: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ChunkSize {
+    pub(crate) name: String,
+    pub(crate) size: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct AssetSize {
+    pub(crate) source: PathBuf,
+    pub(crate) bundled: String,
+    /// The size of the source file. The source of generated assets like the wasm is removed after bundling.
+    pub(crate) source_size: Option<u64>,
+    /// The size of the asset after it was processed by cli-opt
+    pub(crate) bundled_size: u64,
+}
+
+impl BundleReport {
+    pub(crate) fn new(build: &BuildRequest, artifacts: &BuildArtifacts) -> Result<Self> {
+        let wasm = std::fs::read(&artifacts.exe)
+            .with_context(|| format!("Failed to read {}", artifacts.exe.display()))?;
+        let wasm = WasmReport::new(&wasm)?;
+        if wasm.is_none() {
+            tracing::warn!("The wasm binary has no name section, so it can't be broken down by crate. Build with `--debug-symbols` or disable `strip` in your profile.");
+        }
+
+        let asset_dir = build.asset_dir();
+        let mut assets = vec![];
+        let mut chunks = vec![];
+        for asset in artifacts.assets.unique_assets() {
+            let source = PathBuf::from(asset.absolute_source_path());
+            let bundled_size = path_size(&asset_dir.join(asset.bundled_path())).unwrap_or_default();
+
+            if source.extension().is_some_and(|ext| ext == "wasm") {
+                chunks.push(ChunkSize {
+                    name: source
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string(),
+                    size: bundled_size,
+                });
+            }
+
+            assets.push(AssetSize {
+                source_size: path_size(&source),
+                source,
+                bundled: asset.bundled_path().to_string(),
+                bundled_size,
+            });
+        }
+
+        chunks.sort_by(|a, b| b.size.cmp(&a.size));
+        assets.sort_by(|a, b| b.bundled_size.cmp(&a.bundled_size));
+
+        Ok(Self {
+            wasm,
+            chunks,
+            assets,
+        })
+    }
+
+    /// Print a summary of the largest crates, symbols, chunks and assets
+    pub(crate) fn print_summary(&self) {
+        let mut out = String::new();
+
+        if let Some(wasm) = &self.wasm {
+            _ = writeln!(
+                out,
+                "wasm: {} total, {} code, {} data (before wasm-bindgen and wasm-opt)",
+                format_size(wasm.total_size),
+                format_size(wasm.code_size),
+                format_size(wasm.data_size)
+            );
+
+            _ = writeln!(out, "\nLargest crates:");
+            for krate in wasm.crates.iter().take(SUMMARY_ROWS) {
+                _ = writeln!(
+                    out,
+                    "  {:>10}  {:>5.1}%  {} ({} functions)",
+                    format_size(krate.size),
+                    percent(krate.size, wasm.code_size),
+                    krate.name,
+                    krate.functions
+                );
+            }
+
+            _ = writeln!(out, "\nLargest functions:");
+            for symbol in wasm.symbols.iter().take(SUMMARY_ROWS) {
+                _ = writeln!(
+                    out,
+                    "  {:>10}  {}",
+                    format_size(symbol.size),
+                    truncate(&symbol.name, 100)
+                );
+            }
+        }
+
+        if !self.chunks.is_empty() {
+            _ = writeln!(out, "\nwasm chunks:");
+            for chunk in self.chunks.iter() {
+                _ = writeln!(out, "  {:>10}  {}", format_size(chunk.size), chunk.name);
+            }
+        }
+
+        if !self.assets.is_empty() {
+            _ = writeln!(out, "\nLargest assets:");
+            for asset in self.assets.iter().take(SUMMARY_ROWS) {
+                let source_size = asset
+                    .source_size
+                    .map(format_size)
+                    .unwrap_or_else(|| "-".to_string());
+                _ = writeln!(
+                    out,
+                    "  {:>10} -> {:>10}  {}",
+                    source_size,
+                    format_size(asset.bundled_size),
+                    asset.bundled
+                );
+            }
+        }
+
+        tracing::info!("Bundle size report:\n{out}");
+    }
+
+    /// Write the report as `analyze.json` and `analyze.html` to the directory
+    pub(crate) fn write(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(
+            dir.join("analyze.json"),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        std::fs::write(dir.join("analyze.html"), self.to_html())?;
+        tracing::info!(
+            "Wrote the bundle size report to {}",
+            dir.join("analyze.html").display()
+        );
+        Ok(())
+    }
+
+    /// Check the report against the budget, failing if any limit is exceeded
+    pub(crate) fn check_budget(&self, budget: &WebBudgetConfig) -> Result<()> {
+        let mut exceeded = vec![];
+
+        let wasm_size: u64 = self.chunks.iter().map(|chunk| chunk.size).sum();
+        if let Some(limit) = budget.wasm.filter(|limit| wasm_size > *limit) {
+            exceeded.push(format!(
+                "wasm is {} (budget {})",
+                format_size(wasm_size),
+                format_size(limit)
+            ));
+        }
+
+        let assets_size: u64 = self.assets.iter().map(|asset| asset.bundled_size).sum();
+        if let Some(limit) = budget.assets.filter(|limit| assets_size > *limit) {
+            exceeded.push(format!(
+                "assets are {} (budget {})",
+                format_size(assets_size),
+                format_size(limit)
+            ));
+        }
+
+        if let Some(limit) = budget.asset {
+            for asset in self.assets.iter().filter(|a| a.bundled_size > limit) {
+                exceeded.push(format!(
+                    "{} is {} (budget {})",
+                    asset.bundled,
+                    format_size(asset.bundled_size),
+                    format_size(limit)
+                ));
+            }
+        }
+
+        if !budget.crates.is_empty() {
+            let Some(wasm) = &self.wasm else {
+                bail!("The wasm binary has no name section, so the crate budgets in Dioxus.toml can't be checked");
+            };
+            for (name, limit) in budget.crates.iter() {
+                let size = wasm
+                    .crates
+                    .iter()
+                    .find(|krate| krate.name == *name)
+                    .map_or(0, |krate| krate.size);
+                if size > *limit {
+                    exceeded.push(format!(
+                        "crate {name} is {} (budget {})",
+                        format_size(size),
+                        format_size(*limit)
+                    ));
+                }
+            }
+        }
+
+        if !exceeded.is_empty() {
+            bail!(
+                "The bundle exceeds the budget in Dioxus.toml:\n  {}",
+                exceeded.join("\n  ")
+            );
+        }
+
+        Ok(())
+    }
+
+    fn to_html(&self) -> String {
+        let mut html = String::from(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Bundle size report</title>
+<style>
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2rem; }
+th, td { padding: 0.25rem 0.75rem; text-align: left; border-bottom: 1px solid #ddd; }
+td.size { text-align: right; font-variant-numeric: tabular-nums; white-space: nowrap; }
+td.name { font-family: monospace; word-break: break-all; }
+</style>
+</head>
+<body>
+<h1>Bundle size report</h1>
+"#,
+        );
+
+        if let Some(wasm) = &self.wasm {
+            _ = writeln!(
+                html,
+                "<p>wasm: {} total, {} code, {} data (before wasm-bindgen and wasm-opt)</p>",
+                format_size(wasm.total_size),
+                format_size(wasm.code_size),
+                format_size(wasm.data_size)
+            );
+
+            html.push_str("<h2>Crates</h2>\n<table>\n<tr><th>Size</th><th>%</th><th>Functions</th><th>Crate</th></tr>\n");
+            for krate in wasm.crates.iter() {
+                _ = writeln!(
+                    html,
+                    "<tr><td class=\"size\">{}</td><td class=\"size\">{:.1}</td><td class=\"size\">{}</td><td class=\"name\">{}</td></tr>",
+                    format_size(krate.size),
+                    percent(krate.size, wasm.code_size),
+                    krate.functions,
+                    escape_html(&krate.name)
+                );
+            }
+            html.push_str("</table>\n");
+
+            html.push_str("<h2>Functions</h2>\n<table>\n<tr><th>Size</th><th>Crate</th><th>Function</th></tr>\n");
+            for symbol in wasm.symbols.iter() {
+                _ = writeln!(
+                    html,
+                    "<tr><td class=\"size\">{}</td><td class=\"name\">{}</td><td class=\"name\">{}</td></tr>",
+                    format_size(symbol.size),
+                    escape_html(&symbol.krate),
+                    escape_html(&symbol.name)
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("<h2>wasm chunks</h2>\n<table>\n<tr><th>Size</th><th>Chunk</th></tr>\n");
+        for chunk in self.chunks.iter() {
+            _ = writeln!(
+                html,
+                "<tr><td class=\"size\">{}</td><td class=\"name\">{}</td></tr>",
+                format_size(chunk.size),
+                escape_html(&chunk.name)
+            );
+        }
+        html.push_str("</table>\n");
+
+        html.push_str(
+            "<h2>Assets</h2>\n<table>\n<tr><th>Source</th><th>Bundled</th><th>Asset</th></tr>\n",
+        );
+        for asset in self.assets.iter() {
+            _ = writeln!(
+                html,
+                "<tr><td class=\"size\">{}</td><td class=\"size\">{}</td><td class=\"name\">{}</td></tr>",
+                asset
+                    .source_size
+                    .map(format_size)
+                    .unwrap_or_else(|| "-".to_string()),
+                format_size(asset.bundled_size),
+                escape_html(&asset.bundled)
+            );
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+
+        html
+    }
+}
+
+impl WasmReport {
+    /// Break the wasm binary down by function using its name section. Returns `None` if the binary
+    /// doesn't have function names.
+    fn new(bytes: &[u8]) -> Result<Option<Self>> {
+        let mut imported_functions = 0;
+        let mut function_sizes = vec![];
+        let mut function_names = HashMap::new();
+        let mut code_size = 0;
+        let mut data_size = 0;
+
+        for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+            match payload? {
+                Payload::ImportSection(imports) => {
+                    for import in imports {
+                        if matches!(import?.ty, TypeRef::Func(_)) {
+                            imported_functions += 1;
+                        }
+                    }
+                }
+                Payload::CodeSectionStart { range, .. } => code_size = range.len() as u64,
+                Payload::CodeSectionEntry(body) => {
+                    function_sizes.push(body.range().len() as u64);
+                }
+                Payload::DataSection(section) => data_size = section.range().len() as u64,
+                Payload::CustomSection(section) => {
+                    if let KnownCustom::Name(names) = section.as_known() {
+                        for name in names {
+                            if let Ok(Name::Function(map)) = name {
+                                for naming in map {
+                                    let naming = naming?;
+                                    function_names.insert(naming.index, naming.name);
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if function_names.is_empty() {
+            return Ok(None);
+        }
+
+        let mut crates: HashMap<String, CrateSize> = HashMap::new();
+        let mut symbols = vec![];
+        for (idx, size) in function_sizes.into_iter().enumerate() {
+            let name = function_names
+                .get(&(imported_functions + idx as u32))
+                .map(|name| format!("{:#}", rustc_demangle::demangle(name)))
+                .unwrap_or_else(|| format!("func[{}]", imported_functions + idx as u32));
+            let krate = crate_of_symbol(&name);
+
+            let entry = crates.entry(krate.clone()).or_insert_with(|| CrateSize {
+                name: krate.clone(),
+                size: 0,
+                functions: 0,
+            });
+            entry.size += size;
+            entry.functions += 1;
+
+            symbols.push(SymbolSize { name, krate, size });
+        }
+
+        let mut crates: Vec<_> = crates.into_values().collect();
+        crates.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+        symbols.sort_by(|a, b| b.size.cmp(&a.size));
+        symbols.truncate(MAX_SYMBOLS);
+
+        Ok(Some(Self {
+            total_size: bytes.len() as u64,
+            code_size,
+            data_size,
+            crates,
+            symbols,
+        }))
+    }
+}
+
+/// Guess the crate a demangled symbol came from. Trait impls like `<alloc::vec::Vec<T> as core::ops::Drop>::drop`
+/// are attributed to the crate of the implementing type. Symbols that aren't rust paths are grouped together.
+fn crate_of_symbol(name: &str) -> String {
+    let path = name.trim_start_matches('<').trim_start_matches('&');
+    match path.split_once("::") {
+        Some((krate, _))
+            if !krate.is_empty()
+                && krate.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+        {
+            krate.to_string()
+        }
+        _ if name.starts_with("__wbg") || name.starts_with("__wbindgen") => {
+            "wasm-bindgen".to_string()
+        }
+        _ => "[other]".to_string(),
+    }
+}
+
+/// The size of a file, or the total size of the files in a directory
+fn path_size(path: &Path) -> Option<u64> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if metadata.is_file() {
+        return Some(metadata.len());
+    }
+
+    Some(
+        walkdir::WalkDir::new(path)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.metadata().ok())
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len())
+            .sum(),
+    )
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..1_000 => format!("{bytes} B"),
+        1_000..1_000_000 => format!("{:.1} kB", bytes as f64 / 1_000.0),
+        _ => format!("{:.2} MB", bytes as f64 / 1_000_000.0),
+    }
+}
+
+fn percent(size: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    size as f64 / total as f64 * 100.0
+}
+
+fn truncate(name: &str, max: usize) -> String {
+    match name.char_indices().nth(max) {
+        Some((idx, _)) => format!("{}...", &name[..idx]),
+        None => name.to_string(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crate_of_demangled_symbols() {
+        assert_eq!(crate_of_symbol("core::fmt::write"), "core");
+        assert_eq!(
+            crate_of_symbol("<alloc::vec::Vec<T> as core::ops::drop::Drop>::drop"),
+            "alloc"
+        );
+        assert_eq!(crate_of_symbol("<&T as core::fmt::Debug>::fmt"), "[other]");
+        assert_eq!(crate_of_symbol("__wbindgen_malloc"), "wasm-bindgen");
+        assert_eq!(crate_of_symbol("memcpy"), "[other]");
+    }
+
+    #[test]
+    fn budget_is_enforced() {
+        let report = BundleReport {
+            wasm: None,
+            chunks: vec![ChunkSize {
+                name: "main.wasm".to_string(),
+                size: 2_000,
+            }],
+            assets: vec![AssetSize {
+                source: PathBuf::from("main.wasm"),
+                bundled: "main-123.wasm".to_string(),
+                source_size: None,
+                bundled_size: 2_000,
+            }],
+        };
+
+        let budget = WebBudgetConfig {
+            wasm: Some(3_000),
+            ..Default::default()
+        };
+        assert!(report.check_budget(&budget).is_ok());
+
+        let budget = WebBudgetConfig {
+            wasm: Some(1_000),
+            ..Default::default()
+        };
+        assert!(report.check_budget(&budget).is_err());
+    }
+}
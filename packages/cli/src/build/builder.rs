@@ -762,12 +762,38 @@ impl AppBuilder {
             .ok()?;
 
         // The asset might've been renamed thanks to the manifest, let's attempt to reload that too
-        let resources = artifacts.assets.get_assets_for_source(&changed_file)?;
+        let mut resources: Vec<_> = artifacts
+            .assets
+            .get_assets_for_source(&changed_file)
+            .into_iter()
+            .flatten()
+            .collect();
+
+        // Assets like tailwind css also need to be reprocessed when one of the files they depend on changes. A
+        // changed stylesheet may import new files, so the cached dependencies are rescanned.
+        if changed_file.extension().is_some_and(|ext| ext == "css") {
+            dioxus_cli_opt::clear_css_dependencies();
+        }
+        for asset in artifacts.assets.unique_assets() {
+            let source = PathBuf::from(asset.absolute_source_path());
+            if source != changed_file
+                && dioxus_cli_opt::asset_dependencies(asset.options(), &source)
+                    .contains(&changed_file)
+            {
+                resources.push(asset);
+            }
+        }
+
+        if resources.is_empty() {
+            return None;
+        }
+
         let mut bundled_names = Vec::new();
         for resource in resources {
+            let source = PathBuf::from(resource.absolute_source_path());
             let output_path = asset_dir.join(resource.bundled_path());
 
-            tracing::debug!("Hotreloading asset {changed_file:?} in target {asset_dir:?}");
+            tracing::debug!("Hotreloading asset {source:?} in target {asset_dir:?}");
 
             // Remove the old asset if it exists
             _ = std::fs::remove_file(&output_path);
@@ -776,7 +802,7 @@ impl AppBuilder {
             // the asset would be in a new location because the contents and hash have changed. Since we are
             // hotreloading, we need to use the old asset location it was originally written to.
             let options = *resource.options();
-            let res = process_file_to(&options, &changed_file, &output_path);
+            let res = process_file_to(&options, &source, &output_path);
             let bundled_name = PathBuf::from(resource.bundled_path());
             if let Err(e) = res {
                 tracing::debug!("Failed to hotreload asset {e}");
@@ -784,9 +810,7 @@ impl AppBuilder {
 
             // If the emulator is android, we need to copy the asset to the device with `adb push asset /data/local/tmp/dx/assets/filename.ext`
             if self.build.bundle == BundleFormat::Android {
-                _ = self
-                    .copy_file_to_android_tmp(&changed_file, &bundled_name)
-                    .await;
+                _ = self.copy_file_to_android_tmp(&source, &bundled_name).await;
             }
             bundled_names.push(bundled_name);
         }
@@ -0,0 +1,376 @@
+//! A content-addressed cache for the post-processing steps of a build.
+//!
+//! wasm-bindgen, wasm-opt and asset optimization are deterministic given their inputs, but they are
+//! expensive and would otherwise re-run on every `dx build`. Their outputs are stored under a hash of
+//! every input that affects them, so they can be reused across builds, profiles and even `cargo clean`.
+//!
+//! The cache lives in the dioxus home directory and can be cleared with `dx clean --cache`. Entries that haven't been
+//! used for a month are removed automatically, as are the least recently used entries once the cache grows too large.
+
+use crate::{Result, Workspace};
+use anyhow::Context;
+use dioxus_cli_opt::{asset_extra_outputs, process_file_to};
+use filetime::FileTime;
+use manganis::AssetOptions;
+use sha2::{Digest, Sha256};
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, SystemTime},
+};
+use uuid::Uuid;
+
+/// The kinds of outputs stored in the cache. Each kind gets its own directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CacheKind {
+    /// The output directory of wasm-bindgen, keyed by the wasm before bindgen and the bindgen options
+    WasmBindgen,
+    /// The output of wasm-opt, keyed by the input wasm and the wasm-opt config
+    WasmOpt,
+    /// The output of cli-opt for a single asset, keyed by the source contents and the asset options
+    Asset,
+}
+
+impl CacheKind {
+    const ALL: [CacheKind; 3] = [CacheKind::WasmBindgen, CacheKind::WasmOpt, CacheKind::Asset];
+
+    fn dir_name(self) -> &'static str {
+        match self {
+            CacheKind::WasmBindgen => "wasm-bindgen",
+            CacheKind::WasmOpt => "wasm-opt",
+            CacheKind::Asset => "assets",
+        }
+    }
+}
+
+/// The number of lookups that were served from the cache during a build
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct CacheStats {
+    pub(crate) hits: usize,
+    pub(crate) misses: usize,
+}
+
+/// Entries that haven't been used for this long are removed when the cache is pruned
+const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Once the cache is larger than this, the least recently used entries are removed when the cache is pruned
+const MAX_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// How often builds prune the cache. Pruning walks the whole cache, so it shouldn't run on every build
+const PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug)]
+pub(crate) struct BuildCache {
+    dir: PathBuf,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl Default for BuildCache {
+    fn default() -> Self {
+        Self {
+            dir: Self::cache_dir(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl BuildCache {
+    /// ~/.dioxus/build-cache
+    pub(crate) fn cache_dir() -> PathBuf {
+        Workspace::dioxus_home_dir().join("build-cache")
+    }
+
+    /// Remove everything in the cache and return the number of bytes that were freed
+    pub(crate) fn clear() -> Result<u64> {
+        let dir = Self::cache_dir();
+        if !dir.exists() {
+            return Ok(0);
+        }
+
+        let size = disk_size(&dir);
+        std::fs::remove_dir_all(&dir).context("Failed to remove the build cache")?;
+
+        Ok(size)
+    }
+
+    /// Hash the inputs of a step into a cache key.
+    ///
+    /// The version of the CLI is always part of the key since the output of the steps can change between versions.
+    pub(crate) fn key<'a>(inputs: impl IntoIterator<Item = &'a [u8]>) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(crate::VERSION.as_bytes());
+        for input in inputs {
+            // Prefix every input with its length so the boundaries between inputs are part of the hash
+            hasher.update((input.len() as u64).to_le_bytes());
+            hasher.update(input);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    fn entry(&self, kind: CacheKind, key: &str) -> PathBuf {
+        self.dir.join(kind.dir_name()).join(key)
+    }
+
+    /// Copy a cached output to the given path. Returns `false` if the output isn't cached.
+    ///
+    /// Works for both files and directories.
+    pub(crate) fn restore(&self, kind: CacheKind, key: &str, to: &Path) -> Result<bool> {
+        let entry = self.entry(kind, key);
+        if !entry.exists() {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return Ok(false);
+        }
+
+        if let Some(parent) = to.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        if entry.is_dir() {
+            _ = std::fs::remove_dir_all(to);
+            copy_dir(&entry, to)?;
+        } else {
+            std::fs::copy(&entry, to)?;
+        }
+
+        // The modified time of an entry is when it was last used, which decides what gets pruned first
+        _ = filetime::set_file_mtime(&entry, FileTime::now());
+
+        tracing::trace!("Restored {} from the build cache", to.display());
+        self.hits.fetch_add(1, Ordering::Relaxed);
+
+        Ok(true)
+    }
+
+    /// Store an output in the cache under the key.
+    ///
+    /// The output is copied to a temporary path first and then renamed into place, so a build that is
+    /// cancelled halfway through never leaves a partial entry behind.
+    pub(crate) fn store(&self, kind: CacheKind, key: &str, from: &Path) -> Result<()> {
+        let entry = self.entry(kind, key);
+        if entry.exists() {
+            return Ok(());
+        }
+
+        let parent = entry.parent().expect("cache entries always have a parent");
+        std::fs::create_dir_all(parent)?;
+
+        let temp = parent.join(format!(".{key}.{}", Uuid::new_v4()));
+        if from.is_dir() {
+            copy_dir(from, &temp)?;
+        } else {
+            std::fs::copy(from, &temp)?;
+        }
+
+        // Another build may have stored the same output in the meantime, which is fine since it's identical
+        if std::fs::rename(&temp, &entry).is_err() {
+            _ = std::fs::remove_dir_all(&temp);
+            _ = std::fs::remove_file(&temp);
+        }
+
+        Ok(())
+    }
+
+    /// Process an asset with cli-opt, reusing the output of a previous build if the source and options are unchanged.
+    ///
+    /// Folders are always processed since hashing them is about as expensive as copying them. Assets without a hash
+    /// are always processed too, since their output can depend on files other than the source, like the imports of a
+    /// scss file or the files tailwind scans.
+    pub(crate) fn process_asset(
+        &self,
+        options: &AssetOptions,
+        from: &Path,
+        to: &Path,
+    ) -> Result<()> {
+        if !from.is_file() || !options.hash_suffix() {
+            return process_file_to(options, from, to);
+        }
+
+        // The output of a hashed asset is already unique to its contents and options
+        if to.exists() {
+            return Ok(());
+        }
+
+        // The hash in the bundled name covers the dependencies of the asset
+        let contents = std::fs::read(from)
+            .with_context(|| format!("Failed to read asset {}", from.display()))?;
+        let options_json = serde_json::to_vec(options)?;
+        let bundled_name = to.file_name().unwrap_or_default().as_encoded_bytes();
+        let source_maps_mode = format!("{:?}", dioxus_cli_opt::source_maps());
+        let key = Self::key([
+            contents.as_slice(),
+            options_json.as_slice(),
+            bundled_name,
+            source_maps_mode.as_bytes(),
+        ]);
+
+        // Source maps and resized images are written next to the asset and cached alongside it
+        let extra_outputs = asset_extra_outputs(options, from, to);
+        let extra_key = |index: usize| format!("{key}-{index}");
+
+        if self.restore(CacheKind::Asset, &key, to)? {
+            for (index, extra) in extra_outputs.iter().enumerate() {
+                let entry = self.entry(CacheKind::Asset, &extra_key(index));
+                if entry.exists() {
+                    std::fs::copy(&entry, extra)?;
+                    _ = filetime::set_file_mtime(&entry, FileTime::now());
+                }
+            }
+            return Ok(());
+        }
+
+        process_file_to(options, from, to)?;
+
+        // Store the extra outputs first so a cached asset never misses them
+        for (index, extra) in extra_outputs.iter().enumerate() {
+            if extra.exists() {
+                self.store(CacheKind::Asset, &extra_key(index), extra)?;
+            }
+        }
+        self.store(CacheKind::Asset, &key, to)
+    }
+
+    /// Prune the cache if it hasn't been pruned in the last day
+    pub(crate) fn prune_if_needed(&self) -> Result<()> {
+        let marker = self.dir.join(".last-prune");
+        let pruned_recently = std::fs::metadata(&marker)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|elapsed| elapsed < PRUNE_INTERVAL);
+        if pruned_recently || !self.dir.exists() {
+            return Ok(());
+        }
+
+        let freed = self.prune(SystemTime::now(), MAX_AGE, MAX_SIZE)?;
+        std::fs::write(&marker, [])?;
+        if freed > 0 {
+            tracing::debug!("Pruned {freed} bytes from the build cache");
+        }
+
+        Ok(())
+    }
+
+    /// Remove the entries that haven't been used for `max_age`, and then the least recently used entries until the
+    /// cache is no larger than `max_size`. Returns the number of bytes that were freed.
+    fn prune(&self, now: SystemTime, max_age: Duration, max_size: u64) -> Result<u64> {
+        let mut entries = Vec::new();
+        for kind in CacheKind::ALL {
+            let Ok(read_dir) = std::fs::read_dir(self.dir.join(kind.dir_name())) else {
+                continue;
+            };
+            for entry in read_dir.flatten() {
+                let path = entry.path();
+                let last_used = entry
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+                entries.push((last_used, disk_size(&path), path));
+            }
+        }
+
+        // Remove the oldest entries first
+        entries.sort_by_key(|(last_used, ..)| *last_used);
+
+        let mut size: u64 = entries.iter().map(|(_, entry_size, _)| entry_size).sum();
+        let mut freed = 0;
+        for (last_used, entry_size, path) in entries {
+            let expired = now.duration_since(last_used).unwrap_or_default() > max_age;
+            if !expired && size <= max_size {
+                break;
+            }
+
+            if path.is_dir() {
+                std::fs::remove_dir_all(&path)?;
+            } else {
+                std::fs::remove_file(&path)?;
+            }
+            size -= entry_size;
+            freed += entry_size;
+        }
+
+        Ok(freed)
+    }
+
+    /// Take the stats of the lookups since the last call, resetting them
+    pub(crate) fn take_stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.swap(0, Ordering::Relaxed),
+            misses: self.misses.swap(0, Ordering::Relaxed),
+        }
+    }
+}
+
+/// The total size of the files in a file or directory
+fn disk_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    for entry in walkdir::WalkDir::new(from) {
+        let entry = entry?;
+        let target = to.join(entry.path().strip_prefix(from)?);
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_includes_input_boundaries() {
+        let a = BuildCache::key([b"ab".as_slice(), b"c".as_slice()]);
+        let b = BuildCache::key([b"a".as_slice(), b"bc".as_slice()]);
+        assert_ne!(a, b);
+        assert_eq!(a, BuildCache::key([b"ab".as_slice(), b"c".as_slice()]));
+    }
+
+    #[test]
+    fn prune_removes_old_and_least_recently_used_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = BuildCache {
+            dir: dir.path().to_path_buf(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        };
+
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let entry = |kind: CacheKind, key: &str, size: usize, age: Duration| {
+            let path = cache.entry(kind, key);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, vec![0; size]).unwrap();
+            filetime::set_file_mtime(&path, FileTime::from_system_time(now - age)).unwrap();
+            path
+        };
+
+        let expired = entry(CacheKind::WasmOpt, "expired", 10, 40 * day);
+        let oldest = entry(CacheKind::Asset, "oldest", 100, 3 * day);
+        let older = entry(CacheKind::Asset, "older", 100, 2 * day);
+        let newest = entry(CacheKind::WasmOpt, "newest", 100, day);
+
+        // Only the expired entry is removed while the cache is small enough
+        assert_eq!(cache.prune(now, 30 * day, 1000).unwrap(), 10);
+        assert!(!expired.exists());
+        assert!(oldest.exists() && older.exists() && newest.exists());
+
+        // Once the cache is too large, the least recently used entries are removed until it fits
+        assert_eq!(cache.prune(now, 30 * day, 150).unwrap(), 200);
+        assert!(!oldest.exists() && !older.exists());
+        assert!(newest.exists());
+    }
+}
@@ -8,18 +8,24 @@
 //! hot-patching Rust code through binary analysis and a custom linker. The [`builder`] module contains
 //! the management of the ongoing build and methods to open the build as a running app.
 
+mod analyze;
 mod assets;
 mod builder;
+mod cache;
 mod context;
 mod patch;
 mod pre_render;
+mod pwa;
 mod request;
 mod tools;
 
+pub(crate) use analyze::*;
 pub(crate) use assets::*;
 pub(crate) use builder::*;
+pub(crate) use cache::*;
 pub(crate) use context::*;
 pub(crate) use patch::*;
 pub(crate) use pre_render::*;
+pub(crate) use pwa::*;
 pub(crate) use request::*;
 pub(crate) use tools::*;
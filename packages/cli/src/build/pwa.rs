@@ -0,0 +1,359 @@
+//! Progressive web app support for web builds.
+//!
+//! When `[web.pwa]` is set in the Dioxus.toml, the web build writes a `manifest.webmanifest` next to
+//! the index.html so the app can be installed. Release builds also get a `sw.js` service worker that
+//! precaches the index.html and every asset in the asset manifest, so the app keeps working
+//! offline. Requests to server functions and other routes are only cached if a `runtime_caching`
+//! rule matches them.
+
+use crate::{
+    BuildCache, BuildRequest, Result, WebPwaConfig, WebPwaIconConfig, WebPwaRuntimeCacheConfig,
+};
+use anyhow::Context;
+use dioxus_cli_opt::AssetManifest;
+use manganis_core::AssetVariant;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+const SERVICE_WORKER: &str = include_str!("../../assets/web/sw.js");
+
+/// The tags to add to the head of the index.html for the manifest and service worker.
+///
+/// The service worker is only registered in release builds since it would serve stale assets while
+/// developing.
+pub(crate) fn pwa_head_tags(build: &BuildRequest, pwa: &WebPwaConfig) -> String {
+    let mut tags =
+        "<link rel=\"manifest\" href=\"/{base_path}/manifest.webmanifest\">\n".to_string();
+
+    if let Some(theme_color) = &pwa.theme_color {
+        tags.push_str(&format!(
+            "<meta name=\"theme-color\" content=\"{}\">\n",
+            escape_html(theme_color)
+        ));
+    }
+
+    if build.release {
+        tags.push_str(&format!(
+            "<script>if (\"serviceWorker\" in navigator) navigator.serviceWorker.register(\"{scope}sw.js\", {{ scope: \"{scope}\" }});</script>\n",
+            scope = scope(build)
+        ));
+    }
+
+    tags
+}
+
+/// Write the web app manifest, icons and service worker to the root of the web build
+pub(crate) fn write_pwa(
+    build: &BuildRequest,
+    pwa: &WebPwaConfig,
+    assets: &AssetManifest,
+    wasm_path: &str,
+    js_path: &str,
+) -> Result<()> {
+    let root = build.root_dir();
+    let scope = scope(build);
+
+    // Copy the icons next to the manifest
+    let mut icons = vec![];
+    let mut icon_sources = vec![];
+    for icon in default_icons(build, pwa) {
+        let source = build.crate_dir().join(&icon.src);
+        let file_name = source
+            .file_name()
+            .context("PWA icons must be files")?
+            .to_string_lossy()
+            .to_string();
+        let icons_dir = root.join("icons");
+        std::fs::create_dir_all(&icons_dir)?;
+        std::fs::copy(&source, icons_dir.join(&file_name))
+            .with_context(|| format!("Failed to copy PWA icon {}", source.display()))?;
+
+        let sizes = icon
+            .sizes
+            .clone()
+            .or_else(|| png_size(&source).map(|(width, height)| format!("{width}x{height}")))
+            .unwrap_or_else(|| "any".to_string());
+
+        let mut entry = json!({
+            "src": format!("{scope}icons/{file_name}"),
+            "sizes": sizes,
+        });
+        if let Some(mime) = icon_mime_type(&source) {
+            entry["type"] = json!(mime);
+        }
+        if let Some(purpose) = &icon.purpose {
+            entry["purpose"] = json!(purpose);
+        }
+        icons.push(entry);
+        icon_sources.push(source);
+    }
+
+    if icons.is_empty() {
+        tracing::warn!("No icons are set in [web.pwa] or [bundle]. Browsers require an icon to install the app.");
+    }
+
+    let name = pwa
+        .name
+        .clone()
+        .unwrap_or_else(|| build.config.web.app.title.clone());
+    let description = pwa
+        .description
+        .as_ref()
+        .or(build.config.bundle.short_description.as_ref());
+    let manifest = web_manifest(pwa, &name, description, &scope, &icons);
+    std::fs::write(
+        root.join("manifest.webmanifest"),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    if !build.release {
+        _ = std::fs::remove_file(root.join("sw.js"));
+        return Ok(());
+    }
+
+    // Precache the index.html, the icons and every asset except folders, which can't be fetched.
+    // Every url is paired with the file its contents come from.
+    let mut precache = vec![(scope.clone(), root.join("index.html"))];
+    for (icon, source) in icons.iter().zip(icon_sources) {
+        if let Some(src) = icon["src"].as_str() {
+            precache.push((src.to_string(), source));
+        }
+    }
+    for asset in assets.unique_assets() {
+        if !matches!(asset.options().variant(), AssetVariant::Folder(_)) {
+            precache.push((
+                format!("{scope}assets/{}", asset.bundled_path()),
+                PathBuf::from(asset.absolute_source_path()),
+            ));
+        }
+    }
+    for path in [wasm_path, js_path] {
+        let url = format!("{scope}{path}");
+        if !precache.iter().any(|(existing, _)| *existing == url) {
+            precache.push((url, root.join(path)));
+        }
+    }
+
+    let service_worker = service_worker(
+        &build.executable_name(),
+        &scope,
+        &precache,
+        &pwa.runtime_caching,
+    )?;
+    std::fs::write(root.join("sw.js"), service_worker)?;
+
+    Ok(())
+}
+
+/// The contents of the `manifest.webmanifest`
+fn web_manifest(
+    pwa: &WebPwaConfig,
+    name: &str,
+    description: Option<&String>,
+    scope: &str,
+    icons: &[Value],
+) -> Value {
+    let mut manifest = json!({
+        "name": name,
+        "start_url": scope,
+        "scope": scope,
+        "display": pwa.display,
+        "icons": icons,
+    });
+    if let Some(short_name) = &pwa.short_name {
+        manifest["short_name"] = json!(short_name);
+    }
+    if let Some(description) = description {
+        manifest["description"] = json!(description);
+    }
+    if let Some(theme_color) = &pwa.theme_color {
+        manifest["theme_color"] = json!(theme_color);
+    }
+    if let Some(background_color) = &pwa.background_color {
+        manifest["background_color"] = json!(background_color);
+    }
+    manifest
+}
+
+/// Fill in the service worker template for the precached urls and the files they are served from
+fn service_worker(
+    app_name: &str,
+    scope: &str,
+    precache: &[(String, PathBuf)],
+    runtime_caching: &[WebPwaRuntimeCacheConfig],
+) -> Result<String> {
+    // The cache is named after the contents of the precached files, not just their urls. Assets
+    // without a hash in their name keep the same url across builds, so a new build must still
+    // replace the cache of the previous one. Files that aren't written yet are bundled assets
+    // whose url already changes with their contents.
+    let contents: Vec<Vec<u8>> = precache
+        .iter()
+        .map(|(_, path)| std::fs::read(path).unwrap_or_default())
+        .collect();
+    let hash = BuildCache::key(
+        precache
+            .iter()
+            .zip(&contents)
+            .flat_map(|((url, _), contents)| [url.as_bytes(), contents.as_slice()]),
+    );
+    let cache_name = format!("dx-{app_name}-{}", &hash[..16]);
+
+    let urls: Vec<_> = precache.iter().map(|(url, _)| url).collect();
+    let runtime_caching: Vec<_> = runtime_caching
+        .iter()
+        .map(|rule| json!({ "path": rule.path, "strategy": rule.strategy }))
+        .collect();
+
+    Ok(SERVICE_WORKER
+        .replace("{cache_name}", &cache_name)
+        .replace("{scope}", scope)
+        .replace("{precache}", &serde_json::to_string(&urls)?)
+        .replace(
+            "{runtime_caching}",
+            &serde_json::to_string(&runtime_caching)?,
+        ))
+}
+
+/// The path the app is served from, with a leading and trailing slash
+fn scope(build: &BuildRequest) -> String {
+    match build.base_path_or_default() {
+        "." => "/".to_string(),
+        base_path => format!("/{base_path}/"),
+    }
+}
+
+/// The icons in the PWA config, or the PNG icons in the bundle config if none are set
+fn default_icons(build: &BuildRequest, pwa: &WebPwaConfig) -> Vec<WebPwaIconConfig> {
+    if !pwa.icons.is_empty() {
+        return pwa.icons.clone();
+    }
+
+    build
+        .config
+        .bundle
+        .icon
+        .iter()
+        .flatten()
+        .filter(|icon| icon.ends_with(".png"))
+        .map(|icon| WebPwaIconConfig {
+            src: icon.into(),
+            sizes: None,
+            purpose: None,
+        })
+        .collect()
+}
+
+fn icon_mime_type(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()? {
+        "png" => Some("image/png"),
+        "svg" => Some("image/svg+xml"),
+        "webp" => Some("image/webp"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "ico" => Some("image/x-icon"),
+        _ => None,
+    }
+}
+
+/// Read the width and height from the header of a PNG file
+fn png_size(path: &Path) -> Option<(u32, u32)> {
+    let bytes = std::fs::read(path).ok()?;
+    if bytes.len() < 24 || !bytes.starts_with(b"\x89PNG\r\n\x1a\n") || &bytes[12..16] != b"IHDR" {
+        return None;
+    }
+
+    let width = u32::from_be_bytes(bytes[16..20].try_into().ok()?);
+    let height = u32::from_be_bytes(bytes[20..24].try_into().ok()?);
+    Some((width, height))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pwa_config(config: Value) -> WebPwaConfig {
+        serde_json::from_value(config).unwrap()
+    }
+
+    #[test]
+    fn theme_color_is_escaped() {
+        assert_eq!(
+            escape_html("red\"><script>alert(1)</script>"),
+            "red&quot;&gt;&lt;script&gt;alert(1)&lt;/script&gt;"
+        );
+        assert_eq!(escape_html("#ff0000"), "#ff0000");
+    }
+
+    #[test]
+    fn manifest_contains_the_pwa_config() {
+        let pwa = pwa_config(json!({
+            "short_name": "App",
+            "theme_color": "#000000",
+            "icons": [{ "src": "icon.png" }],
+        }));
+        let icons = [json!({ "src": "/app/icons/icon.png", "sizes": "192x192" })];
+        let description = "An app".to_string();
+
+        let manifest = web_manifest(&pwa, "My App", Some(&description), "/app/", &icons);
+        assert_eq!(manifest["name"], "My App");
+        assert_eq!(manifest["short_name"], "App");
+        assert_eq!(manifest["description"], "An app");
+        assert_eq!(manifest["start_url"], "/app/");
+        assert_eq!(manifest["scope"], "/app/");
+        assert_eq!(manifest["display"], "standalone");
+        assert_eq!(manifest["theme_color"], "#000000");
+        assert_eq!(manifest["icons"][0]["src"], "/app/icons/icon.png");
+        assert!(manifest.get("background_color").is_none());
+    }
+
+    #[test]
+    fn service_worker_precaches_urls() {
+        let pwa = pwa_config(json!({
+            "runtime_caching": [{ "path": "/api", "strategy": "network-first" }],
+        }));
+        let precache = [
+            ("/".to_string(), PathBuf::from("missing/index.html")),
+            (
+                "/assets/app.wasm".to_string(),
+                PathBuf::from("missing.wasm"),
+            ),
+        ];
+
+        let sw = service_worker("app", "/", &precache, &pwa.runtime_caching).unwrap();
+        assert!(sw.contains(r#"const PRECACHE = ["/","/assets/app.wasm"];"#));
+        assert!(
+            sw.contains(r#"const RUNTIME_CACHING = [{"path":"/api","strategy":"network-first"}];"#)
+        );
+        assert!(sw.contains(r#"const SCOPE = "/";"#));
+        assert!(sw.contains(r#"const CACHE = "dx-app-"#));
+        assert!(!sw.contains("{cache_name}"));
+    }
+
+    #[test]
+    fn cache_name_changes_with_file_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let style = dir.path().join("style.css");
+        let precache = [("/assets/style.css".to_string(), style.clone())];
+        let cache_name = |sw: String| {
+            sw.lines()
+                .find(|line| line.starts_with("const CACHE ="))
+                .unwrap()
+                .to_string()
+        };
+
+        std::fs::write(&style, "body { color: red; }").unwrap();
+        let first = cache_name(service_worker("app", "/", &precache, &[]).unwrap());
+        let unchanged = cache_name(service_worker("app", "/", &precache, &[]).unwrap());
+        std::fs::write(&style, "body { color: blue; }").unwrap();
+        let changed = cache_name(service_worker("app", "/", &precache, &[]).unwrap());
+
+        assert_eq!(first, unchanged);
+        assert_ne!(first, changed);
+    }
+}
@@ -316,16 +316,17 @@
 //! - xbuild: <https://github.com/rust-mobile/xbuild/blob/master/xbuild/src/command/build.rs>
 
 use crate::{
-    AndroidTools, BuildContext, BundleFormat, DX_RUSTC_WRAPPER_ENV_VAR, DioxusConfig, Error,
-    LinkAction, LinkerFlavor, Renderer, Result, RustcArgs, TargetAlias, TargetArgs, TraceSrc,
-    WasmBindgen, WasmOptConfig, Workspace,
+    AndroidTools, BuildCache, BuildContext, BundleFormat, CacheKind, DX_RUSTC_WRAPPER_ENV_VAR,
+    DioxusConfig, Error, LinkAction, LinkerFlavor, ReleaseSourceMaps, Renderer, Result, RustcArgs,
+    TargetAlias, TargetArgs, TraceSrc, WasmBindgen, WasmOptConfig, Workspace, pwa_head_tags,
+    write_pwa,
 };
 use anyhow::{Context, bail};
 use cargo_metadata::diagnostic::Diagnostic;
 use depinfo::RustcDepInfo;
 use dioxus_cli_config::{APP_TITLE_ENV, ASSET_ROOT_ENV};
 use dioxus_cli_config::{PRODUCT_NAME_ENV, format_base_path_meta_element};
-use dioxus_cli_opt::{AssetManifest, process_file_to};
+use dioxus_cli_opt::{AssetManifest, SourceMapMode};
 use itertools::Itertools;
 use krates::{NodeId, cm::TargetKind};
 use manganis::AssetOptions;
@@ -395,6 +396,7 @@ pub(crate) struct BuildRequest {
     pub(crate) command_file: Arc<NamedTempFile>,
     pub(crate) base_path: Option<String>,
     pub(crate) using_dioxus_explicitly: bool,
+    pub(crate) build_cache: Arc<BuildCache>,
 }
 
 /// dx can produce different "modes" of a build. A "regular" build is a "base" build. The Fat and Thin
@@ -826,6 +828,7 @@ impl BuildRequest {
             wasm_split: args.wasm_split,
             debug_symbols: args.debug_symbols,
             inject_loading_scripts: args.inject_loading_scripts,
+            build_cache: Default::default(),
         })
     }
 
@@ -834,6 +837,9 @@ impl BuildRequest {
         // We need to make sure to not react to this though, so the filemap must cache it
         _ = self.bust_fingerprint(ctx);
 
+        // Rescan the files css assets depend on since imports and sources may have changed since the last build
+        dioxus_cli_opt::clear_css_dependencies();
+
         // Run any pre-build steps like tailwind, etc
         self.prebuild().await?;
 
@@ -874,6 +880,14 @@ impl BuildRequest {
                     .await
                     .context("Failed to assemble build")?;
 
+                let stats = self.build_cache.take_stats();
+                if stats.hits + stats.misses > 0 {
+                    tracing::info!(dx_src = ?TraceSrc::Bundle, "Build cache: {} hits, {} misses", stats.hits, stats.misses);
+                }
+                if let Err(err) = self.build_cache.prune_if_needed() {
+                    tracing::warn!(dx_src = ?TraceSrc::Bundle, "Failed to prune the build cache: {err}");
+                }
+
                 tracing::debug!("Bundle created at {}", self.root_dir().display());
             }
         }
@@ -1221,6 +1235,17 @@ impl BuildRequest {
 
         let asset_dir = self.asset_dir();
 
+        // Css assets compiled with tailwind need the tailwind CLI
+        let uses_tailwind = assets.unique_assets().any(
+            |asset| matches!(asset.options().variant(), AssetVariant::Css(css) if css.tailwind()),
+        );
+        if uses_tailwind {
+            crate::TailwindCli::install_for_assets(&self.package_manifest_dir()).await?;
+        }
+
+        let source_maps = self.source_map_mode();
+        dioxus_cli_opt::set_source_maps(source_maps);
+
         // First, clear the asset dir of any files that don't exist in the new manifest
         _ = std::fs::create_dir_all(&asset_dir);
 
@@ -1288,6 +1313,7 @@ impl BuildRequest {
         // Parallel Copy over the assets and keep track of progress with an atomic counter
         let progress = ctx.tx.clone();
         let ws_dir = self.workspace_dir();
+        let build_cache = self.build_cache.clone();
 
         // Optimizing assets is expensive and blocking, so we do it in a tokio spawn blocking task
         tokio::task::spawn_blocking(move || {
@@ -1300,7 +1326,7 @@ impl BuildRequest {
                         "Starting asset copy {processing}/{asset_count} from {from_:?}"
                     );
 
-                    let res = process_file_to(options, from, to);
+                    let res = build_cache.process_asset(options, from, to);
                     if let Err(err) = res.as_ref() {
                         tracing::error!("Failed to copy asset {from:?}: {err}");
                     }
@@ -1319,6 +1345,11 @@ impl BuildRequest {
         .await
         .map_err(|e| anyhow::anyhow!("A task failed while trying to copy assets: {e}"))??;
 
+        // Hidden source maps are moved out of the bundle so they are never deployed with the app
+        if source_maps == SourceMapMode::Hidden {
+            self.move_source_maps(&asset_dir)?;
+        }
+
         // Remove the wasm dir if we packaged it to an "asset"-type app
         if self.should_bundle_to_asset() {
             _ = std::fs::remove_dir_all(self.wasm_bindgen_out_dir());
@@ -1330,6 +1361,31 @@ impl BuildRequest {
         Ok(())
     }
 
+    /// How source maps are emitted for the css, scss and js assets of this build
+    fn source_map_mode(&self) -> SourceMapMode {
+        let config = &self.config.web.source_maps;
+        match self.release {
+            false if config.dev => SourceMapMode::Linked,
+            false => SourceMapMode::Disabled,
+            true => match config.release {
+                ReleaseSourceMaps::Discard => SourceMapMode::Disabled,
+                ReleaseSourceMaps::Hidden => SourceMapMode::Hidden,
+                ReleaseSourceMaps::Linked => SourceMapMode::Linked,
+            },
+        }
+    }
+
+    /// Move the source maps cli-opt wrote next to the assets into the source maps dir, keeping their relative paths
+    fn move_source_maps(&self, asset_dir: &Path) -> Result<()> {
+        let source_maps_dir = self.source_maps_dir();
+        let moved = dioxus_cli_opt::move_source_maps(asset_dir, &source_maps_dir)?;
+        if moved > 0 {
+            tracing::info!("Wrote {moved} source maps to {}", source_maps_dir.display());
+        }
+
+        Ok(())
+    }
+
     /// Run our custom linker setup to generate a patch file in the right location
     ///
     /// This should be the only case where the cargo output is a "dummy" file and requires us to
@@ -2467,6 +2523,58 @@ impl BuildRequest {
         Ok(env_vars)
     }
 
+    /// Create the `cargo test` command that compiles the test binaries of this build without running them.
+    ///
+    /// The tests are compiled with the same profile, target and features as the app so code gated behind the
+    /// renderer features, like the server half of a fullstack app, is tested the same way it is built.
+    pub(crate) fn cargo_test_command(&self) -> Result<Command> {
+        let mut cmd = Command::new("cargo");
+        cmd.arg("test")
+            .arg("--no-run")
+            .arg("--message-format")
+            .arg("json-diagnostic-rendered-ansi")
+            .current_dir(self.crate_dir())
+            .args(self.profile_args())
+            .arg("--profile")
+            .arg(&self.profile)
+            .arg("--target")
+            .arg(self.triple.to_string())
+            .arg("-p")
+            .arg(&self.package);
+
+        if self.no_default_features {
+            cmd.arg("--no-default-features");
+        }
+
+        if !self.features.is_empty() {
+            cmd.arg("--features").arg(self.features.join(" "));
+        }
+
+        let lock_opts = crate::VERBOSITY.get().cloned().unwrap_or_default();
+        if lock_opts.frozen {
+            cmd.arg("--frozen");
+        }
+        if lock_opts.locked {
+            cmd.arg("--locked");
+        }
+        if lock_opts.offline {
+            cmd.arg("--offline");
+        }
+
+        cmd.args(self.extra_cargo_args.iter());
+
+        if !self.rustflags.flags.is_empty() {
+            cmd.env(
+                "RUSTFLAGS",
+                self.rustflags
+                    .encode_space_separated()
+                    .context("Failed to encode RUSTFLAGS")?,
+            );
+        }
+
+        Ok(cmd)
+    }
+
     /// Set the environment variables required for building on Android.
     ///
     /// This involves setting sysroots, CC, CXX, AR, and other environment variables along with
@@ -2814,6 +2922,18 @@ impl BuildRequest {
         self.build_dir(self.bundle, self.release)
     }
 
+    /// The directory the bundle size report of `dx build --analyze` is written to. This sits next to the
+    /// `public` folder on web so the report isn't deployed with the app.
+    pub(crate) fn analyze_dir(&self) -> PathBuf {
+        self.platform_dir()
+    }
+
+    /// The directory hidden source maps are written to in release builds. Like the bundle report, this sits outside
+    /// of the `public` folder so the maps can be uploaded to an error tracker without being deployed.
+    pub(crate) fn source_maps_dir(&self) -> PathBuf {
+        self.platform_dir().join("sourcemaps")
+    }
+
     fn platform_exe_name(&self) -> String {
         match self.bundle {
             BundleFormat::MacOS => self.executable_name().to_string(),
@@ -3601,24 +3721,40 @@ impl BuildRequest {
         // There's performance implications here. Running with --debug is slower than without
         // We're keeping around lld sections and names but wasm-opt will fix them
         // todo(jon): investigate a good balance of wiping debug symbols during dev (or doing a double build?)
+        //
+        // The output only depends on the input wasm and the options, so we can reuse the output of a
+        // previous build if neither changed.
         ctx.status_wasm_bindgen_start();
-        tracing::debug!(dx_src = ?TraceSrc::Bundle, "Running wasm-bindgen");
-        let start = std::time::Instant::now();
-        WasmBindgen::new(&bindgen_version)
-            .input_path(exe)
-            .target("web")
-            .debug(keep_debug)
-            .demangle(demangle)
-            .keep_debug(keep_debug)
-            .keep_lld_sections(true)
-            .out_name(self.executable_name())
-            .out_dir(&bindgen_outdir)
-            .remove_name_section(!keep_names)
-            .remove_producers_section(!keep_names)
-            .run()
-            .await
-            .context("Failed to generate wasm-bindgen bindings")?;
-        tracing::debug!(dx_src = ?TraceSrc::Bundle, "wasm-bindgen complete in {:?}", start.elapsed());
+        let bindgen_key = BuildCache::key([
+            std::fs::read(exe)?.as_slice(),
+            bindgen_version.as_bytes(),
+            self.executable_name().as_bytes(),
+            [keep_debug as u8, demangle as u8, keep_names as u8].as_slice(),
+        ]);
+        if !self
+            .build_cache
+            .restore(CacheKind::WasmBindgen, &bindgen_key, &bindgen_outdir)?
+        {
+            tracing::debug!(dx_src = ?TraceSrc::Bundle, "Running wasm-bindgen");
+            let start = std::time::Instant::now();
+            WasmBindgen::new(&bindgen_version)
+                .input_path(exe)
+                .target("web")
+                .debug(keep_debug)
+                .demangle(demangle)
+                .keep_debug(keep_debug)
+                .keep_lld_sections(true)
+                .out_name(self.executable_name())
+                .out_dir(&bindgen_outdir)
+                .remove_name_section(!keep_names)
+                .remove_producers_section(!keep_names)
+                .run()
+                .await
+                .context("Failed to generate wasm-bindgen bindings")?;
+            tracing::debug!(dx_src = ?TraceSrc::Bundle, "wasm-bindgen complete in {:?}", start.elapsed());
+            self.build_cache
+                .store(CacheKind::WasmBindgen, &bindgen_key, &bindgen_outdir)?;
+        }
 
         // Run bundle splitting if the user has requested it
         // It's pretty expensive but because of rayon should be running separate threads, hopefully
@@ -3725,7 +3861,21 @@ impl BuildRequest {
         // Make sure to optimize the main wasm file if requested or if bundle splitting
         if should_bundle_split || self.release {
             ctx.status_optimizing_wasm();
-            wasm_opt::optimize(&post_bindgen_wasm, &post_bindgen_wasm, &wasm_opt_options).await?;
+            // Different versions of wasm-opt produce different output for the same input
+            let wasm_opt_key = BuildCache::key([
+                std::fs::read(&post_bindgen_wasm)?.as_slice(),
+                serde_json::to_vec(&wasm_opt_options)?.as_slice(),
+                wasm_opt::version().await?.as_bytes(),
+            ]);
+            if !self
+                .build_cache
+                .restore(CacheKind::WasmOpt, &wasm_opt_key, &post_bindgen_wasm)?
+            {
+                wasm_opt::optimize(&post_bindgen_wasm, &post_bindgen_wasm, &wasm_opt_options)
+                    .await?;
+                self.build_cache
+                    .store(CacheKind::WasmOpt, &wasm_opt_key, &post_bindgen_wasm)?;
+            }
         }
 
         if self.should_bundle_to_asset() {
@@ -3753,6 +3903,18 @@ impl BuildRequest {
         // Write the index.html file with the pre-configured contents we got from pre-rendering
         self.write_index_html(assets)?;
 
+        // Write the web app manifest and service worker if this is a PWA
+        if let Some(pwa) = &self.config.web.pwa {
+            write_pwa(
+                self,
+                pwa,
+                assets,
+                &self.bundled_wasm_path(assets),
+                &self.bundled_js_path(assets),
+            )
+            .context("Failed to write the PWA manifest and service worker")?;
+        }
+
         Ok(())
     }
 
@@ -4411,6 +4573,13 @@ __wbg_init({{module_or_path: "/{}/{wasm_path}"}}).then((wasm) => {{
                         ))
                     }
                 }
+                AssetVariant::Font(font_options) => {
+                    if font_options.preloaded() {
+                        head_resources.push_str(&format!(
+                            "<link rel=\"preload\" as=\"font\" href=\"/{{base_path}}/assets/{asset_path}\" crossorigin>"
+                        ))
+                    }
+                }
                 _ => {}
             }
         }
@@ -4419,6 +4588,11 @@ __wbg_init({{module_or_path: "/{}/{wasm_path}"}}).then((wasm) => {{
         head_resources.push_str(&format!(
             "<link rel=\"preload\" as=\"fetch\" type=\"application/wasm\" href=\"/{{base_path}}/{wasm_path}\" crossorigin>"
         ));
+
+        // Link the web app manifest and register the service worker
+        if let Some(pwa) = &self.config.web.pwa {
+            head_resources.push_str(&pwa_head_tags(self, pwa));
+        }
         Self::replace_or_insert_before("{style_include}", "</head", &head_resources, html);
 
         Ok(())
@@ -1,8 +1,9 @@
 use super::{check::collect_rs_files, *};
 use crate::Workspace;
 use anyhow::{bail, Context};
-use dioxus_autofmt::{IndentOptions, IndentType};
+use dioxus_autofmt::{FmtConfig, IndentOptions, IndentType};
 use rayon::prelude::*;
+use serde::Deserialize;
 use std::{borrow::Cow, fs, path::Path};
 
 // For reference, the rustfmt main.rs file
@@ -53,7 +54,8 @@ impl Autoformat {
             refactor_file(file, split_line_attributes, format_rust_code)?;
         } else if let Some(raw) = raw {
             // Format raw text.
-            let indent = indentation_for(".", self.split_line_attributes)?;
+            let indent = indentation_for(".", self.split_line_attributes)?
+                .with_config(fmt_config_for(Path::new("."))?);
             let formatted =
                 dioxus_autofmt::fmt_block(&raw, 0, indent).context("error formatting codeblock")?;
             println!("{}", formatted);
@@ -87,7 +89,12 @@ fn refactor_file(
     split_line_attributes: bool,
     format_rust_code: bool,
 ) -> Result<(), Error> {
-    let indent = indentation_for(".", split_line_attributes)?;
+    let config_dir = match Path::new(&file).parent() {
+        Some(parent) if file != "-" && !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let indent =
+        indentation_for(".", split_line_attributes)?.with_config(fmt_config_for(config_dir)?);
     let file_content = if file == "-" {
         let mut contents = String::new();
         std::io::stdin().read_to_string(&mut contents)?;
@@ -172,7 +179,8 @@ fn autoformat_project(
         return Ok(());
     }
 
-    let indent = indentation_for(&files_to_format[0], split_line_attributes)?;
+    let indent = indentation_for(&files_to_format[0], split_line_attributes)?
+        .with_config(fmt_config_for(dir.as_ref())?);
 
     let counts = files_to_format
         .into_par_iter()
@@ -239,6 +247,43 @@ fn indentation_for(
     ))
 }
 
+/// Find the `[fmt]` config for the files in a directory
+///
+/// The closest directory with a `dxfmt.toml` or `Dioxus.toml` wins. If a directory has both, the `dxfmt.toml` is used.
+/// The section is read on its own instead of through the `DioxusConfig`, since files are also formatted outside of a
+/// workspace and a `Dioxus.toml` without an `[application]` section is still a valid place for it.
+fn fmt_config_for(dir: &Path) -> Result<FmtConfig> {
+    #[derive(Deserialize)]
+    struct PartialConfig {
+        #[serde(default)]
+        fmt: FmtConfig,
+    }
+
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    for ancestor in dir.ancestors() {
+        let dxfmt = ancestor.join("dxfmt.toml");
+        if dxfmt.is_file() {
+            let contents = fs::read_to_string(&dxfmt)?;
+            return toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse {}", dxfmt.display()));
+        }
+
+        let dioxus_toml = ancestor.join("Dioxus.toml");
+        if dioxus_toml.is_file() {
+            let contents = fs::read_to_string(&dioxus_toml)?;
+            let config = toml::from_str::<PartialConfig>(&contents).with_context(|| {
+                format!(
+                    "Failed to parse the [fmt] section of {}",
+                    dioxus_toml.display()
+                )
+            })?;
+            return Ok(config.fmt);
+        }
+    }
+
+    Ok(FmtConfig::default())
+}
+
 /// Format rust code using prettyplease
 fn format_rust(input: &str) -> Result<String> {
     let syntax_tree = syn::parse_file(input)
@@ -1,5 +1,5 @@
 use crate::BuildMode;
-use crate::{cli::*, AppBuilder, BuildRequest, Workspace};
+use crate::{cli::*, AppBuilder, BuildRequest, BundleFormat, BundleReport, Workspace};
 
 use super::target::TargetArgs;
 
@@ -23,6 +23,13 @@ pub struct BuildArgs {
     #[clap(long)]
     pub(crate) ssg: bool,
 
+    /// Analyze the size of the web bundle and write a report next to the `public` folder [default: false]
+    ///
+    /// The report breaks the wasm down by crate and function, and lists the size of every wasm-split
+    /// chunk and asset.
+    #[clap(long)]
+    pub(crate) analyze: bool,
+
     /// Arguments for the build itself
     #[clap(flatten)]
     pub(crate) build_arguments: TargetArgs,
@@ -72,14 +79,32 @@ impl CommandWithPlatformOverrides<BuildArgs> {
         tracing::info!("Building project...");
 
         let ssg = self.shared.ssg;
+        let analyze = self.shared.analyze;
         let targets = self.into_targets().await?;
 
-        AppBuilder::started(&targets.client, BuildMode::Base { run: false })?
+        let artifacts = AppBuilder::started(&targets.client, BuildMode::Base { run: false })?
             .finish_build()
             .await?;
 
         tracing::info!(path = ?targets.client.root_dir(), "Client build completed successfully! 🚀");
 
+        // Analyze the bundle if requested. Budgets only apply to release builds since debug builds
+        // are unoptimized and much larger.
+        let budget = &targets.client.config.web.budget;
+        let check_budget = targets.client.release && !budget.is_empty();
+        if targets.client.bundle == BundleFormat::Web && (analyze || check_budget) {
+            let report = BundleReport::new(&targets.client, &artifacts)?;
+            if analyze {
+                report.print_summary();
+                report.write(&targets.client.analyze_dir())?;
+            }
+            if check_budget {
+                report.check_budget(budget)?;
+            }
+        } else if analyze {
+            tracing::warn!("Bundle analysis is only supported for web builds");
+        }
+
         if let Some(server) = targets.server.as_ref() {
             // If the server is present, we need to build it as well
             let mut server_build = AppBuilder::started(server, BuildMode::Base { run: false })?;
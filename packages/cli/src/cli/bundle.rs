@@ -1,7 +1,12 @@
 use crate::{AppBuilder, BuildArgs, BuildMode, BuildRequest, BundleFormat};
 use anyhow::{bail, Context};
+use base64::{prelude::BASE64_STANDARD, Engine};
 use path_absolutize::Absolutize;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
 use tauri_bundler::{BundleBinary, BundleSettings, PackageSettings, SettingsBuilder};
 
 use walkdir::WalkDir;
@@ -71,7 +76,18 @@ impl Bundle {
             // By default, mac/win/linux work with tauri bundle
             BundleFormat::MacOS | BundleFormat::Linux | BundleFormat::Windows => {
                 tracing::info!("Running desktop bundler...");
-                for bundle in Self::bundle_desktop(&client, &self.package_types)? {
+                let desktop_bundles = Self::bundle_desktop(&client, &self.package_types)?;
+
+                let wants_updater = self.package_types.as_ref().is_some_and(|types| {
+                    types
+                        .iter()
+                        .any(|ty| matches!(ty, crate::PackageType::Updater))
+                }) || client.config.bundle.updater.is_some();
+                if wants_updater {
+                    bundles.extend(Self::bundle_updater(&client, &desktop_bundles)?);
+                }
+
+                for bundle in desktop_bundles {
                     bundles.extend(bundle.bundle_paths);
                 }
             }
@@ -276,4 +292,143 @@ impl Bundle {
 
         Ok(bundles)
     }
+
+    /// Sign the artifacts the desktop app can update itself from and write the `latest.json` manifest that
+    /// `dioxus_desktop::updater` reads.
+    ///
+    /// The artifact is the AppImage on linux, a `.app.tar.gz` archive of the app bundle on macOS, and the nsis or msi
+    /// installer on windows. Returns the paths of the files that were created next to the bundles.
+    fn bundle_updater(
+        build: &BuildRequest,
+        bundles: &[tauri_bundler::Bundle],
+    ) -> Result<Vec<PathBuf>> {
+        // Prefer the artifacts that can be swapped in place over the installers
+        let rank = |path: &Path| {
+            let name = path.to_string_lossy().to_lowercase();
+            if name.ends_with(".appimage") || name.ends_with(".app") {
+                Some(0)
+            } else if name.ends_with(".exe") {
+                Some(1)
+            } else if name.ends_with(".msi") {
+                Some(2)
+            } else {
+                None
+            }
+        };
+        let artifact = bundles
+            .iter()
+            .flat_map(|bundle| bundle.bundle_paths.iter())
+            .filter_map(|path| Some((rank(path)?, path)))
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, path)| path.clone());
+
+        let Some(mut artifact) = artifact else {
+            tracing::warn!("No updater artifact was bundled. Bundle an AppImage, app bundle, msi or nsis installer to create an update.");
+            return Ok(vec![]);
+        };
+        let mut outputs = vec![];
+        if artifact.extension().is_some_and(|ext| ext == "app") {
+            artifact = Self::archive_app_bundle(&artifact)?;
+            outputs.push(artifact.clone());
+        }
+
+        let signature = Self::sign_updater_artifact(&artifact)?;
+        let signature_path = PathBuf::from(format!("{}.sig", artifact.display()));
+        std::fs::write(&signature_path, &signature)?;
+
+        let settings = build.config.bundle.updater.clone().unwrap_or_default();
+        let file_name = artifact.file_name().unwrap().to_string_lossy().to_string();
+        let url = match settings.base_url {
+            Some(base_url) => format!("{}/{file_name}", base_url.trim_end_matches('/')),
+            None => {
+                tracing::warn!("`bundle.updater.base_url` is not set in `Dioxus.toml`. The manifest will point to the artifact by its file name.");
+                file_name
+            }
+        };
+
+        let os = match build.triple.operating_system {
+            target_lexicon::OperatingSystem::Darwin(_)
+            | target_lexicon::OperatingSystem::MacOSX(_) => "darwin".to_string(),
+            os => os.to_string(),
+        };
+        let target = format!("{os}-{}", build.triple.architecture);
+
+        let manifest = UpdateManifest {
+            version: build.package().version.to_string(),
+            notes: settings.notes,
+            pub_date: chrono::Utc::now().to_rfc3339(),
+            platforms: BTreeMap::from([(target, PlatformUpdate { url, signature })]),
+        };
+        let manifest_path = artifact.with_file_name("latest.json");
+        std::fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+        tracing::info!("Wrote update manifest to {}", manifest_path.display());
+
+        outputs.extend([signature_path, manifest_path]);
+        Ok(outputs)
+    }
+
+    /// The app bundle is a directory, so it needs to be archived before it can be downloaded
+    fn archive_app_bundle(app: &Path) -> Result<PathBuf> {
+        let archive_path = PathBuf::from(format!("{}.tar.gz", app.display()));
+        let file = std::fs::File::create(&archive_path)?;
+        let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+
+        let mut archive = tar::Builder::new(encoder);
+        archive.follow_symlinks(false);
+        archive.append_dir_all(app.file_name().unwrap(), app)?;
+        archive.into_inner()?.finish()?;
+
+        Ok(archive_path)
+    }
+
+    /// Sign an artifact with minisign, and return the base64 encoded signature
+    fn sign_updater_artifact(artifact: &Path) -> Result<String> {
+        let key = std::env::var("DIOXUS_SIGNING_PRIVATE_KEY").context(
+            "Set `DIOXUS_SIGNING_PRIVATE_KEY` to the minisign private key to sign the updater artifacts",
+        )?;
+
+        // The key can either be a path to the key file or the base64 encoded key file itself
+        let key = match std::fs::read_to_string(&key) {
+            Ok(key) => key,
+            Err(_) => String::from_utf8(BASE64_STANDARD.decode(key.trim())?)?,
+        };
+        let password = std::env::var("DIOXUS_SIGNING_PRIVATE_KEY_PASSWORD").unwrap_or_default();
+
+        let secret_key = minisign::SecretKeyBox::from_string(&key)
+            .and_then(|key| key.into_secret_key(Some(password)))
+            .context("Failed to decode the updater signing key")?;
+
+        let file_name = artifact.file_name().unwrap().to_string_lossy();
+        let trusted_comment = format!(
+            "timestamp:{}\tfile:{file_name}",
+            chrono::Utc::now().timestamp()
+        );
+        let signature = minisign::sign(
+            None,
+            &secret_key,
+            std::fs::File::open(artifact)?,
+            Some(&trusted_comment),
+            Some("signature from dioxus secret key"),
+        )
+        .context("Failed to sign the updater artifact")?;
+
+        Ok(BASE64_STANDARD.encode(signature.into_string()))
+    }
+}
+
+/// The manifest `dioxus_desktop::updater` checks for new versions
+#[derive(Serialize)]
+struct UpdateManifest {
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<String>,
+    pub_date: String,
+    platforms: BTreeMap<String, PlatformUpdate>,
+}
+
+#[derive(Serialize)]
+struct PlatformUpdate {
+    url: String,
+    signature: String,
 }
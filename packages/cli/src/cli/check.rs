@@ -6,6 +6,7 @@
 use super::*;
 use crate::BuildRequest;
 use anyhow::{anyhow, Context};
+use dioxus_check::{Applicability, CheckConfig, IssueReport, Level};
 use futures_util::{stream::FuturesUnordered, StreamExt};
 use std::path::Path;
 
@@ -16,44 +17,136 @@ pub(crate) struct Check {
     #[clap(short, long)]
     pub(crate) file: Option<PathBuf>,
 
+    /// Apply the fixes that are safe to apply automatically
+    #[clap(long)]
+    pub(crate) fix: bool,
+
+    /// Also apply the fixes that may not compile or may change behavior, like passing props through
+    /// `use_reactive!`. Review the changes before committing them
+    #[clap(long, requires = "fix")]
+    pub(crate) unsafe_fixes: bool,
+
+    /// The format to report issues in [default: human]
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) format: CheckFormat,
+
+    /// Write the json or sarif report to this file instead of stdout
+    #[clap(long)]
+    pub(crate) output: Option<PathBuf>,
+
     /// Information about the target to check
     #[clap(flatten)]
     pub(crate) build_args: CommandWithPlatformOverrides<BuildArgs>,
 }
 
+/// The format `dx check` reports issues in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum CheckFormat {
+    /// Rustc style diagnostics
+    #[default]
+    Human,
+    /// A JSON array of issues
+    Json,
+    /// A SARIF log for code scanning tools
+    Sarif,
+}
+
 impl Check {
     // Todo: check the entire crate
     pub(crate) async fn check(self) -> Result<StructuredOutput> {
-        let BuildTargets { client, server } = self.build_args.into_targets().await?;
+        let BuildTargets { client, server } = self.build_args.clone().into_targets().await?;
+        let config = client.config.check.clone();
 
-        match self.file {
+        let reports = match self.file.clone() {
             // Default to checking the project
             None => {
-                check_project_and_report(&client)
+                let mut reports = check_project(&client, &config)
                     .await
                     .context("error checking project")?;
 
                 if let Some(server) = server {
                     if server.package != client.package {
-                        check_project_and_report(&server)
-                            .await
-                            .context("error checking project")?;
+                        reports.extend(
+                            check_project(&server, &config)
+                                .await
+                                .context("error checking project")?,
+                        );
                     }
                 }
+
+                reports
             }
-            Some(file) => {
-                check_file_and_report(file)
-                    .await
-                    .context("error checking file")?;
-            }
-        }
+            Some(file) => check_files(vec![file], &config)
+                .await
+                .context("error checking file")?,
+        };
+
+        let reports = match (self.fix, self.unsafe_fixes) {
+            (true, false) => fix_files(reports, Applicability::MachineApplicable).await?,
+            (true, true) => fix_files(reports, Applicability::MaybeIncorrect).await?,
+            (false, _) => reports,
+        };
+
+        self.report(&reports).await?;
 
         Ok(StructuredOutput::Success)
     }
-}
 
-async fn check_file_and_report(path: PathBuf) -> Result<()> {
-    check_files_and_report(vec![path]).await
+    /// Print or write the reports and fail if any of them have errors
+    async fn report(&self, reports: &[IssueReport]) -> Result<()> {
+        let report = match self.format {
+            CheckFormat::Human => None,
+            CheckFormat::Json => Some(dioxus_check::json_report(reports)),
+            CheckFormat::Sarif => Some(dioxus_check::sarif_report(reports)),
+        };
+
+        match report {
+            Some(report) => {
+                let report = serde_json::to_string_pretty(&report)?;
+                match &self.output {
+                    Some(output) => tokio::fs::write(output, report)
+                        .await
+                        .with_context(|| format!("failed to write {}", output.display()))?,
+                    None => println!("{report}"),
+                }
+            }
+            None => {
+                for report in reports {
+                    if !report.issues.is_empty() {
+                        tracing::info!("{}", report);
+                    }
+                }
+            }
+        }
+
+        let total_issues = reports.iter().map(|r| r.issues.len()).sum::<usize>();
+        let total_errors = reports
+            .iter()
+            .flat_map(|report| {
+                report
+                    .issues
+                    .iter()
+                    .filter(|issue| report.level(issue) == Level::Deny)
+            })
+            .count();
+
+        match (total_issues, total_errors) {
+            (0, _) => {
+                tracing::info!("No issues found.");
+                Ok(())
+            }
+            (1, 0) => {
+                tracing::warn!("1 warning found.");
+                Ok(())
+            }
+            (_, 0) => {
+                tracing::warn!("{total_issues} warnings found.");
+                Ok(())
+            }
+            (_, 1) => Err(anyhow!("1 error found.")),
+            _ => Err(anyhow!("{total_errors} errors found.")),
+        }
+    }
 }
 
 /// Read every .rs file accessible when considering the .gitignore and check it
@@ -61,7 +154,7 @@ async fn check_file_and_report(path: PathBuf) -> Result<()> {
 /// Runs using Tokio for multithreading, so it should be really really fast
 ///
 /// Doesn't do mod-descending, so it will still try to check unreachable files. TODO.
-async fn check_project_and_report(build: &BuildRequest) -> Result<()> {
+async fn check_project(build: &BuildRequest, config: &CheckConfig) -> Result<Vec<IssueReport>> {
     let dioxus_crate = build
         .workspace
         .find_main_package(Some(build.package.clone()))?;
@@ -71,20 +164,24 @@ async fn check_project_and_report(build: &BuildRequest) -> Result<()> {
         dioxus_crate.manifest_path.parent().unwrap().as_std_path(),
         &mut files_to_check,
     );
-    check_files_and_report(files_to_check).await
+    check_files(files_to_check, config).await
 }
 
-/// Check a list of files and report the issues.
-async fn check_files_and_report(files_to_check: Vec<PathBuf>) -> Result<()> {
+/// Check a list of files and collect the issues.
+async fn check_files(
+    files_to_check: Vec<PathBuf>,
+    config: &CheckConfig,
+) -> Result<Vec<IssueReport>> {
     let issue_reports = files_to_check
         .into_iter()
         .filter(|file| file.components().all(|f| f.as_os_str() != "target"))
         .map(|path| async move {
             let _path = path.clone();
+            let config = config.clone();
             let res = tokio::spawn(async move {
                 tokio::fs::read_to_string(&_path)
                     .await
-                    .map(|contents| dioxus_check::check_file(_path, &contents))
+                    .map(|contents| dioxus_check::check_file_with_config(_path, &contents, &config))
             })
             .await;
 
@@ -99,28 +196,41 @@ async fn check_files_and_report(files_to_check: Vec<PathBuf>) -> Result<()> {
         .await;
 
     // remove error results which we've already printed
-    let issue_reports = issue_reports
-        .into_iter()
-        .flatten()
-        .flatten()
-        .collect::<Vec<_>>();
-
-    let total_issues = issue_reports.iter().map(|r| r.issues.len()).sum::<usize>();
+    Ok(issue_reports.into_iter().flatten().flatten().collect())
+}
 
-    for report in issue_reports.into_iter() {
-        if !report.issues.is_empty() {
-            tracing::info!("{}", report);
-        }
+/// Apply the fixes that are at least as confident as `applicability` to each file and check it again to find the issues
+/// that are left
+async fn fix_files(
+    reports: Vec<IssueReport>,
+    applicability: Applicability,
+) -> Result<Vec<IssueReport>> {
+    let mut fixed_reports = Vec::with_capacity(reports.len());
+    let mut fixed_files = 0;
+
+    for report in reports {
+        let Some(fixed) = report.fixed_content(applicability) else {
+            fixed_reports.push(report);
+            continue;
+        };
+
+        tokio::fs::write(&report.path, &fixed)
+            .await
+            .with_context(|| format!("failed to write {}", report.path.display()))?;
+        fixed_files += 1;
+
+        fixed_reports.push(dioxus_check::check_file_with_config(
+            report.path.clone(),
+            &fixed,
+            &report.config,
+        ));
     }
 
-    match total_issues {
-        0 => {
-            tracing::info!("No issues found.");
-            Ok(())
-        }
-        1 => Err(anyhow!("1 issue found.")),
-        _ => Err(anyhow!("{total_issues} issues found.")),
+    if fixed_files > 0 {
+        tracing::info!("Applied fixes to {fixed_files} files.");
     }
+
+    Ok(fixed_reports)
 }
 
 pub(crate) fn collect_rs_files(folder: &Path, files: &mut Vec<PathBuf>) {
@@ -0,0 +1,40 @@
+use super::*;
+use crate::BuildCache;
+use anyhow::{bail, Context};
+
+/// Remove the build artifacts of the project.
+#[derive(Clone, Debug, Parser)]
+pub(crate) struct Clean {
+    /// Clear the build cache shared by all projects instead of the target directory.
+    ///
+    /// The build cache stores the output of wasm-bindgen, wasm-opt and asset optimization so it can
+    /// be reused when the inputs of those steps don't change.
+    #[clap(long)]
+    pub(crate) cache: bool,
+}
+
+impl Clean {
+    pub(crate) async fn clean(self) -> Result<StructuredOutput> {
+        if self.cache {
+            let freed = BuildCache::clear()?;
+            tracing::info!(
+                "Cleared the build cache at {} ({:.1} MB)",
+                BuildCache::cache_dir().display(),
+                freed as f64 / 1_000_000.0
+            );
+            return Ok(StructuredOutput::Success);
+        }
+
+        let status = tokio::process::Command::new("cargo")
+            .arg("clean")
+            .status()
+            .await
+            .context("Failed to run cargo clean")?;
+
+        if !status.success() {
+            bail!("cargo clean failed");
+        }
+
+        Ok(StructuredOutput::Success)
+    }
+}
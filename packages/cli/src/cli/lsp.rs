@@ -0,0 +1,20 @@
+use super::*;
+
+/// Run a language server for rsx over stdin and stdout.
+///
+/// Point your editor's LSP client at `dx lsp` to get completions, hover docs, go-to-definition and diagnostics for
+/// rsx. The server is meant to run next to rust-analyzer.
+#[derive(Clone, Debug, Parser)]
+pub(crate) struct Lsp {
+    /// Communicate over stdin and stdout. This is the only transport, the flag is accepted because most LSP clients
+    /// pass it
+    #[clap(long)]
+    pub(crate) stdio: bool,
+}
+
+impl Lsp {
+    pub(crate) async fn lsp(self) -> Result<StructuredOutput> {
+        crate::lsp::run().await;
+        Ok(StructuredOutput::Success)
+    }
+}
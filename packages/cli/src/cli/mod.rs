@@ -3,15 +3,18 @@ pub(crate) mod build;
 pub(crate) mod build_assets;
 pub(crate) mod bundle;
 pub(crate) mod check;
+pub(crate) mod clean;
 pub(crate) mod config;
 pub(crate) mod create;
 pub(crate) mod doctor;
 pub(crate) mod init;
 pub(crate) mod link;
+pub(crate) mod lsp;
 pub(crate) mod platform_override;
 pub(crate) mod run;
 pub(crate) mod serve;
 pub(crate) mod target;
+pub(crate) mod test;
 pub(crate) mod translate;
 pub(crate) mod update;
 pub(crate) mod verbosity;
@@ -92,6 +95,18 @@ pub(crate) enum Commands {
     #[clap(name = "check")]
     Check(check::Check),
 
+    /// Run a language server for rsx.
+    #[clap(name = "lsp")]
+    Lsp(lsp::Lsp),
+
+    /// Build and run the tests of the project.
+    #[clap(name = "test")]
+    Test(test::TestArgs),
+
+    /// Remove the build artifacts of the project, or the shared build cache with `--cache`.
+    #[clap(name = "clean")]
+    Clean(clean::Clean),
+
     /// Dioxus config file controls.
     #[clap(subcommand)]
     #[clap(name = "config")]
@@ -125,6 +140,9 @@ impl Display for Commands {
             Commands::Config(_) => write!(f, "config"),
             Commands::Autoformat(_) => write!(f, "fmt"),
             Commands::Check(_) => write!(f, "check"),
+            Commands::Lsp(_) => write!(f, "lsp"),
+            Commands::Test(_) => write!(f, "test"),
+            Commands::Clean(_) => write!(f, "clean"),
             Commands::Bundle(_) => write!(f, "bundle"),
             Commands::Run(_) => write!(f, "run"),
             Commands::SelfUpdate(_) => write!(f, "self-update"),
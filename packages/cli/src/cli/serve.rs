@@ -64,6 +64,12 @@ pub(crate) struct ServeArgs {
     #[arg(long, default_value_t = false, alias = "hotpatch")]
     pub(crate) hot_patch: bool,
 
+    /// Launch a gallery of the components marked with `#[preview]` instead of the app [default: false]
+    ///
+    /// This enables the `preview` feature of the `dioxus` crate for the build.
+    #[clap(long)]
+    pub(crate) preview: bool,
+
     /// Watch the filesystem for changes and trigger a rebuild [default: true]
     #[clap(long, default_missing_value = "true")]
     pub(crate) watch: Option<bool>,
@@ -0,0 +1,249 @@
+use super::*;
+use crate::{BuildRequest, BundleFormat, TestSuiteResult, TraceSrc, WasmBindgen};
+use anyhow::{bail, Context};
+use cargo_metadata::Message;
+use std::{path::Path, process::Stdio};
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+/// Build and run the tests of the project for a platform.
+///
+/// The tests are compiled with the same features and target as `dx build`. Web tests are run in a
+/// headless browser with wasm-bindgen-test-runner. If the app is fullstack, the tests are also
+/// compiled and run with the server features.
+#[derive(Clone, Debug, Parser)]
+pub(crate) struct TestArgs {
+    /// Only run the tests whose names contain this string
+    pub(crate) filter: Option<String>,
+
+    /// Compile the tests but don't run them
+    #[clap(long)]
+    pub(crate) no_run: bool,
+
+    /// The browser to run web tests in [default: chrome]
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) browser: TestBrowser,
+
+    /// Show the browser window while running web tests instead of running them headless
+    #[clap(long)]
+    pub(crate) headed: bool,
+
+    /// Information about the target to test
+    #[clap(flatten)]
+    pub(crate) args: CommandWithPlatformOverrides<BuildArgs>,
+}
+
+/// The browser wasm-bindgen-test-runner drives with webdriver
+#[derive(Clone, Copy, Debug, Default, clap::ValueEnum)]
+pub(crate) enum TestBrowser {
+    #[default]
+    Chrome,
+    Firefox,
+    Safari,
+}
+
+impl TestBrowser {
+    /// The webdriver binary for the browser and the env var wasm-bindgen-test-runner reads it from
+    fn driver(self) -> (&'static str, &'static str) {
+        match self {
+            TestBrowser::Chrome => ("chromedriver", "CHROMEDRIVER"),
+            TestBrowser::Firefox => ("geckodriver", "GECKODRIVER"),
+            TestBrowser::Safari => ("safaridriver", "SAFARIDRIVER"),
+        }
+    }
+}
+
+impl TestArgs {
+    pub(crate) async fn test(self) -> Result<StructuredOutput> {
+        let BuildTargets { client, server } = self.args.clone().into_targets().await?;
+
+        let mut suites = self.test_build(&client).await?;
+        if let Some(server) = server.as_ref() {
+            suites.extend(self.test_build(server).await?);
+        }
+
+        let failed: Vec<_> = suites
+            .iter()
+            .filter(|suite| !suite.success)
+            .map(|suite| suite.name.clone())
+            .collect();
+        let output = StructuredOutput::TestOutput { suites };
+
+        if !failed.is_empty() {
+            // The error replaces the structured output, so emit the results of every suite before bailing
+            tracing::info!(json = ?output);
+            bail!("Tests failed in {}", failed.join(", "));
+        }
+
+        Ok(output)
+    }
+
+    /// Compile the test binaries for one build and run each of them
+    async fn test_build(&self, build: &BuildRequest) -> Result<Vec<TestSuiteResult>> {
+        tracing::info!("[{}] Compiling tests", build.bundle);
+
+        let output = build
+            .cargo_test_command()?
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .output()
+            .await
+            .context("Failed to run cargo test")?;
+
+        let mut binaries = vec![];
+        for message in Message::parse_stream(output.stdout.as_slice()).flatten() {
+            match message {
+                Message::CompilerArtifact(artifact) if artifact.profile.test => {
+                    if let Some(executable) = artifact.executable {
+                        binaries.push((artifact.target.name, executable.into_std_path_buf()));
+                    }
+                }
+                Message::CompilerMessage(message) => {
+                    if let Some(rendered) = message.message.rendered {
+                        tracing::warn!(dx_src = ?TraceSrc::Cargo, "{rendered}");
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if !output.status.success() {
+            bail!("Failed to compile the tests for {}", build.bundle);
+        }
+
+        if self.no_run {
+            for (_, binary) in binaries.iter() {
+                tracing::info!("[{}] Compiled {}", build.bundle, binary.display());
+            }
+            return Ok(vec![]);
+        }
+
+        let mut suites = vec![];
+        for (name, binary) in binaries {
+            tracing::info!("[{}] Running {}", build.bundle, binary.display());
+
+            let mut command = self.test_binary_command(build, &binary).await?;
+            if let Some(filter) = &self.filter {
+                command.arg(filter);
+            }
+
+            let mut child = command
+                .current_dir(build.crate_dir())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::inherit())
+                .spawn()
+                .with_context(|| format!("Failed to run {}", binary.display()))?;
+
+            let mut report = TestReport::default();
+            let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+            while let Some(line) = lines.next_line().await? {
+                tracing::info!(dx_src = ?TraceSrc::App(build.bundle), "{line}");
+                report.parse_line(&line);
+            }
+            let status = child.wait().await?;
+
+            suites.push(TestSuiteResult {
+                name,
+                platform: build.bundle.to_string(),
+                binary,
+                passed: report.passed,
+                failed: report.failed,
+                ignored: report.ignored,
+                success: status.success(),
+            });
+        }
+
+        Ok(suites)
+    }
+
+    /// Native test binaries are run directly. Wasm test binaries are run by wasm-bindgen-test-runner
+    /// in a browser.
+    async fn test_binary_command(
+        &self,
+        build: &BuildRequest,
+        binary: &Path,
+    ) -> Result<tokio::process::Command> {
+        if build.bundle != BundleFormat::Web {
+            return Ok(tokio::process::Command::new(binary));
+        }
+
+        let version = build
+            .workspace
+            .wasm_bindgen_version()
+            .context("wasm-bindgen is required to run web tests. Add `wasm-bindgen-test` to your dev-dependencies.")?;
+        let runner = WasmBindgen::new(&version).test_runner_path().await?;
+
+        let mut command = tokio::process::Command::new(runner);
+        command.arg(binary);
+
+        // Run in the browser even if the tests didn't call `wasm_bindgen_test_configure!(run_in_browser)`
+        command.env("WASM_BINDGEN_USE_BROWSER", "1");
+
+        let (driver, driver_env) = self.browser.driver();
+        let driver_path = which::which(driver).with_context(|| {
+            format!("Failed to find {driver}. Install it and make sure it is in your PATH to run web tests.")
+        })?;
+        command.env(driver_env, driver_path);
+
+        if self.headed {
+            command.env("NO_HEADLESS", "1");
+        }
+
+        Ok(command)
+    }
+}
+
+/// The results parsed from the output of libtest. wasm-bindgen-test-runner prints results in the same format.
+#[derive(Debug, Default, PartialEq)]
+struct TestReport {
+    passed: usize,
+    failed: Vec<String>,
+    ignored: usize,
+}
+
+impl TestReport {
+    fn parse_line(&mut self, line: &str) {
+        let Some(test) = line.strip_prefix("test ") else {
+            return;
+        };
+        let Some((name, result)) = test.rsplit_once(" ... ") else {
+            return;
+        };
+
+        match result.trim() {
+            "ok" => self.passed += 1,
+            "FAILED" => self.failed.push(name.to_string()),
+            result if result.starts_with("ignored") => self.ignored += 1,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_libtest_output() {
+        let mut report = TestReport::default();
+        for line in [
+            "running 4 tests",
+            "test tests::renders ... ok",
+            "test tests::hydrates ... FAILED",
+            "test tests::slow ... ignored, needs a browser",
+            "test tests::routes ... ok",
+            "",
+            "test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out",
+        ] {
+            report.parse_line(line);
+        }
+
+        assert_eq!(
+            report,
+            TestReport {
+                passed: 2,
+                failed: vec!["tests::hydrates".to_string()],
+                ignored: 1,
+            }
+        );
+    }
+}
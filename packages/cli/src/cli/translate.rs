@@ -1,7 +1,10 @@
 use super::*;
 use crate::{Result, StructuredOutput};
-use anyhow::bail;
+use anyhow::{bail, Context};
 use dioxus_rsx::{BodyNode, CallBody, TemplateBody};
+use dioxus_rsx_rosetta::Icon;
+use std::path::Path;
+use syn::parse::Parser as _;
 
 /// Translate some source file into Dioxus code
 #[derive(Clone, Debug, Parser)]
@@ -22,30 +25,209 @@ pub(crate) struct Translate {
     /// Output file, stdout if not present
     #[arg(short, long)]
     pub(crate) output: Option<PathBuf>,
+
+    /// The language of the input. Inferred from the extension of `--file` and html otherwise
+    #[clap(long, value_enum)]
+    pub(crate) from: Option<TranslateFrom>,
+
+    /// The language to translate into
+    #[clap(long, value_enum, default_value_t)]
+    pub(crate) to: TranslateTo,
+
+    /// Convert a directory of svg files into a module of icon components
+    #[clap(long, conflicts_with_all = ["file", "raw"])]
+    pub(crate) icons: Option<PathBuf>,
+
+    /// Write the icons into a single svg sprite at this path and reference them with `<use>` instead of inlining them
+    #[clap(long, requires = "icons")]
+    pub(crate) sprite: Option<PathBuf>,
+}
+
+/// The languages `dx translate` can read
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum TranslateFrom {
+    /// Html markup
+    Html,
+    /// A rust file with components or the body of an `rsx!` call
+    Rsx,
+    /// React components written in JSX
+    Jsx,
+    /// React components written in TSX
+    Tsx,
+}
+
+/// The languages `dx translate` can write
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum TranslateTo {
+    /// Dioxus rsx
+    #[default]
+    Rsx,
+    /// Static html
+    Html,
+    /// React components in JSX
+    Jsx,
+    /// React components in TSX
+    Tsx,
 }
 
 impl Translate {
     pub(crate) fn translate(self) -> Result<StructuredOutput> {
+        if let Some(icons) = &self.icons {
+            return translate_icons(icons, self.sprite.as_deref(), self.output);
+        }
+
+        let from = self.from.unwrap_or_else(|| {
+            match self
+                .file
+                .as_deref()
+                .and_then(|file| Path::new(file).extension())
+                .and_then(|ext| ext.to_str())
+            {
+                Some("rs") => TranslateFrom::Rsx,
+                Some("jsx" | "js") => TranslateFrom::Jsx,
+                Some("tsx" | "ts") => TranslateFrom::Tsx,
+                _ => TranslateFrom::Html,
+            }
+        });
+
         // Get the right input for the translation
         let contents = determine_input(self.file, self.raw)?;
 
-        // Ensure we're loading valid HTML
-        let dom = html_parser::Dom::parse(&contents)?;
+        let output = match (from, self.to) {
+            (TranslateFrom::Html, TranslateTo::Rsx) => {
+                // Ensure we're loading valid HTML
+                let dom = html_parser::Dom::parse(&contents)?;
+
+                // Convert the HTML to RSX
+                let html = convert_html_to_formatted_rsx(&dom, self.component);
+                write_output(self.output, &html)?;
+
+                return Ok(StructuredOutput::HtmlTranslate { html });
+            }
+            (TranslateFrom::Jsx | TranslateFrom::Tsx, TranslateTo::Rsx) => {
+                dioxus_rsx_rosetta::rsx_from_jsx(&contents, from == TranslateFrom::Tsx)?
+            }
+            (
+                TranslateFrom::Rsx,
+                to @ (TranslateTo::Html | TranslateTo::Jsx | TranslateTo::Tsx),
+            ) => convert_rsx(&contents, to)?,
+            (from, to) => bail!("Translating from {from:?} to {to:?} is not supported"),
+        };
+
+        write_output(self.output, &output)?;
+
+        Ok(StructuredOutput::Translate { output })
+    }
+}
+
+// todo(jon): we should probably use tracing out a different output format
+// right now we're just printing to stdout since some tools rely on that, but likely we don't want that
+// instead we should be printing as json (or maybe even a different format) if we're not interactive
+fn write_output(output: Option<PathBuf>, contents: &str) -> Result<()> {
+    match output {
+        Some(output) => std::fs::write(output, contents)?,
+        None => print!("{contents}"),
+    }
+    Ok(())
+}
 
-        // Convert the HTML to RSX
-        let html = convert_html_to_formatted_rsx(&dom, self.component);
+/// Convert every svg in a directory into an icon component, optionally backed by a sprite sheet
+fn translate_icons(
+    dir: &Path,
+    sprite: Option<&Path>,
+    output: Option<PathBuf>,
+) -> Result<StructuredOutput> {
+    let mut files = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read the icon directory {}", dir.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "svg"))
+        .collect::<Vec<_>>();
+    files.sort();
 
-        // Write the output
-        // todo(jon): we should probably use tracing out a different output format
-        // right now we're just printing to stdout since some tools rely on that, but likely we don't want that
-        // instead we should be printing as json (or maybe even a different format) if we're not interactive
-        match self.output {
-            Some(output) => std::fs::write(output, &html)?,
-            None => print!("{html}"),
+    let mut icons: Vec<Icon> = vec![];
+    for file in files {
+        let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+        let contents = std::fs::read_to_string(&file)?;
+        let Some(icon) = Icon::from_svg(&stem, &contents) else {
+            tracing::warn!("Skipping {} because it isn't a valid svg", file.display());
+            continue;
+        };
+        if icons.iter().any(|existing| existing.name == icon.name) {
+            tracing::warn!(
+                "Skipping {} because another icon is already named {}",
+                file.display(),
+                icon.name
+            );
+            continue;
         }
+        icons.push(icon);
+    }
 
-        Ok(StructuredOutput::HtmlTranslate { html })
+    if icons.is_empty() {
+        bail!("No svg files found in {}", dir.display());
     }
+
+    let sprite_asset = match sprite {
+        Some(sprite) => {
+            if let Some(parent) = sprite
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+            {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(sprite, dioxus_rsx_rosetta::icons_sprite(&icons))?;
+            Some(asset_path(sprite)?)
+        }
+        None => None,
+    };
+
+    let module = dioxus_rsx_rosetta::icons_module(&icons, sprite_asset.as_deref());
+    write_output(output, &module)?;
+
+    Ok(StructuredOutput::Translate { output: module })
+}
+
+/// The path of a file relative to the root of its crate, which is how `asset!()` expects it
+fn asset_path(file: &Path) -> Result<String> {
+    let file = std::path::absolute(file)?;
+    let relative = match file
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("Cargo.toml").exists())
+    {
+        Some(root) => file.strip_prefix(root)?,
+        None => Path::new(file.file_name().unwrap_or_default()),
+    };
+
+    Ok(format!(
+        "/{}",
+        relative.to_string_lossy().replace('\\', "/")
+    ))
+}
+
+/// Convert either a rust file with components or the body of an `rsx!` call into html or JSX
+fn convert_rsx(contents: &str, to: TranslateTo) -> Result<String> {
+    if let Ok(file) = syn::parse_file(contents) {
+        if !file.items.is_empty() {
+            return Ok(match to {
+                TranslateTo::Html => dioxus_rsx_rosetta::html_from_rust(&file),
+                _ => dioxus_rsx_rosetta::jsx_from_rust(&file, to == TranslateTo::Tsx),
+            });
+        }
+    }
+
+    // Accept both `rsx! { div {} }` and `div {}`
+    let tokens = match syn::parse_str::<syn::Macro>(contents) {
+        Ok(mac) => mac.tokens,
+        Err(_) => contents.parse()?,
+    };
+    let body = CallBody::parse_strict.parse2(tokens)?;
+
+    Ok(match to {
+        TranslateTo::Html => dioxus_rsx_rosetta::html_from_rsx(&body),
+        _ => dioxus_rsx_rosetta::jsx_from_rsx(&body),
+    })
 }
 
 pub fn convert_html_to_formatted_rsx(dom: &Dom, component: bool) -> String {
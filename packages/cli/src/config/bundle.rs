@@ -30,6 +30,8 @@ pub(crate) struct BundleConfig {
     pub(crate) windows: Option<WindowsSettings>,
     #[serde(default)]
     pub(crate) android: Option<AndroidSettings>,
+    #[serde(default)]
+    pub(crate) updater: Option<UpdaterSettings>,
     pub(crate) version: Option<String>,
     pub(crate) file_version: Option<String>,
     pub(crate) original_file_name: Option<String>,
@@ -92,6 +94,21 @@ pub(crate) struct DebianSettings {
     pub post_remove_script: Option<PathBuf>,
 }
 
+/// Settings for the update manifest `dx bundle` writes next to the updater artifacts.
+///
+/// The artifacts are signed with the minisign private key in the `DIOXUS_SIGNING_PRIVATE_KEY` environment variable.
+/// The key can be the base64 encoded key or a path to the key file. If the key is encrypted, the password is read
+/// from `DIOXUS_SIGNING_PRIVATE_KEY_PASSWORD`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub(crate) struct UpdaterSettings {
+    /// The URL the artifacts will be hosted at. The file name of each artifact is appended to it in the manifest.
+    #[serde(default)]
+    pub(crate) base_url: Option<String>,
+    /// The release notes to include in the manifest
+    #[serde(default)]
+    pub(crate) notes: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub(crate) struct WixSettings {
     #[serde(default)]
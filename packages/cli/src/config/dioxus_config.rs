@@ -10,6 +10,9 @@ pub(crate) struct DioxusConfig {
 
     #[serde(default)]
     pub(crate) bundle: BundleConfig,
+
+    #[serde(default)]
+    pub(crate) check: dioxus_check::CheckConfig,
 }
 
 impl Default for DioxusConfig {
@@ -52,8 +55,12 @@ impl Default for DioxusConfig {
                 },
                 pre_compress: false,
                 wasm_opt: Default::default(),
+                budget: Default::default(),
+                pwa: None,
+                source_maps: Default::default(),
             },
             bundle: BundleConfig::default(),
+            check: Default::default(),
         }
     }
 }
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct WebConfig {
@@ -25,6 +25,18 @@ pub(crate) struct WebConfig {
     /// The wasm-opt configuration
     #[serde(default)]
     pub(crate) wasm_opt: WasmOptConfig,
+
+    /// Size limits for the bundle. The build fails if any of them are exceeded.
+    #[serde(default)]
+    pub(crate) budget: WebBudgetConfig,
+
+    /// Generate a web app manifest and service worker so the app can be installed and used offline
+    #[serde(default)]
+    pub(crate) pwa: Option<WebPwaConfig>,
+
+    /// Source maps for processed css, scss and js assets
+    #[serde(default)]
+    pub(crate) source_maps: WebSourceMapsConfig,
 }
 
 impl Default for WebConfig {
@@ -34,6 +46,9 @@ impl Default for WebConfig {
             app: Default::default(),
             https: Default::default(),
             wasm_opt: Default::default(),
+            budget: Default::default(),
+            pwa: Default::default(),
+            source_maps: Default::default(),
             proxy: Default::default(),
             watcher: Default::default(),
             resource: Default::default(),
@@ -101,6 +116,44 @@ pub(crate) enum WasmOptLevel {
     Four,
 }
 
+/// Size limits for a web bundle, in bytes.
+///
+/// ```toml
+/// [web.budget]
+/// wasm = 2_000_000
+/// asset = 500_000
+///
+/// [web.budget.crates]
+/// regex = 300_000
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub(crate) struct WebBudgetConfig {
+    /// The total size of every wasm file in the bundle after optimization, including wasm-split chunks
+    #[serde(default)]
+    pub(crate) wasm: Option<u64>,
+
+    /// The total size of every asset in the bundle after optimization
+    #[serde(default)]
+    pub(crate) assets: Option<u64>,
+
+    /// The size of the largest asset in the bundle after optimization
+    #[serde(default)]
+    pub(crate) asset: Option<u64>,
+
+    /// The size of the code each crate contributes to the wasm binary
+    #[serde(default)]
+    pub(crate) crates: HashMap<String, u64>,
+}
+
+impl WebBudgetConfig {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.wasm.is_none()
+            && self.assets.is_none()
+            && self.asset.is_none()
+            && self.crates.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct WebAppConfig {
     #[serde(default = "default_title")]
@@ -117,9 +170,184 @@ impl Default for WebAppConfig {
     }
 }
 
+/// The web app manifest and service worker for a progressive web app.
+///
+/// ```toml
+/// [web.pwa]
+/// short_name = "Todos"
+/// theme_color = "#0f172a"
+///
+/// [[web.pwa.icons]]
+/// src = "assets/icon-512.png"
+///
+/// [[web.pwa.runtime_caching]]
+/// path = "/api/todos"
+/// strategy = "stale-while-revalidate"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub(crate) struct WebPwaConfig {
+    /// The name of the app. Defaults to the title in `[web.app]`.
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+
+    /// The name shown on the home screen when there isn't room for the full name
+    #[serde(default)]
+    pub(crate) short_name: Option<String>,
+
+    /// Defaults to the short description in `[bundle]`
+    #[serde(default)]
+    pub(crate) description: Option<String>,
+
+    #[serde(default)]
+    pub(crate) theme_color: Option<String>,
+
+    #[serde(default)]
+    pub(crate) background_color: Option<String>,
+
+    /// How the app is displayed when it's launched [default: standalone]
+    #[serde(default = "default_pwa_display")]
+    pub(crate) display: String,
+
+    /// The icons of the app, relative to the crate root. Defaults to the PNG icons in `[bundle]`.
+    #[serde(default)]
+    pub(crate) icons: Vec<WebPwaIconConfig>,
+
+    /// Caching strategies for requests that aren't precached, like server functions.
+    ///
+    /// Requests that don't match any of these are always sent to the network. GET and POST requests are cached, and
+    /// POST requests are cached separately for every body, so each call to a server function gets its own entry.
+    #[serde(default)]
+    pub(crate) runtime_caching: Vec<WebPwaRuntimeCacheConfig>,
+}
+
+fn default_pwa_display() -> String {
+    "standalone".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WebPwaIconConfig {
+    /// The path of the icon, relative to the crate root
+    pub(crate) src: PathBuf,
+
+    /// The sizes of the icon, like `192x192`. This is read from the file for PNG icons.
+    #[serde(default)]
+    pub(crate) sizes: Option<String>,
+
+    /// The purpose of the icon, like `maskable`
+    #[serde(default)]
+    pub(crate) purpose: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WebPwaRuntimeCacheConfig {
+    /// The path prefix of the requests this rule applies to, e.g. `/api`
+    pub(crate) path: String,
+
+    /// How matching requests are cached
+    pub(crate) strategy: WebPwaCacheStrategy,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum WebPwaCacheStrategy {
+    /// Use the network and fall back to the cache when offline
+    NetworkFirst,
+    /// Use the cache and only use the network if the request isn't cached yet
+    CacheFirst,
+    /// Use the cache and update it from the network in the background
+    StaleWhileRevalidate,
+    /// Never cache the request
+    NetworkOnly,
+}
+
+/// Source maps for the css, scss and js assets processed by the build.
+///
+/// Dev builds link a `.map` file next to each asset so devtools show the original sources. Release builds discard
+/// them unless configured otherwise. Maps are only written for assets the build transforms: minified css, scss, and js
+/// that is minified, bundled or compiled from typescript or jsx. Other assets are served as is.
+///
+/// ```toml
+/// [web.source_maps]
+/// release = "hidden"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WebSourceMapsConfig {
+    /// Emit linked source maps in dev builds [default: true]
+    #[serde(default = "true_bool")]
+    pub(crate) dev: bool,
+
+    /// What to do with source maps in release builds [default: discard]
+    #[serde(default)]
+    pub(crate) release: ReleaseSourceMaps,
+}
+
+impl Default for WebSourceMapsConfig {
+    fn default() -> Self {
+        Self {
+            dev: true_bool(),
+            release: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum ReleaseSourceMaps {
+    /// Don't emit source maps
+    #[default]
+    Discard,
+    /// Write source maps to a `sourcemaps` directory next to the bundle, without linking them from the assets. Upload
+    /// them to your error tracker to symbolicate stack traces without shipping them to users.
+    Hidden,
+    /// Ship source maps next to the assets and link them, like dev builds
+    Linked,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct WebProxyConfig {
+    /// The URL of the backend, e.g. `http://localhost:8000/api`
     pub(crate) backend: String,
+
+    /// The path the proxy is mounted at on the dev server. Defaults to the path of the backend URL.
+    ///
+    /// If this is set, the path is rewritten before the request is forwarded. With `path = "/api"` and
+    /// `backend = "http://localhost:8000/v1"`, a request to `/api/users` is forwarded to `http://localhost:8000/v1/users`.
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+
+    /// Headers to add to every request forwarded to the backend, e.g. an auth token for development
+    #[serde(default)]
+    pub(crate) request_headers: HashMap<String, String>,
+
+    /// Headers to set on every response from the backend, replacing the headers the backend sent
+    #[serde(default)]
+    pub(crate) response_headers: HashMap<String, String>,
+
+    /// Routes that are served from local files instead of the backend
+    #[serde(default)]
+    pub(crate) mocks: Vec<WebProxyMockConfig>,
+}
+
+/// A route under a proxy that is served from a local JSON file instead of the backend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct WebProxyMockConfig {
+    /// The path of the request on the dev server, e.g. `/api/users`
+    pub(crate) path: String,
+
+    /// The HTTP method to mock. If this is not set, every method is mocked.
+    #[serde(default)]
+    pub(crate) method: Option<String>,
+
+    /// The file to respond with, relative to the crate root
+    pub(crate) file: PathBuf,
+
+    /// The status code to respond with
+    #[serde(default = "default_mock_status")]
+    pub(crate) status: u16,
+}
+
+fn default_mock_status() -> u16 {
+    200
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
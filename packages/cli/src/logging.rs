@@ -38,6 +38,7 @@ use tracing_subscriber::{
     fmt::{
         format::{self, Writer},
         time::FormatTime,
+        writer::BoxMakeWriter,
     },
     prelude::*,
     registry::LookupSpan,
@@ -114,6 +115,12 @@ impl TraceController {
             )
             .with_timer(PrettyUptime::default());
 
+        // The language server talks to the editor over stdout, so its logs go to stderr instead
+        let fmt_layer = match args.action {
+            Commands::Lsp(_) => fmt_layer.with_writer(BoxMakeWriter::new(std::io::stderr)),
+            _ => fmt_layer.with_writer(BoxMakeWriter::new(std::io::stdout)),
+        };
+
         let fmt_layer = if args.verbosity.json_output {
             fmt_layer.json().flatten_event(true).boxed()
         } else {
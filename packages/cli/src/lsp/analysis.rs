@@ -0,0 +1,422 @@
+//! Find out what the cursor is pointing at inside of an rsx macro.
+//!
+//! The file is usually only half written while the user is typing, so this works on the text directly instead of
+//! parsing it with syn.
+
+use dioxus_html::definitions::{self, ElementDefinition};
+use std::collections::HashSet;
+use tower_lsp::lsp_types::*;
+
+/// Where the cursor is inside of an rsx macro
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RsxContext {
+    /// Somewhere only nodes can go, like the root of the macro, the body of a component or the body of a for loop
+    Children,
+    /// Inside the body of an element where attributes, event handlers and children can go
+    Element(&'static ElementDefinition),
+}
+
+/// The kind of block an open bracket started
+#[derive(Debug, Clone, Copy)]
+enum Frame {
+    /// Rust code, like an attribute value, an expression or code outside of rsx
+    Rust,
+    /// A block of rsx that holds children
+    Children,
+    /// The body of an element
+    Element(&'static ElementDefinition),
+}
+
+/// Find the rsx context at the byte offset in the text, if the offset is somewhere new nodes or attributes can go
+pub(crate) fn rsx_context(text: &str, offset: usize) -> Option<RsxContext> {
+    let text = text.get(..offset)?;
+    let bytes = text.as_bytes();
+    let mut stack: Vec<Frame> = vec![];
+    // The start of the current node or attribute in the innermost rsx block
+    let mut segment_start = 0;
+    let mut rsx_macro = false;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let rest = &text[index..];
+        let c = rest.chars().next()?;
+
+        // Skip comments and literals, they can contain brackets
+        if rest.starts_with("//") {
+            match rest.find('\n') {
+                Some(end) => index += end,
+                None => return None,
+            }
+            continue;
+        }
+        if rest.starts_with("/*") {
+            match rest.find("*/") {
+                Some(end) => index += end + 2,
+                None => return None,
+            }
+            continue;
+        }
+        if let Some(len) = string_literal_len(rest) {
+            if index + len > bytes.len() {
+                // The cursor is inside of the string
+                return None;
+            }
+            index += len;
+            continue;
+        }
+        if c == '\'' {
+            index += char_literal_len(rest).unwrap_or(1);
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let after = rest[len..].trim_start();
+            rsx_macro = &rest[..len] == "rsx" && after.starts_with('!');
+            index += len;
+            continue;
+        }
+
+        match c {
+            '{' | '(' | '[' => {
+                let in_rsx = matches!(stack.last(), Some(Frame::Children | Frame::Element(_)));
+                let frame = if rsx_macro {
+                    Frame::Children
+                } else if in_rsx && c == '{' {
+                    block_frame(&text[segment_start..index])
+                } else {
+                    Frame::Rust
+                };
+                stack.push(frame);
+                segment_start = index + 1;
+                rsx_macro = false;
+            }
+            '}' | ')' | ']' => {
+                stack.pop();
+                segment_start = index + 1;
+            }
+            ',' | ';' => segment_start = index + 1,
+            '!' => {}
+            _ if c.is_whitespace() => {}
+            _ => rsx_macro = false,
+        }
+        index += c.len_utf8();
+    }
+
+    // The cursor is after an attribute name and before its value
+    if text[segment_start..].contains(':') {
+        return None;
+    }
+
+    match stack.last()? {
+        Frame::Rust => None,
+        Frame::Children => Some(RsxContext::Children),
+        Frame::Element(element) => Some(RsxContext::Element(element)),
+    }
+}
+
+/// Find what kind of block a brace inside of rsx opens from the text before it in the same node
+fn block_frame(header: &str) -> Frame {
+    let header = header.trim();
+    let last_line = header.lines().last().unwrap_or_default().trim();
+
+    if let Some(element) = definitions::element(last_line) {
+        return Frame::Element(element);
+    }
+
+    // Components, for loops and if chains all hold children
+    let is_path = !last_line.is_empty()
+        && last_line
+            .split("::")
+            .all(|segment| !segment.is_empty() && segment.chars().all(is_ident_char));
+    let is_control_flow = ["for ", "if ", "else"]
+        .iter()
+        .any(|keyword| header.starts_with(keyword) || last_line.starts_with(keyword));
+    if is_path || is_control_flow {
+        return Frame::Children;
+    }
+
+    Frame::Rust
+}
+
+/// The length of the string literal at the start of the text, if there is one. If the string isn't closed, the length
+/// is past the end of the text
+fn string_literal_len(text: &str) -> Option<usize> {
+    let prefix = text
+        .strip_prefix("br")
+        .or_else(|| text.strip_prefix('r'))
+        .or_else(|| text.strip_prefix('b'))
+        .or_else(|| text.strip_prefix('c'))
+        .unwrap_or(text);
+    let prefix_len = text.len() - prefix.len();
+
+    // Raw strings end with a quote followed by the same number of hashes they start with
+    if text[..prefix_len].ends_with('r') {
+        let hashes = prefix.len() - prefix.trim_start_matches('#').len();
+        let body = prefix[hashes..].strip_prefix('"')?;
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let start = prefix_len + hashes + 1;
+        return Some(match body.find(&terminator) {
+            Some(end) => start + end + terminator.len(),
+            None => text.len() + 1,
+        });
+    }
+
+    let body = prefix.strip_prefix('"')?;
+    let mut chars = body.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some(prefix_len + 1 + index + 1),
+            '\\' => {
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+    Some(text.len() + 1)
+}
+
+/// The length of the char literal at the start of the text. Returns `None` for lifetimes
+fn char_literal_len(text: &str) -> Option<usize> {
+    let body = text.strip_prefix('\'')?;
+    if let Some(escaped) = body.strip_prefix('\\') {
+        return Some(1 + 1 + escaped.find('\'')? + 1);
+    }
+    let c = body.chars().next()?;
+    body[c.len_utf8()..]
+        .starts_with('\'')
+        .then(|| 1 + c.len_utf8() + 1)
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The range of the identifier the offset is in or right after
+fn word_at(text: &str, offset: usize) -> Option<(usize, usize)> {
+    let before = text.get(..offset)?;
+    let after = &text[offset..];
+    let start = before
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_ident_char(*c))
+        .last()
+        .map_or(offset, |(index, _)| index);
+    let end = offset + after.find(|c| !is_ident_char(c)).unwrap_or(after.len());
+    if start == end {
+        return None;
+    }
+
+    // Include the `r#` of raw identifiers like `r#type`
+    let start = match text[..start].ends_with("r#") {
+        true => start - 2,
+        false => start,
+    };
+    Some((start, end))
+}
+
+/// Complete elements, attributes and event handlers at the offset
+pub(crate) fn completions(text: &str, offset: usize) -> Vec<CompletionItem> {
+    // Complete from the start of the word the user is typing
+    let start = word_at(text, offset).map_or(offset, |(start, _)| start);
+    let Some(context) = rsx_context(text, start) else {
+        return vec![];
+    };
+
+    let mut items = vec![];
+    if let RsxContext::Element(element) = context {
+        let mut seen = HashSet::new();
+        for attribute in element.attributes.iter().chain(element.shared_attributes()) {
+            if seen.insert(attribute.name) {
+                items.push(CompletionItem {
+                    label: attribute.name.to_string(),
+                    kind: Some(CompletionItemKind::PROPERTY),
+                    detail: Some("attribute".to_string()),
+                    documentation: documentation(attribute.docs()),
+                    insert_text: Some(format!("{}: \"$0\",", attribute.name)),
+                    insert_text_format: Some(InsertTextFormat::SNIPPET),
+                    ..Default::default()
+                });
+            }
+        }
+
+        for event in definitions::events() {
+            items.push(CompletionItem {
+                label: event.name.to_string(),
+                kind: Some(CompletionItemKind::EVENT),
+                detail: Some(format!("Event<{}>", event.data)),
+                documentation: documentation(event.docs()),
+                insert_text: Some(format!("{}: move |_| $0,", event.name)),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            });
+        }
+    }
+
+    for element in definitions::elements() {
+        items.push(CompletionItem {
+            label: element.name.to_string(),
+            kind: Some(CompletionItemKind::STRUCT),
+            detail: Some("element".to_string()),
+            documentation: documentation(element.docs()),
+            insert_text: Some(format!("{} {{ $0 }}", element.name)),
+            insert_text_format: Some(InsertTextFormat::SNIPPET),
+            ..Default::default()
+        });
+    }
+
+    items
+}
+
+fn documentation(docs: String) -> Option<Documentation> {
+    (!docs.is_empty()).then_some(Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value: docs,
+    }))
+}
+
+/// Get the docs for the element, attribute or event handler at the offset along with the range of its name
+pub(crate) fn hover(text: &str, offset: usize) -> Option<(String, Range)> {
+    let (start, end) = word_at(text, offset)?;
+    let name = &text[start..end];
+    let context = rsx_context(text, start)?;
+    let range = Range::new(position_at(text, start), position_at(text, end));
+
+    let docs = match text[end..].trim_start().chars().next()? {
+        '{' => {
+            let element = definitions::element(name)?;
+            format!("```rust\n{name} {{}}\n```\n\n{}", element.docs())
+        }
+        ':' | ',' | '}' => {
+            if let Some(event) = definitions::event(name) {
+                format!(
+                    "```rust\n{name}: Event<{}>\n```\n\n{}",
+                    event.data,
+                    event.docs()
+                )
+            } else {
+                let RsxContext::Element(element) = context else {
+                    return None;
+                };
+                let attribute = element.attribute(name)?;
+                format!("```rust\n{name}\n```\n\n{}", attribute.docs())
+            }
+        }
+        _ => return None,
+    };
+
+    Some((docs, range))
+}
+
+/// Get the name of the component at the offset, if the offset is on a component in rsx
+pub(crate) fn component_at(text: &str, offset: usize) -> Option<String> {
+    let (start, end) = word_at(text, offset)?;
+    let name = &text[start..end];
+    rsx_context(text, start)?;
+
+    // Components start with an uppercase letter and are followed by their body
+    let is_component =
+        name.starts_with(|c: char| c.is_uppercase()) && text[end..].trim_start().starts_with('{');
+    is_component.then(|| name.to_string())
+}
+
+/// Convert an LSP position (0-indexed line, utf-16 column) into a byte offset in the text
+pub(crate) fn offset_at(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        if index == position.line as usize {
+            let mut utf16 = 0;
+            for (offset, c) in line.char_indices() {
+                if utf16 >= position.character as usize || c == '\n' {
+                    return line_start + offset;
+                }
+                utf16 += c.len_utf16();
+            }
+            return line_start + line.len();
+        }
+        line_start += line.len();
+    }
+    text.len()
+}
+
+/// Convert a byte offset in the text into an LSP position
+pub(crate) fn position_at(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let character = before[line_start..].encode_utf16().count();
+    Position::new(line as u32, character as u32)
+}
+
+/// Convert a line (1-indexed) and column (0-indexed, in chars) from a span into an LSP position
+pub(crate) fn position_of(text: &str, line: usize, column: usize) -> Position {
+    let line_text = text.lines().nth(line.saturating_sub(1)).unwrap_or_default();
+    let character = line_text
+        .chars()
+        .take(column)
+        .map(char::len_utf16)
+        .sum::<usize>();
+    Position::new(line.saturating_sub(1) as u32, character as u32)
+}
+
+/// Convert a proc-macro2 span into an LSP range
+pub(crate) fn range_of(text: &str, span: proc_macro2::Span) -> Range {
+    let (start, end) = (span.start(), span.end());
+    Range::new(
+        position_of(text, start.line, start.column),
+        position_of(text, end.line, end.column),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn context_at_cursor(text: &str) -> Option<RsxContext> {
+        let offset = text.find('|').unwrap();
+        rsx_context(&text.replace('|', ""), offset)
+    }
+
+    #[test]
+    fn finds_rsx_context() {
+        assert_eq!(
+            context_at_cursor("fn app() { rsx! { | } }"),
+            Some(RsxContext::Children)
+        );
+        assert_eq!(
+            context_at_cursor("rsx! { div { class: \"a\", | } }"),
+            Some(RsxContext::Element(definitions::element("div").unwrap()))
+        );
+        assert_eq!(
+            context_at_cursor("rsx! { for item in items { | } }"),
+            Some(RsxContext::Children)
+        );
+        assert_eq!(
+            context_at_cursor("rsx! { Child { | } }"),
+            Some(RsxContext::Children)
+        );
+    }
+
+    #[test]
+    fn no_rsx_context_outside_of_nodes() {
+        assert_eq!(context_at_cursor("fn app() { | }"), None);
+        assert_eq!(context_at_cursor("rsx! { div { \"{|}\" } }"), None);
+        assert_eq!(context_at_cursor("rsx! { div { class: | } }"), None);
+        assert_eq!(
+            context_at_cursor("rsx! { div { onclick: move |_| { | } } }"),
+            None
+        );
+        assert_eq!(context_at_cursor("rsx! { div {} } |"), None);
+    }
+
+    #[test]
+    fn converts_positions() {
+        let text = "let a = \"é\";\nlet b = 1;";
+        let offset = text.find('b').unwrap();
+        assert_eq!(position_at(text, offset), Position::new(1, 4));
+        assert_eq!(offset_at(text, Position::new(1, 4)), offset);
+        assert_eq!(position_of(text, 1, 10), Position::new(0, 10));
+    }
+}
@@ -0,0 +1,65 @@
+use super::analysis::range_of;
+use crate::cli::check::collect_rs_files;
+use std::path::Path;
+use syn::visit::Visit;
+use tower_lsp::lsp_types::*;
+
+/// Find every component named `name` in the rust files under the root of the workspace
+pub(crate) fn find_components(root: &Path, name: &str) -> Vec<Location> {
+    let mut files = vec![];
+    collect_rs_files(root, &mut files);
+
+    let mut locations = vec![];
+    for path in files {
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+
+        // Most files don't mention the component at all, so skip parsing them
+        if !text.contains(name) {
+            continue;
+        }
+        let Ok(uri) = Url::from_file_path(&path) else {
+            continue;
+        };
+
+        locations.extend(components_in_file(&uri, &text, name));
+
+        // Span locations are stored per thread until they are invalidated, so they would grow with every file parsed
+        proc_macro2::extra::invalidate_current_thread_spans();
+    }
+
+    locations
+}
+
+fn components_in_file(uri: &Url, text: &str, name: &str) -> Vec<Location> {
+    let Ok(file) = syn::parse_file(text) else {
+        return vec![];
+    };
+
+    let mut finder = FindComponent {
+        name,
+        found: vec![],
+    };
+    finder.visit_file(&file);
+    finder
+        .found
+        .into_iter()
+        .map(|span| Location::new(uri.clone(), range_of(text, span)))
+        .collect()
+}
+
+/// Finds the functions that define a component
+struct FindComponent<'a> {
+    name: &'a str,
+    found: Vec<proc_macro2::Span>,
+}
+
+impl<'ast> Visit<'ast> for FindComponent<'_> {
+    fn visit_item_fn(&mut self, i: &'ast syn::ItemFn) {
+        if i.sig.ident == self.name && dioxus_rsx::is_component_fn(i) {
+            self.found.push(i.sig.ident.span());
+        }
+        syn::visit::visit_item_fn(self, i);
+    }
+}
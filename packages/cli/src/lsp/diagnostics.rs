@@ -0,0 +1,117 @@
+use super::analysis::{position_of, range_of};
+use dioxus_check::{CheckConfig, Level};
+use dioxus_rsx::CallBody;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use std::path::Path;
+use syn::visit::Visit;
+use tower_lsp::lsp_types::*;
+
+/// Collect the rsx parse errors and `dioxus-check` issues in a rust file
+pub(crate) fn diagnostics(path: &Path, text: &str, config: &CheckConfig) -> Vec<Diagnostic> {
+    let diagnostics = collect_diagnostics(path, text, config);
+
+    // Span locations are stored per thread until they are invalidated, so they would grow with every edit. None of the
+    // spans from this analysis outlive it.
+    proc_macro2::extra::invalidate_current_thread_spans();
+
+    diagnostics
+}
+
+fn collect_diagnostics(path: &Path, text: &str, config: &CheckConfig) -> Vec<Diagnostic> {
+    // Rust analyzer already reports syntax errors outside of macros
+    let Ok(file) = syn::parse_file(text) else {
+        return vec![];
+    };
+
+    let mut diagnostics = vec![];
+
+    let mut macros = FindRsxMacros::default();
+    macros.visit_file(&file);
+    for tokens in macros.found {
+        match syn::parse2::<CallBody>(tokens) {
+            // Errors in the body are turned into `compile_error!` calls when the rsx is expanded
+            Ok(body) => compile_errors(text, body.into_token_stream(), &mut diagnostics),
+            Err(errors) => diagnostics.extend(errors.into_iter().map(|error| Diagnostic {
+                range: range_of(text, error.span()),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("rsx".to_string()),
+                message: error.to_string(),
+                ..Default::default()
+            })),
+        }
+    }
+
+    let report = dioxus_check::check_file_with_config(path.to_path_buf(), text, config);
+    for issue in &report.issues {
+        let span = issue.highlight();
+        let rule = issue.rule();
+        diagnostics.push(Diagnostic {
+            range: Range::new(
+                position_of(text, span.start.line, span.start.column),
+                position_of(text, span.end.line, span.end.column),
+            ),
+            severity: Some(match report.level(issue) {
+                Level::Deny => DiagnosticSeverity::ERROR,
+                _ => DiagnosticSeverity::WARNING,
+            }),
+            code: Some(NumberOrString::String(rule.id().to_string())),
+            code_description: rule
+                .help_uri()
+                .and_then(|uri| Url::parse(uri).ok())
+                .map(|href| CodeDescription { href }),
+            source: Some("dioxus-check".to_string()),
+            message: issue.to_string(),
+            ..Default::default()
+        });
+    }
+
+    diagnostics
+}
+
+/// Finds the tokens of every `rsx!` macro in a file
+#[derive(Default)]
+struct FindRsxMacros {
+    found: Vec<TokenStream>,
+}
+
+impl<'ast> Visit<'ast> for FindRsxMacros {
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        if i.path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "rsx")
+        {
+            self.found.push(i.tokens.clone());
+        }
+        syn::visit::visit_macro(self, i);
+    }
+}
+
+/// Turn every `compile_error! { "message" }` in the expanded tokens into a diagnostic
+fn compile_errors(text: &str, tokens: TokenStream, diagnostics: &mut Vec<Diagnostic>) {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    for (index, token) in tokens.iter().enumerate() {
+        match (token, tokens.get(index + 1), tokens.get(index + 2)) {
+            (
+                TokenTree::Ident(ident),
+                Some(TokenTree::Punct(bang)),
+                Some(TokenTree::Group(group)),
+            ) if ident == "compile_error" && bang.as_char() == '!' => {
+                let Ok(message) = syn::parse2::<syn::LitStr>(group.stream()) else {
+                    continue;
+                };
+                let end = range_of(text, group.span()).end;
+                diagnostics.push(Diagnostic {
+                    range: Range::new(range_of(text, ident.span()).start, end),
+                    severity: Some(DiagnosticSeverity::ERROR),
+                    source: Some("rsx".to_string()),
+                    message: message.value(),
+                    ..Default::default()
+                });
+            }
+            (TokenTree::Group(group), _, _) => compile_errors(text, group.stream(), diagnostics),
+            _ => {}
+        }
+    }
+}
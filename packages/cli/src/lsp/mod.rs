@@ -0,0 +1,237 @@
+//! A language server for rsx that any editor with LSP support can run with `dx lsp`.
+//!
+//! The server keeps a copy of each open rust file and uses it to:
+//! - Complete element names, attributes and event handlers from the element definitions in `dioxus-html`
+//! - Show the docs for elements, attributes and event handlers on hover
+//! - Jump to the definition of components
+//! - Report rsx parse errors and `dioxus-check` issues as diagnostics
+//!
+//! Everything else in rust files is left to rust-analyzer.
+
+mod analysis;
+mod definition;
+mod diagnostics;
+
+use dioxus_check::CheckConfig;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+use tower_lsp::jsonrpc::Result;
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+
+/// Run the language server over stdin and stdout until the client shuts it down
+pub(crate) async fn run() {
+    let (service, socket) = LspService::new(DioxusLanguageServer::new);
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
+        .serve(service)
+        .await;
+}
+
+struct DioxusLanguageServer {
+    client: Client,
+    /// The latest contents of each open document
+    documents: RwLock<HashMap<Url, String>>,
+    /// The root of the workspace the editor opened
+    root: RwLock<Option<PathBuf>>,
+    /// The `[check]` section of the Dioxus.toml in the root of the workspace
+    check_config: RwLock<CheckConfig>,
+}
+
+impl DioxusLanguageServer {
+    fn new(client: Client) -> Self {
+        Self {
+            client,
+            documents: Default::default(),
+            root: Default::default(),
+            check_config: Default::default(),
+        }
+    }
+
+    fn document(&self, uri: &Url) -> Option<String> {
+        self.documents.read().unwrap().get(uri).cloned()
+    }
+
+    /// Store the new contents of a document and publish its diagnostics
+    async fn update(&self, uri: Url, text: String, version: Option<i32>) {
+        self.documents
+            .write()
+            .unwrap()
+            .insert(uri.clone(), text.clone());
+
+        let Ok(path) = uri.to_file_path() else {
+            return;
+        };
+        if path.extension() != Some("rs".as_ref()) {
+            return;
+        }
+
+        // Checking the file can panic on code we don't expect, so it runs on its own thread
+        let config = self.check_config.read().unwrap().clone();
+        let diagnostics =
+            tokio::task::spawn_blocking(move || diagnostics::diagnostics(&path, &text, &config))
+                .await
+                .unwrap_or_default();
+
+        self.client
+            .publish_diagnostics(uri, diagnostics, version)
+            .await;
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for DioxusLanguageServer {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        #[allow(deprecated)]
+        let root = params
+            .workspace_folders
+            .as_ref()
+            .and_then(|folders| folders.first())
+            .map(|folder| &folder.uri)
+            .or(params.root_uri.as_ref())
+            .and_then(|uri| uri.to_file_path().ok());
+
+        if let Some(root) = &root {
+            *self.check_config.write().unwrap() = load_check_config(root);
+        }
+        *self.root.write().unwrap() = root;
+
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                    TextDocumentSyncKind::FULL,
+                )),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec!["{".to_string(), ",".to_string()]),
+                    ..Default::default()
+                }),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                definition_provider: Some(OneOf::Left(true)),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: "dx lsp".to_string(),
+                version: Some(crate::dx_build_info::PKG_VERSION.to_string()),
+            }),
+        })
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let document = params.text_document;
+        self.update(document.uri, document.text, Some(document.version))
+            .await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // We only ask for full document syncs, so the last change is the whole document
+        let Some(change) = params.content_changes.into_iter().last() else {
+            return;
+        };
+        let document = params.text_document;
+        self.update(document.uri, change.text, Some(document.version))
+            .await;
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        let uri = params.text_document.uri;
+
+        // The check config may have changed since the server started
+        if uri.path().ends_with("Dioxus.toml") {
+            if let Some(root) = self.root.read().unwrap().as_deref() {
+                *self.check_config.write().unwrap() = load_check_config(root);
+            }
+            return;
+        }
+
+        if let Some(text) = params.text.or_else(|| self.document(&uri)) {
+            self.update(uri, text, None).await;
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.write().unwrap().remove(&uri);
+        self.client.publish_diagnostics(uri, vec![], None).await;
+    }
+
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let position = params.text_document_position;
+        let Some(text) = self.document(&position.text_document.uri) else {
+            return Ok(None);
+        };
+
+        let offset = analysis::offset_at(&text, position.position);
+        let items = analysis::completions(&text, offset);
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let position = params.text_document_position_params;
+        let Some(text) = self.document(&position.text_document.uri) else {
+            return Ok(None);
+        };
+
+        let offset = analysis::offset_at(&text, position.position);
+        Ok(analysis::hover(&text, offset).map(|(docs, range)| Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: docs,
+            }),
+            range: Some(range),
+        }))
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let position = params.text_document_position_params;
+        let Some(text) = self.document(&position.text_document.uri) else {
+            return Ok(None);
+        };
+        let Some(root) = self.root.read().unwrap().clone() else {
+            return Ok(None);
+        };
+
+        let offset = analysis::offset_at(&text, position.position);
+        let Some(name) = analysis::component_at(&text, offset) else {
+            return Ok(None);
+        };
+
+        let locations =
+            tokio::task::spawn_blocking(move || definition::find_components(&root, &name))
+                .await
+                .unwrap_or_default();
+        if locations.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(GotoDefinitionResponse::Array(locations)))
+    }
+}
+
+/// Read the `[check]` section of the Dioxus.toml in the root of the workspace
+fn load_check_config(root: &Path) -> CheckConfig {
+    #[derive(Deserialize)]
+    struct PartialConfig {
+        #[serde(default)]
+        check: CheckConfig,
+    }
+
+    std::fs::read_to_string(root.join("Dioxus.toml"))
+        .ok()
+        .and_then(|contents| toml::from_str::<PartialConfig>(&contents).ok())
+        .map(|config| config.check)
+        .unwrap_or_default()
+}
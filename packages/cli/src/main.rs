@@ -13,6 +13,7 @@ mod dx_build_info;
 mod error;
 mod fastfs;
 mod logging;
+mod lsp;
 mod platform;
 mod rustcwrapper;
 mod serve;
@@ -56,6 +57,9 @@ async fn main() {
         Commands::Config(opts) => opts.config().await,
         Commands::Autoformat(opts) => opts.autoformat().await,
         Commands::Check(opts) => opts.check().await,
+        Commands::Lsp(opts) => opts.lsp().await,
+        Commands::Test(opts) => opts.test().await,
+        Commands::Clean(opts) => opts.clean().await,
         Commands::Build(opts) => opts.build().await,
         Commands::Serve(opts) => opts.serve().await,
         Commands::Bundle(opts) => opts.bundle().await,
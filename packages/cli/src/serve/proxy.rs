@@ -12,11 +12,13 @@ use axum::{
     Router,
 };
 use hyper::header::*;
-use hyper::{Request, Response, Uri};
+use hyper::{Method, Request, Response, Uri};
 use hyper_util::{
     client::legacy::{self, connect::HttpConnector},
     rt::TokioExecutor,
 };
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 struct ProxyClient {
@@ -55,23 +57,33 @@ impl ProxyClient {
 ///
 /// We will proxy requests directed at either:
 ///
-/// - the exact path of the proxy config's backend URL, e.g. /api
+/// - the exact path of the proxy config's backend URL (or its `path` if set), e.g. /api
 /// - the exact path with a trailing slash, e.g. /api/
 /// - any subpath of the backend URL, e.g. /api/foo/bar
-pub(crate) fn add_proxy(mut router: Router, proxy: &WebProxyConfig) -> Result<Router> {
+///
+/// Mocks are resolved relative to the crate dir.
+pub(crate) fn add_proxy(
+    mut router: Router,
+    proxy: &WebProxyConfig,
+    crate_dir: &Path,
+) -> Result<Router> {
     let url: Uri = proxy.backend.parse()?;
-    let path = url.path().to_string();
+    let path = proxy.path.clone().unwrap_or_else(|| url.path().to_string());
     let trimmed_path = path.trim_start_matches('/');
 
     if trimmed_path.is_empty() {
-        bail!(
-            "Proxy backend URL must have a non-empty path, e.g. {}/api instead of {}",
-            proxy.backend.trim_end_matches('/'),
-            proxy.backend
-        );
+        match proxy.path {
+            Some(_) => bail!("Proxy path must not be empty, e.g. /api instead of {path}"),
+            None => bail!(
+                "Proxy backend URL must have a non-empty path, e.g. {}/api instead of {}",
+                proxy.backend.trim_end_matches('/'),
+                proxy.backend
+            ),
+        }
     }
 
-    let method_router = proxy_to(url, false, handle_proxy_error);
+    let rules = ProxyRules::new(proxy, crate_dir)?;
+    let method_router = proxy_with_rules(url, false, handle_proxy_error, rules);
 
     // api/*path
     router = router.route(
@@ -94,10 +106,132 @@ pub(crate) fn add_proxy(mut router: Router, proxy: &WebProxyConfig) -> Result<Ro
     Ok(router)
 }
 
+/// The changes a proxy makes to the requests and responses it forwards
+#[derive(Debug, Clone, Default)]
+struct ProxyRules {
+    /// If set, this prefix of the request path is replaced with the path of the backend URL
+    rewrite_prefix: Option<String>,
+    request_headers: HeaderMap,
+    response_headers: HeaderMap,
+    mocks: Vec<ProxyMock>,
+}
+
+impl ProxyRules {
+    fn new(proxy: &WebProxyConfig, crate_dir: &Path) -> Result<Self> {
+        let parse_headers = |headers: &HashMap<String, String>| -> Result<HeaderMap> {
+            headers
+                .iter()
+                .map(|(name, value)| {
+                    let name = HeaderName::from_bytes(name.as_bytes())
+                        .with_context(|| format!("Invalid proxy header name: {name}"))?;
+                    let value = HeaderValue::from_str(value)
+                        .with_context(|| format!("Invalid value for proxy header {name}"))?;
+                    Ok((name, value))
+                })
+                .collect()
+        };
+
+        let mocks = proxy
+            .mocks
+            .iter()
+            .map(|mock| {
+                let method = mock
+                    .method
+                    .as_deref()
+                    .map(|method| Method::from_bytes(method.to_ascii_uppercase().as_bytes()))
+                    .transpose()
+                    .with_context(|| format!("Invalid method for proxy mock {}", mock.path))?;
+                let status = StatusCode::from_u16(mock.status)
+                    .with_context(|| format!("Invalid status for proxy mock {}", mock.path))?;
+
+                Ok(ProxyMock {
+                    path: mock.path.clone(),
+                    method,
+                    file: crate_dir.join(&mock.file),
+                    status,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            rewrite_prefix: proxy.path.clone(),
+            request_headers: parse_headers(&proxy.request_headers)?,
+            response_headers: parse_headers(&proxy.response_headers)?,
+            mocks,
+        })
+    }
+
+    /// Replace the mount path of the request with the path of the backend, keeping the rest of the path and query
+    fn rewrite_uri(&self, uri: &Uri, backend: &Uri) -> Result<Uri> {
+        let Some(prefix) = self.rewrite_prefix.as_deref() else {
+            return Ok(uri.clone());
+        };
+
+        let path = uri.path();
+        let rest = path
+            .strip_prefix(prefix.trim_end_matches('/'))
+            .unwrap_or(path);
+        let mut new_path = format!("{}{rest}", backend.path().trim_end_matches('/'));
+        if new_path.is_empty() {
+            new_path.push('/');
+        }
+        if let Some(query) = uri.query() {
+            new_path = format!("{new_path}?{query}");
+        }
+
+        let mut parts = uri.clone().into_parts();
+        parts.path_and_query = Some(new_path.parse().context("Invalid rewritten proxy path")?);
+        Uri::from_parts(parts).context("Invalid rewritten proxy URI")
+    }
+}
+
+/// A route that is served from a local file instead of the backend
+#[derive(Debug, Clone)]
+struct ProxyMock {
+    path: String,
+    method: Option<Method>,
+    file: PathBuf,
+    status: StatusCode,
+}
+
+impl ProxyMock {
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        self.path.trim_end_matches('/') == path.trim_end_matches('/')
+            && self.method.as_ref().map_or(true, |m| m == method)
+    }
+
+    /// Read the file on every request so edits to the mock are picked up without restarting the server
+    async fn respond(&self) -> Response<Body> {
+        match tokio::fs::read(&self.file).await {
+            Ok(contents) => Response::builder()
+                .status(self.status)
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(contents))
+                .unwrap(),
+            Err(err) => Response::builder()
+                .status(StatusCode::INTERNAL_SERVER_ERROR)
+                .body(Body::from(format!(
+                    "Failed to read proxy mock {}: {err}",
+                    self.file.display()
+                )))
+                .unwrap(),
+        }
+    }
+}
+
 pub(crate) fn proxy_to(
     url: Uri,
     nocache: bool,
     handle_error: fn(Error) -> Response<Body>,
+) -> MethodRouter {
+    proxy_with_rules(url, nocache, handle_error, ProxyRules::default())
+}
+
+fn proxy_with_rules(
+    url: Uri,
+    nocache: bool,
+    handle_error: fn(Error) -> Response<Body>,
+    rules: ProxyRules,
 ) -> MethodRouter {
     let client = ProxyClient::new(url.clone());
 
@@ -112,10 +246,25 @@ pub(crate) fn proxy_to(
                 .unwrap());
         }
 
+        if let Some(mock) = rules
+            .mocks
+            .iter()
+            .find(|mock| mock.matches(req.method(), req.uri().path()))
+        {
+            let res = mock.respond().await;
+            tracing::info!(dx_src = ?TraceSrc::Dev, "[{} mock] {}", res.status().as_u16(), req.uri());
+            return Ok(res);
+        }
+
+        *req.uri_mut() = rules.rewrite_uri(req.uri(), &url).map_err(handle_error)?;
+
         req.headers_mut().insert(
             "x-proxied-by-dioxus",
             "true".parse().expect("header value is valid"),
         );
+        for (name, value) in rules.request_headers.iter() {
+            req.headers_mut().insert(name.clone(), value.clone());
+        }
 
         let upgrade = req.headers().get(UPGRADE);
         if req.uri().scheme().map(|f| f.as_str()) == Some("ws")
@@ -148,7 +297,12 @@ pub(crate) fn proxy_to(
                     tracing::info!(dx_src = ?TraceSrc::Dev, "[{}] {}", res.status().as_u16(), uri);
                 }
 
-                Ok(res.into_response())
+                let mut res = res.into_response();
+                for (name, value) in rules.response_headers.iter() {
+                    res.headers_mut().insert(name.clone(), value.clone());
+                }
+
+                Ok(res)
             }
             Err(err) => {
                 tracing::error!(dx_src = ?TraceSrc::Dev, "[{}] {}", err.status().as_u16(), uri);
@@ -172,17 +326,24 @@ pub(crate) fn handle_proxy_error(e: Error) -> axum::http::Response<axum::body::B
 mod test {
 
     use super::*;
+    use crate::config::WebProxyMockConfig;
 
     use axum_server::{Handle, Server};
 
     async fn setup_servers(mut config: WebProxyConfig) -> String {
-        let backend_router =
-            Router::new().route(
-                "/{*path}",
-                any(|request: axum::extract::Request| async move {
-                    format!("backend: {}", request.uri())
-                }),
-            );
+        let backend_router = Router::new().route(
+            "/{*path}",
+            any(|request: axum::extract::Request| async move {
+                match request.headers().get("authorization") {
+                    Some(auth) => format!(
+                        "backend: {} authorization: {}",
+                        request.uri(),
+                        auth.to_str().unwrap()
+                    ),
+                    None => format!("backend: {}", request.uri()),
+                }
+            }),
+        );
 
         // The API backend server
         let backend_handle_handle = Handle::new();
@@ -200,7 +361,7 @@ mod test {
         config.backend = format!("http://{}{}", backend_addr, config.backend);
 
         // Now set up our actual filesystem server
-        let router = super::add_proxy(Router::new(), &config);
+        let router = super::add_proxy(Router::new(), &config, &std::env::temp_dir());
         let server_handle_handle = Handle::new();
         let server_handle_handle_ = server_handle_handle.clone();
         tokio::spawn(async move {
@@ -223,6 +384,10 @@ mod test {
             // path together.
             // So in day to day usage, use `http://localhost:8000/api` instead!
             backend: path,
+            path: None,
+            request_headers: Default::default(),
+            response_headers: Default::default(),
+            mocks: Default::default(),
         };
 
         let server_addr = setup_servers(config).await;
@@ -267,4 +432,117 @@ mod test {
     async fn add_proxy_trailing_slash() {
         test_proxy_requests("/api/".to_string()).await;
     }
+
+    #[tokio::test]
+    async fn rewrite_proxy_path() {
+        let config = WebProxyConfig {
+            backend: "/v1".to_string(),
+            path: Some("/api".to_string()),
+            request_headers: Default::default(),
+            response_headers: Default::default(),
+            mocks: Default::default(),
+        };
+
+        let server_addr = setup_servers(config).await;
+
+        assert_eq!(
+            reqwest::get(format!("http://{server_addr}/api/users?page=2"))
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap(),
+            "backend: /v1/users?page=2"
+        );
+
+        assert_eq!(
+            reqwest::get(format!("http://{server_addr}/api"))
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap(),
+            "backend: /v1"
+        );
+    }
+
+    #[tokio::test]
+    async fn proxy_headers() {
+        let config = WebProxyConfig {
+            backend: "/api".to_string(),
+            path: None,
+            request_headers: HashMap::from([(
+                "Authorization".to_string(),
+                "Bearer dev-token".to_string(),
+            )]),
+            response_headers: HashMap::from([(
+                "Content-Type".to_string(),
+                "text/x-dioxus".to_string(),
+            )]),
+            mocks: Default::default(),
+        };
+
+        let server_addr = setup_servers(config).await;
+
+        let response = reqwest::get(format!("http://{server_addr}/api/me"))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/x-dioxus");
+        assert_eq!(
+            response.text().await.unwrap(),
+            "backend: /api/me authorization: Bearer dev-token"
+        );
+    }
+
+    #[tokio::test]
+    async fn proxy_mocks() {
+        let mock_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(mock_file.path(), r#"[{"name":"dioxus"}]"#).unwrap();
+
+        let config = WebProxyConfig {
+            backend: "/api".to_string(),
+            path: None,
+            request_headers: Default::default(),
+            response_headers: Default::default(),
+            mocks: vec![WebProxyMockConfig {
+                path: "/api/users".to_string(),
+                method: Some("get".to_string()),
+                file: mock_file.path().to_path_buf(),
+                status: 200,
+            }],
+        };
+
+        let server_addr = setup_servers(config).await;
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(format!("http://{server_addr}/api/users"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/json");
+        assert_eq!(response.text().await.unwrap(), r#"[{"name":"dioxus"}]"#);
+
+        // Other methods and paths still go to the backend
+        assert_eq!(
+            client
+                .post(format!("http://{server_addr}/api/users"))
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap(),
+            "backend: /api/users"
+        );
+        assert_eq!(
+            reqwest::get(format!("http://{server_addr}/api/posts"))
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap(),
+            "backend: /api/posts"
+        );
+    }
 }
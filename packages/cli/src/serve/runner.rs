@@ -17,6 +17,7 @@ use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
 use futures_util::future::OptionFuture;
 use futures_util::StreamExt;
 use krates::NodeId;
+use manganis_core::AssetVariant;
 use notify::{
     event::{MetadataKind, ModifyKind},
     Config, EventKind, RecursiveMode, Watcher as NotifyWatcher,
@@ -55,6 +56,7 @@ pub(crate) struct AppServer {
     // Tracked state related to open builds and hot reloading
     pub(crate) applied_hot_reload_message: HotReloadMsg,
     pub(crate) file_map: HashMap<PathBuf, CachedFile>,
+    pub(crate) css_modules: HashMap<PathBuf, CssModuleIdents>,
 
     // Resolved args related to how we go about processing the rebuilds and logging
     pub(crate) use_hotpatch_engine: bool,
@@ -84,6 +86,21 @@ pub(crate) struct AppServer {
     pub(crate) tw_watcher: tokio::task::JoinHandle<Result<()>>,
 }
 
+/// The classes and ids of a css module in the last build. The struct generated by `css_module!()` only changes
+/// when these do, so any other edit can be hot reloaded by swapping the stylesheet.
+#[derive(Debug, PartialEq)]
+pub(crate) struct CssModuleIdents {
+    classes: HashSet<String>,
+    ids: HashSet<String>,
+}
+
+impl CssModuleIdents {
+    fn new(css: &str) -> Self {
+        let (classes, ids) = manganis_core::collect_css_idents(css);
+        Self { classes, ids }
+    }
+}
+
 pub(crate) struct CachedFile {
     contents: String,
     most_recent: Option<String>,
@@ -146,7 +163,17 @@ impl AppServer {
             server: args.platform_args.server.map(|s| s.targets),
             client: args.platform_args.client.map(|c| c.targets),
         };
-        let BuildTargets { client, server } = target_args.into_targets().await?;
+        let BuildTargets {
+            mut client,
+            mut server,
+        } = target_args.into_targets().await?;
+
+        // The preview gallery replaces the app on both sides of a fullstack build so hydration still lines up
+        if args.preview {
+            for build in std::iter::once(&mut client).chain(server.as_mut()) {
+                build.features.push("dioxus/preview".to_string());
+            }
+        }
 
         // All servers will end up behind us (the devserver) but on a different port
         // This is so we can serve a loading screen as well as devtools without anything particularly fancy
@@ -187,6 +214,7 @@ impl AppServer {
         // Create the runner
         let mut runner = Self {
             file_map: Default::default(),
+            css_modules: Default::default(),
             applied_hot_reload_message: Default::default(),
             automatic_rebuilds: true,
             watch_fs,
@@ -374,6 +402,22 @@ impl AppServer {
                 .and_then(|v| v.to_str())
                 .unwrap_or_default();
 
+            // Css modules can be swapped in place unless classes or ids were added or removed
+            let css_module = dunce::canonicalize(path)
+                .ok()
+                .and_then(|path| Some((self.css_modules.get(&path)?, path)));
+            let is_css_module = css_module.is_some();
+            if let Some((idents, path)) = css_module {
+                let Ok(contents) = std::fs::read_to_string(&path) else {
+                    continue;
+                };
+                if *idents != CssModuleIdents::new(&contents) {
+                    tracing::debug!("Css module idents changed, rebuilding: {:?}", path);
+                    needs_full_rebuild = true;
+                    break;
+                }
+            }
+
             // If it's an asset, we want to hotreload it
             // todo(jon): don't hardcode this here
             if let Some(bundled_names) = self.client.hotreload_bundled_assets(path).await {
@@ -477,7 +521,7 @@ impl AppServer {
             // If it's not a rust file, then it might be depended on via include! or similar
             if ext != "rs" {
                 if let Some(artifacts) = self.client.artifacts.as_ref() {
-                    if artifacts.depinfo.files.contains(path) {
+                    if artifacts.depinfo.files.contains(path) && !is_css_module {
                         needs_full_rebuild = true;
                         break;
                     }
@@ -564,6 +608,8 @@ impl AppServer {
             _ => self.client.artifacts = Some(artifacts.clone()),
         }
 
+        self.fill_css_modules(artifacts);
+
         let should_open = self.client.stage == BuildStage::Success
             && (self.server.as_ref().map(|s| s.stage == BuildStage::Success)).unwrap_or(true);
 
@@ -912,6 +958,23 @@ impl AppServer {
         }
     }
 
+    /// Remember the idents of every css module in the build so we can tell if an edit changes the generated struct
+    fn fill_css_modules(&mut self, artifacts: &BuildArtifacts) {
+        for asset in artifacts.assets.unique_assets() {
+            if !matches!(asset.options().variant(), AssetVariant::CssModule(_)) {
+                continue;
+            }
+
+            let source = PathBuf::from(asset.absolute_source_path());
+            let Ok(contents) = std::fs::read_to_string(&source) else {
+                continue;
+            };
+            let source = dunce::canonicalize(&source).unwrap_or(source);
+            self.css_modules
+                .insert(source, CssModuleIdents::new(&contents));
+        }
+    }
+
     /// Commit the changes to the filemap, overwriting the contents of the files
     ///
     /// Removes any cached templates and replaces the contents of the files with the most recent
@@ -1227,3 +1290,41 @@ fn is_wsl() -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CSS: &str = ".header { color: red; }\n.footer { color: blue; }\n#main { margin: 0; }\n";
+
+    #[test]
+    fn css_module_declaration_changes_keep_idents() {
+        let edited = ".header { color: green; padding: 4px; }\n.footer { color: blue; }\n#main { margin: 8px; }\n";
+        assert_eq!(CssModuleIdents::new(CSS), CssModuleIdents::new(CSS));
+        assert_eq!(CssModuleIdents::new(CSS), CssModuleIdents::new(edited));
+    }
+
+    #[test]
+    fn css_module_added_class_changes_idents() {
+        let added = format!("{CSS}.sidebar {{ width: 10px; }}\n");
+        assert_ne!(CssModuleIdents::new(CSS), CssModuleIdents::new(&added));
+    }
+
+    #[test]
+    fn css_module_removed_class_changes_idents() {
+        let removed = ".header { color: red; }\n#main { margin: 0; }\n";
+        assert_ne!(CssModuleIdents::new(CSS), CssModuleIdents::new(removed));
+    }
+
+    #[test]
+    fn css_module_renamed_class_changes_idents() {
+        let renamed = CSS.replace(".footer", ".bottom");
+        assert_ne!(CssModuleIdents::new(CSS), CssModuleIdents::new(&renamed));
+    }
+
+    #[test]
+    fn css_module_renamed_id_changes_idents() {
+        let renamed = CSS.replace("#main", "#content");
+        assert_ne!(CssModuleIdents::new(CSS), CssModuleIdents::new(&renamed));
+    }
+}
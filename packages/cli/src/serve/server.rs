@@ -446,7 +446,7 @@ fn build_devserver_router(
 
     // Setup proxy for the endpoint specified in the config
     for proxy_config in build.build.config.web.proxy.iter() {
-        router = super::proxy::add_proxy(router, proxy_config)?;
+        router = super::proxy::add_proxy(router, proxy_config, &build.build.crate_dir())?;
     }
 
     // For fullstack, liveview, and server, forward all requests to the inner server
@@ -74,6 +74,23 @@ impl TailwindCli {
         })
     }
 
+    /// Make sure the tailwind CLI is installed and use it to compile css assets with tailwind enabled.
+    ///
+    /// Assets use the same tailwind version as the project, or v4 if the project doesn't use tailwind otherwise.
+    pub(crate) async fn install_for_assets(manifest_dir: &Path) -> Result<()> {
+        let tailwind = Self::autodetect(manifest_dir).unwrap_or_else(Self::v4);
+
+        let binary_path = tailwind.get_binary_path()?;
+        if !binary_path.exists() {
+            tracing::info!("Installing tailwindcss@{}", tailwind.version);
+            tailwind.install_github().await?;
+        }
+
+        dioxus_cli_opt::set_tailwind_cli(binary_path);
+
+        Ok(())
+    }
+
     /// Use the correct tailwind version based on the manifest directory.
     ///
     /// - If `tailwind.config.js` or `tailwind.config.ts` exists, use v3.
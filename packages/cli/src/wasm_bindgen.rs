@@ -255,6 +255,11 @@ impl WasmBindgen {
         // Get the final binary location.
         let binary_path = self.get_binary_path()?;
 
+        Self::unpack_github_release(&url, self.downloaded_bin_name(), &binary_path).await
+    }
+
+    /// Download a wasm-bindgen-cli release and unpack one of its binaries to the given path
+    async fn unpack_github_release(url: &str, bin_name: &str, path: &Path) -> anyhow::Result<()> {
         // Download then extract wasm-bindgen-cli.
         let bytes = reqwest::get(url).await?.bytes().await?;
 
@@ -264,19 +269,76 @@ impl WasmBindgen {
             .find(|entry| {
                 entry
                     .as_ref()
-                    .map(|e| {
-                        e.path_bytes()
-                            .ends_with(self.downloaded_bin_name().as_bytes())
-                    })
+                    .map(|e| e.path_bytes().ends_with(bin_name.as_bytes()))
                     .unwrap_or(false)
             })
             .context("Failed to find entry")??
-            .unpack(&binary_path)
+            .unpack(path)
             .context("failed to unpack wasm-bindgen-cli binary")?;
 
         Ok(())
     }
 
+    /// Get the path to `wasm-bindgen-test-runner`, installing it if it's missing.
+    ///
+    /// The test runner ships with wasm-bindgen-cli, so it uses the same version as wasm-bindgen itself.
+    pub(crate) async fn test_runner_path(&self) -> anyhow::Result<PathBuf> {
+        let runner_name = if cfg!(windows) {
+            "wasm-bindgen-test-runner.exe"
+        } else {
+            "wasm-bindgen-test-runner"
+        };
+
+        if CliSettings::prefer_no_downloads() {
+            return which::which(runner_name)
+                .map_err(|_| anyhow!("Missing wasm-bindgen-test-runner@{}", self.version));
+        }
+
+        let mut installed_name = format!("wasm-bindgen-test-runner-{}", self.version);
+        if cfg!(windows) {
+            installed_name = format!("{installed_name}.exe");
+        }
+        let path = self.install_dir()?.join(installed_name);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        if let Some(url) = self.git_install_url() {
+            match Self::unpack_github_release(&url, runner_name, &path).await {
+                Ok(()) => return Ok(path),
+                Err(e) => tracing::error!(
+                    "Failed to install wasm-bindgen-test-runner@{}: {e}",
+                    self.version
+                ),
+            }
+        }
+
+        tracing::info!(
+            "Compiling wasm-bindgen-test-runner@{} from source. This may take a while.",
+            self.version
+        );
+        let tempdir = TempDir::new()?;
+        Command::new("cargo")
+            .args([
+                "install",
+                &self.cargo_bin_name(),
+                "--bin",
+                "wasm-bindgen-test-runner",
+                "--no-track",
+                "--force",
+                "--root",
+            ])
+            .arg(tempdir.path())
+            .output()
+            .await
+            .context("failed to install wasm-bindgen-test-runner from cargo-install")?;
+
+        std::fs::copy(tempdir.path().join("bin").join(runner_name), &path)
+            .context("failed to copy wasm-bindgen-test-runner binary")?;
+
+        Ok(path)
+    }
+
     async fn install_binstall(&self) -> anyhow::Result<()> {
         tracing::debug!(
             "Attempting to install wasm-bindgen-cli@{} from cargo-binstall",
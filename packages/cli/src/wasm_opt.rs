@@ -25,6 +25,18 @@ pub async fn optimize(input_path: &Path, output_path: &Path, cfg: &WasmOptConfig
     Ok(())
 }
 
+/// Get the version wasm-opt reports, like `wasm-opt version 123 (version_123)`
+pub async fn version() -> Result<String> {
+    let path = get_binary_path().await?;
+    let output = tokio::process::Command::new(&path)
+        .arg("--version")
+        .output()
+        .await
+        .context("Failed to get the version of wasm-opt")?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 struct WasmOpt {
     path: PathBuf,
     input_path: PathBuf,
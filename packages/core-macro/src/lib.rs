@@ -3,11 +3,13 @@
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 
 use component::{ComponentBody, ComponentMacroOptions};
+use preview::{PreviewBody, PreviewVariants};
 use proc_macro::TokenStream;
 use quote::ToTokens;
 use syn::parse_macro_input;
 
 mod component;
+mod preview;
 mod props;
 mod utils;
 
@@ -40,3 +42,14 @@ pub fn component(_args: TokenStream, input: TokenStream) -> TokenStream {
         .into_token_stream()
         .into()
 }
+
+#[doc = include_str!("../docs/preview.md")]
+#[proc_macro_attribute]
+pub fn preview(args: TokenStream, input: TokenStream) -> TokenStream {
+    let item_fn = parse_macro_input!(input as syn::ItemFn);
+    let variants = parse_macro_input!(args as PreviewVariants);
+    match PreviewBody::new(item_fn, variants) {
+        Ok(body) => body.into_token_stream().into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,79 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens, TokenStreamExt};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::*;
+
+/// A component registered with the preview gallery
+pub struct PreviewBody {
+    pub item_fn: ItemFn,
+    pub variants: Vec<PreviewVariant>,
+}
+
+/// A named set of props to preview a component with: `Primary = ButtonProps { .. }`
+pub struct PreviewVariant {
+    pub name: Ident,
+    pub props: Expr,
+}
+
+impl Parse for PreviewVariant {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let props = input.parse()?;
+        Ok(Self { name, props })
+    }
+}
+
+/// The arguments of the preview attribute
+pub struct PreviewVariants(pub Vec<PreviewVariant>);
+
+impl Parse for PreviewVariants {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let variants = Punctuated::<PreviewVariant, Token![,]>::parse_terminated(input)?;
+        Ok(Self(variants.into_iter().collect()))
+    }
+}
+
+impl PreviewBody {
+    pub fn new(item_fn: ItemFn, variants: PreviewVariants) -> Result<Self> {
+        // Without variants, there are no props to render the component with
+        if variants.0.is_empty() && !item_fn.sig.inputs.is_empty() {
+            return Err(Error::new_spanned(
+                &item_fn.sig.inputs,
+                "Previews of components with props need at least one named variant, like `#[preview(Default = MyComponentProps { .. })]`",
+            ));
+        }
+
+        Ok(Self {
+            item_fn,
+            variants: variants.0,
+        })
+    }
+}
+
+impl ToTokens for PreviewBody {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let item_fn = &self.item_fn;
+        let ident = &item_fn.sig.ident;
+
+        // Components that only exist to be previewed aren't used anywhere else in the app
+        tokens.append_all(quote! {
+            #[allow(dead_code)]
+            #item_fn
+        });
+
+        if self.variants.is_empty() {
+            tokens.append_all(quote! {
+                dioxus::__register_preview!(#ident, ::core::option::Option::None, ());
+            });
+        }
+
+        for PreviewVariant { name, props } in &self.variants {
+            let name = name.to_string();
+            tokens.append_all(quote! {
+                dioxus::__register_preview!(#ident, ::core::option::Option::Some(#name), #props);
+            });
+        }
+    }
+}
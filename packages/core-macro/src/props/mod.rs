@@ -39,6 +39,7 @@ pub fn impl_my_derive(ast: &syn::DeriveInput) -> Result<TokenStream, Error> {
                     .map(|f| struct_info.required_field_impl(f))
                     .collect::<Result<Vec<_>, _>>()?;
                 let build_method = struct_info.build_method_impl();
+                let preview_props = struct_info.preview_props_impl();
 
                 quote! {
                     #builder_creation
@@ -47,6 +48,7 @@ pub fn impl_my_derive(ast: &syn::DeriveInput) -> Result<TokenStream, Error> {
                     #( #extends )*
                     #( #required_fields )*
                     #build_method
+                    #preview_props
                 }
             }
             syn::Fields::Unnamed(_) => {
@@ -557,7 +559,7 @@ mod struct_info {
         empty_type, empty_type_tuple, expr_to_single_string, make_punctuated_single,
         modify_types_generics_hack, path_to_single_string, strip_raw_ident_prefix, type_tuple,
     };
-    use super::{child_owned_type, looks_like_callback_type, looks_like_signal_type};
+    use super::{child_owned_type, knob_kind, looks_like_callback_type, looks_like_signal_type};
 
     #[derive(Debug)]
     pub struct StructInfo<'a> {
@@ -1352,6 +1354,67 @@ Finally, call `.build()` to create the instance of `{name}`.
             generic_param
         }
 
+        /// Implement `PreviewProps` so the fields with simple types can be edited from the preview gallery. The impl is
+        /// only emitted when the gallery is enabled, so normal builds don't carry the knobs of every props struct.
+        pub fn preview_props_impl(&self) -> TokenStream {
+            if !cfg!(feature = "preview") {
+                return TokenStream::new();
+            }
+
+            let name = self.name;
+            let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+            let knobs: Vec<_> = self
+                .fields
+                .iter()
+                .filter_map(|field| Some((field, knob_kind(field.ty)?)))
+                .collect();
+
+            if knobs.is_empty() {
+                return quote! {
+                    impl #impl_generics dioxus_core::PreviewProps for #name #ty_generics #where_clause {}
+                };
+            }
+
+            let fields: Vec<_> = knobs.iter().map(|(field, _)| field.name).collect();
+            let labels: Vec<_> = fields
+                .iter()
+                .map(|name| strip_raw_ident_prefix(name.to_string()))
+                .collect();
+            let kinds = knobs.iter().map(|(_, kind)| kind);
+
+            quote! {
+                impl #impl_generics dioxus_core::PreviewProps for #name #ty_generics #where_clause {
+                    fn knobs(&self) -> ::std::vec::Vec<dioxus_core::Knob> {
+                        ::std::vec![
+                            #(
+                                dioxus_core::Knob {
+                                    name: #labels,
+                                    kind: dioxus_core::KnobKind::#kinds,
+                                    value: ::std::string::ToString::to_string(&self.#fields),
+                                },
+                            )*
+                        ]
+                    }
+
+                    fn set_knob(&mut self, name: &str, value: &str) -> bool {
+                        match name {
+                            #(
+                                #labels => match value.parse() {
+                                    ::core::result::Result::Ok(value) => {
+                                        self.#fields = value;
+                                        true
+                                    }
+                                    ::core::result::Result::Err(_) => false,
+                                },
+                            )*
+                            _ => false,
+                        }
+                    }
+                }
+            }
+        }
+
         pub fn build_method_impl(&self) -> TokenStream {
             let StructInfo {
                 ref name,
@@ -1765,6 +1828,31 @@ fn looks_like_callback_type(ty: &Type) -> bool {
     }
 }
 
+/// The knob used to edit a field in the preview gallery, if the type of the field is simple enough to parse from a
+/// single input
+fn knob_kind(ty: &Type) -> Option<syn::Ident> {
+    let Type::Path(ty) = ty else {
+        return None;
+    };
+    if ty.qself.is_some() {
+        return None;
+    }
+    let segment = ty.path.segments.last()?;
+    if !segment.arguments.is_empty() {
+        return None;
+    }
+
+    let kind = match segment.ident.to_string().as_str() {
+        "String" | "char" => "Text",
+        "bool" => "Bool",
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" | "f32" | "f64" => "Number",
+        _ => return None,
+    };
+
+    Some(syn::Ident::new(kind, proc_macro2::Span::call_site()))
+}
+
 #[test]
 fn test_looks_like_type() {
     assert!(!looks_like_signal_type(&parse_quote!(
@@ -1795,6 +1883,23 @@ fn test_looks_like_type() {
     assert!(looks_like_callback_type(&parse_quote!(Callback<i32, u32>)));
 }
 
+#[test]
+fn test_knob_kind() {
+    assert_eq!(knob_kind(&parse_quote!(String)).unwrap(), "Text");
+    assert_eq!(
+        knob_kind(&parse_quote!(std::string::String)).unwrap(),
+        "Text"
+    );
+    assert_eq!(knob_kind(&parse_quote!(bool)).unwrap(), "Bool");
+    assert_eq!(knob_kind(&parse_quote!(u32)).unwrap(), "Number");
+    assert_eq!(knob_kind(&parse_quote!(f64)).unwrap(), "Number");
+
+    assert!(knob_kind(&parse_quote!(Option<String>)).is_none());
+    assert!(knob_kind(&parse_quote!(Vec<u8>)).is_none());
+    assert!(knob_kind(&parse_quote!(EventHandler)).is_none());
+    assert!(knob_kind(&parse_quote!(&'static str)).is_none());
+}
+
 #[test]
 fn test_remove_option_wrapper() {
     let type_without_option = remove_option_wrapper(parse_quote!(Option<i32>));
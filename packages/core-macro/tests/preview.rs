@@ -0,0 +1,74 @@
+use dioxus::core::{Knob, KnobKind, PreviewProps};
+use dioxus::prelude::*;
+
+#[derive(Props, Clone, PartialEq)]
+struct ButtonProps {
+    label: String,
+    count: u32,
+    disabled: bool,
+    onclick: Option<EventHandler<MouseEvent>>,
+}
+
+#[test]
+fn props_derive_knobs() {
+    let mut props = ButtonProps {
+        label: "Save".to_string(),
+        count: 1,
+        disabled: false,
+        onclick: None,
+    };
+
+    // The event handler can't be edited from a single input, so it isn't a knob
+    assert_eq!(
+        props.knobs(),
+        vec![
+            Knob {
+                name: "label",
+                kind: KnobKind::Text,
+                value: "Save".to_string(),
+            },
+            Knob {
+                name: "count",
+                kind: KnobKind::Number,
+                value: "1".to_string(),
+            },
+            Knob {
+                name: "disabled",
+                kind: KnobKind::Bool,
+                value: "false".to_string(),
+            },
+        ]
+    );
+
+    assert!(props.set_knob("label", "Cancel"));
+    assert!(props.set_knob("count", "5"));
+    assert!(props.set_knob("disabled", "true"));
+    assert_eq!(props.label, "Cancel");
+    assert_eq!(props.count, 5);
+    assert!(props.disabled);
+
+    // Values that don't parse and unknown fields leave the props alone
+    assert!(!props.set_knob("count", "five"));
+    assert!(!props.set_knob("onclick", "true"));
+    assert_eq!(props.count, 5);
+}
+
+// This test just checks that previews compile with and without props
+#[test]
+#[allow(unused)]
+#[allow(non_snake_case)]
+fn previews_compile() {
+    #[preview]
+    fn Spinner() -> Element {
+        rsx! {}
+    }
+
+    #[preview(
+        Primary = CardProps { title: "Primary".to_string() },
+        Empty = CardProps { title: String::new() },
+    )]
+    #[component]
+    fn Card(title: String) -> Element {
+        rsx! { "{title}" }
+    }
+}
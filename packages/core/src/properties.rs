@@ -73,6 +73,47 @@ impl Properties for () {
     }
 }
 
+/// Props that can be edited from the knobs panel of the preview gallery that `dx serve --preview` launches.
+///
+/// The `Props` derive implements this trait for every props struct. Fields with a `String`, `char`, `bool`, integer
+/// or float type become knobs and all other fields are left out of the panel.
+pub trait PreviewProps {
+    /// Get the fields of the props that can be edited along with their current values.
+    fn knobs(&self) -> Vec<Knob> {
+        Vec::new()
+    }
+
+    /// Parse `value` into the field called `name`. Returns `false` if there is no knob with that name or the value
+    /// could not be parsed.
+    fn set_knob(&mut self, _name: &str, _value: &str) -> bool {
+        false
+    }
+}
+
+impl PreviewProps for () {}
+
+/// A field of a props struct that can be edited from the preview gallery.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Knob {
+    /// The name of the field
+    pub name: &'static str,
+    /// The input used to edit the field
+    pub kind: KnobKind,
+    /// The current value of the field, formatted with [`ToString`]
+    pub value: String,
+}
+
+/// The input used to edit a [`Knob`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnobKind {
+    /// A text input for strings and chars
+    Text,
+    /// A number input for integers and floats
+    Number,
+    /// A checkbox for booleans
+    Bool,
+}
+
 /// Root properties never need to be memoized, so we can use a dummy implementation.
 pub(crate) struct RootProps<P>(pub P);
 
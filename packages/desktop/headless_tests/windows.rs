@@ -0,0 +1,53 @@
+use dioxus::prelude::*;
+use dioxus_desktop::{window, Window};
+use std::time::Duration;
+
+#[path = "./utils.rs"]
+mod utils;
+
+fn main() {
+    #[cfg(not(windows))]
+    utils::check_app_exits(check_child_window_opens_and_closes);
+}
+
+/// Wait until the window with the label is open or closed
+async fn wait_for_window(label: &str, open: bool) {
+    while window().get_window_by_label(label).is_some() != open {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+fn check_child_window_opens_and_closes() -> Element {
+    let mut show_child = use_signal(|| true);
+
+    use_hook(move || {
+        spawn(async move {
+            wait_for_window("child", true).await;
+            println!("child window opened");
+
+            // Unmounting the component should close the window
+            show_child.set(false);
+            wait_for_window("child", false).await;
+            println!("child window closed");
+
+            window().close();
+        });
+    });
+
+    let message = "Hello from the parent window";
+
+    rsx! {
+        if show_child() {
+            Window {
+                title: "Child",
+                label: "child",
+                app: move || rsx! { ChildContents { message } },
+            }
+        }
+    }
+}
+
+#[component]
+fn ChildContents(message: String) -> Element {
+    rsx! { "{message}" }
+}
@@ -4,9 +4,12 @@ use crate::{
     event_handlers::WindowEventHandlers,
     file_upload::{DesktopFileUploadForm, FileDialogRequest, NativeFileEngine},
     ipc::{IpcMessage, UserWindowEvent},
+    power::PowerMonitor,
     query::QueryResult,
     shortcut::ShortcutRegistry,
+    single_instance::SecondInstanceEvent,
     webview::{PendingWebview, WebviewInstance},
+    window_registry::{SharedSignals, WindowRegistry},
 };
 use dioxus_core::{ElementId, ScopeId, VirtualDom};
 use dioxus_history::History;
@@ -40,6 +43,9 @@ pub(crate) struct App {
     pub(crate) webviews: HashMap<WindowId, WebviewInstance>,
     pub(crate) float_all: bool,
     pub(crate) show_devtools: bool,
+    pub(crate) deep_link_scheme: Option<String>,
+    pub(crate) pending_deep_link: Option<String>,
+    pub(crate) instance_lock: Option<crate::single_instance::InstanceLock>,
 
     /// This single blob of state is shared between all the windows so they have access to the runtime state
     ///
@@ -55,6 +61,18 @@ pub(crate) struct SharedContext {
     pub(crate) proxy: EventLoopProxy<UserWindowEvent>,
     pub(crate) target: EventLoopWindowTarget<UserWindowEvent>,
     pub(crate) websocket: EditWebsocket,
+    pub(crate) windows: WindowRegistry,
+    pub(crate) shared_signals: SharedSignals,
+    pub(crate) power_monitor: PowerMonitor,
+
+    #[cfg(all(
+        feature = "clipboard",
+        any(target_os = "windows", target_os = "linux", target_os = "macos")
+    ))]
+    pub(crate) clipboard: crate::Clipboard,
+
+    #[cfg(all(feature = "notifications", target_os = "linux"))]
+    pub(crate) notification_handlers: RefCell<HashMap<u64, crate::WryEventHandler>>,
 }
 
 impl App {
@@ -64,6 +82,19 @@ impl App {
             .take()
             .unwrap_or_else(|| EventLoopBuilder::<UserWindowEvent>::with_user_event().build());
 
+        // If another instance of the app is already running, this forwards our arguments to it and exits
+        let instance_lock = cfg
+            .single_instance
+            .then(|| crate::single_instance::acquire(event_loop.create_proxy()))
+            .flatten();
+
+        // The app may have been launched with a deep link. We'll route it once the first window is created
+        let deep_link_scheme = cfg.deep_link_scheme.clone();
+        let pending_deep_link = deep_link_scheme.as_deref().and_then(|scheme| {
+            crate::deep_link::register_scheme(scheme);
+            crate::deep_link::find_url(std::env::args().skip(1), scheme)
+        });
+
         let app = Self {
             exit_on_last_window_close: cfg.exit_on_last_window_close,
             is_visible_before_start: true,
@@ -72,6 +103,9 @@ impl App {
             unmounted_dom: Cell::new(Some(virtual_dom)),
             float_all: false,
             show_devtools: false,
+            deep_link_scheme,
+            pending_deep_link,
+            instance_lock,
             cfg: Cell::new(Some(cfg)),
             shared: Rc::new(SharedContext {
                 event_handlers: WindowEventHandlers::default(),
@@ -80,6 +114,16 @@ impl App {
                 proxy: event_loop.create_proxy(),
                 target: event_loop.clone(),
                 websocket: EditWebsocket::start(),
+                windows: WindowRegistry::default(),
+                shared_signals: SharedSignals::default(),
+                power_monitor: PowerMonitor::default(),
+                #[cfg(all(
+                    feature = "clipboard",
+                    any(target_os = "windows", target_os = "linux", target_os = "macos")
+                ))]
+                clipboard: Default::default(),
+                #[cfg(all(feature = "notifications", target_os = "linux"))]
+                notification_handlers: Default::default(),
             }),
         };
 
@@ -260,6 +304,11 @@ impl App {
         // And then attempt to resume from state
         self.resume_from_state(&webview, explicit_window_size, explicit_window_position);
 
+        // If we were launched with a deep link, start the router at the deep link instead of the index route
+        if let Some(url) = &self.pending_deep_link {
+            webview.history.replace(crate::deep_link::url_to_route(url));
+        }
+
         let id = webview.desktop_context.window.id();
         self.webviews.insert(id, webview);
     }
@@ -293,6 +342,75 @@ impl App {
             .set_visible(self.is_visible_before_start);
 
         _ = self.shared.proxy.send_event(UserWindowEvent::Poll(id));
+
+        // Now that the app is mounted, let any deep link handlers know about the link we were launched with. The router
+        // already started at the deep link when the window was created, so we only notify the handlers here
+        if let Some(url) = self.pending_deep_link.take() {
+            _ = self.shared.proxy.send_event(UserWindowEvent::DeepLink(url));
+        }
+    }
+
+    /// Another instance of the app was launched while we are running
+    ///
+    /// Bring the app to the front and forward any deep link it was launched with
+    pub fn handle_second_instance(&mut self, event: SecondInstanceEvent) {
+        if let Some(window) = self.shared.windows.windows().first() {
+            window.window.set_visible(true);
+            window.window.set_minimized(false);
+            window.window.set_focus();
+        }
+
+        if let Some(scheme) = &self.deep_link_scheme {
+            if let Some(url) = crate::deep_link::find_url(event.args.into_iter().skip(1), scheme) {
+                self.route_deep_link(url);
+            }
+        }
+    }
+
+    /// The OS asked us to open a url. On macOS, this is how deep links are delivered
+    pub fn handle_opened_url(&mut self, url: String) {
+        let Some(scheme) = &self.deep_link_scheme else {
+            return;
+        };
+
+        if !crate::deep_link::matches_scheme(&url, scheme) {
+            return;
+        }
+
+        // If we haven't created the first window yet, route the link once it's created
+        if self.webviews.is_empty() {
+            self.pending_deep_link = Some(url);
+            return;
+        }
+
+        self.route_deep_link(url);
+    }
+
+    /// A notification was acted on or closed, so we don't need to listen for its actions anymore
+    #[cfg(all(feature = "notifications", target_os = "linux"))]
+    pub fn handle_notification_action(&mut self, notification: u64) {
+        let handler = self
+            .shared
+            .notification_handlers
+            .borrow_mut()
+            .remove(&notification);
+        if let Some(handler) = handler {
+            self.shared.event_handlers.remove(handler);
+        }
+    }
+
+    /// Route a deep link that arrived while the app is running into the router of the first window, and then let the
+    /// deep link handlers know about it
+    fn route_deep_link(&mut self, url: String) {
+        if let Some(window) = self.shared.windows.windows().into_iter().next() {
+            if let Some(webview) = self.webviews.get(&window.window.id()) {
+                webview
+                    .history
+                    .navigate_external(crate::deep_link::url_to_route(&url));
+            }
+        }
+
+        _ = self.shared.proxy.send_event(UserWindowEvent::DeepLink(url));
     }
 
     /// Todo: maybe we should poll the virtualdom asking if it has any final actions to apply before closing the webview
@@ -508,9 +626,12 @@ impl App {
     /// This will attempt to save the window position, size, and monitor into the environment before
     /// closing. This way, when the app is restarted, it can attempt to restore the window to the same
     /// position and size it was in before, making a better DX.
-    pub(crate) fn handle_loop_destroyed(&self) {
+    pub(crate) fn handle_loop_destroyed(&mut self) {
         #[cfg(debug_assertions)]
         self.persist_window_state();
+
+        // The event loop exits the process without dropping the app, so release the single instance lock here
+        self.instance_lock.take();
     }
 
     #[cfg(debug_assertions)]
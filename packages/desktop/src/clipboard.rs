@@ -0,0 +1,95 @@
+use std::{cell::RefCell, rc::Rc};
+
+/// An error that occurred while reading from or writing to the clipboard
+pub use arboard::Error as ClipboardError;
+
+/// An image read from or written to the clipboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    /// The width of the image in pixels
+    pub width: usize,
+
+    /// The height of the image in pixels
+    pub height: usize,
+
+    /// The pixels of the image in RGBA order, row by row
+    pub bytes: Vec<u8>,
+}
+
+/// A handle to the system clipboard.
+///
+/// Get a handle with [`crate::use_clipboard`] or [`crate::DesktopService::clipboard`]. All handles share the same
+/// connection to the clipboard, which is opened the first time the clipboard is used.
+#[derive(Clone, Default)]
+pub struct Clipboard {
+    inner: Rc<RefCell<Option<arboard::Clipboard>>>,
+}
+
+impl Clipboard {
+    fn with<O>(
+        &self,
+        f: impl FnOnce(&mut arboard::Clipboard) -> Result<O, ClipboardError>,
+    ) -> Result<O, ClipboardError> {
+        let mut inner = self.inner.borrow_mut();
+        if inner.is_none() {
+            *inner = Some(arboard::Clipboard::new()?);
+        }
+        f(inner.as_mut().unwrap())
+    }
+
+    /// Read the text on the clipboard
+    pub fn get_text(&self) -> Result<String, ClipboardError> {
+        self.with(|clipboard| clipboard.get_text())
+    }
+
+    /// Replace the contents of the clipboard with text
+    pub fn set_text(&self, text: impl Into<String>) -> Result<(), ClipboardError> {
+        let text = text.into();
+        self.with(|clipboard| clipboard.set_text(text))
+    }
+
+    /// Read the html on the clipboard
+    pub fn get_html(&self) -> Result<String, ClipboardError> {
+        self.with(|clipboard| clipboard.get().html())
+    }
+
+    /// Replace the contents of the clipboard with html.
+    ///
+    /// Apps that can't paste html will paste the alt text instead, if it is set.
+    pub fn set_html(
+        &self,
+        html: impl Into<String>,
+        alt_text: Option<String>,
+    ) -> Result<(), ClipboardError> {
+        let html = html.into();
+        self.with(|clipboard| clipboard.set_html(html, alt_text))
+    }
+
+    /// Read the image on the clipboard
+    pub fn get_image(&self) -> Result<ClipboardImage, ClipboardError> {
+        self.with(|clipboard| {
+            let image = clipboard.get_image()?;
+            Ok(ClipboardImage {
+                width: image.width,
+                height: image.height,
+                bytes: image.bytes.into_owned(),
+            })
+        })
+    }
+
+    /// Replace the contents of the clipboard with an image
+    pub fn set_image(&self, image: ClipboardImage) -> Result<(), ClipboardError> {
+        self.with(|clipboard| {
+            clipboard.set_image(arboard::ImageData {
+                width: image.width,
+                height: image.height,
+                bytes: image.bytes.into(),
+            })
+        })
+    }
+
+    /// Clear the contents of the clipboard
+    pub fn clear(&self) -> Result<(), ClipboardError> {
+        self.with(|clipboard| clipboard.clear())
+    }
+}
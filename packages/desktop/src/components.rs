@@ -0,0 +1,165 @@
+use crate::{window, Config, DesktopContext, WeakDesktopContext};
+use dioxus_core::{spawn_forever, use_hook_with_cleanup, Element, VNode, VirtualDom};
+use dioxus_core_macro::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// The props for the [`Window`] component
+#[derive(Clone, Props, PartialEq)]
+pub struct WindowProps {
+    /// The title of the window. Unlike the rest of the props, the title can be updated after the window is created.
+    #[props(into)]
+    title: Option<String>,
+
+    /// The label of the window. See [`Config::with_window_label`] for more details.
+    #[props(into)]
+    label: Option<String>,
+
+    /// The contents of the window.
+    ///
+    /// Each window runs its own [`VirtualDom`], so the contents can't be passed in as children: their event handlers
+    /// and components would belong to the scopes of the parent window. Instead, the closure is called to render the
+    /// root of the new window. It can capture props from the parent or return an inline rsx body. Use
+    /// [`crate::use_shared_signal`] to share state that changes between the windows.
+    #[props(into)]
+    app: WindowApp,
+}
+
+/// The root of a window opened with the [`Window`] component. This is usually created from a component function or a
+/// closure that returns an [`Element`].
+#[derive(Clone)]
+pub struct WindowApp(Rc<dyn Fn() -> Element>);
+
+impl<F: Fn() -> Element + 'static> From<F> for WindowApp {
+    fn from(app: F) -> Self {
+        Self(Rc::new(app))
+    }
+}
+
+impl PartialEq for WindowApp {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Open a new window for as long as this component is mounted.
+///
+/// The window is created when the component is first rendered and closed when the component is dropped.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use dioxus::desktop::Window;
+/// fn App() -> Element {
+///     let mut show_settings = use_signal(|| false);
+///
+///     rsx! {
+///         button { onclick: move |_| show_settings.toggle(), "Settings" }
+///         if show_settings() {
+///             Window { title: "Settings", app: Settings }
+///         }
+///         // Use a closure to pass props to the root of the window or to render it inline
+///         Window { title: "Profile", app: || rsx! { Profile { name: "Ferris" } } }
+///     }
+/// }
+///
+/// fn Settings() -> Element {
+///     rsx! { "This is the settings window!" }
+/// }
+///
+/// #[component]
+/// fn Profile(name: String) -> Element {
+///     rsx! { "Hello, {name}!" }
+/// }
+/// ```
+#[component]
+pub fn Window(props: WindowProps) -> Element {
+    let WindowProps { title, label, app } = props;
+
+    let child = use_hook_with_cleanup(
+        || {
+            let child = Rc::new(ChildWindow {
+                context: RefCell::new(None),
+                title: RefCell::new(title.clone()),
+                unmounted: Cell::new(false),
+            });
+
+            let mut cfg = Config::new();
+            if let Some(title) = &title {
+                cfg.window = cfg.window.with_title(title);
+            }
+            if let Some(label) = label {
+                cfg = cfg.with_window_label(label);
+            }
+
+            let pending =
+                window().new_window(VirtualDom::new_with_props(WindowRoot, app.clone()), cfg);
+
+            // The window is created on the next tick of the event loop, which may be after this component is dropped.
+            // Spawn the task on the root scope so we can still close the window if that happens
+            spawn_forever({
+                let child = child.clone();
+                async move {
+                    let Ok(context) = pending.try_resolve().await else {
+                        return;
+                    };
+
+                    if child.unmounted.get() {
+                        context.close();
+                        return;
+                    }
+
+                    if let Some(title) = child.title.borrow().as_ref() {
+                        context.set_title(title);
+                    }
+                    *child.context.borrow_mut() = Some(Rc::downgrade(&context));
+                }
+            });
+
+            child
+        },
+        |child| {
+            child.unmounted.set(true);
+            if let Some(context) = child.context() {
+                context.close();
+            }
+        },
+    );
+
+    // If the title changes, update the title of the window
+    if let Some(title) = title {
+        let mut last_title = child.title.borrow_mut();
+        if last_title.as_ref() != Some(&title) {
+            if let Some(context) = child.context() {
+                context.set_title(&title);
+            }
+            *last_title = Some(title);
+        }
+    }
+
+    VNode::empty()
+}
+
+/// The root component of a child window that renders the contents passed to [`Window`]
+#[allow(non_snake_case)]
+fn WindowRoot(app: WindowApp) -> Element {
+    (app.0)()
+}
+
+struct ChildWindow {
+    context: RefCell<Option<WeakDesktopContext>>,
+    title: RefCell<Option<String>>,
+    unmounted: Cell<bool>,
+}
+
+impl ChildWindow {
+    fn context(&self) -> Option<DesktopContext> {
+        self.context
+            .borrow()
+            .as_ref()
+            .and_then(|context| context.upgrade())
+    }
+}
@@ -64,6 +64,9 @@ pub struct Config {
     pub(crate) window_close_behavior: WindowCloseBehaviour,
     pub(crate) custom_event_handler: Option<CustomEventHandler>,
     pub(crate) disable_file_drop_handler: bool,
+    pub(crate) window_label: Option<String>,
+    pub(crate) single_instance: bool,
+    pub(crate) deep_link_scheme: Option<String>,
 }
 
 impl LaunchConfig for Config {}
@@ -111,6 +114,9 @@ impl Config {
             window_close_behavior: WindowCloseBehaviour::WindowCloses,
             custom_event_handler: None,
             disable_file_drop_handler: false,
+            window_label: None,
+            single_instance: false,
+            deep_link_scheme: None,
         }
     }
 
@@ -164,6 +170,39 @@ impl Config {
         self
     }
 
+    /// Set a label for the window.
+    ///
+    /// Labels let other windows find this window with [`DesktopService::get_window_by_label`](crate::DesktopService::get_window_by_label)
+    /// without holding on to its [`WindowId`](tao::window::WindowId).
+    pub fn with_window_label(mut self, label: impl Into<String>) -> Self {
+        self.window_label = Some(label.into());
+        self
+    }
+
+    /// Only allow a single instance of the app to run at a time.
+    ///
+    /// When the app is launched while another instance is already running, the new process forwards its arguments
+    /// to the running instance and exits immediately. The running instance focuses its first window and receives the
+    /// arguments through [`crate::use_second_instance`]. If the arguments contain a deep link for the scheme set with
+    /// [`Config::with_deep_link_scheme`], the deep link is handled as if the running instance was opened with it.
+    pub fn with_single_instance(mut self, single_instance: bool) -> Self {
+        self.single_instance = single_instance;
+        self
+    }
+
+    /// Handle deep links with a custom URL scheme, like `myapp://settings/profile`.
+    ///
+    /// On Windows and Linux, the app registers itself as the handler for the scheme with the current user when it starts.
+    /// On macOS, the scheme must be declared in the bundle's `Info.plist`.
+    ///
+    /// Deep links are routed into the router of the first window as `/settings/profile`, and can be received with
+    /// [`crate::use_deep_link`]. Combine this with [`Config::with_single_instance`] to forward deep links to the running
+    /// app instead of launching a new copy.
+    pub fn with_deep_link_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.deep_link_scheme = Some(scheme.into());
+        self
+    }
+
     /// Set the window as child
     pub fn with_as_child_window(mut self) -> Self {
         self.as_child_window = true;
@@ -0,0 +1,174 @@
+//! Helpers for handling custom URL schemes like `myapp://settings/profile`.
+//!
+//! The OS launches the app with the URL as an argument on Windows and Linux. On macOS, the URL is delivered through
+//! a [`tao::event::Event::Opened`] event instead, and the scheme must be declared in the app's `Info.plist` at bundle time.
+
+/// Find the first argument that is a URL for the given scheme
+pub(crate) fn find_url(args: impl IntoIterator<Item = String>, scheme: &str) -> Option<String> {
+    args.into_iter().find(|arg| matches_scheme(arg, scheme))
+}
+
+/// Check if a URL uses the given scheme. Schemes are case-insensitive.
+pub(crate) fn matches_scheme(url: &str, scheme: &str) -> bool {
+    url.split_once(':')
+        .is_some_and(|(url_scheme, _)| url_scheme.eq_ignore_ascii_case(scheme))
+}
+
+/// Convert a deep link into a route for the router.
+///
+/// The host is treated as the first segment of the path, so `myapp://settings/profile?tab=1` becomes `/settings/profile?tab=1`.
+pub(crate) fn url_to_route(url: &str) -> String {
+    let rest = url.split_once(':').map(|(_, rest)| rest).unwrap_or(url);
+    let rest = rest.trim_start_matches('/');
+    format!("/{rest}")
+}
+
+/// Register the app as the handler for the scheme with the current user, unless it is already registered.
+///
+/// This is best effort - if the registration fails, we log a warning and continue. Bundled apps should prefer to
+/// register the scheme in their installer.
+pub(crate) fn register_scheme(scheme: &str) {
+    let Ok(exe) = std::env::current_exe() else {
+        tracing::warn!("Failed to find the current executable to register the {scheme}:// scheme");
+        return;
+    };
+
+    #[cfg(target_os = "windows")]
+    {
+        let key = format!(r"HKCU\Software\Classes\{scheme}");
+        let command = format!("\"{}\" \"%1\"", exe.display());
+
+        // Skip the registration if the scheme already opens this executable
+        let registered = std::process::Command::new("reg")
+            .args(["query", &format!(r"{key}\shell\open\command"), "/ve"])
+            .output()
+            .is_ok_and(|output| {
+                output.status.success()
+                    && String::from_utf8_lossy(&output.stdout).contains(&command)
+            });
+        if registered {
+            return;
+        }
+
+        let entries: [(String, Option<&str>, String); 3] = [
+            (key.clone(), None, format!("URL:{scheme} protocol")),
+            (key.clone(), Some("URL Protocol"), String::new()),
+            (format!(r"{key}\shell\open\command"), None, command),
+        ];
+
+        for (key, name, value) in entries {
+            let mut reg = std::process::Command::new("reg");
+            reg.args(["add", &key, "/f", "/d", &value]);
+            match name {
+                Some(name) => reg.args(["/v", name]),
+                None => reg.arg("/ve"),
+            };
+            if let Err(err) = reg.output() {
+                tracing::warn!("Failed to register the {scheme}:// scheme: {err}");
+                return;
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let Some(data_dir) = std::env::var_os("XDG_DATA_HOME")
+            .map(std::path::PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .map(|home| std::path::PathBuf::from(home).join(".local/share"))
+            })
+        else {
+            return;
+        };
+
+        let applications = data_dir.join("applications");
+        let desktop_file_name = format!("{scheme}-handler.desktop");
+        let desktop_file = format!(
+            "[Desktop Entry]\nType=Application\nName={scheme}\nExec=\"{}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/{scheme};\n",
+            exe.display()
+        );
+
+        // Skip the registration if the handler is already installed and is the default for the scheme
+        let installed = std::fs::read_to_string(applications.join(&desktop_file_name))
+            .is_ok_and(|existing| existing == desktop_file);
+        let is_default = std::process::Command::new("xdg-mime")
+            .args(["query", "default", &format!("x-scheme-handler/{scheme}")])
+            .output()
+            .is_ok_and(|output| {
+                String::from_utf8_lossy(&output.stdout).trim() == desktop_file_name
+            });
+        if installed && is_default {
+            return;
+        }
+
+        let written = std::fs::create_dir_all(&applications)
+            .and_then(|_| std::fs::write(applications.join(&desktop_file_name), desktop_file));
+        if let Err(err) = written {
+            tracing::warn!("Failed to register the {scheme}:// scheme: {err}");
+            return;
+        }
+
+        if let Err(err) = std::process::Command::new("xdg-mime")
+            .args([
+                "default",
+                &desktop_file_name,
+                &format!("x-scheme-handler/{scheme}"),
+            ])
+            .output()
+        {
+            tracing::warn!("Failed to register the {scheme}:// scheme: {err}");
+        }
+    }
+
+    // macOS and mobile read the scheme from the app bundle
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        _ = exe;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_first_url_with_the_scheme() {
+        let args = [
+            "--verbose",
+            "other://home",
+            "myapp://settings",
+            "myapp://profile",
+        ]
+        .map(String::from);
+        assert_eq!(
+            find_url(args, "myapp"),
+            Some("myapp://settings".to_string())
+        );
+
+        let args = ["--verbose", "myapp"].map(String::from);
+        assert_eq!(find_url(args, "myapp"), None);
+    }
+
+    #[test]
+    fn schemes_are_case_insensitive() {
+        assert!(matches_scheme("myapp://settings", "myapp"));
+        assert!(matches_scheme("MyApp://settings", "myapp"));
+        assert!(matches_scheme("myapp:settings", "myapp"));
+        assert!(!matches_scheme("myapp2://settings", "myapp"));
+        assert!(!matches_scheme("https://myapp.com", "myapp"));
+        assert!(!matches_scheme("myapp", "myapp"));
+    }
+
+    #[test]
+    fn urls_become_routes() {
+        assert_eq!(
+            url_to_route("myapp://settings/profile"),
+            "/settings/profile"
+        );
+        assert_eq!(url_to_route("myapp://settings?tab=1"), "/settings?tab=1");
+        assert_eq!(url_to_route("myapp:///settings"), "/settings");
+        assert_eq!(url_to_route("myapp:settings"), "/settings");
+        assert_eq!(url_to_route("myapp://"), "/");
+    }
+}
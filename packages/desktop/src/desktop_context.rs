@@ -10,10 +10,12 @@ use crate::{
 };
 use dioxus_core::{Callback, VirtualDom};
 use std::{
+    any::Any,
     cell::Cell,
     future::{Future, IntoFuture},
     pin::Pin,
     rc::{Rc, Weak},
+    sync::Arc,
 };
 use tao::{
     event::Event,
@@ -68,6 +70,7 @@ pub struct DesktopService {
     pub(crate) asset_handlers: AssetHandlerRegistry,
     pub(crate) file_hover: NativeFileHover,
     pub(crate) close_behaviour: Rc<Cell<WindowCloseBehaviour>>,
+    pub(crate) label: Option<String>,
 
     #[cfg(target_os = "ios")]
     pub(crate) views: Rc<std::cell::RefCell<Vec<*mut objc::runtime::Object>>>,
@@ -90,6 +93,7 @@ impl DesktopService {
         asset_handlers: AssetHandlerRegistry,
         file_hover: NativeFileHover,
         close_behaviour: WindowCloseBehaviour,
+        label: Option<String>,
     ) -> Self {
         Self {
             window,
@@ -98,6 +102,7 @@ impl DesktopService {
             asset_handlers,
             file_hover,
             close_behaviour: Rc::new(Cell::new(close_behaviour)),
+            label,
             query: Default::default(),
             #[cfg(target_os = "ios")]
             views: Default::default(),
@@ -150,6 +155,68 @@ impl DesktopService {
         context
     }
 
+    /// Get the label this window was created with through [`Config::with_window_label`]
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Get a handle to every window that is currently open, in the order they were created
+    pub fn windows(&self) -> Vec<DesktopContext> {
+        self.shared.windows.windows()
+    }
+
+    /// Get a handle to the window with the given ID
+    ///
+    /// Returns `None` if the window has been closed or has not been created yet.
+    pub fn get_window(&self, id: WindowId) -> Option<DesktopContext> {
+        self.shared.windows.get(id)
+    }
+
+    /// Get a handle to the first open window with the given label
+    ///
+    /// Returns `None` if no open window was created with that label.
+    pub fn get_window_by_label(&self, label: &str) -> Option<DesktopContext> {
+        self.shared.windows.get_by_label(label)
+    }
+
+    /// Get a handle to the system clipboard. The handle is shared between every window in the app.
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(
+            feature = "clipboard",
+            any(target_os = "windows", target_os = "linux", target_os = "macos")
+        )))
+    )]
+    #[cfg(all(
+        feature = "clipboard",
+        any(target_os = "windows", target_os = "linux", target_os = "macos")
+    ))]
+    pub fn clipboard(&self) -> crate::Clipboard {
+        self.shared.clipboard.clone()
+    }
+
+    /// Bring a particular window to the front and focus it, given its ID
+    pub fn focus_window(&self, id: WindowId) {
+        if let Some(window) = self.get_window(id) {
+            window.window.set_visible(true);
+            window.window.set_focus();
+        }
+    }
+
+    /// Send a message to a particular window, given its ID
+    ///
+    /// The message is delivered asynchronously on the next tick of the event loop. The target window
+    /// can receive it with [`crate::use_window_message`].
+    pub fn send_message(&self, id: WindowId, message: impl Any + Send + Sync) {
+        let _ = self
+            .shared
+            .proxy
+            .send_event(UserWindowEvent::WindowMessage {
+                target: id,
+                message: Arc::new(message),
+            });
+    }
+
     /// trigger the drag-window event
     ///
     /// Moves the window with the left mouse button until the button is released.
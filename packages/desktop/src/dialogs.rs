@@ -0,0 +1,183 @@
+//! Native dialogs for opening and saving files, picking folders and showing messages.
+//!
+//! These dialogs can be opened from anywhere in your app, not just from an `<input type="file">`. They are asynchronous
+//! so they won't block the event loop while the dialog is open.
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! use dioxus::desktop::dialogs::FileDialog;
+//!
+//! fn app() -> Element {
+//!     rsx! {
+//!         button {
+//!             onclick: move |_| async move {
+//!                 if let Some(path) = FileDialog::new().add_filter("Logs", &["log", "txt"]).pick_file().await {
+//!                     println!("Opening {}", path.display());
+//!                 }
+//!             },
+//!             "Open log"
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::file_upload::NativeFileEngine;
+use dioxus_html::FileEngine;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+pub use rfd::{MessageButtons, MessageDialogResult, MessageLevel};
+
+/// A dialog for opening files, saving files or picking folders.
+#[derive(Default)]
+pub struct FileDialog {
+    dialog: rfd::AsyncFileDialog,
+}
+
+impl FileDialog {
+    /// Create a new file dialog with no filters that opens in the default directory
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a filter to the dialog. Only files with one of the extensions will be shown.
+    ///
+    /// Extensions should not include the leading `.`
+    pub fn add_filter(mut self, name: impl Into<String>, extensions: &[impl ToString]) -> Self {
+        self.dialog = self.dialog.add_filter(name, extensions);
+        self
+    }
+
+    /// Set the directory the dialog opens in
+    pub fn with_directory(mut self, path: impl AsRef<Path>) -> Self {
+        self.dialog = self.dialog.set_directory(path);
+        self
+    }
+
+    /// Set the file name that is selected by default. This is mostly useful for save dialogs.
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.dialog = self.dialog.set_file_name(file_name);
+        self
+    }
+
+    /// Set the title of the dialog window
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.dialog = self.dialog.set_title(title);
+        self
+    }
+
+    /// Show an open dialog for a single file. Returns `None` if the dialog was cancelled.
+    pub async fn pick_file(self) -> Option<PathBuf> {
+        let file = self.dialog.pick_file().await?;
+        Some(file.path().to_path_buf())
+    }
+
+    /// Show an open dialog for multiple files. Returns an empty list if the dialog was cancelled.
+    pub async fn pick_files(self) -> Vec<PathBuf> {
+        self.dialog
+            .pick_files()
+            .await
+            .into_iter()
+            .flatten()
+            .map(|file| file.path().to_path_buf())
+            .collect()
+    }
+
+    /// Show an open dialog for multiple files and return a [`FileEngine`] for the picked files.
+    ///
+    /// This is the same file engine you get from the `onchange` event of an `<input type="file">`, so you can read the
+    /// files in chunks with [`FileEngine::read_file_chunked`](dyn FileEngine::read_file_chunked).
+    pub async fn pick_files_with_engine(self) -> Arc<dyn FileEngine> {
+        Arc::new(NativeFileEngine::new(self.pick_files().await))
+    }
+
+    /// Show a dialog for picking a single folder. Returns `None` if the dialog was cancelled.
+    pub async fn pick_folder(self) -> Option<PathBuf> {
+        let folder = self.dialog.pick_folder().await?;
+        Some(folder.path().to_path_buf())
+    }
+
+    /// Show a dialog for picking multiple folders. Returns an empty list if the dialog was cancelled.
+    pub async fn pick_folders(self) -> Vec<PathBuf> {
+        self.dialog
+            .pick_folders()
+            .await
+            .into_iter()
+            .flatten()
+            .map(|folder| folder.path().to_path_buf())
+            .collect()
+    }
+
+    /// Show a save dialog. Returns the path the user chose, or `None` if the dialog was cancelled.
+    ///
+    /// The file is not created, you need to write to the path yourself.
+    pub async fn save_file(self) -> Option<PathBuf> {
+        let file = self.dialog.save_file().await?;
+        Some(file.path().to_path_buf())
+    }
+}
+
+/// A dialog that shows a message and a set of buttons to the user.
+#[derive(Default)]
+pub struct MessageDialog {
+    dialog: rfd::AsyncMessageDialog,
+}
+
+impl MessageDialog {
+    /// Create a new message dialog with an info level and an ok button
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the level of the message. This changes the icon shown in the dialog.
+    pub fn with_level(mut self, level: MessageLevel) -> Self {
+        self.dialog = self.dialog.set_level(level);
+        self
+    }
+
+    /// Set the title of the dialog window
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.dialog = self.dialog.set_title(title);
+        self
+    }
+
+    /// Set the message shown in the dialog
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.dialog = self.dialog.set_description(description);
+        self
+    }
+
+    /// Set the buttons shown in the dialog
+    pub fn with_buttons(mut self, buttons: MessageButtons) -> Self {
+        self.dialog = self.dialog.set_buttons(buttons);
+        self
+    }
+
+    /// Show the dialog and wait for the user to press one of the buttons
+    pub async fn show(self) -> MessageDialogResult {
+        self.dialog.show().await
+    }
+}
+
+/// Show a message dialog with a single ok button and wait for it to be closed
+pub async fn message(title: impl Into<String>, description: impl Into<String>) {
+    MessageDialog::new()
+        .with_title(title)
+        .with_description(description)
+        .show()
+        .await;
+}
+
+/// Show a yes/no dialog and return `true` if the user pressed yes
+pub async fn confirm(title: impl Into<String>, description: impl Into<String>) -> bool {
+    let result = MessageDialog::new()
+        .with_title(title)
+        .with_description(description)
+        .with_buttons(MessageButtons::YesNo)
+        .show()
+        .await;
+
+    matches!(result, MessageDialogResult::Yes)
+}
@@ -3,7 +3,10 @@
 use std::any::Any;
 
 #[cfg(feature = "tokio_runtime")]
-use tokio::{fs::File, io::AsyncReadExt};
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
 
 use dioxus_html::{
     geometry::{ClientPoint, Coordinates, ElementPoint, PagePoint, ScreenPoint},
@@ -250,11 +253,26 @@ impl PointerInteraction for DesktopFileDragEvent {
 
 pub struct NativeFileEngine {
     files: Vec<PathBuf>,
+    /// The file that is being read in chunks. Reading a file chunk by chunk usually continues where the last chunk
+    /// ended, so we keep the handle open instead of reopening and seeking the file for every chunk.
+    #[cfg(feature = "tokio_runtime")]
+    chunked: tokio::sync::Mutex<Option<ChunkedFile>>,
+}
+
+#[cfg(feature = "tokio_runtime")]
+struct ChunkedFile {
+    path: String,
+    file: File,
+    offset: u64,
 }
 
 impl NativeFileEngine {
     pub fn new(files: Vec<PathBuf>) -> Self {
-        Self { files }
+        Self {
+            files,
+            #[cfg(feature = "tokio_runtime")]
+            chunked: Default::default(),
+        }
     }
 }
 
@@ -295,6 +313,53 @@ impl FileEngine for NativeFileEngine {
         }
     }
 
+    async fn read_file_chunk(&self, file: &str, offset: u64, len: usize) -> Option<Vec<u8>> {
+        #[cfg(feature = "tokio_runtime")]
+        {
+            let mut chunked = self.chunked.lock().await;
+            let open = match chunked.take() {
+                Some(open) if open.path == file => Some(open),
+                _ => None,
+            };
+            let mut open = match open {
+                Some(open) => open,
+                None => ChunkedFile {
+                    path: file.to_string(),
+                    file: File::open(file).await.ok()?,
+                    offset: 0,
+                },
+            };
+
+            // Only seek if the chunk doesn't continue from the end of the last one
+            if open.offset != offset {
+                open.file
+                    .seek(std::io::SeekFrom::Start(offset))
+                    .await
+                    .ok()?;
+                open.offset = offset;
+            }
+
+            let mut contents = Vec::with_capacity(len);
+            (&mut open.file)
+                .take(len as u64)
+                .read_to_end(&mut contents)
+                .await
+                .ok()?;
+            open.offset += contents.len() as u64;
+
+            // Close the file once it has been read to the end
+            if !contents.is_empty() {
+                *chunked = Some(open);
+            }
+
+            Some(contents)
+        }
+        #[cfg(not(feature = "tokio_runtime"))]
+        {
+            None
+        }
+    }
+
     async fn read_file_to_string(&self, file: &str) -> Option<String> {
         #[cfg(feature = "tokio_runtime")]
         {
@@ -0,0 +1,60 @@
+use dioxus_history::{History, MemoryHistory};
+use std::{cell::RefCell, sync::Arc};
+
+/// The [`History`] provider for desktop windows.
+///
+/// Navigation is stored in memory, but unlike [`MemoryHistory`] the router is notified when the route changes from
+/// outside the app, for example when the app is opened with a deep link.
+#[derive(Default)]
+pub(crate) struct DesktopHistory {
+    inner: MemoryHistory,
+    updater: RefCell<Option<Arc<dyn Fn() + Send + Sync>>>,
+}
+
+impl DesktopHistory {
+    /// Navigate to a route from outside the app and let the router know it needs to update
+    pub(crate) fn navigate_external(&self, route: String) {
+        self.inner.push(route);
+        if let Some(updater) = self.updater.borrow().as_ref() {
+            updater();
+        }
+    }
+}
+
+impl History for DesktopHistory {
+    fn current_route(&self) -> String {
+        self.inner.current_route()
+    }
+
+    fn current_prefix(&self) -> Option<String> {
+        self.inner.current_prefix()
+    }
+
+    fn can_go_back(&self) -> bool {
+        self.inner.can_go_back()
+    }
+
+    fn go_back(&self) {
+        self.inner.go_back()
+    }
+
+    fn can_go_forward(&self) -> bool {
+        self.inner.can_go_forward()
+    }
+
+    fn go_forward(&self) {
+        self.inner.go_forward()
+    }
+
+    fn push(&self, route: String) {
+        self.inner.push(route)
+    }
+
+    fn replace(&self, path: String) {
+        self.inner.replace(path)
+    }
+
+    fn updater(&self, callback: Arc<dyn Fn() + Send + Sync>) {
+        *self.updater.borrow_mut() = Some(callback);
+    }
+}
@@ -1,13 +1,19 @@
-use std::rc::Rc;
+use std::{any::Any, rc::Rc};
 
 use crate::{
     assets::*, ipc::UserWindowEvent, shortcut::IntoAccelerator, window, DesktopContext,
-    HotKeyState, ShortcutHandle, ShortcutRegistryError, WryEventHandler,
+    HotKeyState, PowerEvent, SecondInstanceEvent, ShortcutHandle, ShortcutRegistryError,
+    WryEventHandler,
 };
-use dioxus_core::{consume_context, use_hook, use_hook_with_cleanup, Runtime};
+use dioxus_core::{consume_context, spawn, use_hook, use_hook_with_cleanup, Runtime};
 
 use dioxus_hooks::use_callback;
-use tao::{event::Event, event_loop::EventLoopWindowTarget};
+use dioxus_signals::{ReadOnlySignal, Readable, Signal, SyncSignal, Writable};
+use tao::{
+    event::{Event, WindowEvent},
+    event_loop::EventLoopWindowTarget,
+    window::Theme,
+};
 use wry::RequestAsyncResponder;
 
 /// Get an imperative handle to the current window
@@ -35,6 +41,171 @@ pub fn use_wry_event_handler(
     )
 }
 
+/// Register an event handler that runs when the current window receives a window event.
+///
+/// This includes events like [`WindowEvent::Moved`], [`WindowEvent::Resized`] and [`WindowEvent::Focused`].
+/// Events for other windows are not passed to the handler.
+pub fn use_window_event(mut handler: impl FnMut(&WindowEvent) + 'static) -> WryEventHandler {
+    use_wry_event_handler(move |event, _| {
+        if let Event::WindowEvent { event, .. } = event {
+            handler(event);
+        }
+    })
+}
+
+/// Register an event handler that runs when another window sends a message of type `T` to the current window
+/// with [`crate::DesktopService::send_message`].
+///
+/// Messages of other types are ignored.
+pub fn use_window_message<T: Any + Send + Sync>(
+    mut handler: impl FnMut(&T) + 'static,
+) -> WryEventHandler {
+    let id = use_hook(|| window().id());
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::WindowMessage { target, message }) = event {
+            if *target != id {
+                return;
+            }
+            if let Some(message) = message.downcast_ref::<T>() {
+                handler(message);
+            }
+        }
+    })
+}
+
+/// Get a signal that is shared between every window in the app.
+///
+/// Every window that calls this hook with the same key gets the same signal, and writes from any window
+/// rerender the components that read it in all windows. The `init` function only runs for the first window
+/// that creates the signal. The signal lives for the rest of the app's lifetime, even if that window closes.
+///
+/// ## Panics
+///
+/// This hook will panic if the key was already used for a shared signal of a different type.
+#[track_caller]
+pub fn use_shared_signal<T: Send + Sync + 'static>(
+    key: &str,
+    init: impl FnOnce() -> T,
+) -> SyncSignal<T> {
+    let caller = std::panic::Location::caller();
+
+    use_hook(|| {
+        window()
+            .shared
+            .shared_signals
+            .get_or_insert_with(key, || Signal::leak_with_caller(init(), caller))
+    })
+}
+
+/// Register an event handler that runs when the app is opened with a deep link.
+///
+/// The handler receives the full URL, like `myapp://settings/profile`. Deep links are only delivered for the scheme set
+/// with [`crate::Config::with_deep_link_scheme`]. The deep link is also routed into the router of the first window.
+pub fn use_deep_link(mut handler: impl FnMut(&str) + 'static) -> WryEventHandler {
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::DeepLink(url)) = event {
+            handler(url);
+        }
+    })
+}
+
+/// Register an event handler that runs when another instance of the app is launched.
+///
+/// This only runs if the app was configured with [`crate::Config::with_single_instance`]. The new instance exits
+/// immediately after forwarding its arguments to this instance.
+pub fn use_second_instance(
+    mut handler: impl FnMut(&SecondInstanceEvent) + 'static,
+) -> WryEventHandler {
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::SecondInstance(event)) = event {
+            handler(event);
+        }
+    })
+}
+
+/// Get the current theme of the system as a signal.
+///
+/// The signal is updated whenever the user switches between light and dark mode.
+pub fn use_system_theme() -> ReadOnlySignal<Theme> {
+    let mut theme = use_hook(|| Signal::new(window().theme()));
+
+    use_window_event(move |event| {
+        if let WindowEvent::ThemeChanged(new_theme) = event {
+            if *theme.peek() != *new_theme {
+                theme.set(*new_theme);
+            }
+        }
+    });
+
+    theme.into()
+}
+
+/// Get a handle to the system clipboard
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "clipboard",
+        any(target_os = "windows", target_os = "linux", target_os = "macos")
+    )))
+)]
+#[cfg(all(
+    feature = "clipboard",
+    any(target_os = "windows", target_os = "linux", target_os = "macos")
+))]
+pub fn use_clipboard() -> crate::Clipboard {
+    use_hook(|| window().clipboard())
+}
+
+/// Register an event handler that runs when the system goes to sleep or wakes back up.
+///
+/// On Linux, sleep and resume are reported by systemd-logind. Elsewhere detecting power changes is best effort: a
+/// resume is noticed within a few seconds of the system waking up, but [`PowerEvent::Suspended`] is never sent. On
+/// platforms other than Linux and macOS, setting the system clock forward can be reported as a resume.
+pub fn use_power_event(mut handler: impl FnMut(&PowerEvent) + 'static) -> WryEventHandler {
+    use_hook(|| {
+        let window = window();
+        window
+            .shared
+            .power_monitor
+            .start(window.shared.proxy.clone());
+    });
+
+    use_wry_event_handler(move |event, _| {
+        if let Event::UserEvent(UserWindowEvent::PowerEvent(event)) = event {
+            handler(event);
+        }
+    })
+}
+
+/// Get whether the system is connected to the network as a signal.
+///
+/// This reads `navigator.onLine` from the webview, which only knows if the system is connected to a network, not if
+/// that network can reach the internet.
+pub fn use_online_status() -> ReadOnlySignal<bool> {
+    let mut online = use_hook(|| Signal::new(true));
+
+    use_hook(|| {
+        spawn(async move {
+            let mut eval = dioxus_document::eval(
+                r#"
+                dioxus.send(navigator.onLine);
+                window.addEventListener("online", () => dioxus.send(true));
+                window.addEventListener("offline", () => dioxus.send(false));
+                "#,
+            );
+
+            while let Ok(status) = eval.recv::<bool>().await {
+                if *online.peek() != status {
+                    online.set(status);
+                }
+            }
+        })
+    });
+
+    online.into()
+}
+
 /// Register an event handler that runs when a muda event is processed.
 #[cfg_attr(
     docsrs,
@@ -1,4 +1,6 @@
+use crate::{PowerEvent, SecondInstanceEvent};
 use serde::{Deserialize, Serialize};
+use std::{any::Any, sync::Arc};
 use tao::window::WindowId;
 
 #[non_exhaustive]
@@ -38,6 +40,28 @@ pub enum UserWindowEvent {
     /// Create a new window
     NewWindow,
 
+    /// Send a message to a given window (could be any window!)
+    WindowMessage {
+        target: WindowId,
+        message: Arc<dyn Any + Send + Sync>,
+    },
+
+    /// Another instance of the app was launched and forwarded its arguments to us
+    SecondInstance(SecondInstanceEvent),
+
+    /// The app was opened with a deep link
+    DeepLink(String),
+
+    /// The user acted on or closed a notification. The action is `None` if the notification was closed
+    #[cfg(all(feature = "notifications", target_os = "linux"))]
+    NotificationAction {
+        notification: u64,
+        action: Option<String>,
+    },
+
+    /// The power state of the system changed
+    PowerEvent(PowerEvent),
+
     /// Close a given window (could be any window!)
     CloseWindow(WindowId),
 
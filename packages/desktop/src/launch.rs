@@ -27,6 +27,11 @@ pub fn launch_virtual_dom_blocking(virtual_dom: VirtualDom, mut desktop_config:
         match window_event {
             Event::NewEvents(StartCause::Init) => app.handle_start_cause_init(),
             Event::LoopDestroyed => app.handle_loop_destroyed(),
+            Event::Opened { urls } => {
+                for url in urls {
+                    app.handle_opened_url(url.to_string());
+                }
+            }
             Event::WindowEvent {
                 event, window_id, ..
             } => match event {
@@ -40,6 +45,18 @@ pub fn launch_virtual_dom_blocking(virtual_dom: VirtualDom, mut desktop_config:
                 UserWindowEvent::Poll(id) => app.poll_vdom(id),
                 UserWindowEvent::NewWindow => app.handle_new_window(),
                 UserWindowEvent::CloseWindow(id) => app.handle_close_msg(id),
+                // Window messages are delivered to the window's own wry event handlers
+                UserWindowEvent::WindowMessage { .. } => {}
+                UserWindowEvent::SecondInstance(event) => app.handle_second_instance(event),
+                // Deep links are routed before the event is sent, so the event is only for the handlers from use_deep_link
+                UserWindowEvent::DeepLink(_) => {}
+                // Power events are only delivered to the wry event handlers
+                UserWindowEvent::PowerEvent(_) => {}
+
+                #[cfg(all(feature = "notifications", target_os = "linux"))]
+                UserWindowEvent::NotificationAction { notification, .. } => {
+                    app.handle_notification_action(notification)
+                }
                 UserWindowEvent::Shutdown => app.control_flow = tao::event_loop::ControlFlow::Exit,
 
                 #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
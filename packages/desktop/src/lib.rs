@@ -7,7 +7,9 @@
 mod android_sync_lock;
 mod app;
 mod assets;
+mod components;
 mod config;
+mod deep_link;
 mod default_icon;
 mod desktop_context;
 mod document;
@@ -16,15 +18,19 @@ mod element;
 mod event_handlers;
 mod events;
 mod file_upload;
+mod history;
 mod hooks;
 mod ipc;
 mod menubar;
 mod mobile;
+mod power;
 mod protocol;
 mod query;
 mod shortcut;
+mod single_instance;
 mod waker;
 mod webview;
+mod window_registry;
 
 pub use default_icon::default_icon;
 
@@ -39,23 +45,101 @@ pub mod launch;
 pub use tao;
 pub use tao::dpi::{LogicalPosition, LogicalSize};
 pub use tao::event::WindowEvent;
-pub use tao::window::WindowBuilder;
+pub use tao::window::{Theme, WindowBuilder};
 pub use wry;
 // Reexport muda only if we are on desktop platforms that support menus
 #[cfg(not(any(target_os = "ios", target_os = "android")))]
 pub use muda;
 
+// Native dialogs are only supported on desktop platforms
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))
+)]
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub mod dialogs;
+
+// The updater is only supported on desktop platforms
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "updater",
+        any(target_os = "windows", target_os = "linux", target_os = "macos")
+    )))
+)]
+#[cfg(all(
+    feature = "updater",
+    any(target_os = "windows", target_os = "linux", target_os = "macos")
+))]
+pub mod updater;
+
+// Clipboard and notifications are only supported on desktop platforms
+#[cfg(all(
+    feature = "clipboard",
+    any(target_os = "windows", target_os = "linux", target_os = "macos")
+))]
+mod clipboard;
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "clipboard",
+        any(target_os = "windows", target_os = "linux", target_os = "macos")
+    )))
+)]
+#[cfg(all(
+    feature = "clipboard",
+    any(target_os = "windows", target_os = "linux", target_os = "macos")
+))]
+pub use clipboard::{Clipboard, ClipboardError, ClipboardImage};
+
+#[cfg(all(
+    feature = "notifications",
+    any(target_os = "windows", target_os = "linux", target_os = "macos")
+))]
+mod notification;
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "notifications",
+        any(target_os = "windows", target_os = "linux", target_os = "macos")
+    )))
+)]
+#[cfg(all(
+    feature = "notifications",
+    any(target_os = "windows", target_os = "linux", target_os = "macos")
+))]
+pub use notification::{Notification, NotificationError};
+
 // Tray icon
 #[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
 pub mod trayicon;
 
 // Public exports
 pub use assets::AssetRequest;
+pub use components::{Window, WindowApp, WindowProps};
 pub use config::{Config, WindowCloseBehaviour};
 pub use desktop_context::{
     window, DesktopContext, DesktopService, PendingDesktopContext, WeakDesktopContext,
 };
 pub use event_handlers::WryEventHandler;
 pub use hooks::*;
+pub use power::PowerEvent;
 pub use shortcut::{HotKeyState, ShortcutHandle, ShortcutRegistryError};
+pub use single_instance::SecondInstanceEvent;
 pub use wry::RequestAsyncResponder;
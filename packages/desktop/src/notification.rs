@@ -0,0 +1,123 @@
+/// An error that occurred while showing a notification
+pub use notify_rust::error::Error as NotificationError;
+
+#[cfg(target_os = "linux")]
+static NEXT_NOTIFICATION_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// A system notification.
+///
+/// # Example
+///
+/// ```rust, no_run
+/// use dioxus_desktop::Notification;
+///
+/// Notification::new("Download finished")
+///     .with_body("logs.tar.gz was saved to your downloads folder")
+///     .show()
+///     .unwrap();
+/// ```
+pub struct Notification {
+    inner: notify_rust::Notification,
+}
+
+impl Notification {
+    /// Create a new notification with a summary. The summary is the title of the notification.
+    pub fn new(summary: &str) -> Self {
+        let mut inner = notify_rust::Notification::new();
+        inner.summary(summary);
+        if let Some(title) = dioxus_cli_config::app_title() {
+            inner.appname(&title);
+        }
+        Self { inner }
+    }
+
+    /// Set the body of the notification
+    pub fn with_body(mut self, body: &str) -> Self {
+        self.inner.body(body);
+        self
+    }
+
+    /// Set the icon of the notification. This can be a path to an image or the name of an icon from the system theme.
+    pub fn with_icon(mut self, icon: &str) -> Self {
+        self.inner.icon(icon);
+        self
+    }
+
+    /// Add a button to the notification. When the user clicks it, the callback passed to
+    /// `Notification::show_with_callback` is called with the identifier.
+    ///
+    /// Actions are only supported on Linux.
+    pub fn with_action(mut self, identifier: &str, label: &str) -> Self {
+        self.inner.action(identifier, label);
+        self
+    }
+
+    /// Show the notification without listening for actions
+    pub fn show(self) -> Result<(), NotificationError> {
+        self.inner.show()?;
+        Ok(())
+    }
+
+    /// Show the notification and call `on_action` with the identifier of the action the user picked.
+    ///
+    /// The callback is removed once the user acts on or closes the notification. This is only available on Linux,
+    /// where any desktop that implements the freedesktop notification spec reports actions.
+    ///
+    /// ```rust, no_run
+    /// use dioxus_desktop::Notification;
+    ///
+    /// Notification::new("Download finished")
+    ///     .with_action("open", "Open")
+    ///     .show_with_callback(|action| {
+    ///         if action == "open" {
+    ///             // ...
+    ///         }
+    ///     })
+    ///     .unwrap();
+    /// ```
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+    pub fn show_with_callback(
+        self,
+        mut on_action: impl FnMut(&str) + 'static,
+    ) -> Result<(), NotificationError> {
+        use crate::{ipc::UserWindowEvent, window};
+        use std::sync::atomic::Ordering;
+        use tao::event::Event;
+
+        let handle = self.inner.show()?;
+
+        let id = NEXT_NOTIFICATION_ID.fetch_add(1, Ordering::Relaxed);
+        let context = window();
+        let handler = context.create_wry_event_handler(move |event, _| {
+            if let Event::UserEvent(UserWindowEvent::NotificationAction {
+                notification,
+                action: Some(action),
+            }) = event
+            {
+                if *notification == id {
+                    on_action(action);
+                }
+            }
+        });
+        context
+            .shared
+            .notification_handlers
+            .borrow_mut()
+            .insert(id, handler);
+
+        let proxy = context.shared.proxy.clone();
+        std::thread::spawn(move || {
+            handle.wait_for_action(|action| {
+                // notify-rust reports the notification being closed as a special action
+                let action = (action != "__closed").then(|| action.to_string());
+                _ = proxy.send_event(UserWindowEvent::NotificationAction {
+                    notification: id,
+                    action,
+                });
+            });
+        });
+
+        Ok(())
+    }
+}
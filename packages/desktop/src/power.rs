@@ -0,0 +1,192 @@
+use crate::ipc::UserWindowEvent;
+use std::{cell::Cell, time::Duration};
+use tao::event_loop::EventLoopProxy;
+
+/// A change in the power state of the system
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PowerEvent {
+    /// The system is about to go to sleep
+    ///
+    /// This is only reported on Linux with systemd-logind, which announces sleep before it happens.
+    Suspended,
+
+    /// The system woke up after being asleep
+    Resumed {
+        /// Roughly how long the system was asleep
+        slept_for: Duration,
+    },
+}
+
+/// How often the monitor checks if the system was asleep
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How much the clocks need to drift apart before we assume the system was asleep
+const SLEEP_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Watches for the system going to sleep and waking back up.
+///
+/// On Linux, the monitor listens for the `PrepareForSleep` signal of systemd-logind, which is sent both before the
+/// system sleeps and after it resumes.
+///
+/// Everywhere else, or if logind isn't available, the monitor is best effort. It compares a clock that stops while
+/// the system is asleep with one that keeps running. If the second clock moves much further than the first between
+/// two checks, the system was asleep in between. That means the monitor only notices sleep once the system has
+/// resumed, so there is no [`PowerEvent::Suspended`] event.
+///
+/// On Linux and macOS both clocks are monotonic, so changes to the system time are ignored. Other platforms compare
+/// the monotonic clock with the wall clock, so setting the system clock forward by more than a few seconds is reported
+/// as a resume.
+#[derive(Default)]
+pub(crate) struct PowerMonitor {
+    started: Cell<bool>,
+}
+
+impl PowerMonitor {
+    /// Start the monitor if it isn't already running
+    pub(crate) fn start(&self, proxy: EventLoopProxy<UserWindowEvent>) {
+        if self.started.replace(true) {
+            return;
+        }
+
+        std::thread::spawn(move || {
+            #[cfg(target_os = "linux")]
+            match watch_logind(&proxy) {
+                // The event loop was closed
+                Ok(()) => return,
+                Err(err) => tracing::debug!(
+                    "Failed to listen for sleep from logind, falling back to polling the clocks: {err}"
+                ),
+            }
+
+            let mut last = Clocks::now();
+
+            loop {
+                std::thread::sleep(POLL_INTERVAL);
+
+                let now = Clocks::now();
+                if let Some(slept_for) = now.slept_since(&last) {
+                    let event = PowerEvent::Resumed { slept_for };
+                    if proxy
+                        .send_event(UserWindowEvent::PowerEvent(event))
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+
+                last = now;
+            }
+        });
+    }
+}
+
+/// Forward the `PrepareForSleep` signals of systemd-logind until the event loop closes
+#[cfg(target_os = "linux")]
+fn watch_logind(proxy: &EventLoopProxy<UserWindowEvent>) -> zbus::Result<()> {
+    let connection = zbus::blocking::Connection::system()?;
+    let login = zbus::blocking::Proxy::new(
+        &connection,
+        "org.freedesktop.login1",
+        "/org/freedesktop/login1",
+        "org.freedesktop.login1.Manager",
+    )?;
+
+    let mut suspended_at = None;
+    for message in login.receive_signal("PrepareForSleep")? {
+        let sleeping: bool = message.body().deserialize()?;
+        let event = if sleeping {
+            suspended_at = Some(Clocks::now());
+            PowerEvent::Suspended
+        } else {
+            let slept_for = suspended_at
+                .take()
+                .map(|suspended_at| Clocks::now().total.saturating_sub(suspended_at.total))
+                .unwrap_or_default();
+            PowerEvent::Resumed { slept_for }
+        };
+
+        if proxy
+            .send_event(UserWindowEvent::PowerEvent(event))
+            .is_err()
+        {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// A reading of a clock that stops while the system is asleep and one that keeps running
+struct Clocks {
+    awake: Duration,
+    total: Duration,
+}
+
+impl Clocks {
+    /// On Linux, `CLOCK_MONOTONIC` stops during suspend while `CLOCK_BOOTTIME` keeps counting
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn now() -> Self {
+        Self {
+            awake: clock_gettime(libc::CLOCK_MONOTONIC),
+            total: clock_gettime(libc::CLOCK_BOOTTIME),
+        }
+    }
+
+    /// On Apple platforms, `CLOCK_UPTIME_RAW` stops during sleep while `CLOCK_MONOTONIC` keeps counting
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    fn now() -> Self {
+        Self {
+            awake: clock_gettime(libc::CLOCK_UPTIME_RAW),
+            total: clock_gettime(libc::CLOCK_MONOTONIC),
+        }
+    }
+
+    /// Elsewhere, fall back to comparing the monotonic clock with the wall clock
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios"
+    )))]
+    fn now() -> Self {
+        use std::time::{Instant, SystemTime};
+
+        thread_local! {
+            static START: Instant = Instant::now();
+        }
+
+        Self {
+            awake: START.with(|start| start.elapsed()),
+            total: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// If the system was asleep between the two readings, roughly how long it slept for
+    fn slept_since(&self, last: &Self) -> Option<Duration> {
+        let awake = self.awake.saturating_sub(last.awake);
+        let total = self.total.saturating_sub(last.total);
+
+        (total > awake + SLEEP_THRESHOLD).then(|| total - awake)
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios"
+))]
+fn clock_gettime(clock: libc::clockid_t) -> Duration {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+
+    // SAFETY: the timespec is valid for writes and both clocks are supported on this platform
+    unsafe { libc::clock_gettime(clock, &mut time) };
+
+    Duration::new(time.tv_sec as u64, time.tv_nsec as u32)
+}
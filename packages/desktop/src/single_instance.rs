@@ -0,0 +1,261 @@
+use crate::ipc::UserWindowEvent;
+use rand::{distr::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Write},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tao::event_loop::EventLoopProxy;
+
+/// The arguments of a second launch of the app, forwarded to the instance that is already running.
+///
+/// Listen for this event with [`crate::use_second_instance`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecondInstanceEvent {
+    /// The command line arguments the second instance was launched with, including the executable name
+    pub args: Vec<String>,
+
+    /// The working directory of the second instance
+    pub cwd: Option<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ForwardedLaunch {
+    token: String,
+    event: SecondInstanceEvent,
+}
+
+const ACK: &str = "ok";
+
+/// The lock that makes this process the primary instance of the app. The OS releases the lock when the process exits,
+/// even if it crashes.
+pub(crate) struct InstanceLock {
+    _lock: File,
+    info_file: PathBuf,
+}
+
+impl Drop for InstanceLock {
+    fn drop(&mut self) {
+        // The lock file itself is left in place. Removing it while another instance is waiting to lock it would let a
+        // third instance lock a new file at the same path
+        _ = std::fs::remove_file(&self.info_file);
+    }
+}
+
+/// Make sure only one instance of the app is running.
+///
+/// If another instance is already running, our arguments are forwarded to it over a local socket and the process
+/// exits. Otherwise, we become the primary instance and listen for launches from other instances until the returned
+/// lock is dropped.
+pub(crate) fn acquire(proxy: EventLoopProxy<UserWindowEvent>) -> Option<InstanceLock> {
+    let (lock_file, info_file) = instance_files();
+
+    let lock = match try_lock(&lock_file) {
+        Ok(Some(lock)) => lock,
+        Ok(None) => forward_to_primary(&info_file),
+        Err(err) => {
+            tracing::warn!("Failed to lock the single instance lock file: {err}");
+            return None;
+        }
+    };
+
+    // We hold the lock, so we are the primary instance. Any info file left over from a crashed instance is overwritten
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, 0)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            tracing::warn!("Failed to listen for other instances of the app: {err}");
+            return None;
+        }
+    };
+    let address = listener.local_addr().ok()?;
+
+    // Other processes can connect to the socket, so we require them to know a secret that is only stored in the info file
+    let token: String = StdRng::from_os_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+
+    if let Err(err) = write_info_file(&info_file, &format!("{}\n{token}", address.port())) {
+        tracing::warn!("Failed to write the single instance info file: {err}");
+        return None;
+    }
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let Some(event) = receive_launch(&stream, &token) else {
+                continue;
+            };
+
+            if proxy
+                .send_event(UserWindowEvent::SecondInstance(event))
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+
+    Some(InstanceLock {
+        _lock: lock,
+        info_file,
+    })
+}
+
+/// Another instance holds the lock. Forward our launch to it and exit.
+///
+/// The primary instance writes its port after it takes the lock, so we retry for a little while if it just started.
+fn forward_to_primary(info_file: &Path) -> ! {
+    for _ in 0..20 {
+        let info = std::fs::read_to_string(info_file).unwrap_or_default();
+        if let Some((port, token)) = info.trim().split_once('\n') {
+            if let Ok(port) = port.parse::<u16>() {
+                if forward_launch(port, token).is_ok() {
+                    std::process::exit(0);
+                }
+            }
+        }
+
+        std::thread::sleep(Duration::from_millis(100));
+    }
+
+    tracing::error!(
+        "Another instance of the app is running, but it did not respond to this launch"
+    );
+    std::process::exit(1);
+}
+
+/// Take an exclusive lock on the lock file. Returns `None` if another process already holds the lock.
+#[cfg(unix)]
+fn try_lock(path: &Path) -> std::io::Result<Option<File>> {
+    use std::os::unix::{fs::OpenOptionsExt, io::AsRawFd};
+
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .mode(0o600)
+        .open(path)?;
+
+    // SAFETY: the file descriptor is valid for as long as the file is open
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(Some(file));
+    }
+
+    let err = std::io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::EWOULDBLOCK) => Ok(None),
+        _ => Err(err),
+    }
+}
+
+/// Take an exclusive lock on the lock file. Returns `None` if another process already holds the lock.
+///
+/// Opening the file without sharing it locks it until the handle is closed.
+#[cfg(windows)]
+fn try_lock(path: &Path) -> std::io::Result<Option<File>> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .share_mode(0)
+        .open(path);
+
+    match file {
+        Ok(file) => Ok(Some(file)),
+        Err(err) if err.raw_os_error() == Some(ERROR_SHARING_VIOLATION) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn try_lock(_path: &Path) -> std::io::Result<Option<File>> {
+    Err(std::io::Error::other(
+        "single instance locks are not supported on this platform",
+    ))
+}
+
+/// Send our launch to the primary instance and wait for it to acknowledge it
+fn forward_launch(port: u16, token: &str) -> std::io::Result<()> {
+    let mut stream =
+        TcpStream::connect_timeout(&(Ipv4Addr::LOCALHOST, port).into(), Duration::from_secs(1))?;
+    stream.set_read_timeout(Some(Duration::from_secs(1)))?;
+
+    let launch = ForwardedLaunch {
+        token: token.to_string(),
+        event: SecondInstanceEvent {
+            args: std::env::args().collect(),
+            cwd: std::env::current_dir().ok(),
+        },
+    };
+    let mut message = serde_json::to_string(&launch)?;
+    message.push('\n');
+    stream.write_all(message.as_bytes())?;
+
+    // Whatever is listening on the port might not be our app, so make sure it acknowledges the launch
+    let mut response = String::new();
+    BufReader::new(&stream).read_line(&mut response)?;
+    if response.trim() != ACK {
+        return Err(std::io::Error::other(
+            "the running instance did not acknowledge the launch",
+        ));
+    }
+
+    Ok(())
+}
+
+fn receive_launch(mut stream: &TcpStream, token: &str) -> Option<SecondInstanceEvent> {
+    stream.set_read_timeout(Some(Duration::from_secs(1))).ok()?;
+
+    let mut message = String::new();
+    BufReader::new(stream).read_line(&mut message).ok()?;
+
+    let launch: ForwardedLaunch = serde_json::from_str(&message).ok()?;
+    if launch.token != token {
+        tracing::warn!("Ignoring a launch from another process with an invalid token");
+        return None;
+    }
+
+    _ = writeln!(stream, "{ACK}");
+
+    Some(launch.event)
+}
+
+fn write_info_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options.open(path)?.write_all(contents.as_bytes())
+}
+
+/// The lock and info files are keyed by the name of the executable so different apps don't collide
+fn instance_files() -> (PathBuf, PathBuf) {
+    let name = std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.file_stem()?.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "dioxus-app".to_string());
+
+    let dir = std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir);
+
+    (
+        dir.join(format!("{name}.instance.lock")),
+        dir.join(format!("{name}.instance")),
+    )
+}
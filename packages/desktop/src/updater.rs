@@ -0,0 +1,515 @@
+//! Update a bundled app at runtime.
+//!
+//! `dx bundle --package-types updater` produces signed update artifacts and a `latest.json` manifest that lists the
+//! artifact for every platform. Host those files anywhere, and point an [`Updater`] at the manifest:
+//!
+//! ```rust, no_run
+//! # use dioxus::prelude::*;
+//! use dioxus::desktop::updater::{use_updater, UpdateStatus, Updater};
+//!
+//! const PUBLIC_KEY: &str = "dW50cnVzdGVkIGNvbW1lbnQ6IG1pbmlzaWduIHB1YmxpYyBrZXk6...";
+//!
+//! fn app() -> Element {
+//!     let updater = use_updater(|| {
+//!         Updater::new(
+//!             "https://releases.example.com/latest.json",
+//!             env!("CARGO_PKG_VERSION"),
+//!             PUBLIC_KEY,
+//!         )
+//!     });
+//!
+//!     match updater.status()() {
+//!         UpdateStatus::Ready(version) => rsx! {
+//!             button { onclick: move |_| _ = updater.restart(), "Restart to update to {version}" }
+//!         },
+//!         _ => rsx! {},
+//!     }
+//! }
+//! ```
+//!
+//! Updates are verified against the public key of the key pair that signed them before they are installed.
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use dioxus_core::{spawn, use_hook};
+use dioxus_signals::{ReadOnlySignal, Readable, Signal, Writable};
+use serde::Deserialize;
+use std::{collections::HashMap, path::PathBuf};
+use thiserror::Error;
+use tokio::io::AsyncWriteExt;
+
+/// An error that occurred while checking for, downloading or installing an update
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum UpdaterError {
+    /// The request for the manifest or the update failed
+    #[error("Failed to fetch the update: {0}")]
+    Request(#[from] reqwest::Error),
+    /// The manifest could not be parsed
+    #[error("Failed to parse the update manifest: {0}")]
+    InvalidManifest(serde_json::Error),
+    /// The current version or the version in the manifest is not a valid semver version
+    #[error("Invalid version: {0}")]
+    InvalidVersion(#[from] semver::Error),
+    /// The manifest doesn't contain an update for this platform
+    #[error("The update manifest has no update for {0}")]
+    UnsupportedTarget(String),
+    /// The public key or the signature of the update is invalid, or the update was not signed by the key
+    #[error("Failed to verify the signature of the update: {0}")]
+    InvalidSignature(String),
+    /// The update can't be installed on this platform or with this kind of install
+    #[error("The update can't be installed: {0}")]
+    UnsupportedInstall(String),
+    /// Failed to write the update to disk or replace the current app
+    #[error("Failed to install the update: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Checks a manifest for a newer version of the app
+#[derive(Clone)]
+pub struct Updater {
+    manifest_url: String,
+    current_version: String,
+    public_key: String,
+    target: String,
+    headers: Vec<(String, String)>,
+    client: reqwest::Client,
+}
+
+impl Updater {
+    /// Create a new updater that checks the manifest at the given URL.
+    ///
+    /// The current version is usually `env!("CARGO_PKG_VERSION")`. The public key is the base64 encoded minisign public
+    /// key that matches the private key the updates were signed with.
+    pub fn new(
+        manifest_url: impl Into<String>,
+        current_version: impl Into<String>,
+        public_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            manifest_url: manifest_url.into(),
+            current_version: current_version.into(),
+            public_key: public_key.into(),
+            target: default_target(),
+            headers: Vec::new(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Set the key of the platform to look up in the manifest. Defaults to `{os}-{arch}`, like `darwin-aarch64`,
+    /// `linux-x86_64` or `windows-x86_64`.
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = target.into();
+        self
+    }
+
+    /// Add a header to every request the updater makes, for example to authenticate with a private update server
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Fetch the manifest and return the update for this platform if it is newer than the current version
+    pub async fn check(&self) -> Result<Option<Update>, UpdaterError> {
+        let current_version = semver::Version::parse(&self.current_version)?;
+
+        let body = self
+            .request(&self.manifest_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        let manifest: UpdateManifest =
+            serde_json::from_slice(&body).map_err(UpdaterError::InvalidManifest)?;
+
+        let version = semver::Version::parse(manifest.version.trim_start_matches('v'))?;
+        if version <= current_version {
+            return Ok(None);
+        }
+
+        let platform = manifest
+            .platforms
+            .get(&self.target)
+            .ok_or_else(|| UpdaterError::UnsupportedTarget(self.target.clone()))?;
+
+        Ok(Some(Update {
+            version,
+            notes: manifest.notes,
+            pub_date: manifest.pub_date,
+            url: platform.url.clone(),
+            signature: platform.signature.clone(),
+            updater: self.clone(),
+        }))
+    }
+
+    fn request(&self, url: &str) -> reqwest::RequestBuilder {
+        self.headers
+            .iter()
+            .fold(self.client.get(url), |request, (name, value)| {
+                request.header(name, value)
+            })
+    }
+}
+
+/// The manifest `dx bundle` writes next to the update artifacts
+#[derive(Deserialize)]
+struct UpdateManifest {
+    version: String,
+    notes: Option<String>,
+    pub_date: Option<String>,
+    platforms: HashMap<String, PlatformUpdate>,
+}
+
+#[derive(Deserialize)]
+struct PlatformUpdate {
+    url: String,
+    signature: String,
+}
+
+/// A newer version of the app that is available to download
+#[derive(Clone)]
+pub struct Update {
+    /// The version of the update
+    pub version: semver::Version,
+    /// The release notes of the update, if the manifest has any
+    pub notes: Option<String>,
+    /// The date the update was published, if the manifest has one
+    pub pub_date: Option<String>,
+    /// The URL the update is downloaded from
+    pub url: String,
+    signature: String,
+    updater: Updater,
+}
+
+/// How much of an update has been downloaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DownloadProgress {
+    /// The number of bytes downloaded so far
+    pub downloaded: u64,
+    /// The total size of the update, if the server sent it
+    pub total: Option<u64>,
+}
+
+impl Update {
+    /// Download the update and verify its signature.
+    ///
+    /// The update is streamed to a temporary file, so it is never held in memory. If the signature doesn't match, the
+    /// file is removed.
+    ///
+    /// The progress callback is called every time a chunk of the update is received.
+    pub async fn download(
+        &self,
+        mut on_progress: impl FnMut(DownloadProgress),
+    ) -> Result<DownloadedUpdate, UpdaterError> {
+        let mut response = self
+            .updater
+            .request(&self.url)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let (public_key, signature) = decode_signature(&self.updater.public_key, &self.signature)?;
+
+        let file_name = self
+            .url
+            .rsplit('/')
+            .next()
+            .and_then(|name| name.split(['?', '#']).next())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("update");
+        let dir = std::env::temp_dir().join(format!(
+            "{}-update-{}",
+            dioxus_cli_config::product_name().unwrap_or_else(|| "dioxus-app".to_string()),
+            self.version
+        ));
+        tokio::fs::create_dir_all(&dir).await?;
+        let path = dir.join(file_name);
+
+        // Stream the update to disk and verify it as it arrives so large updates are never held in memory. Legacy
+        // minisign signatures can't be verified in a stream, so those are verified from the file once it is written
+        let mut verifier = public_key.verify_stream(&signature).ok();
+        let mut file = tokio::fs::File::create(&path).await?;
+        let mut progress = DownloadProgress {
+            downloaded: 0,
+            total: response.content_length(),
+        };
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+            if let Some(verifier) = &mut verifier {
+                verifier.update(&chunk);
+            }
+            progress.downloaded += chunk.len() as u64;
+            on_progress(progress);
+        }
+        file.flush().await?;
+        drop(file);
+
+        let verified = match verifier {
+            Some(verifier) => verifier.finalize(),
+            None => public_key.verify(&tokio::fs::read(&path).await?, &signature, true),
+        };
+        if let Err(err) = verified {
+            _ = tokio::fs::remove_file(&path).await;
+            return Err(UpdaterError::InvalidSignature(err.to_string()));
+        }
+
+        Ok(DownloadedUpdate {
+            version: self.version.clone(),
+            path,
+        })
+    }
+}
+
+/// Updates are signed with minisign. Both the key and the signature are base64 encoded minisign files.
+fn decode_signature(
+    public_key: &str,
+    signature: &str,
+) -> Result<(minisign_verify::PublicKey, minisign_verify::Signature), UpdaterError> {
+    let decode = |text: &str| {
+        let decoded = BASE64_STANDARD
+            .decode(text.trim())
+            .map_err(|err| UpdaterError::InvalidSignature(err.to_string()))?;
+        String::from_utf8(decoded).map_err(|err| UpdaterError::InvalidSignature(err.to_string()))
+    };
+
+    let public_key = minisign_verify::PublicKey::decode(&decode(public_key)?)
+        .map_err(|err| UpdaterError::InvalidSignature(err.to_string()))?;
+    let signature = minisign_verify::Signature::decode(&decode(signature)?)
+        .map_err(|err| UpdaterError::InvalidSignature(err.to_string()))?;
+
+    Ok((public_key, signature))
+}
+
+/// An update that was downloaded and verified, ready to be installed
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadedUpdate {
+    version: semver::Version,
+    path: PathBuf,
+}
+
+impl DownloadedUpdate {
+    /// The version of the update
+    pub fn version(&self) -> &semver::Version {
+        &self.version
+    }
+
+    /// The path the update was downloaded to
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Replace the running app with the update and restart it.
+    ///
+    /// On Linux, the app must be running from an AppImage. On macOS, the update is an archive of the `.app` bundle that
+    /// replaces the bundle the app is running from. On Windows, the installer is launched and takes over once the
+    /// app exits.
+    ///
+    /// This only returns if the update could not be installed.
+    pub fn install_and_restart(self) -> Result<(), UpdaterError> {
+        let args: Vec<_> = std::env::args_os().skip(1).collect();
+
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let app_image = std::env::var_os("APPIMAGE")
+                .map(PathBuf::from)
+                .ok_or_else(|| {
+                    UpdaterError::UnsupportedInstall(
+                        "updates are only supported when running from an AppImage".to_string(),
+                    )
+                })?;
+
+            // Copy next to the AppImage first so the final rename is atomic
+            let staged = app_image.with_extension("update");
+            std::fs::copy(&self.path, &staged)?;
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+            std::fs::rename(&staged, &app_image)?;
+
+            std::process::Command::new(&app_image).args(args).spawn()?;
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let exe = std::env::current_exe()?;
+            let app_bundle = exe
+                .ancestors()
+                .find(|path| path.extension().is_some_and(|ext| ext == "app"))
+                .ok_or_else(|| {
+                    UpdaterError::UnsupportedInstall(
+                        "updates are only supported when running from an app bundle".to_string(),
+                    )
+                })?
+                .to_path_buf();
+
+            let staging = self.path.with_extension("extracted");
+            _ = std::fs::remove_dir_all(&staging);
+            std::fs::create_dir_all(&staging)?;
+            let status = std::process::Command::new("tar")
+                .arg("-xzf")
+                .arg(&self.path)
+                .arg("-C")
+                .arg(&staging)
+                .status()?;
+            if !status.success() {
+                return Err(UpdaterError::UnsupportedInstall(
+                    "failed to extract the update archive".to_string(),
+                ));
+            }
+
+            let new_bundle = std::fs::read_dir(&staging)?
+                .flatten()
+                .map(|entry| entry.path())
+                .find(|path| path.extension().is_some_and(|ext| ext == "app"))
+                .ok_or_else(|| {
+                    UpdaterError::UnsupportedInstall(
+                        "the update archive does not contain an app bundle".to_string(),
+                    )
+                })?;
+
+            // Keep the old bundle around until the new one is in place so a failed update doesn't break the app
+            let backup = app_bundle.with_extension("app.old");
+            _ = std::fs::remove_dir_all(&backup);
+            std::fs::rename(&app_bundle, &backup)?;
+            if let Err(err) = std::fs::rename(&new_bundle, &app_bundle) {
+                _ = std::fs::rename(&backup, &app_bundle);
+                return Err(err.into());
+            }
+            _ = std::fs::remove_dir_all(&backup);
+
+            std::process::Command::new("open")
+                .arg("-n")
+                .arg(&app_bundle)
+                .arg("--args")
+                .args(args)
+                .spawn()?;
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            _ = args;
+            let extension = self
+                .path
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+
+            match extension.as_deref() {
+                Some("msi") => {
+                    std::process::Command::new("msiexec")
+                        .arg("/i")
+                        .arg(&self.path)
+                        .args(["/passive", "/norestart"])
+                        .spawn()?;
+                }
+                Some("exe") => {
+                    // The NSIS installer relaunches the app once it is done
+                    std::process::Command::new(&self.path)
+                        .args(["/P", "/R"])
+                        .spawn()?;
+                }
+                _ => {
+                    return Err(UpdaterError::UnsupportedInstall(
+                        "the update is not an msi or nsis installer".to_string(),
+                    ))
+                }
+            }
+        }
+
+        std::process::exit(0);
+    }
+}
+
+/// The platform key of this build in the manifest
+fn default_target() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "darwin",
+        os => os,
+    };
+    format!("{os}-{}", std::env::consts::ARCH)
+}
+
+/// The state of the update started by [`use_updater`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateStatus {
+    /// The updater is checking the manifest
+    Checking,
+    /// The app is already running the latest version
+    UpToDate,
+    /// A new version is being downloaded
+    Downloading {
+        /// The version that is being downloaded
+        version: semver::Version,
+        /// How much of the update has been downloaded
+        progress: DownloadProgress,
+    },
+    /// A new version was downloaded and will be installed when the app restarts
+    Ready(semver::Version),
+    /// Checking for or downloading the update failed
+    Failed(String),
+}
+
+/// A handle to the update started by [`use_updater`]
+#[derive(Clone, Copy, PartialEq)]
+pub struct UpdaterHandle {
+    status: Signal<UpdateStatus>,
+    downloaded: Signal<Option<DownloadedUpdate>>,
+}
+
+impl UpdaterHandle {
+    /// Get the status of the update
+    pub fn status(&self) -> ReadOnlySignal<UpdateStatus> {
+        self.status.into()
+    }
+
+    /// Install the downloaded update and restart the app. Does nothing if the update hasn't finished downloading.
+    pub fn restart(&self) -> Result<(), UpdaterError> {
+        match self.downloaded.peek().clone() {
+            Some(update) => update.install_and_restart(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Check for an update when the component is first rendered and download it in the background.
+///
+/// The update is installed when [`UpdaterHandle::restart`] is called.
+pub fn use_updater(init: impl FnOnce() -> Updater) -> UpdaterHandle {
+    use_hook(|| {
+        let mut status = Signal::new(UpdateStatus::Checking);
+        let mut downloaded = Signal::new(None);
+        let updater = init();
+
+        spawn(async move {
+            let update = match updater.check().await {
+                Ok(Some(update)) => update,
+                Ok(None) => {
+                    status.set(UpdateStatus::UpToDate);
+                    return;
+                }
+                Err(err) => {
+                    status.set(UpdateStatus::Failed(err.to_string()));
+                    return;
+                }
+            };
+
+            let result = update
+                .download(|progress| {
+                    status.set(UpdateStatus::Downloading {
+                        version: update.version.clone(),
+                        progress,
+                    })
+                })
+                .await;
+
+            match result {
+                Ok(update) => {
+                    status.set(UpdateStatus::Ready(update.version.clone()));
+                    downloaded.set(Some(update));
+                }
+                Err(err) => status.set(UpdateStatus::Failed(err.to_string())),
+            }
+        });
+
+        UpdaterHandle { status, downloaded }
+    })
+}
@@ -13,11 +13,11 @@ use crate::{
     waker::tao_waker,
     Config, DesktopContext, DesktopService,
 };
-use crate::{document::DesktopDocument, WeakDesktopContext};
+use crate::{document::DesktopDocument, history::DesktopHistory, WeakDesktopContext};
 use base64::prelude::BASE64_STANDARD;
 use dioxus_core::{Runtime, ScopeId, VirtualDom};
 use dioxus_document::Document;
-use dioxus_history::{History, MemoryHistory};
+use dioxus_history::History;
 use dioxus_hooks::to_owned;
 use dioxus_html::{HasFileData, HtmlEvent, PlatformEventData};
 use futures_util::{pin_mut, FutureExt};
@@ -161,6 +161,7 @@ pub(crate) struct WebviewInstance {
     pub dom: VirtualDom,
     pub edits: WebviewEdits,
     pub desktop_context: DesktopContext,
+    pub history: Rc<DesktopHistory>,
     pub waker: Waker,
 
     // Wry assumes the webcontext is alive for the lifetime of the webview.
@@ -423,12 +424,17 @@ impl WebviewInstance {
             asset_handlers,
             file_hover,
             WindowCloseBehaviour::WindowCloses,
+            cfg.window_label.take(),
         ));
 
+        // Make the window discoverable from every other window
+        shared.windows.register(&desktop_context);
+
         // Provide the desktop context to the virtual dom and edit handler
         edits.set_desktop_context(Rc::downgrade(&desktop_context));
         let provider: Rc<dyn Document> = Rc::new(DesktopDocument::new(desktop_context.clone()));
-        let history_provider: Rc<dyn History> = Rc::new(MemoryHistory::default());
+        let history = Rc::new(DesktopHistory::default());
+        let history_provider: Rc<dyn History> = history.clone();
         dom.in_runtime(|| {
             ScopeId::ROOT.provide_context(desktop_context.clone());
             ScopeId::ROOT.provide_context(provider);
@@ -440,6 +446,7 @@ impl WebviewInstance {
             edits,
             waker: tao_waker(shared.proxy.clone(), desktop_context.window.id()),
             desktop_context,
+            history,
             _menu: menu,
             _web_context: web_context,
         }
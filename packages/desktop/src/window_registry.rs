@@ -0,0 +1,77 @@
+use crate::{DesktopContext, WeakDesktopContext};
+use dioxus_signals::SyncSignal;
+use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+use tao::window::WindowId;
+
+/// A registry of every window the app has open.
+///
+/// The registry only holds weak references to the windows. Windows are owned by the app's webview map,
+/// so once a window is closed its entry will fail to upgrade and is pruned the next time the registry is read.
+#[derive(Default)]
+pub(crate) struct WindowRegistry {
+    windows: RefCell<Vec<(WindowId, WeakDesktopContext)>>,
+}
+
+impl WindowRegistry {
+    pub(crate) fn register(&self, context: &DesktopContext) {
+        self.windows
+            .borrow_mut()
+            .push((context.window.id(), Rc::downgrade(context)));
+    }
+
+    /// Get all of the windows that are still alive, in the order they were created
+    pub(crate) fn windows(&self) -> Vec<DesktopContext> {
+        let mut windows = self.windows.borrow_mut();
+        windows.retain(|(_, window)| window.strong_count() > 0);
+        windows
+            .iter()
+            .filter_map(|(_, window)| window.upgrade())
+            .collect()
+    }
+
+    pub(crate) fn get(&self, id: WindowId) -> Option<DesktopContext> {
+        self.windows
+            .borrow()
+            .iter()
+            .find(|(window_id, _)| *window_id == id)
+            .and_then(|(_, window)| window.upgrade())
+    }
+
+    pub(crate) fn get_by_label(&self, label: &str) -> Option<DesktopContext> {
+        self.windows()
+            .into_iter()
+            .find(|window| window.label() == Some(label))
+    }
+}
+
+/// Signals that are shared between every window in the app.
+///
+/// Each window runs its own [`dioxus_core::VirtualDom`], so signals created in one window are owned by
+/// that window's scopes. Shared signals are leaked instead and live for as long as the app does.
+#[derive(Default)]
+pub(crate) struct SharedSignals {
+    signals: RefCell<HashMap<String, Box<dyn Any>>>,
+}
+
+impl SharedSignals {
+    pub(crate) fn get_or_insert_with<T: Send + Sync + 'static>(
+        &self,
+        key: &str,
+        init: impl FnOnce() -> SyncSignal<T>,
+    ) -> SyncSignal<T> {
+        if let Some(signal) = self.signals.borrow().get(key) {
+            return *signal.downcast_ref::<SyncSignal<T>>().unwrap_or_else(|| {
+                panic!(
+                    "Shared signal with key {key:?} was already created with a different type than {}",
+                    std::any::type_name::<T>()
+                )
+            });
+        }
+
+        let signal = init();
+        self.signals
+            .borrow_mut()
+            .insert(key.to_string(), Box::new(signal));
+        signal
+    }
+}
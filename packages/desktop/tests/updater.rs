@@ -0,0 +1,272 @@
+//! Tests for the updater against a local HTTP server that stands in for the update server
+#![cfg(all(
+    feature = "updater",
+    any(target_os = "windows", target_os = "linux", target_os = "macos")
+))]
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use dioxus_desktop::updater::{Updater, UpdaterError};
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::{Arc, Mutex},
+};
+
+const TARGET: &str = "linux-x86_64";
+
+/// A tiny HTTP server that serves fixed responses and records the requests it receives
+struct UpdateServer {
+    url: String,
+    routes: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl UpdateServer {
+    fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let routes = Arc::new(Mutex::new(HashMap::<String, Vec<u8>>::new()));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        std::thread::spawn({
+            let routes = routes.clone();
+            let requests = requests.clone();
+            move || {
+                for mut stream in listener.incoming().flatten() {
+                    let mut reader = BufReader::new(&stream);
+                    let mut head = String::new();
+                    loop {
+                        let mut line = String::new();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                            break;
+                        }
+                        head.push_str(&line);
+                    }
+
+                    let path = head.split_whitespace().nth(1).unwrap_or("/").to_string();
+                    requests.lock().unwrap().push(head);
+
+                    let body = routes.lock().unwrap().get(&path).cloned();
+                    let mut response = match &body {
+                        Some(body) => format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            body.len()
+                        ),
+                        None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_string(),
+                    }
+                    .into_bytes();
+                    response.extend(body.unwrap_or_default());
+                    _ = stream.write_all(&response);
+                }
+            }
+        });
+
+        Self {
+            url,
+            routes,
+            requests,
+        }
+    }
+
+    fn serve(&self, path: &str, body: impl Into<Vec<u8>>) {
+        self.routes
+            .lock()
+            .unwrap()
+            .insert(path.to_string(), body.into());
+    }
+
+    fn manifest_url(&self) -> String {
+        format!("{}/latest.json", self.url)
+    }
+
+    /// Serve a manifest for `version` that points at an update with the given signature
+    fn serve_manifest(&self, version: &str, signature: &str) {
+        let manifest = serde_json::json!({
+            "version": version,
+            "notes": "Bug fixes",
+            "pub_date": "2025-01-01T00:00:00Z",
+            "platforms": {
+                TARGET: {
+                    "url": format!("{}/app.AppImage", self.url),
+                    "signature": signature,
+                }
+            }
+        });
+        self.serve("/latest.json", manifest.to_string());
+    }
+}
+
+/// A minisign key pair to sign updates with
+struct Signer {
+    public_key: String,
+    secret_key: minisign::SecretKey,
+}
+
+impl Signer {
+    fn new() -> Self {
+        let keys = minisign::KeyPair::generate_unencrypted_keypair().unwrap();
+        let public_key = keys.pk.to_box().unwrap().to_string();
+
+        Self {
+            public_key: BASE64_STANDARD.encode(public_key),
+            secret_key: keys.sk,
+        }
+    }
+
+    fn sign(&self, bytes: &[u8]) -> String {
+        let signature = minisign::sign(None, &self.secret_key, bytes, None, None).unwrap();
+        BASE64_STANDARD.encode(signature.into_string())
+    }
+
+    fn updater(&self, server: &UpdateServer, current_version: &str) -> Updater {
+        Updater::new(server.manifest_url(), current_version, &self.public_key).with_target(TARGET)
+    }
+}
+
+#[tokio::test]
+async fn newer_versions_are_updates() {
+    let server = UpdateServer::start();
+    let signer = Signer::new();
+    server.serve_manifest("v1.2.0", &signer.sign(b"update"));
+
+    let update = signer
+        .updater(&server, "1.0.0")
+        .check()
+        .await
+        .unwrap()
+        .expect("1.2.0 is newer than 1.0.0");
+    assert_eq!(update.version, semver::Version::new(1, 2, 0));
+    assert_eq!(update.notes.as_deref(), Some("Bug fixes"));
+    assert_eq!(update.url, format!("{}/app.AppImage", server.url));
+
+    // The same or an older version is not an update
+    for current in ["1.2.0", "1.10.0", "2.0.0"] {
+        let update = signer.updater(&server, current).check().await.unwrap();
+        assert!(update.is_none(), "1.2.0 is not newer than {current}");
+    }
+
+    // Prereleases sort before the release they lead up to
+    let update = signer
+        .updater(&server, "1.2.0-beta.1")
+        .check()
+        .await
+        .unwrap();
+    assert!(update.is_some());
+}
+
+#[tokio::test]
+async fn manifest_errors() {
+    let server = UpdateServer::start();
+    let signer = Signer::new();
+
+    // There is no manifest yet
+    let err = signer.updater(&server, "1.0.0").check().await.unwrap_err();
+    assert!(matches!(err, UpdaterError::Request(_)), "{err}");
+
+    server.serve("/latest.json", "not json");
+    let err = signer.updater(&server, "1.0.0").check().await.unwrap_err();
+    assert!(matches!(err, UpdaterError::InvalidManifest(_)), "{err}");
+
+    server.serve_manifest("one point two", &signer.sign(b"update"));
+    let err = signer.updater(&server, "1.0.0").check().await.unwrap_err();
+    assert!(matches!(err, UpdaterError::InvalidVersion(_)), "{err}");
+
+    server.serve_manifest("1.2.0", &signer.sign(b"update"));
+    let err = signer
+        .updater(&server, "1.0.0")
+        .with_target("windows-aarch64")
+        .check()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, UpdaterError::UnsupportedTarget(_)), "{err}");
+}
+
+#[tokio::test]
+async fn headers_are_sent_with_every_request() {
+    let server = UpdateServer::start();
+    let signer = Signer::new();
+    // Every test downloads a different version so they don't write to the same file
+    server.serve_manifest("1.1.0", &signer.sign(b"update"));
+    server.serve("/app.AppImage", "update");
+
+    let update = signer
+        .updater(&server, "1.0.0")
+        .with_header("Authorization", "Bearer secret")
+        .check()
+        .await
+        .unwrap()
+        .unwrap();
+    update.download(|_| {}).await.unwrap();
+
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(requests.len(), 2);
+    for request in requests.iter() {
+        assert!(
+            request
+                .to_ascii_lowercase()
+                .contains("authorization: bearer secret"),
+            "{request}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn downloads_are_verified() {
+    let server = UpdateServer::start();
+    let signer = Signer::new();
+    let contents: Vec<u8> = (0..=255).cycle().take(1024 * 1024).collect();
+    server.serve_manifest("1.2.0", &signer.sign(&contents));
+    server.serve("/app.AppImage", contents.clone());
+
+    let update = signer
+        .updater(&server, "1.0.0")
+        .check()
+        .await
+        .unwrap()
+        .unwrap();
+
+    let mut last_progress = None;
+    let downloaded = update
+        .download(|progress| last_progress = Some(progress))
+        .await
+        .unwrap();
+
+    let progress = last_progress.unwrap();
+    assert_eq!(progress.downloaded, contents.len() as u64);
+    assert_eq!(progress.total, Some(contents.len() as u64));
+    assert_eq!(downloaded.version(), &semver::Version::new(1, 2, 0));
+    assert_eq!(std::fs::read(downloaded.path()).unwrap(), contents);
+}
+
+#[tokio::test]
+async fn tampered_downloads_are_rejected() {
+    let server = UpdateServer::start();
+    let signer = Signer::new();
+    server.serve_manifest("1.3.0", &signer.sign(b"the real update"));
+    server.serve("/app.AppImage", "a tampered update");
+
+    let update = signer
+        .updater(&server, "1.0.0")
+        .check()
+        .await
+        .unwrap()
+        .unwrap();
+    let err = update.download(|_| {}).await.unwrap_err();
+    assert!(matches!(err, UpdaterError::InvalidSignature(_)), "{err}");
+
+    // An update signed with a different key is rejected too
+    let other_signer = Signer::new();
+    server.serve_manifest("1.4.0", &other_signer.sign(b"the real update"));
+    server.serve("/app.AppImage", "the real update");
+
+    let update = signer
+        .updater(&server, "1.0.0")
+        .check()
+        .await
+        .unwrap()
+        .unwrap();
+    let err = update.download(|_| {}).await.unwrap_err();
+    assert!(matches!(err, UpdaterError::InvalidSignature(_)), "{err}");
+}
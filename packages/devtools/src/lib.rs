@@ -4,7 +4,7 @@ use dioxus_signals::{GlobalKey, Signal, Writable};
 
 pub use dioxus_devtools_types::*;
 pub use subsecond;
-use subsecond::PatchError;
+use subsecond::{JumpTable, PatchError};
 
 /// Applies template and literal changes to the VirtualDom
 ///
@@ -43,7 +43,7 @@ pub fn try_apply_changes(dom: &VirtualDom, msg: &HotReloadMsg) -> Result<(), Pat
                 };
 
                 if msg.for_pid == our_pid {
-                    unsafe { subsecond::apply_patch(jump_table) }?;
+                    unsafe { apply_patch(jump_table) }?;
                     dioxus_core::force_all_dirty();
                     ctx.clear::<Signal<Option<HotReloadedTemplate>>>();
                 }
@@ -54,6 +54,21 @@ pub fn try_apply_changes(dom: &VirtualDom, msg: &HotReloadMsg) -> Result<(), Pat
     })
 }
 
+/// Apply a hot patch with [`subsecond::apply_patch`].
+///
+/// Hot signals on the current thread are saved with the old code first so they can be migrated if their type changes.
+///
+/// # Safety
+///
+/// The same as [`subsecond::apply_patch`].
+#[doc(hidden)]
+pub unsafe fn apply_patch(table: JumpTable) -> Result<(), PatchError> {
+    #[cfg(feature = "hot-migrate")]
+    dioxus_signals::save_hot_signals();
+
+    subsecond::apply_patch(table)
+}
+
 /// Connect to the devserver and handle its messages with a callback.
 ///
 /// This doesn't use any form of security or protocol, so it's not safe to expose to the internet.
@@ -78,7 +93,7 @@ pub fn connect_subsecond() {
         if let DevserverMsg::HotReload(hot_reload_msg) = msg {
             if let Some(jumptable) = hot_reload_msg.jump_table {
                 if hot_reload_msg.for_pid == Some(std::process::id()) {
-                    unsafe { subsecond::apply_patch(jumptable).unwrap() };
+                    unsafe { apply_patch(jumptable).unwrap() };
                 }
             }
         }
@@ -0,0 +1,60 @@
+use dioxus_core::Element;
+use dioxus_core_macro::{component, rsx};
+use dioxus_html as dioxus_elements;
+use manganis::{Asset, AssetVariant};
+
+/// Preload a font asset and register it with the `@font-face` generated from its options.
+///
+/// The preload link and the `@font-face` style are injected into the head through [`dioxus_document`], so they work
+/// with server side rendering and on every platform. Fonts created with `AssetOptions::font().with_preload(true)` are
+/// already preloaded by the CLI, so no second preload link is added for them. The family of the font defaults to its file name and can be set
+/// with `AssetOptions::font().with_family(..)`.
+///
+/// # Example
+/// ```rust, ignore
+/// # use dioxus::prelude::*;
+/// const BRAND: Asset = asset!(
+///     "/assets/brand.ttf",
+///     AssetOptions::font()
+///         .with_family("Brand")
+///         .with_latin()
+///         .with_woff2(true)
+/// );
+///
+/// fn App() -> Element {
+///     rsx! {
+///         FontFace { src: BRAND }
+///         h1 { font_family: "Brand", "Hello world" }
+///     }
+/// }
+/// ```
+#[component]
+pub fn FontFace(src: Asset) -> Element {
+    let href = src.to_string();
+    let mime = match href.rsplit('.').next() {
+        Some("woff2") => "font/woff2",
+        Some("woff") => "font/woff",
+        Some("otf") => "font/otf",
+        _ => "font/ttf",
+    };
+    let font_face = src.font_face();
+    let preloaded = matches!(
+        src.bundled().options().variant(),
+        AssetVariant::Font(font) if font.preloaded()
+    );
+
+    rsx! {
+        if !preloaded {
+            dioxus_document::Link {
+                rel: "preload",
+                href,
+                r#as: "font",
+                r#type: mime,
+                crossorigin: "anonymous",
+            }
+        }
+        if let Some(font_face) = font_face {
+            dioxus_document::Style { "{font_face}" }
+        }
+    }
+}
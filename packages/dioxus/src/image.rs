@@ -0,0 +1,75 @@
+use dioxus_core::{Attribute, Element};
+use dioxus_core_macro::{component, rsx, Props};
+use dioxus_hooks::use_signal;
+use dioxus_html as dioxus_elements;
+use dioxus_signals::Writable;
+use manganis::Asset;
+
+/// The properties for a [`ResponsiveImage`].
+#[derive(Props, Clone, PartialEq)]
+pub struct ResponsiveImageProps {
+    /// The image asset to render
+    pub src: Asset,
+
+    /// The alt text of the image
+    pub alt: String,
+
+    /// The [`sizes`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLImageElement/sizes) attribute that tells
+    /// the browser which width from the `srcset` to pick. Defaults to the full width of the viewport.
+    #[props(into)]
+    pub sizes: Option<String>,
+
+    #[props(extends = img, extends = GlobalAttributes)]
+    attributes: Vec<Attribute>,
+}
+
+/// Render an `<img>` with the `srcset` and low quality placeholder generated for an image asset.
+///
+/// The widths in the `srcset` come from `AssetOptions::image().with_sizes(..)` and the placeholder from
+/// `AssetOptions::image().with_low_quality_preview(true)`. The placeholder is shown as the background of the image
+/// until the image loads.
+///
+/// # Example
+/// ```rust, ignore
+/// # use dioxus::prelude::*;
+/// const HERO: Asset = asset!(
+///     "/assets/image.png",
+///     AssetOptions::image()
+///         .with_webp()
+///         .with_sizes([320, 640, 1280])
+///         .with_low_quality_preview(true)
+/// );
+///
+/// fn Hero() -> Element {
+///     rsx! {
+///         ResponsiveImage { src: HERO, alt: "The hero image", sizes: "(max-width: 640px) 100vw, 50vw" }
+///     }
+/// }
+/// ```
+#[component]
+pub fn ResponsiveImage(props: ResponsiveImageProps) -> Element {
+    let mut loaded = use_signal(|| false);
+    let placeholder = props.src.placeholder();
+    let style = match (&placeholder, loaded()) {
+        (Some(placeholder), false) => Some(format!(
+            "background-image: url({placeholder}); background-size: cover; background-position: center;"
+        )),
+        _ => None,
+    };
+    let srcset = props.src.srcset();
+    let sizes = srcset
+        .as_ref()
+        .map(|_| props.sizes.unwrap_or_else(|| "100vw".to_string()));
+
+    rsx! {
+        img {
+            src: props.src,
+            alt: props.alt,
+            srcset,
+            sizes,
+            style,
+            onload: move |_| loaded.set(true),
+            ..props.attributes,
+        }
+    }
+}
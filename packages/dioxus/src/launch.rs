@@ -296,6 +296,14 @@ impl LaunchBuilder {
         #[cfg(feature = "logger")]
         dioxus_logger::initialize_default();
 
+        // Swap the app for the preview gallery if the CLI is serving previews
+        #[cfg(feature = "preview")]
+        let app: fn() -> Element = {
+            // The app itself isn't rendered, only the components marked with `#[preview]`
+            let _ = app;
+            crate::preview::PreviewGallery
+        };
+
         // Set any flags if we're running under fullstack
         #[cfg(feature = "fullstack")]
         {
@@ -9,6 +9,8 @@
 //! - `html`: (default) exports `dioxus-html` as the default elements to use in rsx
 //! - `hooks`: (default) re-exports `dioxus-hooks`
 //! - `hot-reload`: (default) enables hot rsx reloading in all renderers that support it
+//! - `preview`: launches a gallery of the components marked with `#[preview]` instead of the app. `dx serve --preview` enables this for you
+//! - `hot-migrate`: keeps the value of signals created with `use_hot_signal` across hot patches that change their type
 //! - `router`: exports the [router](https://dioxuslabs.com/learn/0.6/router) and enables any router features for the current platform
 //! - `third-party-renderer`: Just disables warnings about no active platform when no renderers are enabled
 //! - `logger`: Enable the default tracing subscriber for Dioxus apps
@@ -35,10 +37,94 @@ mod launch;
 
 pub use dioxus_core as core;
 
+#[cfg(all(
+    feature = "asset",
+    feature = "html",
+    feature = "macro",
+    feature = "hooks",
+    feature = "signals"
+))]
+mod image;
+
+#[cfg(all(
+    feature = "asset",
+    feature = "html",
+    feature = "macro",
+    feature = "document"
+))]
+mod font;
+
+#[cfg(all(
+    feature = "asset",
+    feature = "html",
+    feature = "macro",
+    feature = "hooks",
+    feature = "signals"
+))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "asset",
+        feature = "html",
+        feature = "macro",
+        feature = "hooks",
+        feature = "signals"
+    )))
+)]
+pub use crate::image::*;
+
+#[cfg(all(
+    feature = "asset",
+    feature = "html",
+    feature = "macro",
+    feature = "document"
+))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(all(
+        feature = "asset",
+        feature = "html",
+        feature = "macro",
+        feature = "document"
+    )))
+)]
+pub use crate::font::*;
+
 #[cfg(feature = "launch")]
 #[cfg_attr(docsrs, doc(cfg(feature = "launch")))]
 pub use crate::launch::*;
 
+#[cfg(feature = "preview")]
+#[cfg_attr(docsrs, doc(cfg(feature = "preview")))]
+pub mod preview;
+
+/// Register a component with the preview gallery. This is emitted by `#[preview]`.
+#[cfg(feature = "preview")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_preview {
+    ($component:ident, $variant:expr, $props:expr) => {
+        $crate::preview::inventory::submit! {
+            $crate::preview::Preview::new(module_path!(), stringify!($component), $variant, || {
+                $crate::preview::PreviewState::boxed($component, $props, stringify!($component))
+            })
+        }
+    };
+}
+
+/// Without the gallery, previews are only type checked so they don't break when the gallery is enabled.
+#[cfg(not(feature = "preview"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_preview {
+    ($component:ident, $variant:expr, $props:expr) => {
+        const _: () = {
+            let _ =
+                || $crate::dioxus_core::VComponent::new($component, $props, stringify!($component));
+        };
+    };
+}
+
 #[cfg(feature = "hooks")]
 #[cfg_attr(docsrs, doc(cfg(feature = "hooks")))]
 pub use dioxus_hooks as hooks;
@@ -155,7 +241,7 @@ pub mod prelude {
     #[cfg_attr(docsrs, doc(cfg(feature = "macro")))]
     #[allow(deprecated)]
     #[doc(inline)]
-    pub use dioxus_core_macro::{component, rsx, Props};
+    pub use dioxus_core_macro::{component, preview, rsx, Props};
 
     #[cfg(feature = "launch")]
     #[cfg_attr(docsrs, doc(cfg(feature = "launch")))]
@@ -215,6 +301,44 @@ pub mod prelude {
     #[doc(inline)]
     pub use manganis::{self, *};
 
+    #[cfg(all(
+        feature = "asset",
+        feature = "html",
+        feature = "macro",
+        feature = "hooks",
+        feature = "signals"
+    ))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(
+            feature = "asset",
+            feature = "html",
+            feature = "macro",
+            feature = "hooks",
+            feature = "signals"
+        )))
+    )]
+    #[doc(inline)]
+    pub use crate::image::{ResponsiveImage, ResponsiveImageProps};
+
+    #[cfg(all(
+        feature = "asset",
+        feature = "html",
+        feature = "macro",
+        feature = "document"
+    ))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(all(
+            feature = "asset",
+            feature = "html",
+            feature = "macro",
+            feature = "document"
+        )))
+    )]
+    #[doc(inline)]
+    pub use crate::font::{FontFace, FontFaceProps};
+
     #[cfg(feature = "wasm-split")]
     #[cfg_attr(docsrs, doc(cfg(feature = "wasm-split")))]
     pub use wasm_splitter as wasm_split;
@@ -0,0 +1,504 @@
+//! The gallery that `dx serve --preview` launches instead of the app.
+//!
+//! Components are registered with the gallery by the `#[preview]` attribute. The registration only exists when the
+//! `preview` feature is enabled, which the CLI turns on when it is passed `--preview`.
+//!
+//! Every preview is rendered in a `VirtualDom` of its own, so previews don't share context or global signals with the
+//! gallery or with each other. On the web, the selected preview is rendered in an iframe that loads the app again with
+//! the selection in its route. On desktop, it is rendered in a child window. Renderers that can't host a second
+//! document (native, mobile and liveview) mount the preview in its own keyed subtree of the gallery instead.
+
+use dioxus_core::{
+    use_hook, ComponentFunction, DynamicNode, Element, ErrorBoundary, ErrorContext, Knob, KnobKind,
+    PreviewProps, Properties, VComponent,
+};
+use dioxus_core_macro::{component, rsx};
+use dioxus_hooks::use_signal;
+use dioxus_html as dioxus_elements;
+use dioxus_signals::{Readable, Signal, Writable};
+use std::rc::Rc;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// A component registered with the gallery by `#[preview]`
+pub struct Preview {
+    module: &'static str,
+    component: &'static str,
+    variant: Option<&'static str>,
+    create: fn() -> Box<dyn AnyPreview>,
+}
+
+inventory::collect!(Preview);
+
+impl Preview {
+    #[doc(hidden)]
+    pub const fn new(
+        module: &'static str,
+        component: &'static str,
+        variant: Option<&'static str>,
+        create: fn() -> Box<dyn AnyPreview>,
+    ) -> Self {
+        Self {
+            module,
+            component,
+            variant,
+            create,
+        }
+    }
+
+    /// The path of the module the component is defined in
+    pub fn module(&self) -> &'static str {
+        self.module
+    }
+
+    /// The name of the component
+    pub fn component(&self) -> &'static str {
+        self.component
+    }
+
+    /// The name of the props variant, if the component has props
+    pub fn variant(&self) -> Option<&'static str> {
+        self.variant
+    }
+
+    /// The name of the preview shown in the gallery
+    pub fn name(&self) -> String {
+        match self.variant {
+            Some(variant) => format!("{} / {variant}", self.component),
+            None => self.component.to_string(),
+        }
+    }
+
+    /// Create the preview with the props from the latest version of the code
+    fn create(&self) -> Box<dyn AnyPreview> {
+        subsecond::HotFn::current(self.create).call(())
+    }
+}
+
+/// Get every preview registered in the app, sorted by module and name
+pub fn previews() -> Vec<&'static Preview> {
+    let mut previews: Vec<_> = inventory::iter::<Preview>.into_iter().collect();
+    previews.sort_by_key(|preview| (preview.module, preview.component, preview.variant));
+    previews
+}
+
+/// The query parameter with the index of the preview a frame of the gallery renders
+const SELECTION_QUERY: &str = "dx-preview";
+
+/// The preview selected in the gallery and the values of its knobs
+#[derive(Clone, PartialEq, Debug)]
+struct Selection {
+    index: usize,
+    knobs: Vec<(String, String)>,
+}
+
+impl Selection {
+    fn new(index: usize, knobs: Vec<Knob>) -> Self {
+        let knobs = knobs
+            .into_iter()
+            .map(|knob| (knob.name.to_string(), knob.value))
+            .collect();
+        Self { index, knobs }
+    }
+
+    /// Read the selection from the route of a frame, like `/?dx-preview=2&label=Save`
+    #[cfg(any(
+        test,
+        all(not(feature = "desktop"), any(feature = "web", feature = "server"))
+    ))]
+    fn from_route(route: &str) -> Option<Self> {
+        let (_, query) = route.split_once('?')?;
+        let query = query.split('#').next().unwrap_or_default();
+
+        let mut index = None;
+        let mut knobs = Vec::new();
+        for (name, value) in form_urlencoded::parse(query.as_bytes()) {
+            match &*name {
+                SELECTION_QUERY => index = value.parse().ok(),
+                _ => knobs.push((name.into_owned(), value.into_owned())),
+            }
+        }
+
+        Some(Self {
+            index: index?,
+            knobs,
+        })
+    }
+
+    /// The route of the frame that renders the selection, relative to the current page
+    fn route(&self) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::from("?"));
+        query.append_pair(SELECTION_QUERY, &self.index.to_string());
+        for (name, value) in &self.knobs {
+            query.append_pair(name, value);
+        }
+        query.finish()
+    }
+
+    /// Create the selected preview with the values of the knobs applied to its props
+    fn create(&self) -> Option<Box<dyn AnyPreview>> {
+        let mut preview = previews().get(self.index)?.create();
+        for (name, value) in &self.knobs {
+            preview.set_knob(name, value);
+        }
+        Some(preview)
+    }
+}
+
+/// A preview with the type of its props erased
+#[doc(hidden)]
+pub trait AnyPreview {
+    /// Get the knobs of the current props
+    fn knobs(&self) -> Vec<Knob>;
+
+    /// Edit one field of the current props
+    fn set_knob(&mut self, name: &str, value: &str) -> bool;
+
+    /// Render the component with the current props
+    fn render(&self) -> Element;
+}
+
+/// The props a preview is currently rendered with
+#[doc(hidden)]
+pub struct PreviewState<P> {
+    props: P,
+    render: Box<dyn Fn(P) -> VComponent>,
+}
+
+impl<P: Properties + PreviewProps> PreviewState<P> {
+    /// Create the state for a preview of `component` with the initial props of its variant
+    pub fn boxed<M: 'static>(
+        component: impl ComponentFunction<P, M>,
+        props: P,
+        name: &'static str,
+    ) -> Box<dyn AnyPreview> {
+        // Render through a VComponent so the component picks up hot patches like any other component
+        let render = move |props| VComponent::new(component.clone(), props, name);
+        Box::new(Self {
+            props,
+            render: Box::new(render),
+        })
+    }
+}
+
+impl<P: Properties + PreviewProps> AnyPreview for PreviewState<P> {
+    fn knobs(&self) -> Vec<Knob> {
+        self.props.knobs()
+    }
+
+    fn set_knob(&mut self, name: &str, value: &str) -> bool {
+        self.props.set_knob(name, value)
+    }
+
+    fn render(&self) -> Element {
+        let component = (self.render)(self.props.clone());
+        rsx! {
+            {DynamicNode::Component(component)}
+        }
+    }
+}
+
+const GALLERY_STYLE: &str =
+    "display: flex; height: 100vh; margin: 0; font-family: sans-serif; font-size: 14px;";
+const SIDEBAR_STYLE: &str = "display: flex; flex-direction: column; width: 220px; padding: 8px; overflow-y: auto; border-right: 1px solid #ddd; background: #fafafa;";
+const ENTRY_STYLE: &str = "padding: 6px 8px; border: none; border-radius: 4px; text-align: left; cursor: pointer; background: transparent;";
+const SELECTED_ENTRY_STYLE: &str = "padding: 6px 8px; border: none; border-radius: 4px; text-align: left; cursor: pointer; background: #e0e7ff;";
+const CANVAS_STYLE: &str = "flex: 1; padding: 16px; overflow: auto;";
+const KNOBS_STYLE: &str = "display: flex; flex-direction: column; gap: 8px; width: 220px; padding: 8px; overflow-y: auto; border-left: 1px solid #ddd; background: #fafafa;";
+const KNOB_STYLE: &str = "display: flex; flex-direction: column; gap: 4px;";
+const FRAME_STYLE: &str = "width: 100%; height: 100%; border: none;";
+
+/// The root component of the app when the `preview` feature is enabled.
+///
+/// The sidebar lists every preview in the app and the selected preview is rendered next to it in a `VirtualDom` of its
+/// own. If the props of the preview have simple fields, a panel of knobs lets you edit them. Selecting another preview
+/// or editing a knob renders the preview again with fresh state.
+#[allow(non_snake_case)]
+pub fn PreviewGallery() -> Element {
+    // The frames of the gallery load the app again with the preview to render in their route
+    #[cfg(all(not(feature = "desktop"), any(feature = "web", feature = "server")))]
+    if let Some(selection) =
+        use_hook(|| Selection::from_route(&dioxus_history::history().current_route()))
+    {
+        return rsx! {
+            PreviewFrame { selection }
+        };
+    }
+
+    rsx! {
+        Gallery {}
+    }
+}
+
+/// The sidebar, canvas and knobs of the gallery
+#[component]
+fn Gallery() -> Element {
+    let previews = use_hook(previews);
+    let mut selected = use_signal(|| 0);
+    let mut preview = use_signal(|| previews.first().map(|preview| preview.create()));
+
+    if previews.is_empty() {
+        return rsx! {
+            div { style: CANVAS_STYLE,
+                "No previews found. Add #[preview] to a component to show it here."
+            }
+        };
+    }
+
+    let current = selected();
+    let knobs = preview
+        .read()
+        .as_ref()
+        .map(|preview| preview.knobs())
+        .unwrap_or_default();
+    let selection = Selection::new(current, knobs.clone());
+    let route = selection.route();
+
+    rsx! {
+        div { style: GALLERY_STYLE,
+            nav { style: SIDEBAR_STYLE,
+                for (index, entry) in previews.iter().copied().enumerate() {
+                    button {
+                        key: "{index}",
+                        style: if index == current { SELECTED_ENTRY_STYLE } else { ENTRY_STYLE },
+                        title: entry.module(),
+                        onclick: move |_| {
+                            selected.set(index);
+                            preview.set(Some(entry.create()));
+                        },
+                        "{entry.name()}"
+                    }
+                }
+            }
+            main { style: CANVAS_STYLE,
+                // The key changes with the selection and the knobs so the preview is rendered again with fresh state
+                for _ in previews.get(current) {
+                    PreviewCanvas { key: "{route}", selection: selection.clone() }
+                }
+            }
+            if !knobs.is_empty() {
+                aside { style: KNOBS_STYLE,
+                    for knob in knobs {
+                        KnobInput { key: "{knob.name}", knob: knob.clone(), preview }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Render the selected preview in a `VirtualDom` of its own, or in a subtree of the gallery if the renderer can't host
+/// a second document
+#[allow(clippy::needless_return)]
+#[component]
+fn PreviewCanvas(selection: Selection) -> Element {
+    #[cfg(feature = "desktop")]
+    {
+        let title = previews()[selection.index].name();
+        return rsx! {
+            dioxus_desktop::Window {
+                title,
+                app: move || rsx! {
+                    PreviewFrame { selection: selection.clone() }
+                },
+            }
+        };
+    }
+
+    #[cfg(all(not(feature = "desktop"), any(feature = "web", feature = "server")))]
+    return rsx! {
+        iframe { style: FRAME_STYLE, src: selection.route() }
+    };
+
+    #[cfg(not(any(feature = "desktop", feature = "web", feature = "server")))]
+    rsx! {
+        PreviewFrame { selection }
+    }
+}
+
+/// The root of the `VirtualDom` a preview is rendered in
+#[component]
+fn PreviewFrame(selection: Selection) -> Element {
+    let preview = use_hook(|| Rc::new(selection.create()));
+
+    rsx! {
+        ErrorBoundary {
+            handle_error: |errors: ErrorContext| rsx! {
+                pre { "{errors:#?}" }
+            },
+            {(*preview).as_ref().map(|preview| preview.render())}
+        }
+    }
+}
+
+/// An input that edits one field of the props of the selected preview
+#[component]
+fn KnobInput(knob: Knob, preview: Signal<Option<Box<dyn AnyPreview>>>) -> Element {
+    let name = knob.name;
+    let mut set = move |value: String| {
+        if let Some(preview) = &mut *preview.write() {
+            preview.set_knob(name, &value);
+        }
+    };
+
+    let input = match knob.kind {
+        KnobKind::Bool => rsx! {
+            input {
+                r#type: "checkbox",
+                checked: knob.value == "true",
+                onchange: move |evt| set(evt.checked().to_string()),
+            }
+        },
+        KnobKind::Number => rsx! {
+            input {
+                r#type: "number",
+                value: knob.value,
+                onchange: move |evt| set(evt.value()),
+            }
+        },
+        KnobKind::Text => rsx! {
+            input {
+                r#type: "text",
+                value: knob.value,
+                onchange: move |evt| set(evt.value()),
+            }
+        },
+    };
+
+    rsx! {
+        label { style: KNOB_STYLE,
+            "{name}"
+            {input}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core_macro::Props;
+
+    #[derive(Props, Clone, PartialEq)]
+    struct ButtonProps {
+        label: String,
+        count: u32,
+        disabled: bool,
+    }
+
+    #[allow(non_snake_case)]
+    fn Button(props: ButtonProps) -> Element {
+        rsx! { "{props.label}" }
+    }
+
+    fn button() -> Box<dyn AnyPreview> {
+        let props = ButtonProps {
+            label: "Save".to_string(),
+            count: 1,
+            disabled: false,
+        };
+        PreviewState::boxed(Button, props, "Button")
+    }
+
+    inventory::submit! { Preview::new("app::forms", "Button", Some("Secondary"), button) }
+    inventory::submit! { Preview::new("app::forms", "Button", Some("Primary"), button) }
+    inventory::submit! { Preview::new("app::forms", "Button", None, button) }
+    inventory::submit! { Preview::new("app::alerts", "Toast", None, button) }
+
+    #[test]
+    fn previews_are_sorted_by_module_and_name() {
+        let previews: Vec<_> = previews()
+            .iter()
+            .map(|preview| format!("{} {}", preview.module(), preview.name()))
+            .collect();
+        assert_eq!(
+            previews,
+            [
+                "app::alerts Toast",
+                "app::forms Button",
+                "app::forms Button / Primary",
+                "app::forms Button / Secondary",
+            ]
+        );
+    }
+
+    #[test]
+    fn knobs_round_trip_through_any_preview() {
+        let mut preview = button();
+        assert_eq!(
+            preview.knobs(),
+            [
+                Knob {
+                    name: "label",
+                    kind: KnobKind::Text,
+                    value: "Save".to_string(),
+                },
+                Knob {
+                    name: "count",
+                    kind: KnobKind::Number,
+                    value: "1".to_string(),
+                },
+                Knob {
+                    name: "disabled",
+                    kind: KnobKind::Bool,
+                    value: "false".to_string(),
+                },
+            ]
+        );
+
+        assert!(preview.set_knob("label", "Delete"));
+        assert!(preview.set_knob("count", "3"));
+        assert!(preview.set_knob("disabled", "true"));
+        assert!(!preview.set_knob("count", "many"));
+        assert!(!preview.set_knob("missing", "1"));
+
+        let values: Vec<_> = preview.knobs().into_iter().map(|knob| knob.value).collect();
+        assert_eq!(values, ["Delete", "3", "true"]);
+    }
+
+    #[test]
+    fn selections_round_trip_through_the_route() {
+        let selection = Selection::new(2, button().knobs());
+        let route = selection.route();
+        assert_eq!(route, "?dx-preview=2&label=Save&count=1&disabled=false");
+        assert_eq!(Selection::from_route(&format!("/{route}")), Some(selection));
+
+        let selection = Selection {
+            index: 0,
+            knobs: vec![("label".to_string(), "Save & close?".to_string())],
+        };
+        let route = format!("/docs{}#top", selection.route());
+        assert_eq!(Selection::from_route(&route), Some(selection));
+
+        assert_eq!(Selection::from_route("/"), None);
+        assert_eq!(Selection::from_route("/?label=Save"), None);
+    }
+
+    #[test]
+    fn selections_apply_their_knobs() {
+        let previews = previews();
+        let index = previews
+            .iter()
+            .position(|preview| preview.name() == "Button / Primary")
+            .unwrap();
+        let selection = Selection {
+            index,
+            knobs: vec![
+                ("label".to_string(), "Delete".to_string()),
+                ("count".to_string(), "many".to_string()),
+            ],
+        };
+
+        let values: Vec<_> = selection
+            .create()
+            .unwrap()
+            .knobs()
+            .into_iter()
+            .map(|knob| knob.value)
+            .collect();
+        assert_eq!(values, ["Delete", "1", "false"]);
+        assert!(Selection::new(previews.len(), Vec::new())
+            .create()
+            .is_none());
+    }
+}
@@ -44,6 +44,12 @@ pub enum StructuredOutput {
     HtmlTranslate {
         html: String,
     },
+    Translate {
+        output: String,
+    },
+    TestOutput {
+        suites: Vec<TestSuiteResult>,
+    },
     Success,
     Error {
         message: String,
@@ -56,6 +62,25 @@ impl std::fmt::Debug for StructuredOutput {
     }
 }
 
+/// The results of running one test binary with `dx test`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestSuiteResult {
+    /// The name of the test target, e.g. the crate name for unit tests or the file name for integration tests
+    pub name: String,
+    /// The bundle format the tests were built for, e.g. `web`, `desktop` or `server`
+    pub platform: String,
+    /// The test binary that was run
+    pub binary: PathBuf,
+    /// The number of tests that passed
+    pub passed: usize,
+    /// The names of the tests that failed
+    pub failed: Vec<String>,
+    /// The number of tests that were ignored
+    pub ignored: usize,
+    /// Whether the test binary exited successfully
+    pub success: bool,
+}
+
 /// The current stage of the ongoing build
 ///
 /// This is a perma-unstable interface that is subject to change at any time.
@@ -1,10 +1,24 @@
 //! This file exports functions into the vscode extension
 
-use dioxus_autofmt::{FormattedBlock, IndentOptions, IndentType};
+use dioxus_autofmt::{FmtConfig, FormattedBlock, IndentOptions, IndentType};
 use wasm_bindgen::prelude::*;
 
+/// Read the contents of either a `dxfmt.toml` or a `Dioxus.toml` with a `[fmt]` section
+///
+/// Invalid or empty configs fall back to the default config so formatting still works while the file is being edited
+fn fmt_config(config: &str) -> FmtConfig {
+    let Ok(mut table) = config.parse::<toml::Table>() else {
+        return FmtConfig::default();
+    };
+    let table = match table.remove("fmt") {
+        Some(toml::Value::Table(fmt)) => fmt,
+        _ => table,
+    };
+    table.try_into().unwrap_or_default()
+}
+
 #[wasm_bindgen]
-pub fn format_rsx(raw: String, use_tabs: bool, indent_size: usize) -> String {
+pub fn format_rsx(raw: String, use_tabs: bool, indent_size: usize, config: String) -> String {
     let block = dioxus_autofmt::fmt_block(
         &raw,
         0,
@@ -16,7 +30,8 @@ pub fn format_rsx(raw: String, use_tabs: bool, indent_size: usize) -> String {
             },
             indent_size,
             false,
-        ),
+        )
+        .with_config(fmt_config(&config)),
     );
     block.unwrap()
 }
@@ -27,6 +42,7 @@ pub fn format_selection(
     use_tabs: bool,
     indent_size: usize,
     base_indent: usize,
+    config: String,
 ) -> String {
     let block = dioxus_autofmt::fmt_block(
         &raw,
@@ -39,7 +55,8 @@ pub fn format_selection(
             },
             indent_size,
             false,
-        ),
+        )
+        .with_config(fmt_config(&config)),
     );
     block.unwrap()
 }
@@ -64,7 +81,12 @@ impl FormatBlockInstance {
 }
 
 #[wasm_bindgen]
-pub fn format_file(contents: String, use_tabs: bool, indent_size: usize) -> FormatBlockInstance {
+pub fn format_file(
+    contents: String,
+    use_tabs: bool,
+    indent_size: usize,
+    config: String,
+) -> FormatBlockInstance {
     // todo: use rustfmt for this instead
     let options = IndentOptions::new(
         if use_tabs {
@@ -74,7 +96,8 @@ pub fn format_file(contents: String, use_tabs: bool, indent_size: usize) -> Form
         },
         indent_size,
         false,
-    );
+    )
+    .with_config(fmt_config(&config));
 
     let Ok(Ok(_edits)) = syn::parse_file(&contents)
         .map(|file| dioxus_autofmt::try_fmt_file(&contents, &file, options))
@@ -25,6 +25,40 @@ export async function activate(context: vscode.ExtensionContext) {
 
 	context.subscriptions.push(vscode.window.registerUriHandler(new UriLaunchServer()));
 
+	// Keep the `[fmt]` configs in sync with the workspace so formatting doesn't need to wait on the file system
+	const watcher = vscode.workspace.createFileSystemWatcher(FMT_CONFIG_GLOB);
+	watcher.onDidCreate(loadFmtConfig);
+	watcher.onDidChange(loadFmtConfig);
+	watcher.onDidDelete(uri => fmtConfigs.delete(uri.toString()));
+	context.subscriptions.push(watcher);
+
+	const configFiles = await vscode.workspace.findFiles(FMT_CONFIG_GLOB, '**/target/**');
+	await Promise.all(configFiles.map(loadFmtConfig));
+}
+
+const FMT_CONFIG_GLOB = '**/{dxfmt.toml,Dioxus.toml}';
+
+// The contents of every dxfmt.toml and Dioxus.toml in the workspace, keyed by their uri
+const fmtConfigs = new Map<string, string>();
+
+async function loadFmtConfig(uri: vscode.Uri) {
+	const contents = await vscode.workspace.fs.readFile(uri);
+	fmtConfigs.set(uri.toString(), new TextDecoder().decode(contents));
+}
+
+// Find the config of the closest dxfmt.toml or Dioxus.toml above the document, preferring dxfmt.toml
+function fmtConfigFor(document: vscode.TextDocument): string {
+	let dir = vscode.Uri.joinPath(document.uri, '..');
+	while (true) {
+		for (const name of ['dxfmt.toml', 'Dioxus.toml']) {
+			const config = fmtConfigs.get(vscode.Uri.joinPath(dir, name).toString());
+			if (config !== undefined) return config;
+		}
+
+		const parent = vscode.Uri.joinPath(dir, '..');
+		if (parent.path === dir.path) return '';
+		dir = parent;
+	}
 }
 
 function translate(component: boolean) {
@@ -117,7 +151,7 @@ function fmtSelection() {
 	let base_indentation = (lines_above.match(/{/g) || []).length - (lines_above.match(/}/g) || []).length - 1;
 
 	try {
-		let formatted = dioxus.format_selection(unformatted, !editor.options.insertSpaces, tabSize, base_indentation);
+		let formatted = dioxus.format_selection(unformatted, !editor.options.insertSpaces, tabSize, base_indentation, fmtConfigFor(editor.document));
 		for (let i = 0; i <= base_indentation; i++) {
 			formatted = (editor.options.insertSpaces ? " ".repeat(tabSize) : "\t") + formatted;
 		}
@@ -160,7 +194,7 @@ function fmtDocument(document: vscode.TextDocument) {
 		} else {
 			tabSize = 4;
 		}
-		const formatted = dioxus.format_file(contents, !editor.options.insertSpaces, tabSize);
+		const formatted = dioxus.format_file(contents, !editor.options.insertSpaces, tabSize, fmtConfigFor(document));
 
 		// Replace the entire text document
 		// Yes, this is a bit heavy handed, but the dioxus side doesn't know the line/col scheme that vscode is using
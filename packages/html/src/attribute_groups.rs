@@ -94,6 +94,7 @@ macro_rules! mod_methods {
         $mod:ident;
         $fn:ident;
         $fn_html_to_rsx:ident;
+        $definitions:ident;
         $(
             $(#[$attr:meta])*
             $name:ident $(: $(no-$alias:ident)? $js_name:literal)? $(in $ns:literal)?;
@@ -134,6 +135,16 @@ macro_rules! mod_methods {
             None
         }
 
+        #[cfg(feature = "definitions")]
+        pub(crate) const $definitions: &[crate::definitions::AttributeDefinition] = &[
+            $(
+                crate::definitions::AttributeDefinition {
+                    name: stringify!($name),
+                    raw_docs: stringify!($($attr)*),
+                },
+            )+
+        ];
+
         impl_extension_attributes![$mod { $($name,)* }];
     };
 
@@ -215,6 +226,7 @@ mod_methods! {
     global_attributes;
     map_global_attributes;
     map_html_global_attributes_to_rsx;
+    GLOBAL_ATTRIBUTE_DEFINITIONS;
 
     #[deprecated(note = "This attribute does nothing. For most renderers, you should prefer calling [`dioxus_core::Event::prevent_default`] on the event instead. For liveview, you can use `\"onclick\": (evt) => evt.prevent_default()` to prevent the default action for this element.")]
     /// This attribute has been deprecated in favor of [`dioxus_core::Event::prevent_default`]
@@ -1745,6 +1757,7 @@ mod_methods! {
     svg_attributes;
     map_svg_attributes;
     map_html_svg_attributes_to_rsx;
+    SVG_ATTRIBUTE_DEFINITIONS;
 
     /// Prevent the default action for this element. This attribute is only recommended in the LiveView renderer
     /// which does not support the prevent default method on events.
@@ -0,0 +1,253 @@
+//! Descriptions of every element, attribute and event in this crate that tools like language servers can read at
+//! runtime.
+//!
+//! The docs are collected from the doc comments of the definitions in this crate.
+
+use crate::events::*;
+
+/// An element that can be used in rsx
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElementDefinition {
+    /// The name of the element in rsx
+    pub name: &'static str,
+    /// The namespace of the element, if it is not an html element
+    pub namespace: Option<&'static str>,
+    /// The attributes specific to this element
+    pub attributes: &'static [AttributeDefinition],
+    pub(crate) raw_docs: &'static str,
+}
+
+impl ElementDefinition {
+    /// The documentation for the element as markdown
+    pub fn docs(&self) -> String {
+        docs_from_attributes(self.raw_docs)
+    }
+
+    /// The attributes that can be used on this element along with any element specific attributes
+    pub fn shared_attributes(&self) -> &'static [AttributeDefinition] {
+        match self.namespace {
+            Some(_) => svg_attributes(),
+            None => global_attributes(),
+        }
+    }
+
+    /// Find an attribute that can be used on this element by name
+    pub fn attribute(&self, name: &str) -> Option<&'static AttributeDefinition> {
+        self.attributes
+            .iter()
+            .chain(self.shared_attributes())
+            .find(|attribute| attribute.name == name)
+    }
+}
+
+/// An attribute that can be used on an element in rsx
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttributeDefinition {
+    /// The name of the attribute in rsx
+    pub name: &'static str,
+    pub(crate) raw_docs: &'static str,
+}
+
+impl AttributeDefinition {
+    /// The documentation for the attribute as markdown
+    pub fn docs(&self) -> String {
+        docs_from_attributes(self.raw_docs)
+    }
+}
+
+/// An event handler that can be used on an element in rsx
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventDefinition {
+    /// The name of the event handler in rsx, e.g. `onclick`
+    pub name: &'static str,
+    /// The type of the data the event handler receives, e.g. `MouseData`
+    pub data: &'static str,
+    pub(crate) raw_docs: &'static str,
+}
+
+impl EventDefinition {
+    /// The documentation for the event as markdown
+    pub fn docs(&self) -> String {
+        docs_from_attributes(self.raw_docs)
+    }
+}
+
+/// Implemented for the data of each group of events
+pub(crate) trait EventGroup {
+    const EVENTS: &'static [EventDefinition];
+}
+
+/// Every element that can be used in rsx
+pub fn elements() -> &'static [ElementDefinition] {
+    crate::elements::ELEMENT_DEFINITIONS
+}
+
+/// Find an element by its name in rsx
+pub fn element(name: &str) -> Option<&'static ElementDefinition> {
+    elements().iter().find(|element| element.name == name)
+}
+
+/// The attributes every html element accepts
+pub fn global_attributes() -> &'static [AttributeDefinition] {
+    crate::attribute_groups::GLOBAL_ATTRIBUTE_DEFINITIONS
+}
+
+/// The attributes every svg and mathml element accepts
+pub fn svg_attributes() -> &'static [AttributeDefinition] {
+    crate::attribute_groups::SVG_ATTRIBUTE_DEFINITIONS
+}
+
+/// Every event handler that can be used in rsx
+pub fn events() -> impl Iterator<Item = &'static EventDefinition> {
+    [
+        AnimationData::EVENTS,
+        ClipboardData::EVENTS,
+        CompositionData::EVENTS,
+        DragData::EVENTS,
+        FocusData::EVENTS,
+        FormData::EVENTS,
+        ImageData::EVENTS,
+        KeyboardData::EVENTS,
+        MediaData::EVENTS,
+        MountedData::EVENTS,
+        MouseData::EVENTS,
+        PointerData::EVENTS,
+        ResizeData::EVENTS,
+        ScrollData::EVENTS,
+        SelectionData::EVENTS,
+        ToggleData::EVENTS,
+        TouchData::EVENTS,
+        TransitionData::EVENTS,
+        VisibleData::EVENTS,
+        WheelData::EVENTS,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Find an event handler by its name in rsx
+pub fn event(name: &str) -> Option<&'static EventDefinition> {
+    events().find(|event| event.name == name)
+}
+
+/// Collect the docs from the stringified attributes of a definition, e.g. `doc = r" Build a" doc = r" div"`.
+///
+/// Doc comments are passed to macros as either normal or raw string literals, so both need to be handled. Any other
+/// attributes like `deprecated(note = "...")` are skipped.
+fn docs_from_attributes(attributes: &str) -> String {
+    let mut lines = vec![];
+    let mut rest = attributes;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after_doc) = rest.strip_prefix("doc") {
+            let after_eq = after_doc
+                .trim_start()
+                .strip_prefix('=')
+                .map(str::trim_start);
+            if let Some((line, after)) = after_eq.and_then(parse_string_literal) {
+                lines.push(line);
+                rest = after;
+                continue;
+            }
+        }
+        if c == '"' || c == 'r' {
+            if let Some((_, after)) = parse_string_literal(rest) {
+                rest = after;
+                continue;
+            }
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+
+    // Doc comments keep the space after the `///`
+    lines
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse a string literal at the start of the input, returning its value and the rest of the input
+fn parse_string_literal(input: &str) -> Option<(String, &str)> {
+    if let Some(raw) = input.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let body = raw[hashes..].strip_prefix('"')?;
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        let end = body.find(&terminator)?;
+        return Some((body[..end].to_string(), &body[end + terminator.len()..]));
+    }
+
+    let body = input.strip_prefix('"')?;
+    let mut value = String::new();
+    let mut chars = body.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, &body[index + 1..])),
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                '0' => value.push('\0'),
+                'x' => {
+                    let code = u8::from_str_radix(body.get(index + 2..index + 4)?, 16).ok()?;
+                    value.push(char::from(code));
+                    chars.nth(1)?;
+                }
+                'u' => {
+                    let rest = &body[index + 2..];
+                    let end = rest.find('}')?;
+                    let code = u32::from_str_radix(rest.get(1..end)?, 16).ok()?;
+                    value.push(char::from_u32(code)?);
+                    while chars.next()?.1 != '}' {}
+                }
+                escaped => value.push(escaped),
+            },
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_literals() {
+        assert_eq!(
+            parse_string_literal(r#""a \"quoted\" word" rest"#),
+            Some((r#"a "quoted" word"#.to_string(), " rest"))
+        );
+        assert_eq!(
+            parse_string_literal(r#""tab\tnewline\n\x41\u{1F600}\\""#),
+            Some(("tab\tnewline\nA😀\\".to_string(), ""))
+        );
+        assert_eq!(
+            parse_string_literal(r#"r" raw \n""#),
+            Some((r" raw \n".to_string(), ""))
+        );
+        assert_eq!(
+            parse_string_literal(r###"r#"with "quotes""# after"###),
+            Some((r#"with "quotes""#.to_string(), " after"))
+        );
+        assert_eq!(parse_string_literal(r#""unterminated"#), None);
+        assert_eq!(parse_string_literal("not a string"), None);
+    }
+
+    #[test]
+    fn docs_are_collected_from_doc_attributes() {
+        let attributes = r##"doc = r" Build a" doc = " `div`\n  element" deprecated(note = "doc = \"not docs\"") doc=r#" with a link"#"##;
+        assert_eq!(
+            docs_from_attributes(attributes),
+            "Build a\n`div`\n  element\nwith a link"
+        );
+        assert_eq!(docs_from_attributes("inline"), "");
+    }
+
+    #[test]
+    fn definitions_have_docs() {
+        let div = element("div").unwrap();
+        assert!(!div.docs().is_empty());
+        assert!(div.attribute("class").is_some());
+        assert_eq!(event("onclick").unwrap().data, "MouseData");
+    }
+}
@@ -482,6 +482,19 @@ macro_rules! impl_map_global_attributes {
     };
 }
 
+#[cfg(feature = "definitions")]
+macro_rules! impl_definition_namespace {
+    (None) => {
+        None
+    };
+    ($namespace:literal) => {
+        Some($namespace)
+    };
+    ([$_:literal, $namespace:tt]) => {
+        Some($namespace)
+    };
+}
+
 macro_rules! builder_constructors {
     (
         $(
@@ -558,6 +571,25 @@ macro_rules! builder_constructors {
             None
         }
 
+        #[cfg(feature = "definitions")]
+        pub(crate) const ELEMENT_DEFINITIONS: &[crate::definitions::ElementDefinition] = &[
+            $(
+                crate::definitions::ElementDefinition {
+                    name: stringify!($name),
+                    namespace: impl_definition_namespace!($namespace),
+                    attributes: &[
+                        $(
+                            crate::definitions::AttributeDefinition {
+                                name: stringify!($fil),
+                                raw_docs: stringify!($($attr_method)*),
+                            },
+                        )*
+                    ],
+                    raw_docs: stringify!($($attr)*),
+                },
+            )*
+        ];
+
         $(
             impl_element!(
                 $(#[$attr])*
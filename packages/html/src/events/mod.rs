@@ -11,6 +11,19 @@ macro_rules! impl_event {
             $name:ident $(: $js_name:literal)?
         )*
     ) => {
+        #[cfg(feature = "definitions")]
+        impl crate::definitions::EventGroup for $data {
+            const EVENTS: &'static [crate::definitions::EventDefinition] = &[
+                $(
+                    crate::definitions::EventDefinition {
+                        name: stringify!($name),
+                        data: stringify!($data),
+                        raw_docs: stringify!($($attr)*),
+                    },
+                )*
+            ];
+        }
+
         $(
             $( #[$attr] )*
             /// <details open>
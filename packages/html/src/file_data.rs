@@ -1,3 +1,10 @@
+use futures_util::{ready, AsyncRead, Stream};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
 pub trait HasFileData: std::any::Any {
     fn files(&self) -> Option<std::sync::Arc<dyn FileEngine>> {
         None
@@ -27,6 +34,13 @@ impl FileEngine for SerializedFileEngine {
         self.files.get(file).cloned()
     }
 
+    async fn read_file_chunk(&self, file: &str, offset: u64, len: usize) -> Option<Vec<u8>> {
+        let file = self.files.get(file)?;
+        let start = (offset as usize).min(file.len());
+        let end = start.saturating_add(len).min(file.len());
+        Some(file[start..end].to_vec())
+    }
+
     async fn read_file_to_string(&self, file: &str) -> Option<String> {
         self.read_file(file)
             .await
@@ -54,6 +68,146 @@ pub trait FileEngine {
     // read a file to string
     async fn read_file_to_string(&self, file: &str) -> Option<String>;
 
+    // read at most `len` bytes of a file, starting at `offset`. An empty chunk means the end of the file was reached.
+    //
+    // The default implementation reads the whole file into memory, so engines that can seek should override it.
+    async fn read_file_chunk(&self, file: &str, offset: u64, len: usize) -> Option<Vec<u8>> {
+        let bytes = self.read_file(file).await?;
+        let start = (offset as usize).min(bytes.len());
+        let end = start.saturating_add(len).min(bytes.len());
+        Some(bytes[start..end].to_vec())
+    }
+
     // returns a file in platform's native representation
     async fn get_native_file(&self, file: &str) -> Option<Box<dyn std::any::Any>>;
 }
+
+impl dyn FileEngine {
+    /// Read a file in chunks of at most `chunk_size` bytes without loading the whole file into memory.
+    ///
+    /// The chunks can be read one at a time with [`FileChunks::next_chunk`], as a [`Stream`] of chunks or through
+    /// [`AsyncRead`] to pass the file to anything that reads from an async reader.
+    ///
+    /// ```rust, ignore
+    /// let mut chunks = files.read_file_chunked(&name, 64 * 1024);
+    /// while let Some(chunk) = chunks.next_chunk().await {
+    ///     process(&chunk);
+    /// }
+    /// ```
+    pub fn read_file_chunked<'a>(&'a self, file: &'a str, chunk_size: usize) -> FileChunks<'a> {
+        FileChunks {
+            engine: self,
+            file,
+            chunk_size: chunk_size.max(1),
+            offset: 0,
+            pending: None,
+            buffer: Vec::new(),
+            buffer_offset: 0,
+            done: false,
+        }
+    }
+}
+
+type ChunkFuture<'a> = Pin<Box<dyn Future<Output = Option<Vec<u8>>> + 'a>>;
+
+/// A reader that pulls a file out of a [`FileEngine`] one chunk at a time. Created with [`FileEngine::read_file_chunked`](dyn FileEngine::read_file_chunked).
+///
+/// The reader implements both [`Stream`], which yields the chunks as they are read, and [`AsyncRead`]. A file that fails
+/// to read ends the stream early and returns an error from [`AsyncRead`].
+pub struct FileChunks<'a> {
+    engine: &'a dyn FileEngine,
+    file: &'a str,
+    chunk_size: usize,
+    offset: u64,
+    pending: Option<ChunkFuture<'a>>,
+    // The part of the last chunk that hasn't been read through AsyncRead yet
+    buffer: Vec<u8>,
+    buffer_offset: usize,
+    done: bool,
+}
+
+impl FileChunks<'_> {
+    /// Read the next chunk of the file. Returns `None` once the whole file has been read or if the file could not be read.
+    pub async fn next_chunk(&mut self) -> Option<Vec<u8>> {
+        std::future::poll_fn(|cx| self.poll_chunk(cx))
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// The number of bytes that have been read from the engine so far
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Poll the next chunk of the file. Resolves to `Ok(None)` at the end of the file and to an error if the file
+    /// could not be read.
+    fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<Option<Vec<u8>>>> {
+        if self.done {
+            return Poll::Ready(Ok(None));
+        }
+
+        let (engine, file) = (self.engine, self.file);
+        let (offset, chunk_size) = (self.offset, self.chunk_size);
+        let pending = self
+            .pending
+            .get_or_insert_with(|| engine.read_file_chunk(file, offset, chunk_size));
+        let chunk = ready!(pending.as_mut().poll(cx));
+        self.pending = None;
+
+        match chunk {
+            Some(chunk) if !chunk.is_empty() => {
+                self.offset += chunk.len() as u64;
+                Poll::Ready(Ok(Some(chunk)))
+            }
+            Some(_) => {
+                self.done = true;
+                Poll::Ready(Ok(None))
+            }
+            None => {
+                self.done = true;
+                Poll::Ready(Err(std::io::Error::other(format!(
+                    "Failed to read {}",
+                    self.file
+                ))))
+            }
+        }
+    }
+}
+
+impl Stream for FileChunks<'_> {
+    type Item = Vec<u8>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut()
+            .poll_chunk(cx)
+            .map(|chunk| chunk.ok().flatten())
+    }
+}
+
+impl AsyncRead for FileChunks<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+
+        if this.buffer_offset >= this.buffer.len() {
+            match ready!(this.poll_chunk(cx))? {
+                Some(chunk) => {
+                    this.buffer = chunk;
+                    this.buffer_offset = 0;
+                }
+                None => return Poll::Ready(Ok(0)),
+            }
+        }
+
+        let remaining = &this.buffer[this.buffer_offset..];
+        let len = remaining.len().min(buf.len());
+        buf[..len].copy_from_slice(&remaining[..len]);
+        this.buffer_offset += len;
+
+        Poll::Ready(Ok(len))
+    }
+}
@@ -16,6 +16,8 @@
 //!
 //! Currently, we don't validate for structures, but do validate attributes.
 
+#[cfg(feature = "definitions")]
+pub mod definitions;
 pub mod elements;
 #[cfg(feature = "hot-reload-context")]
 pub use elements::HtmlCtx;
@@ -0,0 +1,109 @@
+#![cfg(feature = "serialize")]
+
+use dioxus_html::{FileEngine, SerializedFileEngine};
+use futures_util::{AsyncReadExt, StreamExt};
+use std::collections::HashMap;
+
+fn engine() -> SerializedFileEngine {
+    let log: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+    SerializedFileEngine {
+        files: HashMap::from([("log.txt".to_string(), log)]),
+    }
+}
+
+#[tokio::test]
+async fn next_chunk_reads_every_chunk() {
+    let engine = engine();
+    let expected = engine.files["log.txt"].clone();
+    let files: &dyn FileEngine = &engine;
+
+    let mut chunks = files.read_file_chunked("log.txt", 4096);
+    let mut sizes = Vec::new();
+    let mut contents = Vec::new();
+    while let Some(chunk) = chunks.next_chunk().await {
+        sizes.push(chunk.len());
+        contents.extend(chunk);
+    }
+
+    assert_eq!(sizes, [4096, 4096, 1808]);
+    assert_eq!(contents, expected);
+    assert_eq!(chunks.offset(), 10_000);
+    assert_eq!(chunks.next_chunk().await, None);
+}
+
+#[tokio::test]
+async fn stream_yields_chunks() {
+    let engine = engine();
+    let expected = engine.files["log.txt"].clone();
+    let files: &dyn FileEngine = &engine;
+
+    let chunks: Vec<Vec<u8>> = files.read_file_chunked("log.txt", 3000).collect().await;
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(chunks.concat(), expected);
+}
+
+#[tokio::test]
+async fn async_read_reads_across_chunks() {
+    let engine = engine();
+    let expected = engine.files["log.txt"].clone();
+    let files: &dyn FileEngine = &engine;
+
+    // Read with a buffer that doesn't line up with the chunks
+    let mut reader = files.read_file_chunked("log.txt", 1024);
+    let mut contents = Vec::new();
+    let mut buf = [0; 700];
+    loop {
+        let read = reader.read(&mut buf).await.unwrap();
+        if read == 0 {
+            break;
+        }
+        contents.extend_from_slice(&buf[..read]);
+    }
+    assert_eq!(contents, expected);
+
+    let mut contents = Vec::new();
+    files
+        .read_file_chunked("log.txt", 1024)
+        .read_to_end(&mut contents)
+        .await
+        .unwrap();
+    assert_eq!(contents, expected);
+}
+
+#[tokio::test]
+async fn missing_file_is_an_error() {
+    let engine = engine();
+    let files: &dyn FileEngine = &engine;
+
+    assert_eq!(
+        files
+            .read_file_chunked("missing.txt", 1024)
+            .next_chunk()
+            .await,
+        None
+    );
+
+    let mut contents = Vec::new();
+    let result = files
+        .read_file_chunked("missing.txt", 1024)
+        .read_to_end(&mut contents)
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn read_file_chunk_slices_the_file() {
+    let engine = engine();
+    let expected = engine.files["log.txt"].clone();
+
+    let chunk = engine
+        .read_file_chunk("log.txt", 9_000, 4096)
+        .await
+        .unwrap();
+    assert_eq!(chunk, expected[9_000..]);
+    assert_eq!(
+        engine.read_file_chunk("log.txt", 20_000, 10).await,
+        Some(vec![])
+    );
+    assert_eq!(engine.read_file_chunk("missing.txt", 0, 10).await, None);
+}
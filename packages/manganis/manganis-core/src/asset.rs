@@ -1,4 +1,4 @@
-use crate::AssetOptions;
+use crate::{image_width_variant, AssetOptions, AssetVariant};
 use const_serialize::{deserialize_const, ConstStr, ConstVec, SerializeConst};
 use std::{fmt::Debug, hash::Hash, path::PathBuf};
 
@@ -174,6 +174,122 @@ impl Asset {
             self.bundled().bundled_path.as_str().trim_start_matches('/'),
         ))
     }
+
+    /// Get the low quality placeholder of an image asset as a data url. Placeholders are only generated for images
+    /// with [`with_low_quality_preview`](crate::AssetOptionsBuilder::with_low_quality_preview)
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// # use dioxus::prelude::*;
+    /// const HERO: Asset = asset!("/assets/image.png", AssetOptions::image().with_low_quality_preview(true));
+    /// let placeholder = HERO.placeholder().unwrap_or_default();
+    /// rsx! {
+    ///     img { src: HERO, style: "background-image: url({placeholder})" }
+    /// };
+    /// ```
+    pub fn placeholder(&self) -> Option<String> {
+        let bundled = self.bundled();
+        let AssetVariant::Image(image) = bundled.options().variant() else {
+            return None;
+        };
+        let placeholder = image.placeholder()?;
+        Some(format!("data:image/png;base64,{placeholder}"))
+    }
+
+    /// Get the `srcset` of an image asset that was resized with
+    /// [`with_sizes`](crate::AssetOptionsBuilder::with_sizes). Returns `None` if the image wasn't resized or the app
+    /// isn't bundled, since the resized images are only created by the CLI.
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// # use dioxus::prelude::*;
+    /// const HERO: Asset = asset!("/assets/image.png", AssetOptions::image().with_sizes([320, 640]));
+    /// rsx! {
+    ///     img { src: HERO, srcset: HERO.srcset(), sizes: "100vw" }
+    /// };
+    /// ```
+    pub fn srcset(&self) -> Option<String> {
+        let bundled = self.bundled();
+        let AssetVariant::Image(image) = bundled.options().variant() else {
+            return None;
+        };
+        if image.sizes().is_empty() {
+            return None;
+        }
+
+        #[cfg(feature = "dioxus")]
+        if !dioxus_core_types::is_bundled_app() {
+            return None;
+        }
+
+        let path = self.resolve();
+        let path = path.to_string_lossy();
+        let srcset = image
+            .sizes()
+            .iter()
+            .map(|width| format!("{} {width}w", image_width_variant(&path, *width)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(srcset)
+    }
+
+    /// Get the `@font-face` rule for a font asset with the `font-display`, weight and unicode ranges from its
+    /// [`FontAssetOptions`](crate::FontAssetOptions). The family defaults to the file name of the font.
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// # use dioxus::prelude::*;
+    /// const BRAND: Asset = asset!("/assets/font.ttf", AssetOptions::font().with_family("Brand").with_latin());
+    /// rsx! {
+    ///     style { {BRAND.font_face()} }
+    /// };
+    /// ```
+    pub fn font_face(&self) -> Option<String> {
+        let bundled = self.bundled();
+        let AssetVariant::Font(font) = bundled.options().variant() else {
+            return None;
+        };
+
+        let family = match font.family() {
+            Some(family) => family.to_string(),
+            None => PathBuf::from(bundled.absolute_source_path())
+                .file_stem()?
+                .to_string_lossy()
+                .to_string(),
+        };
+        let path = self.resolve();
+        let format = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("woff2") => "woff2",
+            Some("woff") => "woff",
+            Some("otf") => "opentype",
+            _ => "truetype",
+        };
+
+        let mut font_face = format!(
+            "@font-face {{ font-family: \"{family}\"; src: url(\"{}\") format(\"{format}\"); font-display: {};",
+            path.display(),
+            font.display().as_str()
+        );
+        if let Some(weight) = font.weight() {
+            font_face.push_str(&format!(" font-weight: {weight};"));
+        }
+        if font.italic() {
+            font_face.push_str(" font-style: italic;");
+        }
+        // Fonts subset to the rsx don't have a fixed range, so the browser uses them for every character they contain
+        if !font.unicode_ranges().is_empty() && !font.subset_rsx() {
+            let ranges = font
+                .unicode_ranges()
+                .iter()
+                .map(|range| range.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            font_face.push_str(&format!(" unicode-range: {ranges};"));
+        }
+        font_face.push_str(" }");
+
+        Some(font_face)
+    }
 }
 
 impl From<Asset> for String {
@@ -17,6 +17,8 @@ use const_serialize::SerializeConst;
 pub struct CssAssetOptions {
     minify: bool,
     preload: bool,
+    tailwind: bool,
+    postcss: bool,
 }
 
 impl Default for CssAssetOptions {
@@ -36,6 +38,8 @@ impl CssAssetOptions {
         Self {
             preload: false,
             minify: true,
+            tailwind: false,
+            postcss: false,
         }
     }
 
@@ -48,6 +52,16 @@ impl CssAssetOptions {
     pub const fn minified(&self) -> bool {
         self.minify
     }
+
+    /// Check if the asset is compiled with tailwind
+    pub const fn tailwind(&self) -> bool {
+        self.tailwind
+    }
+
+    /// Check if the asset is processed with postcss
+    pub const fn postcss(&self) -> bool {
+        self.postcss
+    }
 }
 
 impl AssetOptions {
@@ -89,6 +103,35 @@ impl AssetOptionsBuilder<CssAssetOptions> {
         self
     }
 
+    /// Compile the css with the tailwind CLI before it is minified
+    ///
+    /// The stylesheet is the tailwind entrypoint, so it can use `@import "tailwindcss"`, `@source` and `@theme`. Every
+    /// tailwind asset is compiled separately, so an app can have multiple entrypoints. The asset is recompiled whenever
+    /// a file tailwind scans for class names changes.
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/input.css", AssetOptions::css().with_tailwind());
+    /// ```
+    pub const fn with_tailwind(mut self) -> Self {
+        self.variant.tailwind = true;
+        self
+    }
+
+    /// Process the css with postcss before it is minified
+    ///
+    /// This runs `postcss` through `npx` with the `postcss.config.js` closest to the stylesheet, so `postcss-cli` and
+    /// any plugins need to be installed with npm.
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/style.css", AssetOptions::css().with_postcss());
+    /// ```
+    pub const fn with_postcss(mut self) -> Self {
+        self.variant.postcss = true;
+        self
+    }
+
     /// Convert the options into options for a generic asset
     pub const fn into_asset_options(self) -> AssetOptions {
         AssetOptions {
@@ -0,0 +1,376 @@
+use const_serialize::{ConstStr, SerializeConst};
+
+use crate::{AssetOptions, AssetOptionsBuilder, AssetVariant};
+
+/// How a font is displayed while it loads. You can read more about the tradeoffs [here](https://developer.mozilla.org/en-US/docs/Web/CSS/@font-face/font-display)
+#[derive(
+    Debug,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Clone,
+    Copy,
+    Hash,
+    SerializeConst,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[repr(u8)]
+pub enum FontDisplay {
+    /// Let the browser decide
+    Auto,
+    /// Hide the text for a short period and then swap to the font when it loads
+    Block,
+    /// Show the text in a fallback font immediately and swap to the font when it loads
+    Swap,
+    /// Hide the text for a very short period, and only swap to the font if it loads soon after
+    Fallback,
+    /// Only use the font if it is available almost immediately
+    Optional,
+}
+
+impl FontDisplay {
+    /// The value of the `font-display` descriptor
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            FontDisplay::Auto => "auto",
+            FontDisplay::Block => "block",
+            FontDisplay::Swap => "swap",
+            FontDisplay::Fallback => "fallback",
+            FontDisplay::Optional => "optional",
+        }
+    }
+}
+
+/// An inclusive range of unicode code points to keep in a font
+#[derive(
+    Debug,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Clone,
+    Copy,
+    Hash,
+    SerializeConst,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct UnicodeRange {
+    start: u32,
+    end: u32,
+}
+
+impl UnicodeRange {
+    /// Create a new unicode range from the first and last code point in the range
+    pub const fn new(start: u32, end: u32) -> Self {
+        assert!(
+            start <= end,
+            "The start of a unicode range must not be after its end"
+        );
+        Self { start, end }
+    }
+
+    /// The first code point in the range
+    pub const fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// The last code point in the range
+    pub const fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// The unused slots of the fixed size array of ranges. [`UnicodeRange::new`] never creates a range that starts after
+    /// it ends, so this can't be confused with a real range like `U+0000`.
+    const fn empty() -> Self {
+        Self {
+            start: u32::MAX,
+            end: 0,
+        }
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+}
+
+impl std::fmt::Display for UnicodeRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.start == self.end {
+            true => write!(f, "U+{:04X}", self.start),
+            false => write!(f, "U+{:04X}-{:04X}", self.start, self.end),
+        }
+    }
+}
+
+/// The maximum number of unicode ranges a font asset can be subset to
+pub const MAX_UNICODE_RANGES: usize = 16;
+
+/// The unicode ranges of the latin subset, matching the ranges Google Fonts uses
+const LATIN: [UnicodeRange; 13] = [
+    UnicodeRange::new(0x0000, 0x00FF),
+    UnicodeRange::new(0x0131, 0x0131),
+    UnicodeRange::new(0x0152, 0x0153),
+    UnicodeRange::new(0x02BB, 0x02BC),
+    UnicodeRange::new(0x02C6, 0x02C6),
+    UnicodeRange::new(0x02DA, 0x02DA),
+    UnicodeRange::new(0x02DC, 0x02DC),
+    UnicodeRange::new(0x2000, 0x206F),
+    UnicodeRange::new(0x20AC, 0x20AC),
+    UnicodeRange::new(0x2122, 0x2122),
+    UnicodeRange::new(0x2212, 0x2212),
+    UnicodeRange::new(0xFEFF, 0xFEFF),
+    UnicodeRange::new(0xFFFD, 0xFFFD),
+];
+
+/// Options for a font asset
+#[derive(
+    Debug,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Clone,
+    Copy,
+    Hash,
+    SerializeConst,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub struct FontAssetOptions {
+    preload: bool,
+    woff2: bool,
+    display: FontDisplay,
+    subset_rsx: bool,
+    unicode_ranges: [UnicodeRange; MAX_UNICODE_RANGES],
+    family: ConstStr,
+    weight: u16,
+    italic: bool,
+}
+
+impl Default for FontAssetOptions {
+    fn default() -> Self {
+        Self::default()
+    }
+}
+
+impl FontAssetOptions {
+    /// Create a new font asset options builder
+    pub const fn new() -> AssetOptionsBuilder<FontAssetOptions> {
+        AssetOptions::font()
+    }
+
+    /// Create a default font asset options
+    pub const fn default() -> Self {
+        Self {
+            preload: false,
+            woff2: false,
+            display: FontDisplay::Swap,
+            subset_rsx: false,
+            unicode_ranges: [UnicodeRange::empty(); MAX_UNICODE_RANGES],
+            family: ConstStr::new(""),
+            weight: 0,
+            italic: false,
+        }
+    }
+
+    /// Check if the asset is preloaded
+    pub const fn preloaded(&self) -> bool {
+        self.preload
+    }
+
+    /// Check if the font is converted to woff2
+    pub const fn woff2(&self) -> bool {
+        self.woff2
+    }
+
+    /// Get the `font-display` of the font
+    pub const fn display(&self) -> FontDisplay {
+        self.display
+    }
+
+    /// Check if the font is subset to the characters used in the rust sources of the crate
+    pub const fn subset_rsx(&self) -> bool {
+        self.subset_rsx
+    }
+
+    /// Get the unicode ranges the font is subset to. If this is empty and the font isn't subset to the rsx, every glyph is kept
+    pub fn unicode_ranges(&self) -> &[UnicodeRange] {
+        let len = self
+            .unicode_ranges
+            .iter()
+            .position(UnicodeRange::is_empty)
+            .unwrap_or(MAX_UNICODE_RANGES);
+        &self.unicode_ranges[..len]
+    }
+
+    /// Check if the font is subset
+    pub fn subset(&self) -> bool {
+        self.subset_rsx || !self.unicode_ranges().is_empty()
+    }
+
+    /// Get the family name of the font in the generated `@font-face`, if one was set
+    pub fn family(&self) -> Option<&str> {
+        let family = self.family.as_str();
+        (!family.is_empty()).then_some(family)
+    }
+
+    /// Get the weight of the font in the generated `@font-face`, if one was set
+    pub const fn weight(&self) -> Option<u16> {
+        match self.weight {
+            0 => None,
+            weight => Some(weight),
+        }
+    }
+
+    /// Check if the font is italic
+    pub const fn italic(&self) -> bool {
+        self.italic
+    }
+
+    pub(crate) const fn extension(&self) -> Option<&'static str> {
+        match self.woff2 {
+            true => Some("woff2"),
+            false => None,
+        }
+    }
+}
+
+impl AssetOptions {
+    /// Create a new font asset builder
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/font.ttf", AssetOptions::font());
+    /// ```
+    pub const fn font() -> AssetOptionsBuilder<FontAssetOptions> {
+        AssetOptionsBuilder::variant(FontAssetOptions::default())
+    }
+}
+
+impl AssetOptionsBuilder<FontAssetOptions> {
+    /// Make the asset preloaded
+    ///
+    /// Preloading a font will make the font start to load as soon as possible instead of when the first text that uses it is rendered
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/font.ttf", AssetOptions::font().with_preload(true));
+    /// ```
+    pub const fn with_preload(mut self, preload: bool) -> Self {
+        self.variant.preload = preload;
+        self
+    }
+
+    /// Convert the font to [woff2](https://developer.mozilla.org/en-US/docs/Web/CSS/CSS_fonts/WOFF)
+    ///
+    /// Woff2 fonts are compressed with brotli and are usually around a third smaller than ttf or otf fonts
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/font.ttf", AssetOptions::font().with_woff2(true));
+    /// ```
+    pub const fn with_woff2(mut self, woff2: bool) -> Self {
+        self.variant.woff2 = woff2;
+        self
+    }
+
+    /// Sets the `font-display` of the generated `@font-face`. Defaults to [`FontDisplay::Swap`]
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions, FontDisplay};
+    /// const _: Asset = asset!("/assets/font.ttf", AssetOptions::font().with_display(FontDisplay::Optional));
+    /// ```
+    pub const fn with_display(mut self, display: FontDisplay) -> Self {
+        self.variant.display = display;
+        self
+    }
+
+    /// Only keep the glyphs in a range of unicode code points. This can be called multiple times to keep multiple ranges
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// // Only keep the ascii digits
+    /// const _: Asset = asset!("/assets/font.ttf", AssetOptions::font().with_unicode_range(0x30, 0x39));
+    /// ```
+    pub const fn with_unicode_range(mut self, start: u32, end: u32) -> Self {
+        let mut i = 0;
+        while i < MAX_UNICODE_RANGES {
+            if self.variant.unicode_ranges[i].is_empty() {
+                self.variant.unicode_ranges[i] = UnicodeRange::new(start, end);
+                return self;
+            }
+            i += 1;
+        }
+        panic!("Fonts can be subset to at most 16 unicode ranges");
+    }
+
+    /// Only keep the glyphs in the latin subset. This covers english and most western european languages
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/font.ttf", AssetOptions::font().with_latin().with_woff2(true));
+    /// ```
+    pub const fn with_latin(mut self) -> Self {
+        let mut i = 0;
+        while i < LATIN.len() {
+            self = self.with_unicode_range(LATIN[i].start, LATIN[i].end);
+            i += 1;
+        }
+        self
+    }
+
+    /// Only keep the glyphs for the characters used in the rust sources of the crate the font belongs to
+    ///
+    /// This works well for fonts that are only used for static text in your rsx, like headings or logos. Characters in
+    /// text loaded at runtime will fall back to another font. The printable ascii characters are always kept.
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/font.ttf", AssetOptions::font().with_rsx_subset(true));
+    /// ```
+    pub const fn with_rsx_subset(mut self, subset_rsx: bool) -> Self {
+        self.variant.subset_rsx = subset_rsx;
+        self
+    }
+
+    /// Sets the family name of the generated `@font-face`. Defaults to the file name of the font
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/font.ttf", AssetOptions::font().with_family("Brand"));
+    /// ```
+    pub const fn with_family(mut self, family: &str) -> Self {
+        self.variant.family = ConstStr::new(family);
+        self
+    }
+
+    /// Sets the `font-weight` of the generated `@font-face`
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/font.ttf", AssetOptions::font().with_family("Brand").with_weight(700));
+    /// ```
+    pub const fn with_weight(mut self, weight: u16) -> Self {
+        self.variant.weight = weight;
+        self
+    }
+
+    /// Sets the `font-style` of the generated `@font-face` to italic
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/font.ttf", AssetOptions::font().with_family("Brand").with_italic(true));
+    /// ```
+    pub const fn with_italic(mut self, italic: bool) -> Self {
+        self.variant.italic = italic;
+        self
+    }
+
+    /// Convert the options into options for a generic asset
+    pub const fn into_asset_options(self) -> AssetOptions {
+        AssetOptions {
+            add_hash: self.add_hash,
+            variant: AssetVariant::Font(self.variant),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use const_serialize::SerializeConst;
+use const_serialize::{ConstStr, SerializeConst};
 
 use crate::{AssetOptions, AssetOptionsBuilder, AssetVariant};
 
@@ -55,6 +55,23 @@ pub enum ImageSize {
     Automatic,
 }
 
+/// The maximum number of widths an image asset can be resized to with [`AssetOptionsBuilder::with_sizes`]
+pub const MAX_IMAGE_SIZES: usize = 8;
+
+/// The path of the copy of an image resized to a width with [`AssetOptionsBuilder::with_sizes`]. The width is added
+/// to the file stem, e.g. `image-dxh123.webp` becomes `image-dxh123-640w.webp`.
+#[doc(hidden)]
+pub fn image_width_variant(path: &str, width: u32) -> String {
+    let file_start = path.rfind('/').map(|i| i + 1).unwrap_or_default();
+    match path[file_start..].find('.') {
+        Some(dot) => {
+            let (stem, ext) = path.split_at(file_start + dot);
+            format!("{stem}-{width}w{ext}")
+        }
+        None => format!("{path}-{width}w"),
+    }
+}
+
 /// Options for an image asset
 #[derive(
     Debug,
@@ -73,6 +90,9 @@ pub struct ImageAssetOptions {
     low_quality_preview: bool,
     size: ImageSize,
     preload: bool,
+    sizes: [u32; MAX_IMAGE_SIZES],
+    /// The base64 encoded low quality placeholder. This is empty until the CLI fills it in
+    placeholder: ConstStr,
 }
 
 impl Default for ImageAssetOptions {
@@ -94,6 +114,8 @@ impl ImageAssetOptions {
             low_quality_preview: false,
             size: ImageSize::Automatic,
             preload: false,
+            sizes: [0; MAX_IMAGE_SIZES],
+            placeholder: ConstStr::new(""),
         }
     }
 
@@ -112,6 +134,51 @@ impl ImageAssetOptions {
         self.size
     }
 
+    /// Check if a low quality placeholder should be generated for the image
+    pub const fn low_quality_preview(&self) -> bool {
+        self.low_quality_preview
+    }
+
+    /// Get the widths the image is resized to for a responsive `srcset`
+    pub fn sizes(&self) -> &[u32] {
+        let len = self
+            .sizes
+            .iter()
+            .position(|width| *width == 0)
+            .unwrap_or(MAX_IMAGE_SIZES);
+        &self.sizes[..len]
+    }
+
+    #[doc(hidden)]
+    /// This should only be called from the CLI
+    /// Drop the widths larger than the image. Images are never scaled up, so those widths would just be copies of the
+    /// image at its original size.
+    pub fn without_sizes_above(mut self, width: u32) -> Self {
+        let mut sizes = [0; MAX_IMAGE_SIZES];
+        for (slot, size) in sizes
+            .iter_mut()
+            .zip(self.sizes().iter().filter(|size| **size <= width))
+        {
+            *slot = *size;
+        }
+        self.sizes = sizes;
+        self
+    }
+
+    /// Get the base64 encoded png placeholder of the image, if the CLI generated one
+    pub fn placeholder(&self) -> Option<&str> {
+        let placeholder = self.placeholder.as_str();
+        (!placeholder.is_empty()).then_some(placeholder)
+    }
+
+    #[doc(hidden)]
+    /// This should only be called from the CLI
+    /// Set the base64 encoded low quality placeholder of the image
+    pub const fn with_placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = ConstStr::new(placeholder);
+        self
+    }
+
     pub(crate) const fn extension(&self) -> Option<&'static str> {
         match self.ty {
             ImageFormat::Png => Some("png"),
@@ -225,6 +292,43 @@ impl AssetOptionsBuilder<ImageAssetOptions> {
         self
     }
 
+    /// Generate a tiny blurred placeholder for the image
+    ///
+    /// The placeholder is inlined into your app as a base64 data url, so it can be shown while the full image loads. You can read it with [`Asset::placeholder`](crate::Asset::placeholder)
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/image.png", AssetOptions::image().with_low_quality_preview(true));
+    /// ```
+    pub const fn with_low_quality_preview(mut self, low_quality_preview: bool) -> Self {
+        self.variant.low_quality_preview = low_quality_preview;
+        self
+    }
+
+    /// Resize the image to multiple widths for a responsive `srcset`
+    ///
+    /// Each width is written next to the image in the chosen format and keeps the aspect ratio of the image. You can read the `srcset` with [`Asset::srcset`](crate::Asset::srcset). At most [`MAX_IMAGE_SIZES`] widths are supported.
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/image.png", AssetOptions::image().with_webp().with_sizes([320, 640, 1280]));
+    /// ```
+    pub const fn with_sizes<const N: usize>(mut self, sizes: [u32; N]) -> Self {
+        assert!(
+            N <= MAX_IMAGE_SIZES,
+            "Images can be resized to at most 8 widths"
+        );
+        let mut sizes_set = [0; MAX_IMAGE_SIZES];
+        let mut i = 0;
+        while i < N {
+            assert!(sizes[i] > 0, "Image widths must be greater than zero");
+            sizes_set[i] = sizes[i];
+            i += 1;
+        }
+        self.variant.sizes = sizes_set;
+        self
+    }
+
     /// Convert the options into options for a generic asset
     pub const fn into_asset_options(self) -> AssetOptions {
         AssetOptions {
@@ -1,7 +1,44 @@
-use const_serialize::SerializeConst;
+use const_serialize::{ConstStr, SerializeConst};
 
 use crate::{AssetOptions, AssetOptionsBuilder, AssetVariant};
 
+/// The version of javascript a js asset is compiled down to
+#[derive(
+    Debug,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Clone,
+    Copy,
+    Hash,
+    SerializeConst,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[repr(u8)]
+pub enum EsTarget {
+    /// ES5 for very old browsers
+    Es5,
+    /// ES2015 (ES6)
+    Es2015,
+    /// ES2016
+    Es2016,
+    /// ES2017
+    Es2017,
+    /// ES2018
+    Es2018,
+    /// ES2019
+    Es2019,
+    /// ES2020
+    Es2020,
+    /// ES2021
+    Es2021,
+    /// ES2022
+    Es2022,
+    /// The latest version of javascript. Syntax is left as is
+    EsNext,
+}
+
 /// Options for a javascript asset
 #[derive(
     Debug,
@@ -18,6 +55,10 @@ use crate::{AssetOptions, AssetOptionsBuilder, AssetVariant};
 pub struct JsAssetOptions {
     minify: bool,
     preload: bool,
+    target: EsTarget,
+    /// The external imports separated by newlines. They share one string so every asset doesn't pay for a fixed
+    /// number of slots in the binary.
+    external: ConstStr,
 }
 
 impl Default for JsAssetOptions {
@@ -37,6 +78,8 @@ impl JsAssetOptions {
         Self {
             preload: false,
             minify: true,
+            target: EsTarget::EsNext,
+            external: ConstStr::new(""),
         }
     }
 
@@ -49,11 +92,27 @@ impl JsAssetOptions {
     pub const fn minified(&self) -> bool {
         self.minify
     }
+
+    /// Get the version of javascript the asset is compiled down to
+    pub const fn target(&self) -> EsTarget {
+        self.target
+    }
+
+    /// Get the imports that are left as is instead of being bundled into the asset
+    pub fn external(&self) -> impl Iterator<Item = &str> {
+        self.external
+            .as_str()
+            .split('\n')
+            .filter(|import| !import.is_empty())
+    }
 }
 
 impl AssetOptions {
     /// Create a new js asset builder
     ///
+    /// Typescript (`.ts`, `.mts`, `.cts`, `.tsx`) and jsx (`.jsx`) entry points are compiled to javascript. Types are stripped
+    /// without type checking and jsx is compiled to `React.createElement` calls.
+    ///
     /// ```rust
     /// # use manganis::{asset, Asset, AssetOptions};
     /// const _: Asset = asset!("/assets/script.js", AssetOptions::js());
@@ -92,6 +151,36 @@ impl AssetOptionsBuilder<JsAssetOptions> {
         self
     }
 
+    /// Sets the version of javascript the asset is compiled down to (default: [`EsTarget::EsNext`])
+    ///
+    /// Newer syntax like optional chaining or classes is rewritten into syntax the target supports. This does not add
+    /// polyfills for missing apis.
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions, EsTarget};
+    /// const _: Asset = asset!("/assets/script.js", AssetOptions::js().with_target(EsTarget::Es2017));
+    /// ```
+    pub const fn with_target(mut self, target: EsTarget) -> Self {
+        self.variant.target = target;
+        self
+    }
+
+    /// Keep an import external instead of bundling it into the asset. The import is left in the output as is, so it
+    /// must be resolvable by the browser, for example through an import map. This can be called multiple times to keep
+    /// multiple imports external, as long as the names of all external imports add up to less than 256 bytes.
+    ///
+    /// ```rust
+    /// # use manganis::{asset, Asset, AssetOptions};
+    /// const _: Asset = asset!("/assets/script.js", AssetOptions::js().with_external("three"));
+    /// ```
+    pub const fn with_external(mut self, import: &str) -> Self {
+        if !self.variant.external.is_empty() {
+            self.variant.external = self.variant.external.push('\n');
+        }
+        self.variant.external = self.variant.external.push_str(import);
+        self
+    }
+
     /// Convert the builder into asset options with the given variant
     pub const fn into_asset_options(self) -> AssetOptions {
         AssetOptions {
@@ -13,6 +13,9 @@ pub use css::*;
 mod js;
 pub use js::*;
 
+mod fonts;
+pub use fonts::*;
+
 mod asset;
 pub use asset::*;
 
@@ -1,7 +1,8 @@
 use const_serialize::SerializeConst;
 
 use crate::{
-    CssAssetOptions, CssModuleAssetOptions, FolderAssetOptions, ImageAssetOptions, JsAssetOptions,
+    CssAssetOptions, CssModuleAssetOptions, FolderAssetOptions, FontAssetOptions,
+    ImageAssetOptions, JsAssetOptions,
 };
 
 /// Settings for a generic asset
@@ -41,6 +42,14 @@ impl AssetOptions {
         self.add_hash
     }
 
+    #[doc(hidden)]
+    /// This should only be called from the CLI
+    /// Replace the variant of the asset
+    pub const fn with_variant(mut self, variant: AssetVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
     /// Try to get the extension for the asset. If the asset options don't define an extension, this will return None
     pub const fn extension(&self) -> Option<&'static str> {
         match self.variant {
@@ -48,6 +57,7 @@ impl AssetOptions {
             AssetVariant::Css(_) => Some("css"),
             AssetVariant::CssModule(_) => Some("css"),
             AssetVariant::Js(_) => Some("js"),
+            AssetVariant::Font(font) => font.extension(),
             AssetVariant::Folder(_) => None,
             AssetVariant::Unknown => None,
         }
@@ -169,6 +179,8 @@ pub enum AssetVariant {
     CssModule(CssModuleAssetOptions),
     /// A javascript asset
     Js(JsAssetOptions),
+    /// A font asset
+    Font(FontAssetOptions),
     /// An unknown asset
     Unknown,
 }
@@ -117,6 +117,9 @@ impl ToTokens for CssModuleParser {
         let options = &self.asset_parser.options;
         let styles_vis = &self.styles_vis;
 
+        // Track the css file so the struct is regenerated when its idents change
+        let path_str = path.to_string_lossy();
+
         // We use a PhantomData to prevent Rust from complaining about an unused lifetime if a css module without any idents is used.
         tokens.extend(quote! {
             #[doc(hidden)]
@@ -127,6 +130,8 @@ impl ToTokens for CssModuleParser {
 
                 #linker_tokens;
 
+                const _: &[u8] = include_bytes!(#path_str);
+
                 // Get the hash to use when builidng hashed css idents.
                 const __ASSET_OPTIONS: manganis::AssetOptions = #options.into_asset_options();
                 pub(super) const __ASSET_HASH: manganis::macro_helpers::const_serialize::ConstStr = manganis::macro_helpers::hash_asset(&__ASSET_OPTIONS, #hash);
@@ -10,5 +10,6 @@ pub use manganis_macro::css_module;
 
 pub use manganis_core::{
     Asset, AssetOptions, AssetVariant, BundledAsset, CssAssetOptions, CssModuleAssetOptions,
-    FolderAssetOptions, ImageAssetOptions, ImageFormat, ImageSize, JsAssetOptions,
+    EsTarget, FolderAssetOptions, FontAssetOptions, FontDisplay, ImageAssetOptions, ImageFormat,
+    ImageSize, JsAssetOptions, UnicodeRange,
 };
@@ -8,10 +8,13 @@
 //!  - `hot-reload`: Enables hot-reloading of Dioxus RSX.
 //!  - `menu`: Enables the [`muda`](https://docs.rs/muda/latest/muda/) menubar.
 //!  - `tracing`: Enables tracing support.
+//!  - `cpu-render`: Enables `DioxusDocument::render_to_buffer` to paint the document into an image on the CPU.
 
 mod dioxus_document;
 mod events;
 mod mutation_writer;
+#[cfg(feature = "cpu-render")]
+mod render;
 pub use blitz_dom::DocumentConfig;
 pub use dioxus_document::DioxusDocument;
 
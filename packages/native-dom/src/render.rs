@@ -0,0 +1,41 @@
+//! Paint a [`DioxusDocument`] into an image without a window or a GPU.
+
+use crate::DioxusDocument;
+use anyrender::render_to_buffer;
+use anyrender_vello_cpu::VelloCpuImageRenderer;
+use blitz_traits::shell::Viewport;
+
+impl DioxusDocument {
+    /// Lay out the document at the size of the viewport and rasterize it on the CPU.
+    ///
+    /// The pixels are returned as RGBA8 row by row, so the buffer holds `width * height * 4` bytes. This doesn't need
+    /// a window or a GPU, which makes it useful for screenshots and visual regression tests.
+    ///
+    /// ```rust, no_run
+    /// use blitz_traits::shell::{ColorScheme, Viewport};
+    /// use dioxus::prelude::*;
+    /// use dioxus_native_dom::{DioxusDocument, DocumentConfig};
+    ///
+    /// fn app() -> Element {
+    ///     rsx! { div { "Hello, world!" } }
+    /// }
+    ///
+    /// let mut doc = DioxusDocument::new(VirtualDom::new(app), DocumentConfig::default());
+    /// doc.initial_build();
+    /// let pixels = doc.render_to_buffer(Viewport::new(800, 600, 1.0, ColorScheme::Light));
+    /// assert_eq!(pixels.len(), 800 * 600 * 4);
+    /// ```
+    pub fn render_to_buffer(&mut self, viewport: Viewport) -> Vec<u8> {
+        let (width, height) = viewport.window_size;
+        let scale = f64::from(viewport.scale());
+
+        self.inner.set_viewport(viewport);
+        self.inner.resolve();
+
+        render_to_buffer::<VelloCpuImageRenderer, _>(
+            |scene| blitz_paint::paint_scene(scene, &self.inner, scale, width, height),
+            width,
+            height,
+        )
+    }
+}
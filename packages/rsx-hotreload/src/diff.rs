@@ -22,6 +22,10 @@
 //! - Dynamic attributes: `div { width: dynamic } -> div { width: dynamic, height: dynamic }`
 //! - Dynamic nodes: `div { {children} } -> div { {children} {children} }`
 //!
+//! New expressions can also be hot reloaded if we can evaluate them without the compiler:
+//! - Formatted expressions: `div { "{count}" } -> div { "{count}" {format!("{count} clicks")} }`
+//! - Literal component props: `Counter { step: 1 } -> Counter { step: -1 }`
+//!
 //! But we cannot clone rsx bodies themselves because we cannot hot reload the new rsx body:
 //! - `div { Component { "{text}" } } -> div { Component { "{text}" } Component { "hello" } }` // We can't create a template for both "{text}" and "hello"
 //!
@@ -251,13 +255,17 @@ impl HotReloadResult {
 
     fn hotreload_raw_expr(&mut self, expr: &ExprNode) -> Option<()> {
         // Try to find the raw expr in the last build
-        let expr_index = self
+        let Some(expr_index) = self
             .full_rebuild_state
             .dynamic_nodes
             .position(|node| match &node {
                 BodyNode::RawExpr(raw_expr) => raw_expr.expr == expr.expr,
                 _ => false,
-            })?;
+            })
+        else {
+            // If the expression is new, we can still hot reload it if it only formats a string from the last build
+            return self.hotreload_formatted_expr(expr);
+        };
 
         // If we find it, push it as a dynamic node
         self.dynamic_nodes
@@ -266,6 +274,18 @@ impl HotReloadResult {
         Some(())
     }
 
+    /// Hot reload a new expression like `{format!("{count} clicks")}` by turning it into a text node
+    ///
+    /// The expression can't have side effects and every segment must have been formatted in the last build
+    fn hotreload_formatted_expr(&mut self, expr: &ExprNode) -> Option<()> {
+        // Parse the expression without the braces around it
+        let expr = syn::parse2::<syn::Expr>(expr.expr.expr.clone()).ok()?;
+        let formatted_segments = self.full_rebuild_state.hot_reload_formatted_expr(&expr)?;
+        self.dynamic_nodes
+            .push(HotReloadDynamicNode::Formatted(formatted_segments));
+        Some(())
+    }
+
     fn hotreload_for_loop<Ctx>(&mut self, forloop: &ForLoop) -> Option<()>
     where
         Ctx: HotReloadingContext,
@@ -445,6 +465,14 @@ impl HotReloadResult {
                     let literal = self.full_rebuild_state.hotreload_hot_literal(new_value)?;
                     literal_component_properties[*index] = Some(literal);
                 }
+                // If the value was a literal in the last build, we can evaluate simple expressions with the same type
+                (AttributeValue::AttrExpr(new_value), AttributeValue::AttrLiteral(old_value)) => {
+                    let new_value = syn::parse2::<syn::Expr>(new_value.expr.clone()).ok()?;
+                    let literal = self
+                        .full_rebuild_state
+                        .hotreload_literal_expr(old_value, &new_value)?;
+                    literal_component_properties[*index] = Some(literal);
+                }
                 _ => {
                     if new_field.value != old_field.value {
                         return None;
@@ -1,6 +1,7 @@
 use dioxus_core::internal::{FmtSegment, FmtedSegments, HotReloadLiteral};
 use dioxus_rsx::*;
 use std::cell::Cell;
+use syn::{Expr, ExprUnary, Lit, UnOp};
 
 /// A pool of items we can grab from during hot reloading.
 /// We have three different pools we can pull from:
@@ -129,6 +130,98 @@ impl LastBuildState {
         }
     }
 
+    /// Interpret a simple expression as a literal with the same type as a literal property from the last build.
+    ///
+    /// This lets props that were compiled as literals change into expressions we can evaluate without the compiler.
+    /// Eg: `count: 1` -> `count: -1` or `label: "hello"` -> `label: format!("{name}")`
+    pub fn hotreload_literal_expr(
+        &self,
+        old: &HotLiteral,
+        expr: &Expr,
+    ) -> Option<HotReloadLiteral> {
+        match (old, expr) {
+            (_, Expr::Paren(paren)) => self.hotreload_literal_expr(old, &paren.expr),
+            (_, Expr::Group(group)) => self.hotreload_literal_expr(old, &group.expr),
+            (
+                HotLiteral::Int(_),
+                Expr::Unary(ExprUnary {
+                    op: UnOp::Neg(_),
+                    expr,
+                    ..
+                }),
+            ) => match self.hotreload_literal_expr(old, expr)? {
+                HotReloadLiteral::Int(int) => Some(HotReloadLiteral::Int(int.checked_neg()?)),
+                _ => None,
+            },
+            (
+                HotLiteral::Float(_),
+                Expr::Unary(ExprUnary {
+                    op: UnOp::Neg(_),
+                    expr,
+                    ..
+                }),
+            ) => match self.hotreload_literal_expr(old, expr)? {
+                HotReloadLiteral::Float(float) => Some(HotReloadLiteral::Float(-float)),
+                _ => None,
+            },
+            (
+                HotLiteral::Bool(_),
+                Expr::Unary(ExprUnary {
+                    op: UnOp::Not(_),
+                    expr,
+                    ..
+                }),
+            ) => match self.hotreload_literal_expr(old, expr)? {
+                HotReloadLiteral::Bool(value) => Some(HotReloadLiteral::Bool(!value)),
+                _ => None,
+            },
+            (HotLiteral::Fmted(_), _) => self
+                .hot_reload_formatted_expr(expr)
+                .map(HotReloadLiteral::Fmted),
+            (_, Expr::Lit(lit)) => {
+                let new = match &lit.lit {
+                    Lit::Int(int) => HotLiteral::Int(int.clone()),
+                    Lit::Float(float) => HotLiteral::Float(float.clone()),
+                    Lit::Bool(value) => HotLiteral::Bool(value.clone()),
+                    _ => return None,
+                };
+                // The literal must have the same type that the property was compiled with
+                if std::mem::discriminant(&new) != std::mem::discriminant(old) {
+                    return None;
+                }
+                self.hotreload_hot_literal(&new)
+            }
+            _ => None,
+        }
+    }
+
+    /// Hot reload an expression that only formats a string from segments in the last build.
+    ///
+    /// Eg: `"hello"` or `format!("{count} clicks")` if `{count}` was formatted in the last build
+    pub fn hot_reload_formatted_expr(&self, expr: &Expr) -> Option<FmtedSegments> {
+        match expr {
+            Expr::Paren(paren) => self.hot_reload_formatted_expr(&paren.expr),
+            Expr::Group(group) => self.hot_reload_formatted_expr(&group.expr),
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => {
+                let input = IfmtInput::new_litstr(lit.clone()).ok()?;
+                self.hot_reload_formatted_segments(&input.into())
+            }
+            // Only inline arguments can be hot reloaded. Positional arguments could be any expression
+            Expr::Macro(mac) => {
+                let name = &mac.mac.path.segments.last()?.ident;
+                if name != "format" && name != "format_args" {
+                    return None;
+                }
+                let lit = mac.mac.parse_body::<syn::LitStr>().ok()?;
+                let input = IfmtInput::new_litstr(lit).ok()?;
+                self.hot_reload_formatted_segments(&input.into())
+            }
+            _ => None,
+        }
+    }
+
     pub fn hot_reload_formatted_segments(
         &self,
         new: &HotReloadFormattedSegment,
@@ -1286,3 +1286,129 @@ fn valid_spread() {
 
     assert!(valid);
 }
+
+// New expressions that only format segments from the last build can be turned into text nodes
+#[test]
+fn valid_new_formatted_expr() {
+    let old = quote! {
+        div { "count is {count}" }
+    };
+    let new = quote! {
+        div {
+            "count is {count}"
+            {format!("{count} clicks")}
+            {"static text"}
+        }
+    };
+
+    let templates = hot_reload_from_tokens(old, new).unwrap();
+    let template = &templates[&0];
+
+    assert_eq!(
+        template.dynamic_nodes,
+        &[
+            HotReloadDynamicNode::Formatted(FmtedSegments::new(vec![
+                FmtSegment::Literal { value: "count is " },
+                FmtSegment::Dynamic { id: 0 }
+            ])),
+            HotReloadDynamicNode::Formatted(FmtedSegments::new(vec![
+                FmtSegment::Dynamic { id: 0 },
+                FmtSegment::Literal { value: " clicks" }
+            ])),
+            HotReloadDynamicNode::Formatted(FmtedSegments::new(vec![FmtSegment::Literal {
+                value: "static text"
+            }])),
+        ]
+    );
+}
+
+#[test]
+fn invalid_new_formatted_expr() {
+    let old = quote! {
+        div { "count is {count}" }
+    };
+
+    // Segments that weren't formatted in the last build need the compiler
+    assert!(!can_hotreload(
+        old.clone(),
+        quote! {
+            div { {format!("{other} clicks")} }
+        },
+    ));
+
+    // Positional arguments could be any expression
+    assert!(!can_hotreload(
+        old.clone(),
+        quote! {
+            div { {format!("{} clicks", count)} }
+        },
+    ));
+
+    // Other expressions might not render as text
+    assert!(!can_hotreload(
+        old,
+        quote! {
+            div { {count} }
+        },
+    ));
+}
+
+// Props that were literals in the last build can be changed to simple expressions of the same type
+#[test]
+fn component_literal_exprs() {
+    let a = quote! {
+        Component {
+            step: 1,
+            scale: 2.5,
+            enabled: true,
+            label: "hello {name}",
+        }
+    };
+
+    let b = quote! {
+        Component {
+            step: -1,
+            scale: (-0.5),
+            enabled: !true,
+            label: format!("goodbye {name}"),
+        }
+    };
+
+    let hot_reload = hot_reload_from_tokens(a, b).unwrap();
+    let template = hot_reload.get(&0).unwrap();
+    assert_eq!(
+        template.component_values,
+        &[
+            HotReloadLiteral::Int(-1),
+            HotReloadLiteral::Float(-0.5),
+            HotReloadLiteral::Bool(false),
+            HotReloadLiteral::Fmted(FmtedSegments::new(vec![
+                FmtSegment::Literal { value: "goodbye " },
+                FmtSegment::Dynamic { id: 0 }
+            ])),
+        ]
+    );
+}
+
+#[test]
+fn component_literal_exprs_invalid() {
+    let a = quote! {
+        Component { step: 1 }
+    };
+
+    // The type of the literal must stay the same
+    assert!(!can_hotreload(
+        a.clone(),
+        quote! {
+            Component { step: -1.5 }
+        },
+    ));
+
+    // Arbitrary expressions need the compiler
+    assert!(!can_hotreload(
+        a,
+        quote! {
+            Component { step: value + 1 }
+        },
+    ));
+}
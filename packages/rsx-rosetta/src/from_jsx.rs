@@ -0,0 +1,1554 @@
+//! Convert React components written in JSX or TSX into Dioxus components
+
+use crate::{
+    to_jsx::JSX_ATTRIBUTES,
+    unparse::{unparse_expr, unparse_stmts},
+};
+use convert_case::{Case, Casing};
+use dioxus_html::{map_html_attribute_to_rsx, map_html_element_to_rsx};
+use dioxus_rsx::CallBody;
+use htmlentity::entity::ICodedDataTrait;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use std::collections::{HashMap, HashSet};
+use swc_common::{sync::Lrc, FileName, Globals, SourceMap, Spanned, GLOBALS};
+use swc_ecma_ast::{
+    ArrowExpr, BlockStmtOrExpr, Callee, Decl, DefaultDecl, Expr, Function, JSXAttrOrSpread,
+    JSXAttrValue, JSXElement, JSXElementChild, JSXExpr, Lit, MemberProp, ModuleDecl, ModuleItem,
+    ObjectPatProp, Pat, Prop as ObjectProp, PropOrSpread, Stmt, Tpl, TsType, TsTypeElement,
+};
+use swc_ecma_parser::{parse_file_as_module, EsSyntax, Syntax, TsSyntax};
+use syn::{parse::Parser, LitStr};
+
+/// Css properties that don't take a unit when they are set to a number in React
+const UNITLESS_STYLES: &[&str] = &[
+    "flex",
+    "flex-grow",
+    "flex-shrink",
+    "font-weight",
+    "line-height",
+    "opacity",
+    "order",
+    "z-index",
+    "zoom",
+];
+
+/// An error converting JSX into rsx
+#[derive(Debug, thiserror::Error)]
+pub enum JsxError {
+    /// The source could not be parsed as JSX or TSX
+    #[error("Failed to parse the source on line {line}: {message}")]
+    Parse { line: usize, message: String },
+
+    /// The source didn't contain any components or JSX
+    #[error("No components or JSX were found in the source")]
+    NoComponents,
+
+    /// The converted JSX could not be parsed as rsx
+    #[error("Failed to convert {component} into rsx: {error}")]
+    Rsx {
+        component: String,
+        error: syn::Error,
+    },
+}
+
+/// Convert the React components in a JSX or TSX file into Dioxus components.
+///
+/// Every function that returns JSX becomes a `#[component]` with its props taken from the destructured parameter and
+/// its interface or type if there is one. `useState` becomes `use_signal`, `className` becomes `class`, event handlers
+/// are lowercased, `{cond && ...}` and ternaries become `if` blocks and `.map()` calls become `for` loops. JSX outside
+/// of a component is converted into a plain `rsx!` block.
+///
+/// Anything that doesn't have a direct translation is kept as a `todo!()` or a comment with the original JavaScript.
+pub fn rsx_from_jsx(source: &str, typescript: bool) -> Result<String, JsxError> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon.into(), source.to_string());
+    let syntax = match typescript {
+        true => Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        }),
+        false => Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+    };
+
+    let module = GLOBALS
+        .set(&Globals::new(), || {
+            parse_file_as_module(&fm, syntax, Default::default(), None, &mut Vec::new())
+        })
+        .map_err(|err| {
+            let offset = (err.span().lo.0.saturating_sub(fm.start_pos.0)) as usize;
+            JsxError::Parse {
+                line: source[..offset.min(source.len())].lines().count().max(1),
+                message: err.kind().msg().to_string(),
+            }
+        })?;
+
+    let mut converter = Converter {
+        source,
+        offset: fm.start_pos.0,
+        types: HashMap::new(),
+        scope: Scope::default(),
+    };
+    converter.collect_types(&module.body);
+
+    let mut out = vec![];
+    for item in &module.body {
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                if let DefaultDecl::Fn(func) = &export.decl {
+                    let name = func
+                        .ident
+                        .as_ref()
+                        .map(|ident| ident.sym.to_string())
+                        .unwrap_or_else(|| "App".to_string());
+                    out.extend(converter.component(
+                        &name,
+                        ComponentFn::Function(&func.function),
+                        None,
+                    )?);
+                }
+                continue;
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                if let Some(func) = component_fn(&export.expr) {
+                    out.extend(converter.component("App", func, None)?);
+                }
+                continue;
+            }
+            ModuleItem::Stmt(Stmt::Expr(stmt)) if is_jsx(&stmt.expr) => {
+                converter.scope = Scope::default();
+                out.push(converter.rsx_block("rsx", &stmt.expr, 0)?);
+                continue;
+            }
+            _ => continue,
+        };
+
+        match decl {
+            Decl::Fn(func) => {
+                out.extend(converter.component(
+                    &func.ident.sym,
+                    ComponentFn::Function(&func.function),
+                    None,
+                )?);
+            }
+            Decl::Var(var) => {
+                for declarator in &var.decls {
+                    let Pat::Ident(binding) = &declarator.name else {
+                        continue;
+                    };
+                    let Some(func) = declarator.init.as_deref().and_then(component_fn) else {
+                        continue;
+                    };
+                    // `const Card: React.FC<CardProps> = ...` types the props on the variable
+                    let props_type = binding
+                        .type_ann
+                        .as_ref()
+                        .map(|ann| converter.snippet(ann.type_ann.span()))
+                        .and_then(|ty| {
+                            let (_, generics) = ty.split_once('<')?;
+                            Some(generics.trim_end_matches('>').trim().to_string())
+                        });
+                    out.extend(converter.component(
+                        &binding.id.sym,
+                        func,
+                        props_type.as_deref(),
+                    )?);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if out.is_empty() {
+        return Err(JsxError::NoComponents);
+    }
+
+    Ok(format!("use dioxus::prelude::*;\n\n{}\n", out.join("\n\n")))
+}
+
+/// A function that might be a component
+#[derive(Clone, Copy)]
+enum ComponentFn<'a> {
+    Function(&'a Function),
+    Arrow(&'a ArrowExpr),
+}
+
+impl<'a> ComponentFn<'a> {
+    fn params(self) -> Vec<&'a Pat> {
+        match self {
+            ComponentFn::Function(func) => func.params.iter().map(|param| &param.pat).collect(),
+            ComponentFn::Arrow(arrow) => arrow.params.iter().collect(),
+        }
+    }
+
+    /// The statements before the returned JSX and the JSX itself
+    fn body(self) -> Option<(&'a [Stmt], &'a Expr)> {
+        let stmts = match self {
+            ComponentFn::Function(func) => &func.body.as_ref()?.stmts,
+            ComponentFn::Arrow(arrow) => match arrow.body.as_ref() {
+                BlockStmtOrExpr::BlockStmt(block) => &block.stmts,
+                BlockStmtOrExpr::Expr(expr) => {
+                    let empty: &[Stmt] = &[];
+                    return is_jsx(expr).then_some((empty, expr.as_ref()));
+                }
+            },
+        };
+        let (last, stmts) = stmts.split_last()?;
+        let Stmt::Return(ret) = last else {
+            return None;
+        };
+        let ret = ret.arg.as_deref()?;
+        is_jsx(ret).then_some((stmts, ret))
+    }
+}
+
+/// Unwrap `memo(...)`, `forwardRef(...)` and parentheses to find the function of a component
+fn component_fn(expr: &Expr) -> Option<ComponentFn<'_>> {
+    match expr {
+        Expr::Paren(paren) => component_fn(&paren.expr),
+        Expr::Arrow(arrow) => Some(ComponentFn::Arrow(arrow)),
+        Expr::Fn(func) => Some(ComponentFn::Function(&func.function)),
+        Expr::Call(call) => {
+            let Callee::Expr(callee) = &call.callee else {
+                return None;
+            };
+            let name = match callee.as_ref() {
+                Expr::Ident(ident) => ident.sym.to_string(),
+                Expr::Member(member) => match &member.prop {
+                    MemberProp::Ident(ident) => ident.sym.to_string(),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            if name != "memo" && name != "forwardRef" {
+                return None;
+            }
+            component_fn(&call.args.first()?.expr)
+        }
+        _ => None,
+    }
+}
+
+/// Check if an expression is JSX, possibly behind parentheses or a conditional
+fn is_jsx(expr: &Expr) -> bool {
+    match expr {
+        Expr::JSXElement(_) | Expr::JSXFragment(_) => true,
+        Expr::Paren(paren) => is_jsx(&paren.expr),
+        Expr::Cond(cond) => is_jsx(&cond.cons) || is_jsx(&cond.alt),
+        Expr::Bin(bin) => is_jsx(&bin.right),
+        _ => false,
+    }
+}
+
+/// A prop of a component
+struct Prop {
+    name: String,
+    ty: String,
+    optional: bool,
+    default: Option<String>,
+}
+
+/// The variables in a component that need special handling
+#[derive(Default)]
+struct Scope {
+    /// Signals created from `useState` and `useMemo`. These are read with `name()`
+    signals: HashSet<String>,
+
+    /// The setters from `useState` and the signal they set
+    setters: HashMap<String, String>,
+
+    /// Props that are event handlers. These are called with `name.call(..)`
+    handlers: HashSet<String>,
+
+    /// Props that hold JSX, like `children`. These are rendered as nodes instead of text
+    elements: HashSet<String>,
+
+    /// The name of the props parameter if it isn't destructured
+    props_object: Option<String>,
+}
+
+/// A converted child of an element
+enum Child {
+    /// Text with `{expr}` for dynamic segments
+    Text(String),
+    Nodes(TokenStream),
+}
+
+struct Converter<'a> {
+    source: &'a str,
+    offset: u32,
+
+    /// The props declared by interfaces and type aliases
+    types: HashMap<String, Vec<Prop>>,
+    scope: Scope,
+}
+
+impl<'a> Converter<'a> {
+    fn snippet(&self, span: swc_common::Span) -> &'a str {
+        let lo = span.lo.0.saturating_sub(self.offset) as usize;
+        let hi = span.hi.0.saturating_sub(self.offset) as usize;
+        self.source.get(lo..hi).unwrap_or_default()
+    }
+
+    /// Write some JavaScript we can't translate as a `todo!()`
+    fn todo(&self, span: swc_common::Span) -> TokenStream {
+        let snippet = self
+            .snippet(span)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        quote! { todo!(#snippet) }
+    }
+
+    fn collect_types(&mut self, items: &[ModuleItem]) {
+        for item in items {
+            let decl = match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+                _ => continue,
+            };
+            match decl {
+                Decl::TsInterface(interface) => {
+                    let props = self.type_members(&interface.body.body);
+                    self.types.insert(interface.id.sym.to_string(), props);
+                }
+                Decl::TsTypeAlias(alias) => {
+                    if let TsType::TsTypeLit(lit) = alias.type_ann.as_ref() {
+                        let props = self.type_members(&lit.members);
+                        self.types.insert(alias.id.sym.to_string(), props);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn type_members(&self, members: &[TsTypeElement]) -> Vec<Prop> {
+        members
+            .iter()
+            .filter_map(|member| {
+                let TsTypeElement::TsPropertySignature(prop) = member else {
+                    return None;
+                };
+                let name = self
+                    .snippet(prop.key.span())
+                    .trim_matches(['"', '\''])
+                    .to_string();
+                let ty = prop
+                    .type_ann
+                    .as_ref()
+                    .map(|ann| self.snippet(ann.type_ann.span()))
+                    .unwrap_or("any");
+                let (ty, nullable) = rust_type(&name, ty);
+                Some(Prop {
+                    name,
+                    ty,
+                    optional: prop.optional || nullable,
+                    default: None,
+                })
+            })
+            .collect()
+    }
+
+    /// The props of a component from its first parameter
+    fn props(&mut self, func: ComponentFn, props_type: Option<&str>) -> Vec<Prop> {
+        let Some(param) = func.params().first().copied() else {
+            return vec![];
+        };
+
+        let type_ann = match param {
+            Pat::Object(object) => object.type_ann.as_ref(),
+            Pat::Ident(ident) => ident.type_ann.as_ref(),
+            _ => None,
+        };
+        let typed = match type_ann.map(|ann| ann.type_ann.as_ref()) {
+            Some(TsType::TsTypeLit(lit)) => self.type_members(&lit.members),
+            Some(ty) => self.types_named(self.snippet(ty.span())),
+            None => props_type
+                .map(|name| self.types_named(name))
+                .unwrap_or_default(),
+        };
+
+        let props = match param {
+            // `function Card(props: CardProps)` reads the props with `props.title`
+            Pat::Ident(ident) => {
+                self.scope.props_object = Some(ident.id.sym.to_string());
+                typed
+            }
+            Pat::Object(object) => object
+                .props
+                .iter()
+                .filter_map(|prop| {
+                    let (name, default) = match prop {
+                        ObjectPatProp::Assign(assign) => (
+                            assign.key.id.sym.to_string(),
+                            assign
+                                .value
+                                .as_deref()
+                                .map(|value| self.snippet(value.span())),
+                        ),
+                        ObjectPatProp::KeyValue(key_value) => {
+                            let name = self.snippet(key_value.key.span()).to_string();
+                            let default = match key_value.value.as_ref() {
+                                Pat::Assign(assign) => Some(self.snippet(assign.right.span())),
+                                _ => None,
+                            };
+                            (name, default)
+                        }
+                        ObjectPatProp::Rest(_) => return None,
+                    };
+
+                    let typed = typed.iter().find(|prop| prop.name == name);
+                    let (ty, optional) = match typed {
+                        Some(prop) => (prop.ty.clone(), prop.optional),
+                        None => (type_from_default(&name, default), false),
+                    };
+                    Some(Prop {
+                        name,
+                        default: default.map(|default| rust_default(&ty, default)),
+                        ty,
+                        optional,
+                    })
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        for prop in &props {
+            if prop.ty.starts_with("EventHandler") {
+                self.scope.handlers.insert(prop.name.clone());
+            }
+            if prop.ty == "Element" {
+                self.scope.elements.insert(prop.name.clone());
+            }
+        }
+
+        props
+    }
+
+    fn types_named(&self, name: &str) -> Vec<Prop> {
+        let name = name.split('<').next().unwrap_or(name).trim();
+        self.types
+            .get(name)
+            .map(|props| {
+                props
+                    .iter()
+                    .map(|prop| Prop {
+                        name: prop.name.clone(),
+                        ty: prop.ty.clone(),
+                        optional: prop.optional,
+                        default: None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Convert a function that returns JSX into a component
+    fn component(
+        &mut self,
+        name: &str,
+        func: ComponentFn,
+        props_type: Option<&str>,
+    ) -> Result<Option<String>, JsxError> {
+        // Components are always capitalized in React
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return Ok(None);
+        }
+        let Some((stmts, jsx)) = func.body() else {
+            return Ok(None);
+        };
+
+        self.scope = Scope::default();
+        let props = self.props(func, props_type);
+
+        let mut body = vec![];
+        for stmt in stmts {
+            body.extend(self.hook_or_stmt(stmt));
+        }
+
+        let params = props
+            .iter()
+            .map(|prop| {
+                let name = rust_name(&prop.name);
+                let ty = match prop.optional && prop.default.is_none() && prop.ty != "Element" {
+                    true => format!("Option<{}>", prop.ty),
+                    false => prop.ty.clone(),
+                };
+                match &prop.default {
+                    Some(default) => format!("#[props(default = {default})] {name}: {ty}"),
+                    None => format!("{name}: {ty}"),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut out = String::from("#[component]\n");
+        let signature = format!("pub fn {name}({}) -> Element {{", params.join(", "));
+        if signature.len() <= 100 {
+            out.push_str(&signature);
+        } else {
+            out.push_str(&format!("pub fn {name}(\n"));
+            for param in params {
+                out.push_str(&format!("    {param},\n"));
+            }
+            out.push_str(") -> Element {");
+        }
+        out.push('\n');
+
+        for line in &body {
+            out.push_str("    ");
+            out.push_str(line);
+            out.push('\n');
+        }
+        if !body.is_empty() {
+            out.push('\n');
+        }
+
+        out.push_str(&self.rsx_block(name, jsx, 1)?);
+        out.push_str("\n}");
+        Ok(Some(out))
+    }
+
+    /// Write a `rsx!` call for some JSX indented to the given level
+    fn rsx_block(&self, name: &str, jsx: &Expr, indent: usize) -> Result<String, JsxError> {
+        let tokens = self.nodes(jsx);
+        let body = CallBody::parse_strict
+            .parse2(tokens)
+            .map_err(|error| JsxError::Rsx {
+                component: name.to_string(),
+                error,
+            })?;
+        let raw = dioxus_autofmt::write_block_out(&body).unwrap_or_default();
+
+        let indent = "    ".repeat(indent);
+        let mut out = format!("{indent}rsx! {{");
+        if raw.contains('\n') {
+            for line in raw.lines().skip_while(|line| line.trim().is_empty()) {
+                out.push('\n');
+                if !line.is_empty() {
+                    out.push_str(&indent);
+                    out.push_str(line);
+                }
+            }
+            out.push('\n');
+            out.push_str(&indent);
+            out.push('}');
+        } else {
+            out.push_str(&raw);
+            out.push('}');
+        }
+        Ok(out)
+    }
+
+    /// Convert a statement in the body of a component, turning hooks into their Dioxus equivalent
+    fn hook_or_stmt(&mut self, stmt: &Stmt) -> Vec<String> {
+        if let Stmt::Decl(Decl::Var(var)) = stmt {
+            if let [declarator] = var.decls.as_slice() {
+                if let Some(Expr::Call(call)) = declarator.init.as_deref() {
+                    let hook = match &call.callee {
+                        Callee::Expr(callee) => self.snippet(callee.span()),
+                        _ => "",
+                    };
+                    let hook = hook.trim_start_matches("React.");
+                    let init = call.args.first().map(|arg| arg.expr.as_ref());
+
+                    match (hook, &declarator.name) {
+                        // const [count, setCount] = useState(0)
+                        ("useState", Pat::Array(array)) => {
+                            let mut names = array.elems.iter().map(|elem| match elem {
+                                Some(Pat::Ident(ident)) => Some(ident.id.sym.to_string()),
+                                _ => None,
+                            });
+                            if let Some(Some(state)) = names.next() {
+                                let signal = rust_ident(&rust_name(&state));
+                                self.scope.signals.insert(state.clone());
+                                if let Some(Some(setter)) = names.next() {
+                                    self.scope.setters.insert(setter, state);
+                                }
+                                let init = match init {
+                                    Some(Expr::Arrow(arrow)) => self.closure_body(arrow),
+                                    Some(init) => self.expr(init),
+                                    None => quote! { Default::default() },
+                                };
+                                return self.stmt_lines(
+                                    quote! { let mut #signal = use_signal(|| #init); },
+                                );
+                            }
+                        }
+                        // const total = useMemo(() => ..., [deps])
+                        ("useMemo", Pat::Ident(ident)) => {
+                            if let Some(Expr::Arrow(arrow)) = init {
+                                let name = ident.id.sym.to_string();
+                                let memo = rust_ident(&rust_name(&name));
+                                let body = self.closure_body(arrow);
+                                self.scope.signals.insert(name);
+                                return self
+                                    .stmt_lines(quote! { let #memo = use_memo(move || #body); });
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // useEffect(() => { ... }, [deps])
+        if let Stmt::Expr(expr) = stmt {
+            if let Expr::Call(call) = expr.expr.as_ref() {
+                let hook = match &call.callee {
+                    Callee::Expr(callee) => self.snippet(callee.span()),
+                    _ => "",
+                };
+                if let ("useEffect" | "React.useEffect", Some(Expr::Arrow(arrow))) =
+                    (hook, call.args.first().map(|arg| arg.expr.as_ref()))
+                {
+                    let body = self.closure_body(arrow);
+                    return self.stmt_lines(quote! { use_effect(move || { #body; }); });
+                }
+            }
+        }
+
+        match self.stmt(stmt) {
+            Some(tokens) => self.stmt_lines(tokens),
+            None => self.comment_lines(stmt.span()),
+        }
+    }
+
+    /// Keep some JavaScript as comments
+    fn comment_lines(&self, span: swc_common::Span) -> Vec<String> {
+        self.snippet(span)
+            .lines()
+            .map(|line| format!("// {}", line.trim()))
+            .collect()
+    }
+
+    fn stmt_lines(&self, tokens: TokenStream) -> Vec<String> {
+        match syn::parse2::<syn::Stmt>(tokens) {
+            Ok(stmt) => unparse_stmts(&[stmt]),
+            Err(_) => vec![],
+        }
+    }
+
+    /// Convert a statement we know how to translate
+    fn stmt(&self, stmt: &Stmt) -> Option<TokenStream> {
+        match stmt {
+            Stmt::Expr(expr) => {
+                let expr = self.expr(&expr.expr);
+                Some(quote! { #expr; })
+            }
+            Stmt::Decl(Decl::Var(var)) => {
+                let [declarator] = var.decls.as_slice() else {
+                    return None;
+                };
+                let Pat::Ident(ident) = &declarator.name else {
+                    return None;
+                };
+                let name = rust_ident(&rust_name(&ident.id.sym));
+                let init = self.expr(declarator.init.as_deref()?);
+                Some(quote! { let #name = #init; })
+            }
+            Stmt::Return(ret) => {
+                let arg = ret.arg.as_deref().map(|arg| self.expr(arg));
+                Some(quote! { return #arg; })
+            }
+            _ => None,
+        }
+    }
+
+    /// The body of an arrow function as a rust expression
+    fn closure_body(&self, arrow: &ArrowExpr) -> TokenStream {
+        match arrow.body.as_ref() {
+            BlockStmtOrExpr::Expr(expr) => self.expr(expr),
+            BlockStmtOrExpr::BlockStmt(block) => {
+                let stmts = block.stmts.iter().map(|stmt| {
+                    self.stmt(stmt).unwrap_or_else(|| {
+                        let todo = self.todo(stmt.span());
+                        quote! { #todo; }
+                    })
+                });
+                quote! { { #(#stmts)* } }
+            }
+        }
+    }
+
+    /// An arrow function as a rust closure
+    fn closure(&self, arrow: &ArrowExpr) -> TokenStream {
+        let params = arrow.params.iter().map(|param| match param {
+            Pat::Ident(ident) => {
+                let ident = rust_ident(&rust_name(&ident.id.sym));
+                quote! { #ident }
+            }
+            _ => quote! { _ },
+        });
+        let params = match arrow.params.is_empty() {
+            true => quote! { _ },
+            false => quote! { #(#params),* },
+        };
+        let body = self.closure_body(arrow);
+        quote! { move |#params| #body }
+    }
+
+    /// Convert a JavaScript expression into rust
+    fn expr(&self, expr: &Expr) -> TokenStream {
+        match expr {
+            Expr::Paren(paren) => {
+                let inner = self.expr(&paren.expr);
+                quote! { (#inner) }
+            }
+            Expr::TsAs(cast) => self.expr(&cast.expr),
+            Expr::TsNonNull(non_null) => self.expr(&non_null.expr),
+            Expr::Ident(ident) => self.ident(&ident.sym),
+            Expr::Lit(Lit::Str(_)) => {
+                let text = LitStr::new(&js_string(self.snippet(expr.span())), Span::call_site());
+                quote! { #text }
+            }
+            Expr::Lit(Lit::Num(_) | Lit::Bool(_)) => self
+                .snippet(expr.span())
+                .parse()
+                .unwrap_or_else(|_| self.todo(expr.span())),
+            Expr::Lit(Lit::Null(_)) => quote! { None },
+            Expr::Tpl(tpl) => {
+                let (format, args) = self.format_string(tpl);
+                let format = LitStr::new(&format, Span::call_site());
+                quote! { format!(#format, #(#args),*) }
+            }
+            Expr::Array(array) => {
+                let elems = array.elems.iter().flatten().map(|elem| match elem.spread {
+                    Some(_) => self.todo(elem.span()),
+                    None => self.expr(&elem.expr),
+                });
+                quote! { vec![#(#elems),*] }
+            }
+            Expr::Member(member) => self.member(member),
+            Expr::Call(call) => self.call(call).unwrap_or_else(|| self.todo(expr.span())),
+            Expr::Arrow(arrow) => self.closure(arrow),
+            Expr::Cond(cond) => {
+                let test = self.expr(&cond.test);
+                let cons = self.expr(&cond.cons);
+                let alt = self.expr(&cond.alt);
+                quote! { if #test { #cons } else { #alt } }
+            }
+            Expr::Unary(unary) => {
+                let op = self.snippet(unary.span()).trim_start();
+                let arg = self.expr(&unary.arg);
+                match op.chars().next() {
+                    Some('!') => quote! { !#arg },
+                    Some('-') => quote! { -#arg },
+                    _ => self.todo(expr.span()),
+                }
+            }
+            Expr::Bin(bin) => {
+                let left = self.expr(&bin.left);
+                let right = self.expr(&bin.right);
+                match self.binary_op(bin) {
+                    "===" | "==" => quote! { #left == #right },
+                    "!==" | "!=" => quote! { #left != #right },
+                    "??" => quote! { #left.unwrap_or(#right) },
+                    op @ ("&&" | "||" | "+" | "-" | "*" | "/" | "%" | "<" | "<=" | ">" | ">=") => {
+                        let op: TokenStream = op.parse().unwrap_or_default();
+                        quote! { #left #op #right }
+                    }
+                    _ => self.todo(expr.span()),
+                }
+            }
+            Expr::JSXElement(_) | Expr::JSXFragment(_) => {
+                let nodes = self.nodes(expr);
+                quote! { rsx! { #nodes } }
+            }
+            _ => self.todo(expr.span()),
+        }
+    }
+
+    /// Read a variable. Signals are read by calling them
+    fn ident(&self, name: &str) -> TokenStream {
+        if name == "undefined" {
+            return quote! { None };
+        }
+        let ident = rust_ident(&rust_name(name));
+        match self.scope.signals.contains(name) {
+            true => quote! { #ident() },
+            false => quote! { #ident },
+        }
+    }
+
+    fn member(&self, member: &swc_ecma_ast::MemberExpr) -> TokenStream {
+        let prop = match &member.prop {
+            MemberProp::Ident(prop) => prop.sym.to_string(),
+            MemberProp::Computed(computed) => {
+                let obj = self.expr(&member.obj);
+                let index = self.expr(&computed.expr);
+                return quote! { #obj[#index] };
+            }
+            _ => return self.todo(member.span()),
+        };
+
+        // `props.title` is just `title` once the props are destructured
+        if let Expr::Ident(obj) = member.obj.as_ref() {
+            if self.scope.props_object.as_deref() == Some(&*obj.sym) {
+                return self.ident(&prop);
+            }
+        }
+
+        // `event.target.value` is `event.value()` in Dioxus
+        if let Expr::Member(target) = member.obj.as_ref() {
+            if let MemberProp::Ident(target_prop) = &target.prop {
+                if &*target_prop.sym == "target" && (prop == "value" || prop == "checked") {
+                    let event = self.expr(&target.obj);
+                    let method = rust_ident(&prop);
+                    return quote! { #event.#method() };
+                }
+            }
+        }
+
+        let obj = self.expr(&member.obj);
+        if prop == "length" {
+            return quote! { #obj.len() };
+        }
+        let prop = rust_ident(&rust_name(&prop));
+        quote! { #obj.#prop }
+    }
+
+    fn call(&self, call: &swc_ecma_ast::CallExpr) -> Option<TokenStream> {
+        let Callee::Expr(callee) = &call.callee else {
+            return None;
+        };
+        let args = call
+            .args
+            .iter()
+            .map(|arg| match arg.spread {
+                Some(_) => self.todo(arg.span()),
+                None => self.expr(&arg.expr),
+            })
+            .collect::<Vec<_>>();
+
+        match callee.as_ref() {
+            Expr::Ident(ident) => {
+                let name = ident.sym.to_string();
+
+                // setCount(count + 1) or setCount(count => count + 1)
+                if let Some(state) = self.scope.setters.get(&name) {
+                    let signal = rust_ident(&rust_name(state));
+                    let value = match call.args.first().map(|arg| arg.expr.as_ref()) {
+                        Some(Expr::Arrow(arrow)) => {
+                            let previous = match arrow.params.first() {
+                                Some(Pat::Ident(ident)) => rust_ident(&rust_name(&ident.id.sym)),
+                                _ => rust_ident("previous"),
+                            };
+                            let body = self.closure_body(arrow);
+                            quote! { { let #previous = #signal(); #body } }
+                        }
+                        _ => args.first().cloned().unwrap_or_default(),
+                    };
+                    return Some(quote! { #signal.set(#value) });
+                }
+
+                // onClick(event) calls an event handler prop
+                if self.scope.handlers.contains(&name) {
+                    let handler = rust_ident(&rust_name(&name));
+                    let arg = args.first().cloned().unwrap_or_else(|| quote! { () });
+                    return Some(quote! { #handler.call(#arg) });
+                }
+
+                let func = rust_ident(&rust_name(&name));
+                Some(quote! { #func(#(#args),*) })
+            }
+            Expr::Member(member) => {
+                let MemberProp::Ident(method) = &member.prop else {
+                    return None;
+                };
+                let obj = self.expr(&member.obj);
+                let method = rust_ident(&rust_name(&method.sym));
+                Some(quote! { #obj.#method(#(#args),*) })
+            }
+            _ => None,
+        }
+    }
+
+    /// Convert a template literal into a format string and its arguments
+    fn format_string(&self, tpl: &Tpl) -> (String, Vec<TokenStream>) {
+        let mut format = String::new();
+        let mut args = vec![];
+        for (idx, quasi) in tpl.quasis.iter().enumerate() {
+            format.push_str(&escape_braces(&unescape_template(
+                self.snippet(quasi.span()),
+            )));
+            if let Some(expr) = tpl.exprs.get(idx) {
+                format.push_str("{}");
+                args.push(self.expr(expr));
+            }
+        }
+        (format, args)
+    }
+
+    /// Convert a template literal into an rsx formatted string with the expressions inline
+    fn ifmt(&self, tpl: &Tpl) -> String {
+        let mut out = String::new();
+        for (idx, quasi) in tpl.quasis.iter().enumerate() {
+            out.push_str(&escape_braces(&unescape_template(
+                self.snippet(quasi.span()),
+            )));
+            if let Some(expr) = tpl.exprs.get(idx) {
+                out.push_str(&self.ifmt_segment(expr));
+            }
+        }
+        out
+    }
+
+    /// An expression inside of an rsx formatted string like `{count}`
+    fn ifmt_segment(&self, expr: &Expr) -> String {
+        let tokens = self.expr(expr);
+        let expr = match syn::parse2::<syn::Expr>(tokens.clone()) {
+            Ok(expr) => unparse_expr(&expr),
+            Err(_) => tokens.to_string(),
+        };
+        format!("{{{expr}}}")
+    }
+
+    /// Convert JSX or an expression that holds JSX into rsx nodes
+    fn nodes(&self, expr: &Expr) -> TokenStream {
+        match self.child(expr) {
+            Child::Text(text) => {
+                let text = LitStr::new(&text, Span::call_site());
+                quote! { #text }
+            }
+            Child::Nodes(nodes) => nodes,
+        }
+    }
+
+    /// Convert an expression inside of JSX
+    fn child(&self, expr: &Expr) -> Child {
+        match expr {
+            Expr::Paren(paren) => self.child(&paren.expr),
+            Expr::JSXElement(el) => Child::Nodes(self.element(el)),
+            Expr::JSXFragment(fragment) => Child::Nodes(self.children(&fragment.children)),
+            Expr::Lit(Lit::Str(_)) => {
+                Child::Text(escape_braces(&js_string(self.snippet(expr.span()))))
+            }
+            Expr::Lit(Lit::Num(_)) => Child::Text(self.snippet(expr.span()).to_string()),
+            Expr::Lit(Lit::Null(_) | Lit::Bool(_)) => Child::Nodes(TokenStream::new()),
+            Expr::Ident(ident) if &*ident.sym == "undefined" => Child::Nodes(TokenStream::new()),
+            Expr::Tpl(tpl) => Child::Text(self.ifmt(tpl)),
+
+            // Props like `children` hold elements instead of text
+            Expr::Ident(ident) if self.scope.elements.contains(&*ident.sym) => {
+                let ident = self.expr(expr);
+                Child::Nodes(quote! { {#ident} })
+            }
+            Expr::Member(member) if matches!(&member.prop, MemberProp::Ident(prop) if self.scope.elements.contains(&*prop.sym)) =>
+            {
+                let member = self.expr(expr);
+                Child::Nodes(quote! { {#member} })
+            }
+            Expr::Ident(_) | Expr::Member(_) => Child::Text(self.ifmt_segment(expr)),
+
+            // {cond && <div />}
+            Expr::Bin(bin) if self.binary_op(bin) == "&&" => {
+                let cond = self.expr(&bin.left);
+                let then = self.nodes(&bin.right);
+                Child::Nodes(quote! { if #cond { #then } })
+            }
+
+            // {cond ? <div /> : <span />}
+            Expr::Cond(cond) => {
+                let test = self.expr(&cond.test);
+                let cons = self.nodes(&cond.cons);
+                let alt = self.nodes(&cond.alt);
+                match alt.is_empty() {
+                    true => Child::Nodes(quote! { if #test { #cons } }),
+                    false => Child::Nodes(quote! { if #test { #cons } else { #alt } }),
+                }
+            }
+
+            // {items.map((item, index) => <li key={item.id}>{item.name}</li>)}
+            Expr::Call(call) => match self.map_loop(call) {
+                Some(nodes) => Child::Nodes(nodes),
+                None => {
+                    let expr = self.expr(expr);
+                    Child::Nodes(quote! { {#expr} })
+                }
+            },
+
+            _ => {
+                let expr = self.expr(expr);
+                Child::Nodes(quote! { {#expr} })
+            }
+        }
+    }
+
+    fn binary_op(&self, bin: &swc_ecma_ast::BinExpr) -> &'a str {
+        self.source
+            .get(
+                (bin.left.span().hi.0 - self.offset) as usize
+                    ..(bin.right.span().lo.0 - self.offset) as usize,
+            )
+            .unwrap_or_default()
+            .trim()
+    }
+
+    /// Convert `items.map(item => ...)` into a for loop
+    fn map_loop(&self, call: &swc_ecma_ast::CallExpr) -> Option<TokenStream> {
+        let Callee::Expr(callee) = &call.callee else {
+            return None;
+        };
+        let Expr::Member(member) = callee.as_ref() else {
+            return None;
+        };
+        let MemberProp::Ident(method) = &member.prop else {
+            return None;
+        };
+        if &*method.sym != "map" {
+            return None;
+        }
+
+        let (params, body) = match call.args.first().map(|arg| arg.expr.as_ref())? {
+            Expr::Arrow(arrow) => {
+                let body = match arrow.body.as_ref() {
+                    BlockStmtOrExpr::Expr(expr) => expr.as_ref(),
+                    BlockStmtOrExpr::BlockStmt(block) => match block.stmts.last()? {
+                        Stmt::Return(ret) if block.stmts.len() == 1 => ret.arg.as_deref()?,
+                        _ => return None,
+                    },
+                };
+                (arrow.params.iter().collect::<Vec<_>>(), body)
+            }
+            _ => return None,
+        };
+
+        let names = params
+            .iter()
+            .map(|param| match param {
+                Pat::Ident(ident) => Some(rust_ident(&rust_name(&ident.id.sym))),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let collection = self.expr(&member.obj);
+        let body = self.nodes(body);
+        match names.as_slice() {
+            [item] => Some(quote! { for #item in #collection.iter() { #body } }),
+            [item, index] => {
+                Some(quote! { for (#index, #item) in #collection.iter().enumerate() { #body } })
+            }
+            _ => None,
+        }
+    }
+
+    /// Convert the children of an element, merging text and simple expressions into formatted strings
+    fn children(&self, children: &[JSXElementChild]) -> TokenStream {
+        let mut nodes = TokenStream::new();
+        let mut text = String::new();
+
+        fn flush(text: &mut String, nodes: &mut TokenStream) {
+            if !text.trim().is_empty() {
+                let lit = LitStr::new(text.trim(), Span::call_site());
+                nodes.extend(quote! { #lit });
+            }
+            text.clear();
+        }
+
+        for child in children {
+            let converted = match child {
+                JSXElementChild::JSXText(jsx_text) => Child::Text(escape_braces(
+                    &collapse_whitespace(self.snippet(jsx_text.span())),
+                )),
+                JSXElementChild::JSXExprContainer(container) => match &container.expr {
+                    JSXExpr::JSXEmptyExpr(_) => continue,
+                    JSXExpr::Expr(expr) => self.child(expr),
+                },
+                JSXElementChild::JSXElement(el) => Child::Nodes(self.element(el)),
+                JSXElementChild::JSXFragment(fragment) => {
+                    Child::Nodes(self.children(&fragment.children))
+                }
+                JSXElementChild::JSXSpreadChild(spread) => {
+                    let todo = self.todo(spread.span());
+                    Child::Nodes(quote! { {#todo} })
+                }
+            };
+
+            match converted {
+                Child::Text(segment) => text.push_str(&segment),
+                Child::Nodes(converted) => {
+                    flush(&mut text, &mut nodes);
+                    nodes.extend(converted);
+                }
+            }
+        }
+        flush(&mut text, &mut nodes);
+
+        nodes
+    }
+
+    fn element(&self, el: &JSXElement) -> TokenStream {
+        let name = self.snippet(el.opening.name.span());
+        let children = self.children(&el.children);
+
+        // Capitalized names are components
+        if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            let path: TokenStream = name.replace('.', "::").parse().unwrap_or_default();
+            let attrs = el.opening.attrs.iter().map(|attr| self.prop(attr));
+            return quote! { #path { #(#attrs)* #children } };
+        }
+
+        let name = match map_html_element_to_rsx(name) {
+            Some(name) => {
+                let ident = rust_ident(name);
+                quote! { #ident }
+            }
+            None if name.contains('-') => {
+                let name = LitStr::new(name, Span::call_site());
+                quote! { #name }
+            }
+            None => {
+                let ident = rust_ident(&name.to_case(Case::Snake));
+                quote! { #ident }
+            }
+        };
+
+        let mut attrs = vec![];
+        let mut spreads = vec![];
+        for attr in &el.opening.attrs {
+            match attr {
+                JSXAttrOrSpread::JSXAttr(attr) => attrs
+                    .extend(self.attribute(self.snippet(attr.name.span()), attr.value.as_ref())),
+                JSXAttrOrSpread::SpreadElement(spread) => {
+                    let expr = self.expr(&spread.expr);
+                    spreads.push(quote! { ..#expr, });
+                }
+            }
+        }
+
+        quote! { #name { #(#attrs)* #(#spreads)* #children } }
+    }
+
+    /// Convert an attribute of an html element
+    fn attribute(&self, name: &str, value: Option<&JSXAttrValue>) -> Option<TokenStream> {
+        if name == "ref" {
+            return None;
+        }
+
+        let value_expr = match value {
+            Some(JSXAttrValue::JSXExprContainer(container)) => match &container.expr {
+                JSXExpr::Expr(expr) => Some(expr.as_ref()),
+                JSXExpr::JSXEmptyExpr(_) => return None,
+            },
+            _ => None,
+        };
+
+        if name == "key" {
+            let value = self.text_value(value);
+            return Some(quote! { key: #value, });
+        }
+
+        // dangerouslySetInnerHTML={{ __html: html }}
+        if name == "dangerouslySetInnerHTML" {
+            let html = match value_expr {
+                Some(Expr::Object(object)) => object.props.iter().find_map(|prop| match prop {
+                    PropOrSpread::Prop(prop) => match prop.as_ref() {
+                        ObjectProp::KeyValue(kv) if self.snippet(kv.key.span()) == "__html" => {
+                            Some(self.expr(&kv.value))
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                }),
+                _ => None,
+            }?;
+            return Some(quote! { dangerous_inner_html: #html, });
+        }
+
+        // style={{ color: "red", marginTop: 4 }}
+        if name == "style" {
+            if let Some(Expr::Object(object)) = value_expr {
+                let style = self.style(object);
+                return Some(quote! { style: #style, });
+            }
+        }
+
+        // onClick={() => ...}
+        if let Some(event) = event_name(name) {
+            let event = rust_ident(&event);
+            let handler = self.handler(value_expr);
+            return Some(quote! { #event: #handler, });
+        }
+
+        let html = JSX_ATTRIBUTES
+            .iter()
+            .find(|(_, jsx)| *jsx == name)
+            .map(|(html, _)| html.to_string())
+            .unwrap_or_else(|| name.to_string());
+        let rsx = map_html_attribute_to_rsx(&html).or_else(|| {
+            // Svg attributes like `strokeWidth` are `stroke-width` in html
+            map_html_attribute_to_rsx(&html.to_case(Case::Kebab))
+        });
+
+        let value = self.attribute_value(value);
+        match rsx {
+            Some(rsx) if !html.starts_with("data-") && !html.starts_with("aria-") => {
+                let ident = rust_ident(rsx.trim_start_matches("r#"));
+                Some(quote! { #ident: #value, })
+            }
+            _ => {
+                let name = LitStr::new(&html, Span::call_site());
+                Some(quote! { #name: #value, })
+            }
+        }
+    }
+
+    /// Convert a prop passed to a component
+    fn prop(&self, attr: &JSXAttrOrSpread) -> TokenStream {
+        let attr = match attr {
+            JSXAttrOrSpread::JSXAttr(attr) => attr,
+            JSXAttrOrSpread::SpreadElement(spread) => {
+                let expr = self.expr(&spread.expr);
+                return quote! { ..#expr, };
+            }
+        };
+
+        let name = self.snippet(attr.name.span());
+        let value_expr = match &attr.value {
+            Some(JSXAttrValue::JSXExprContainer(container)) => match &container.expr {
+                JSXExpr::Expr(expr) => Some(expr.as_ref()),
+                JSXExpr::JSXEmptyExpr(_) => return TokenStream::new(),
+            },
+            _ => None,
+        };
+
+        match name {
+            "key" => {
+                let value = self.text_value(attr.value.as_ref());
+                quote! { key: #value, }
+            }
+            "className" => {
+                let value = self.attribute_value(attr.value.as_ref());
+                quote! { class: #value, }
+            }
+            name if event_name(name).is_some() => {
+                let name = rust_ident(&rust_name(name));
+                let handler = self.handler(value_expr);
+                quote! { #name: #handler, }
+            }
+            name => {
+                let name = rust_ident(&rust_name(name));
+                let value = self.attribute_value(attr.value.as_ref());
+                quote! { #name: #value, }
+            }
+        }
+    }
+
+    /// The value of an attribute as a string literal, formatted string or expression
+    fn attribute_value(&self, value: Option<&JSXAttrValue>) -> TokenStream {
+        match value {
+            None => quote! { true },
+            Some(JSXAttrValue::JSXExprContainer(container)) => match &container.expr {
+                JSXExpr::Expr(expr) => match expr.as_ref() {
+                    Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) => self.text_value(value),
+                    expr => self.expr(expr),
+                },
+                JSXExpr::JSXEmptyExpr(_) => quote! { true },
+            },
+            Some(JSXAttrValue::JSXElement(el)) => {
+                let el = self.element(el);
+                quote! { rsx! { #el } }
+            }
+            Some(JSXAttrValue::JSXFragment(fragment)) => {
+                let children = self.children(&fragment.children);
+                quote! { rsx! { #children } }
+            }
+            Some(value) => {
+                let text = escape_braces(&js_string(self.snippet(value.span())));
+                let text = LitStr::new(&text, Span::call_site());
+                quote! { #text }
+            }
+        }
+    }
+
+    /// The value of an attribute as a formatted string, like `key: "{item.id}"`
+    fn text_value(&self, value: Option<&JSXAttrValue>) -> TokenStream {
+        let text = match value {
+            Some(JSXAttrValue::JSXExprContainer(container)) => match &container.expr {
+                JSXExpr::Expr(expr) => match expr.as_ref() {
+                    Expr::Lit(Lit::Str(_)) => escape_braces(&js_string(self.snippet(expr.span()))),
+                    Expr::Tpl(tpl) => self.ifmt(tpl),
+                    expr => self.ifmt_segment(expr),
+                },
+                JSXExpr::JSXEmptyExpr(_) => String::new(),
+            },
+            Some(value) => escape_braces(&js_string(self.snippet(value.span()))),
+            None => String::new(),
+        };
+        let text = LitStr::new(&text, Span::call_site());
+        quote! { #text }
+    }
+
+    /// Convert an event handler into a closure
+    fn handler(&self, value: Option<&Expr>) -> TokenStream {
+        match value {
+            Some(Expr::Arrow(arrow)) => self.closure(arrow),
+            // onClick={onSelect} forwards the event to a handler prop
+            Some(Expr::Ident(ident)) if self.scope.handlers.contains(&*ident.sym) => {
+                let handler = rust_ident(&rust_name(&ident.sym));
+                quote! { move |event| #handler.call(event) }
+            }
+            Some(Expr::Ident(ident)) => {
+                let handler = rust_ident(&rust_name(&ident.sym));
+                quote! { move |event| #handler(event) }
+            }
+            Some(expr) => self.expr(expr),
+            None => quote! { move |_| {} },
+        }
+    }
+
+    /// Convert a style object into a css string
+    fn style(&self, object: &swc_ecma_ast::ObjectLit) -> TokenStream {
+        let mut style = vec![];
+        for prop in &object.props {
+            let PropOrSpread::Prop(prop) = prop else {
+                continue;
+            };
+            let (key, value) = match prop.as_ref() {
+                ObjectProp::KeyValue(kv) => (
+                    self.snippet(kv.key.span()).trim_matches(['"', '\'']),
+                    kv.value.as_ref(),
+                ),
+                _ => continue,
+            };
+
+            // Vendor prefixes like `WebkitTransform` start with a dash
+            let mut property = key.to_case(Case::Kebab);
+            if key.starts_with(|c: char| c.is_ascii_uppercase()) {
+                property.insert(0, '-');
+            }
+
+            let value = match value {
+                Expr::Lit(Lit::Str(_)) => escape_braces(&js_string(self.snippet(value.span()))),
+                Expr::Lit(Lit::Num(_)) => {
+                    let number = self.snippet(value.span());
+                    match number == "0" || UNITLESS_STYLES.contains(&property.as_str()) {
+                        true => number.to_string(),
+                        false => format!("{number}px"),
+                    }
+                }
+                Expr::Tpl(tpl) => self.ifmt(tpl),
+                value => self.ifmt_segment(value),
+            };
+            style.push(format!("{property}: {value};"));
+        }
+
+        let style = LitStr::new(&style.join(" "), Span::call_site());
+        quote! { #style }
+    }
+}
+
+/// The rust type and whether it is nullable for a TypeScript type
+fn rust_type(name: &str, ty: &str) -> (String, bool) {
+    let ty = ty.trim();
+
+    let variants = ty.split('|').map(str::trim).collect::<Vec<_>>();
+    let nullable = variants
+        .iter()
+        .any(|variant| *variant == "undefined" || *variant == "null");
+    let variants = variants
+        .into_iter()
+        .filter(|variant| *variant != "undefined" && *variant != "null")
+        .collect::<Vec<_>>();
+
+    let rust = match variants.as_slice() {
+        // A union of string literals like `"primary" | "secondary"`
+        variants
+            if variants.len() > 1
+                && variants
+                    .iter()
+                    .all(|variant| variant.starts_with(['"', '\''])) =>
+        {
+            "String".to_string()
+        }
+        [ty] => rust_type_of(name, ty),
+        _ => "String".to_string(),
+    };
+
+    (rust, nullable)
+}
+
+fn rust_type_of(name: &str, ty: &str) -> String {
+    if let Some(inner) = ty.strip_suffix("[]") {
+        return format!("Vec<{}>", rust_type_of(name, inner));
+    }
+    if let Some(inner) = ty
+        .strip_prefix("Array<")
+        .and_then(|inner| inner.strip_suffix('>'))
+    {
+        return format!("Vec<{}>", rust_type_of(name, inner));
+    }
+
+    // Functions are event handlers
+    if ty.contains("=>") {
+        for (react, dioxus) in [
+            ("MouseEvent", "MouseEvent"),
+            ("ChangeEvent", "FormEvent"),
+            ("FormEvent", "FormEvent"),
+            ("KeyboardEvent", "KeyboardEvent"),
+            ("FocusEvent", "FocusEvent"),
+        ] {
+            if ty.contains(react) {
+                return format!("EventHandler<{dioxus}>");
+            }
+        }
+        return "EventHandler".to_string();
+    }
+
+    match ty.trim_start_matches("React.") {
+        "string" => "String".to_string(),
+        "number" => "f64".to_string(),
+        "boolean" => "bool".to_string(),
+        "ReactNode" | "ReactElement" | "JSX.Element" => "Element".to_string(),
+        "any" | "unknown" if name == "children" => "Element".to_string(),
+        "any" | "unknown" => "String".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Guess the type of an untyped prop from its name and default value
+fn type_from_default(name: &str, default: Option<&str>) -> String {
+    if name == "children" {
+        return "Element".to_string();
+    }
+    if event_name(name).is_some() {
+        return "EventHandler".to_string();
+    }
+    match default {
+        Some("true" | "false") => "bool".to_string(),
+        Some(default) if default.parse::<f64>().is_ok() => "f64".to_string(),
+        _ => "String".to_string(),
+    }
+}
+
+/// Convert the default value of a prop into rust
+fn rust_default(ty: &str, default: &str) -> String {
+    match ty {
+        "String" if default.starts_with(['"', '\'', '`']) => {
+            format!("{:?}.to_string()", js_string(default))
+        }
+        "f64" if default.parse::<f64>().is_ok() && !default.contains('.') => format!("{default}.0"),
+        _ => default.to_string(),
+    }
+}
+
+/// The Dioxus event name for a React event handler like `onClick`
+fn event_name(name: &str) -> Option<String> {
+    let event = name.strip_prefix("on")?;
+    event
+        .starts_with(|c: char| c.is_ascii_uppercase())
+        .then(|| name.to_ascii_lowercase())
+}
+
+/// Convert a JavaScript name into a rust name, keeping capitalized names like components
+fn rust_name(name: &str) -> String {
+    match name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        true => name.to_string(),
+        false => name.to_case(Case::Snake),
+    }
+}
+
+/// Create an identifier, escaping keywords like `type`
+fn rust_ident(name: &str) -> Ident {
+    match syn::parse_str::<Ident>(name) {
+        Ok(ident) => ident,
+        Err(_) => Ident::new_raw(name, Span::call_site()),
+    }
+}
+
+/// The text of a JavaScript string literal without the quotes
+fn js_string(literal: &str) -> String {
+    let inner = literal
+        .get(1..literal.len().saturating_sub(1))
+        .unwrap_or_default();
+
+    // Escapes are decoded in one pass so an escaped backslash is never read as the start of another escape
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('b') => out.push('\u{8}'),
+            Some('f') => out.push('\u{c}'),
+            Some('v') => out.push('\u{b}'),
+            Some('0') => out.push('\0'),
+            Some('x') => {
+                let hex: String = chars.by_ref().take(2).collect();
+                out.extend(decode_code_point(&hex));
+            }
+            Some('u') => {
+                let rest = chars.as_str();
+                let hex = match rest.strip_prefix('{') {
+                    Some(braced) => braced.split('}').next().unwrap_or_default(),
+                    None => rest.get(..4).unwrap_or(rest),
+                };
+                let len = hex.len() + if rest.starts_with('{') { 2 } else { 0 };
+                chars = rest.get(len..).unwrap_or_default().chars();
+                out.extend(decode_code_point(hex));
+            }
+            // A backslash before a newline continues the string on the next line
+            Some('\n') => {}
+            // Any other escaped character, including quotes and backslashes, stands for itself
+            Some(c) => out.push(c),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn decode_code_point(hex: &str) -> Option<char> {
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+fn unescape_template(raw: &str) -> String {
+    raw.replace("\\`", "`").replace("\\$", "$")
+}
+
+/// Text in rsx is a formatted string, so braces need to be doubled
+fn escape_braces(text: &str) -> String {
+    text.replace('{', "{{").replace('}', "}}")
+}
+
+/// Collapse the whitespace in JSX text the same way React does: lines are trimmed and joined with a space, and lines
+/// that are only whitespace are removed
+fn collapse_whitespace(raw: &str) -> String {
+    let text = htmlentity::entity::decode(raw.as_bytes())
+        .to_string()
+        .unwrap_or_else(|_| raw.to_string());
+    if !text.contains('\n') {
+        return text;
+    }
+
+    let lines = text.lines().collect::<Vec<_>>();
+    let last = lines.len() - 1;
+    let mut out = String::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let mut line = *line;
+        if idx != 0 {
+            line = line.trim_start();
+        }
+        if idx != last {
+            line = line.trim_end();
+        }
+        if line.is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(line);
+    }
+    out
+}
@@ -0,0 +1,312 @@
+//! Generate icon components and svg sprites from svg files
+
+use crate::{html_from_rsx, rsx_node_from_html};
+use convert_case::{Case, Casing};
+use dioxus_rsx::{
+    Attribute, AttributeName, AttributeValue, BodyNode, CallBody, Element, ElementName, HotLiteral,
+    TemplateBody,
+};
+use html_parser::{Dom, Node};
+use proc_macro2::{Ident, Span};
+use std::collections::HashSet;
+use syn::parse::Parser;
+
+/// The size of an icon that doesn't set a width or view box
+const DEFAULT_SIZE: u32 = 24;
+
+/// Attributes of the root svg that are replaced by the props of the icon or aren't needed once it is inlined. Namespace
+/// declarations like `xmlns:xlink` are removed as well
+const REPLACED_ATTRIBUTES: &[&str] = &[
+    "width", "height", "fill", "class", "id", "style", "xlink", "version", "view_box",
+];
+
+/// An icon parsed from an svg file, ready to be written as a component
+#[derive(Debug, Clone)]
+pub struct Icon {
+    /// The name of the component, like `ArrowLeft`
+    pub name: String,
+
+    /// The id of the icon in a sprite sheet, like `arrow-left`
+    pub id: String,
+
+    view_box: Option<String>,
+    size: u32,
+    fill: String,
+
+    /// Attributes of the root svg that are kept, like `stroke`, as rsx attributes
+    attributes: Vec<String>,
+
+    /// The contents of the svg written as rsx
+    children: String,
+}
+
+impl Icon {
+    /// Parse an icon from the contents of an svg file.
+    ///
+    /// The attributes of the svg are normalized: the size, fill and class become props of the component, `xmlns`
+    /// attributes are removed, and icons that only use a single color are recolored with `currentColor` so they
+    /// follow the color of the text around them.
+    pub fn from_svg(file_stem: &str, svg: &str) -> Option<Icon> {
+        // html_parser doesn't understand the xml prolog
+        let svg = svg
+            .lines()
+            .filter(|line| {
+                let line = line.trim_start();
+                !line.starts_with("<?xml") && !line.starts_with("<!DOCTYPE")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let dom = Dom::parse(&svg).ok()?;
+        let node = find_svg(&dom.children)?;
+        let BodyNode::Element(mut root) = rsx_node_from_html(node)? else {
+            return None;
+        };
+
+        let root_value = |name: &str| {
+            root.raw_attributes
+                .iter()
+                .find(|attr| attr_name(attr) == name)
+                .and_then(literal_text)
+        };
+        let view_box = root_value("view_box");
+        let width = root_value("width");
+
+        let size = width
+            .as_deref()
+            .and_then(|width| width.trim_end_matches("px").parse::<f64>().ok())
+            .or_else(|| {
+                let view_box = view_box.as_deref()?;
+                view_box.split_whitespace().nth(2)?.parse::<f64>().ok()
+            })
+            .map(|size| size.round() as u32)
+            .unwrap_or(DEFAULT_SIZE);
+
+        // Icons with a single color follow the color of the surrounding text
+        let mut colors = HashSet::new();
+        collect_colors(&root, &mut colors);
+        if colors.len() == 1 {
+            recolor(&mut root);
+        }
+
+        let fill = root
+            .raw_attributes
+            .iter()
+            .find(|attr| attr_name(attr) == "fill")
+            .and_then(literal_text)
+            .unwrap_or_else(|| "currentColor".to_string());
+
+        let attributes = root
+            .raw_attributes
+            .iter()
+            .filter(|attr| {
+                let name = attr_name(attr);
+                !REPLACED_ATTRIBUTES.contains(&name.as_str())
+                    && !name.starts_with("xmlns")
+                    && !name.starts_with("data-")
+            })
+            .filter_map(|attr| {
+                let value = literal_text(attr)?;
+                let name = match &attr.name {
+                    AttributeName::BuiltIn(name) => name.to_string(),
+                    AttributeName::Custom(name) => format!("{:?}", name.value()),
+                    AttributeName::Spread(_) => return None,
+                };
+                Some(format!("{name}: {}", rsx_string(&value)))
+            })
+            .collect();
+
+        escape_keywords(&mut root.children);
+        let children = match root.children.is_empty() {
+            true => String::new(),
+            false => {
+                dioxus_autofmt::write_block_out(&CallBody::new(TemplateBody::new(root.children)))?
+            }
+        };
+
+        let id = file_stem.to_case(Case::Kebab);
+        let mut name = file_stem.to_case(Case::Pascal);
+        if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            name.insert_str(0, "Icon");
+        }
+
+        Some(Icon {
+            name,
+            id,
+            view_box,
+            size,
+            fill,
+            attributes,
+            children,
+        })
+    }
+}
+
+/// Write a rust module with a component for every icon.
+///
+/// Each component takes `size`, `fill` and `class` props. If `sprite` is the path of a sprite sheet created with
+/// [`icons_sprite`], the components reference their symbol in the sprite with `<use>` instead of inlining the svg.
+pub fn icons_module(icons: &[Icon], sprite: Option<&str>) -> String {
+    let mut out = String::from("//! Icons generated by `dx translate --icons`\n\n");
+    out.push_str("use dioxus::prelude::*;\n");
+
+    if let Some(sprite) = sprite {
+        out.push_str(&format!("\nconst SPRITE: Asset = asset!({sprite:?});\n"));
+    }
+
+    for icon in icons {
+        let mut rsx = String::from("svg {\n");
+        if let Some(view_box) = &icon.view_box {
+            rsx.push_str(&format!("view_box: {},\n", rsx_string(view_box)));
+        }
+        rsx.push_str(
+            "width: \"{size}\",\nheight: \"{size}\",\nfill: \"{fill}\",\nclass: \"{class}\",\n",
+        );
+        for attribute in &icon.attributes {
+            rsx.push_str(attribute);
+            rsx.push_str(",\n");
+        }
+        match sprite {
+            Some(_) => rsx.push_str(&format!("r#use {{ href: \"{{SPRITE}}#{}\" }}\n", icon.id)),
+            None => rsx.push_str(&icon.children),
+        }
+        rsx.push('}');
+
+        let body = CallBody::parse_strict
+            .parse_str(&rsx)
+            .ok()
+            .and_then(|body| dioxus_autofmt::write_block_out(&body))
+            .unwrap_or_default();
+
+        out.push_str(&format!(
+            r#"
+#[component]
+pub fn {}(
+    #[props(default = {})] size: u32,
+    #[props(default = {:?}.to_string())] fill: String,
+    #[props(default)] class: String,
+) -> Element {{
+    rsx! {{"#,
+            icon.name, icon.size, icon.fill
+        ));
+        for line in body.lines().skip_while(|line| line.is_empty()) {
+            out.push('\n');
+            if !line.is_empty() {
+                out.push_str("    ");
+                out.push_str(line);
+            }
+        }
+        out.push_str("\n    }\n}\n");
+    }
+
+    out
+}
+
+/// Write an svg sprite sheet with a `<symbol>` for every icon
+pub fn icons_sprite(icons: &[Icon]) -> String {
+    let mut out = String::from("<svg xmlns=\"http://www.w3.org/2000/svg\">\n");
+
+    for icon in icons {
+        out.push_str(&format!("  <symbol id=\"{}\"", icon.id));
+        if let Some(view_box) = &icon.view_box {
+            out.push_str(&format!(" viewBox=\"{view_box}\""));
+        }
+        out.push_str(">\n");
+
+        // Reparse the rsx so the text of the attributes is available to the html writer
+        let html = CallBody::parse_strict
+            .parse_str(&icon.children)
+            .map(|body| html_from_rsx(&body))
+            .unwrap_or_default();
+        for line in html.lines() {
+            out.push_str("    ");
+            out.push_str(line);
+            out.push('\n');
+        }
+
+        out.push_str("  </symbol>\n");
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+fn find_svg(nodes: &[Node]) -> Option<&Node> {
+    nodes.iter().find_map(|node| match node {
+        Node::Element(el) if el.name == "svg" => Some(node),
+        Node::Element(el) => find_svg(&el.children),
+        _ => None,
+    })
+}
+
+fn attr_name(attr: &Attribute) -> String {
+    match &attr.name {
+        AttributeName::BuiltIn(name) => name.to_string().trim_start_matches("r#").to_string(),
+        AttributeName::Custom(name) => name.value(),
+        AttributeName::Spread(_) => String::new(),
+    }
+}
+
+/// The text of an attribute created from html
+fn literal_text(attr: &Attribute) -> Option<String> {
+    match &attr.value {
+        AttributeValue::AttrLiteral(HotLiteral::Fmted(text)) => {
+            Some(text.formatted_input.source.value())
+        }
+        _ => None,
+    }
+}
+
+/// Check if a fill or stroke is an actual color
+fn is_color(value: &str) -> bool {
+    !matches!(value, "none" | "currentColor" | "transparent" | "inherit")
+        && !value.starts_with("url(")
+}
+
+fn collect_colors(el: &Element, colors: &mut HashSet<String>) {
+    for attr in &el.raw_attributes {
+        if matches!(attr_name(attr).as_str(), "fill" | "stroke") {
+            colors.extend(literal_text(attr).filter(|color| is_color(color)));
+        }
+    }
+    for child in &el.children {
+        if let BodyNode::Element(child) = child {
+            collect_colors(child, colors);
+        }
+    }
+}
+
+/// Replace every color with `currentColor`
+fn recolor(el: &mut Element) {
+    for attr in &mut el.raw_attributes {
+        let is_colored = matches!(attr_name(attr).as_str(), "fill" | "stroke")
+            && literal_text(attr).is_some_and(|color| is_color(&color));
+        if is_colored {
+            attr.value = AttributeValue::AttrLiteral(HotLiteral::from_raw_text("currentColor"));
+        }
+    }
+    for child in &mut el.children {
+        if let BodyNode::Element(child) = child {
+            recolor(child);
+        }
+    }
+}
+
+/// Elements like `use` are keywords in rust and need to be written as raw identifiers
+fn escape_keywords(nodes: &mut [BodyNode]) {
+    for node in nodes {
+        if let BodyNode::Element(el) = node {
+            if let ElementName::Ident(ident) = &el.name {
+                if syn::parse_str::<syn::Ident>(&ident.to_string()).is_err() {
+                    el.name =
+                        ElementName::Ident(Ident::new_raw(&ident.to_string(), Span::call_site()));
+                }
+            }
+            escape_keywords(&mut el.children);
+        }
+    }
+}
+
+/// Write a string literal for rsx, where braces need to be escaped
+fn rsx_string(value: &str) -> String {
+    format!("{:?}", value.replace('{', "{{").replace('}', "}}"))
+}
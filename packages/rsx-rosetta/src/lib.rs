@@ -13,6 +13,21 @@ use htmlentity::entity::ICodedDataTrait;
 use proc_macro2::{Ident, Span};
 use syn::{punctuated::Punctuated, LitStr};
 
+mod icons;
+mod to_html;
+mod to_jsx;
+mod unparse;
+
+#[cfg(feature = "jsx")]
+mod from_jsx;
+
+pub use icons::{icons_module, icons_sprite, Icon};
+pub use to_html::{html_from_rsx, html_from_rust};
+pub use to_jsx::{jsx_from_rsx, jsx_from_rust};
+
+#[cfg(feature = "jsx")]
+pub use from_jsx::{rsx_from_jsx, JsxError};
+
 /// Convert an HTML DOM tree into an RSX CallBody
 pub fn rsx_from_html(dom: &Dom) -> CallBody {
     let nodes = dom
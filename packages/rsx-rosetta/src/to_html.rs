@@ -0,0 +1,276 @@
+//! Render rsx back into static html
+
+use crate::to_jsx::component_body;
+use crate::unparse::{
+    iterated_collection, unparse_expr, unparse_partial_expr, unparse_pat, unparse_segment,
+};
+use dioxus_core_types::HotReloadingContext;
+use dioxus_html::HtmlCtx;
+use dioxus_rsx::{
+    is_component_fn, Attribute, AttributeName, AttributeValue, BodyNode, CallBody, Element,
+    ElementName, HotLiteral, IfChain, IfmtInput, Segment,
+};
+use quote::ToTokens;
+
+const INDENT: &str = "  ";
+
+/// Elements that can't have children and don't have a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Render an rsx body into static html.
+///
+/// Only the static parts of the template have an html equivalent, so the dynamic parts are kept as placeholders:
+/// - Formatted segments in text and attributes are written as `{expr}`
+/// - Expressions, components, loops and conditionals are written as comments around their contents. Every branch of a
+///   conditional is rendered.
+/// - Event handlers, keys and spreads are skipped
+pub fn html_from_rsx(body: &CallBody) -> String {
+    let mut writer = HtmlWriter::default();
+    writer.write_nodes(&body.body.roots, 0);
+    writer.out.trim_end().to_string()
+}
+
+/// Render the `rsx!` body of every component in a rust file into static html, one after another
+pub fn html_from_rust(file: &syn::File) -> String {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Fn(item_fn) if is_component_fn(item_fn) => {
+                let (_, body) = component_body(item_fn)?;
+                Some(format!(
+                    "{}\n{}",
+                    comment(&item_fn.sig.ident.to_string()),
+                    html_from_rsx(&body)
+                ))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[derive(Default)]
+struct HtmlWriter {
+    out: String,
+}
+
+impl HtmlWriter {
+    fn line(&mut self, depth: usize, contents: &str) {
+        for _ in 0..depth {
+            self.out.push_str(INDENT);
+        }
+        self.out.push_str(contents);
+        self.out.push('\n');
+    }
+
+    fn write_nodes(&mut self, nodes: &[BodyNode], depth: usize) {
+        for node in nodes {
+            self.write_node(node, depth);
+        }
+    }
+
+    fn write_node(&mut self, node: &BodyNode, depth: usize) {
+        match node {
+            BodyNode::Element(el) => self.write_element(el, depth),
+            BodyNode::Text(text) => self.line(depth, &escape_text(&ifmt_text(&text.input))),
+            BodyNode::RawExpr(expr) => {
+                self.line(depth, &comment(&unparse_partial_expr(&expr.expr)))
+            }
+            BodyNode::Component(component) => {
+                let mut name = component.name.to_token_stream().to_string();
+                name.retain(|c| !c.is_whitespace());
+                self.line(depth, &comment(&name));
+                if !component.children.roots.is_empty() {
+                    self.write_nodes(&component.children.roots, depth + 1);
+                    self.line(depth, &comment(&format!("/{name}")));
+                }
+            }
+            BodyNode::ForLoop(forloop) => {
+                let pat = unparse_pat(&forloop.pat);
+                let expr = unparse_expr(iterated_collection(&forloop.expr));
+                self.line(depth, &comment(&format!("for {pat} in {expr}")));
+                self.write_nodes(&forloop.body.roots, depth + 1);
+                self.line(depth, &comment("/for"));
+            }
+            BodyNode::IfChain(ifchain) => self.write_if_chain(ifchain, depth),
+        }
+    }
+
+    fn write_if_chain(&mut self, ifchain: &IfChain, depth: usize) {
+        let mut branch = Some(ifchain);
+        let mut keyword = "if";
+        while let Some(chain) = branch {
+            let cond = unparse_expr(&chain.cond);
+            self.line(depth, &comment(&format!("{keyword} {cond}")));
+            self.write_nodes(&chain.then_branch.roots, depth + 1);
+
+            if let Some(else_branch) = &chain.else_branch {
+                self.line(depth, &comment("else"));
+                self.write_nodes(&else_branch.roots, depth + 1);
+            }
+
+            keyword = "else if";
+            branch = chain.else_if_branch.as_deref();
+        }
+        self.line(depth, &comment("/if"));
+    }
+
+    fn write_element(&mut self, el: &Element, depth: usize) {
+        let (tag, rsx_name) = match &el.name {
+            ElementName::Ident(ident) => {
+                let name = ident.to_string();
+                let tag = HtmlCtx::map_element(&name)
+                    .map(|(tag, _)| tag.to_string())
+                    .unwrap_or_else(|| name.clone());
+                (tag, Some(name))
+            }
+            ElementName::Custom(name) => (name.value(), None),
+        };
+        let open = format!(
+            "<{tag}{}>",
+            html_attributes(rsx_name.as_deref(), &el.raw_attributes)
+        );
+
+        match el.children.as_slice() {
+            [] if VOID_ELEMENTS.contains(&tag.as_str()) => self.line(depth, &open),
+            [] => self.line(depth, &format!("{open}</{tag}>")),
+            // Keep elements that only contain text on one line
+            [BodyNode::Text(text)] => {
+                let text = escape_text(&ifmt_text(&text.input));
+                self.line(depth, &format!("{open}{text}</{tag}>"));
+            }
+            children => {
+                self.line(depth, &open);
+                self.write_nodes(children, depth + 1);
+                self.line(depth, &format!("</{tag}>"));
+            }
+        }
+    }
+}
+
+/// The value of an attribute in html
+enum HtmlValue {
+    /// A boolean attribute that is set, e.g. `disabled`
+    Present,
+    Text(String),
+}
+
+fn html_attributes(element: Option<&str>, attributes: &[Attribute]) -> String {
+    let mut written: Vec<(String, HtmlValue)> = vec![];
+    let mut style = vec![];
+
+    for attr in attributes {
+        // Event handlers and keys don't exist in static html
+        if attr.name.is_likely_event() || attr.name.is_likely_key() {
+            continue;
+        }
+        let Some(value) = html_value(&attr.value) else {
+            continue;
+        };
+
+        let (name, namespace) = match &attr.name {
+            AttributeName::BuiltIn(name) => {
+                let name = name.to_string();
+                let name = name.trim_start_matches("r#");
+                element
+                    .and_then(|element| HtmlCtx::map_attribute(element, name))
+                    .map(|(name, namespace)| (name.to_string(), namespace))
+                    .unwrap_or_else(|| (name.to_string(), None))
+            }
+            AttributeName::Custom(name) => (name.value(), None),
+            AttributeName::Spread(_) => continue,
+        };
+
+        match (namespace, value) {
+            (Some("style"), HtmlValue::Text(value)) => style.push(format!("{name}: {value};")),
+            (_, HtmlValue::Text(value)) if name == "style" => style.push(value),
+            (Some("style"), HtmlValue::Present) => {}
+            (_, value) => match written.iter_mut().find(|(existing, _)| *existing == name) {
+                // rsx joins attributes that are set more than once, like multiple classes
+                Some((_, HtmlValue::Text(existing))) => {
+                    if let HtmlValue::Text(value) = value {
+                        existing.push(' ');
+                        existing.push_str(&value);
+                    }
+                }
+                Some(_) => {}
+                None => written.push((name, value)),
+            },
+        }
+    }
+
+    if !style.is_empty() {
+        written.push(("style".to_string(), HtmlValue::Text(style.join(" "))));
+    }
+
+    let mut out = String::new();
+    for (name, value) in written {
+        out.push(' ');
+        out.push_str(&name);
+        if let HtmlValue::Text(value) = value {
+            out.push_str("=\"");
+            out.push_str(&escape_attribute(&value));
+            out.push('"');
+        }
+    }
+    out
+}
+
+fn html_value(value: &AttributeValue) -> Option<HtmlValue> {
+    match value {
+        AttributeValue::AttrLiteral(HotLiteral::Fmted(text)) => {
+            Some(HtmlValue::Text(ifmt_text(&text.formatted_input)))
+        }
+        AttributeValue::AttrLiteral(HotLiteral::Bool(value)) => {
+            value.value.then_some(HtmlValue::Present)
+        }
+        AttributeValue::AttrLiteral(HotLiteral::Int(value)) => {
+            Some(HtmlValue::Text(value.base10_digits().to_string()))
+        }
+        AttributeValue::AttrLiteral(HotLiteral::Float(value)) => {
+            Some(HtmlValue::Text(value.base10_digits().to_string()))
+        }
+        AttributeValue::Shorthand(ident) => Some(HtmlValue::Text(format!("{{{ident}}}"))),
+        AttributeValue::AttrExpr(expr) => Some(HtmlValue::Text(format!(
+            "{{{}}}",
+            unparse_partial_expr(expr)
+        ))),
+        // Static html can only show one branch, so we show the first one
+        AttributeValue::IfExpr(if_chain) => html_value(&if_chain.then_value),
+        AttributeValue::EventTokens(_) => None,
+    }
+}
+
+/// The text of a formatted string with the dynamic segments written as `{expr}`
+fn ifmt_text(input: &IfmtInput) -> String {
+    let mut out = String::new();
+    for segment in &input.segments {
+        match segment {
+            Segment::Literal(text) => out.push_str(text),
+            Segment::Formatted(segment) => {
+                out.push('{');
+                out.push_str(&unparse_segment(segment));
+                out.push('}');
+            }
+        }
+    }
+    out
+}
+
+fn comment(contents: &str) -> String {
+    // `--` ends a comment early
+    format!("<!-- {} -->", contents.replace("--", "- -"))
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn escape_attribute(value: &str) -> String {
+    escape_text(value).replace('"', "&quot;")
+}
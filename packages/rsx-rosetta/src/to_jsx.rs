@@ -0,0 +1,722 @@
+//! Render rsx into JSX and rust components into React components
+
+use crate::unparse::{
+    iterated_collection, unparse_closure, unparse_expr, unparse_partial_expr, unparse_pat,
+    unparse_segment, unparse_stmts,
+};
+use convert_case::{Case, Casing};
+use dioxus_core_types::HotReloadingContext;
+use dioxus_html::HtmlCtx;
+use dioxus_rsx::{
+    is_component_fn, Attribute, AttributeName, AttributeValue, BodyNode, CallBody, Component,
+    Element, ElementName, HotLiteral, IfChain, IfmtInput, Segment,
+};
+use quote::ToTokens;
+use syn::{parse::Parser, FnArg, GenericArgument, ItemFn, Pat, PathArguments, Stmt, Type};
+
+const INDENT: &str = "  ";
+
+/// Html attributes that are written in camelCase in JSX
+pub(crate) const JSX_ATTRIBUTES: &[(&str, &str)] = &[
+    ("class", "className"),
+    ("for", "htmlFor"),
+    ("accept-charset", "acceptCharset"),
+    ("accesskey", "accessKey"),
+    ("allowfullscreen", "allowFullScreen"),
+    ("autocomplete", "autoComplete"),
+    ("autofocus", "autoFocus"),
+    ("autoplay", "autoPlay"),
+    ("cellpadding", "cellPadding"),
+    ("cellspacing", "cellSpacing"),
+    ("charset", "charSet"),
+    ("colspan", "colSpan"),
+    ("contenteditable", "contentEditable"),
+    ("crossorigin", "crossOrigin"),
+    ("datetime", "dateTime"),
+    ("enctype", "encType"),
+    ("formaction", "formAction"),
+    ("frameborder", "frameBorder"),
+    ("http-equiv", "httpEquiv"),
+    ("inputmode", "inputMode"),
+    ("ismap", "isMap"),
+    ("itemprop", "itemProp"),
+    ("maxlength", "maxLength"),
+    ("minlength", "minLength"),
+    ("novalidate", "noValidate"),
+    ("playsinline", "playsInline"),
+    ("readonly", "readOnly"),
+    ("referrerpolicy", "referrerPolicy"),
+    ("rowspan", "rowSpan"),
+    ("spellcheck", "spellCheck"),
+    ("srcset", "srcSet"),
+    ("tabindex", "tabIndex"),
+    ("usemap", "useMap"),
+];
+
+/// The names of event handlers in React. Dioxus uses the same names in lowercase.
+pub(crate) const REACT_EVENTS: &[&str] = &[
+    "onAbort",
+    "onAnimationEnd",
+    "onAnimationIteration",
+    "onAnimationStart",
+    "onBlur",
+    "onCanPlay",
+    "onCanPlayThrough",
+    "onChange",
+    "onClick",
+    "onCompositionEnd",
+    "onCompositionStart",
+    "onCompositionUpdate",
+    "onContextMenu",
+    "onCopy",
+    "onCut",
+    "onDoubleClick",
+    "onDrag",
+    "onDragEnd",
+    "onDragEnter",
+    "onDragExit",
+    "onDragLeave",
+    "onDragOver",
+    "onDragStart",
+    "onDrop",
+    "onDurationChange",
+    "onEmptied",
+    "onEnded",
+    "onError",
+    "onFocus",
+    "onFocusIn",
+    "onFocusOut",
+    "onGotPointerCapture",
+    "onInput",
+    "onInvalid",
+    "onKeyDown",
+    "onKeyPress",
+    "onKeyUp",
+    "onLoad",
+    "onLoadedData",
+    "onLoadedMetadata",
+    "onLoadStart",
+    "onLostPointerCapture",
+    "onMouseDown",
+    "onMouseEnter",
+    "onMouseLeave",
+    "onMouseMove",
+    "onMouseOut",
+    "onMouseOver",
+    "onMouseUp",
+    "onPaste",
+    "onPause",
+    "onPlay",
+    "onPlaying",
+    "onPointerCancel",
+    "onPointerDown",
+    "onPointerEnter",
+    "onPointerLeave",
+    "onPointerMove",
+    "onPointerOut",
+    "onPointerOver",
+    "onPointerUp",
+    "onProgress",
+    "onRateChange",
+    "onReset",
+    "onScroll",
+    "onSeeked",
+    "onSeeking",
+    "onSelect",
+    "onStalled",
+    "onSubmit",
+    "onSuspend",
+    "onTimeUpdate",
+    "onToggle",
+    "onTouchCancel",
+    "onTouchEnd",
+    "onTouchMove",
+    "onTouchStart",
+    "onTransitionEnd",
+    "onVolumeChange",
+    "onWaiting",
+    "onWheel",
+];
+
+/// Render an rsx body into JSX.
+///
+/// Rust expressions are copied into the JSX as they are, so anything more complex than a variable will need to be
+/// ported by hand. Event handlers are written as empty arrow functions with the original closure in a comment.
+pub fn jsx_from_rsx(body: &CallBody) -> String {
+    let mut writer = JsxWriter::default();
+    writer.write_root(&body.body.roots, 0);
+    writer.out.trim_end().to_string()
+}
+
+/// Convert every component in a rust file into a React component.
+///
+/// The props of the component become the props of the function, and with `typescript` they are typed with the
+/// closest TypeScript type. Any statements before the `rsx!` call are kept as comments to port by hand.
+pub fn jsx_from_rust(file: &syn::File, typescript: bool) -> String {
+    let mut components = vec![];
+    let mut uses_react_node = false;
+
+    for item in &file.items {
+        let syn::Item::Fn(item_fn) = item else {
+            continue;
+        };
+        if !is_component_fn(item_fn) {
+            continue;
+        }
+        let Some((statements, body)) = component_body(item_fn) else {
+            continue;
+        };
+
+        let props = props_of(item_fn);
+        uses_react_node |= typescript && props.iter().any(|prop| prop.ty.contains("ReactNode"));
+
+        let mut out = format!(
+            "export function {}({}) {{\n",
+            item_fn.sig.ident,
+            props_signature(&props, typescript)
+        );
+        for statement in unparse_stmts(&statements) {
+            out.push_str(INDENT);
+            out.push_str("// ");
+            out.push_str(&statement);
+            out.push('\n');
+        }
+
+        if body.body.roots.is_empty() {
+            out.push_str(INDENT);
+            out.push_str("return null;\n");
+        } else {
+            let mut writer = JsxWriter::default();
+            writer.write_root(&body.body.roots, 2);
+            out.push_str(INDENT);
+            out.push_str("return (\n");
+            out.push_str(&writer.out);
+            out.push_str(INDENT);
+            out.push_str(");\n");
+        }
+        out.push('}');
+        components.push(out);
+    }
+
+    let mut out = String::new();
+    if uses_react_node {
+        out.push_str("import type { ReactNode } from \"react\";\n\n");
+    }
+    out.push_str(&components.join("\n\n"));
+    out
+}
+
+#[derive(Default)]
+struct JsxWriter {
+    out: String,
+}
+
+impl JsxWriter {
+    fn line(&mut self, depth: usize, contents: &str) {
+        for _ in 0..depth {
+            self.out.push_str(INDENT);
+        }
+        self.out.push_str(contents);
+        self.out.push('\n');
+    }
+
+    /// JSX expressions can only have one root, so multiple nodes are wrapped in a fragment
+    fn write_root(&mut self, nodes: &[BodyNode], depth: usize) {
+        match nodes {
+            [] => self.line(depth, "null"),
+            [node] => self.write_node(node, depth),
+            nodes => {
+                self.line(depth, "<>");
+                self.write_nodes(nodes, depth + 1);
+                self.line(depth, "</>");
+            }
+        }
+    }
+
+    fn write_nodes(&mut self, nodes: &[BodyNode], depth: usize) {
+        for node in nodes {
+            self.write_node(node, depth);
+        }
+    }
+
+    fn write_node(&mut self, node: &BodyNode, depth: usize) {
+        match node {
+            BodyNode::Element(el) => self.write_element(el, depth),
+            BodyNode::Component(component) => self.write_component(component, depth),
+            BodyNode::Text(text) => self.line(depth, &jsx_text(&text.input)),
+            BodyNode::RawExpr(expr) => {
+                self.line(depth, &format!("{{{}}}", unparse_partial_expr(&expr.expr)))
+            }
+            BodyNode::ForLoop(forloop) => {
+                let pat = unparse_pat(&forloop.pat);
+                let expr = unparse_expr(iterated_collection(&forloop.expr));
+                self.line(depth, &format!("{{{expr}.map(({pat}) => ("));
+                self.write_root(&forloop.body.roots, depth + 1);
+                self.line(depth, "))}");
+            }
+            BodyNode::IfChain(ifchain) => self.write_if_chain(ifchain, depth),
+        }
+    }
+
+    fn write_if_chain(&mut self, ifchain: &IfChain, depth: usize) {
+        let cond = unparse_expr(&ifchain.cond);
+
+        // A lone `if` is the same as `cond && (...)` in JSX
+        if ifchain.else_if_branch.is_none() && ifchain.else_branch.is_none() {
+            self.line(depth, &format!("{{{cond} && ("));
+            self.write_root(&ifchain.then_branch.roots, depth + 1);
+            self.line(depth, ")}");
+            return;
+        }
+
+        self.line(depth, &format!("{{{cond} ? ("));
+        let mut chain = ifchain;
+        loop {
+            self.write_root(&chain.then_branch.roots, depth + 1);
+            if let Some(else_if) = &chain.else_if_branch {
+                let cond = unparse_expr(&else_if.cond);
+                self.line(depth, &format!(") : {cond} ? ("));
+                chain = else_if;
+            } else if let Some(else_branch) = &chain.else_branch {
+                self.line(depth, ") : (");
+                self.write_root(&else_branch.roots, depth + 1);
+                self.line(depth, ")}");
+                break;
+            } else {
+                self.line(depth, ") : null}");
+                break;
+            }
+        }
+    }
+
+    fn write_element(&mut self, el: &Element, depth: usize) {
+        let (tag, rsx_name) = match &el.name {
+            ElementName::Ident(ident) => {
+                let name = ident.to_string();
+                let tag = HtmlCtx::map_element(&name)
+                    .map(|(tag, _)| tag.to_string())
+                    .unwrap_or_else(|| name.clone());
+                (tag, Some(name))
+            }
+            ElementName::Custom(name) => (name.value(), None),
+        };
+
+        let mut attributes = jsx_attributes(rsx_name.as_deref(), &el.raw_attributes);
+        attributes.extend(
+            el.spreads
+                .iter()
+                .map(|spread| format!("{{...{}}}", unparse_expr(&spread.expr))),
+        );
+        self.write_tag(&tag, &attributes, &el.children, depth);
+    }
+
+    fn write_component(&mut self, component: &Component, depth: usize) {
+        let mut name = component.name.to_token_stream().to_string();
+        name.retain(|c| !c.is_whitespace());
+        let name = name.replace("::", ".");
+
+        let mut attributes = component
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let name = match &field.name {
+                    AttributeName::BuiltIn(name) => {
+                        let name = name.to_string();
+                        let name = name.trim_start_matches("r#");
+                        if field.name.is_likely_event() {
+                            react_event_name(name)
+                        } else if name == "class" {
+                            "className".to_string()
+                        } else {
+                            name.to_case(Case::Camel)
+                        }
+                    }
+                    AttributeName::Custom(name) => name.value(),
+                    AttributeName::Spread(_) => return None,
+                };
+                jsx_attribute(&name, &field.value)
+            })
+            .collect::<Vec<_>>();
+        attributes.extend(
+            component
+                .spreads
+                .iter()
+                .map(|spread| format!("{{...{}}}", unparse_expr(&spread.expr))),
+        );
+        self.write_tag(&name, &attributes, &component.children.roots, depth);
+    }
+
+    fn write_tag(&mut self, tag: &str, attributes: &[String], children: &[BodyNode], depth: usize) {
+        let mut open = format!("<{tag}");
+        for attribute in attributes {
+            open.push(' ');
+            open.push_str(attribute);
+        }
+
+        match children {
+            [] => self.line(depth, &format!("{open} />")),
+            // Keep elements that only contain text on one line
+            [BodyNode::Text(text)] => {
+                let text = jsx_text(&text.input);
+                self.line(depth, &format!("{open}>{text}</{tag}>"));
+            }
+            children => {
+                self.line(depth, &format!("{open}>"));
+                self.write_nodes(children, depth + 1);
+                self.line(depth, &format!("</{tag}>"));
+            }
+        }
+    }
+}
+
+fn jsx_attributes(element: Option<&str>, attributes: &[Attribute]) -> Vec<String> {
+    let mut written = vec![];
+    let mut style = vec![];
+
+    for attr in attributes {
+        let name = match &attr.name {
+            AttributeName::BuiltIn(name) => name.to_string(),
+            AttributeName::Custom(name) => {
+                written.extend(jsx_attribute(&name.value(), &attr.value));
+                continue;
+            }
+            AttributeName::Spread(_) => continue,
+        };
+        let name = name.trim_start_matches("r#");
+
+        if attr.name.is_likely_event() {
+            written.extend(jsx_attribute(&react_event_name(name), &attr.value));
+            continue;
+        }
+        if attr.name.is_likely_key() {
+            written.extend(jsx_attribute("key", &attr.value));
+            continue;
+        }
+
+        match element.and_then(|element| HtmlCtx::map_attribute(element, name)) {
+            Some((name, Some("style"))) => {
+                if let Some(value) = js_value(&attr.value) {
+                    style.push(format!("{}: {value}", name.to_case(Case::Camel)));
+                }
+            }
+            Some((name, _)) => {
+                written.extend(jsx_attribute(&jsx_attribute_name(name), &attr.value))
+            }
+            None => written.extend(jsx_attribute(&jsx_attribute_name(name), &attr.value)),
+        }
+    }
+
+    if !style.is_empty() {
+        written.push(format!("style={{{{ {} }}}}", style.join(", ")));
+    }
+
+    written
+}
+
+/// Write an attribute like `className="card"` or `onClick={...}`
+fn jsx_attribute(name: &str, value: &AttributeValue) -> Option<String> {
+    let value = js_value(value)?;
+    if value == "true" {
+        return Some(name.to_string());
+    }
+
+    // Plain strings don't need to be wrapped in braces
+    if let Some(text) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        if !text.contains(['"', '\\']) {
+            return Some(format!("{name}={value}"));
+        }
+    }
+
+    Some(format!("{name}={{{value}}}"))
+}
+
+/// Convert an attribute value into a JavaScript expression
+fn js_value(value: &AttributeValue) -> Option<String> {
+    Some(match value {
+        AttributeValue::AttrLiteral(HotLiteral::Fmted(text)) => js_string(&text.formatted_input),
+        AttributeValue::AttrLiteral(HotLiteral::Bool(value)) => value.value.to_string(),
+        AttributeValue::AttrLiteral(HotLiteral::Int(value)) => value.base10_digits().to_string(),
+        AttributeValue::AttrLiteral(HotLiteral::Float(value)) => value.base10_digits().to_string(),
+        AttributeValue::Shorthand(ident) => ident.to_string(),
+        AttributeValue::AttrExpr(expr) => unparse_partial_expr(expr),
+        AttributeValue::IfExpr(if_chain) => {
+            let cond = unparse_expr(&if_chain.condition);
+            let then = js_value(&if_chain.then_value)?;
+            let otherwise = match &if_chain.else_value {
+                Some(value) => js_value(value)?,
+                None => "undefined".to_string(),
+            };
+            format!("{cond} ? {then} : {otherwise}")
+        }
+        AttributeValue::EventTokens(closure) => {
+            let closure = unparse_closure(closure)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .replace("*/", "* /");
+            format!("() => {{ /* {closure} */ }}")
+        }
+    })
+}
+
+/// Convert a formatted string into a string or template literal
+fn js_string(input: &IfmtInput) -> String {
+    if input.is_static() {
+        let text = input
+            .segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.as_str(),
+                Segment::Formatted(_) => "",
+            })
+            .collect::<String>();
+        return format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+    }
+
+    // `"{id}"` is just `id`
+    if let [Segment::Formatted(segment)] = input.segments.as_slice() {
+        if segment.format_args.is_empty() {
+            return unparse_segment(segment);
+        }
+    }
+
+    let mut out = String::from("`");
+    for segment in &input.segments {
+        match segment {
+            Segment::Literal(text) => out.push_str(
+                &text
+                    .replace('\\', "\\\\")
+                    .replace('`', "\\`")
+                    .replace("${", "\\${"),
+            ),
+            Segment::Formatted(segment) => {
+                out.push_str("${");
+                out.push_str(&unparse_segment(segment));
+                out.push('}');
+            }
+        }
+    }
+    out.push('`');
+    out
+}
+
+/// Write a text node with the dynamic segments as `{expr}`
+fn jsx_text(input: &IfmtInput) -> String {
+    let mut out = String::new();
+    for segment in &input.segments {
+        match segment {
+            Segment::Literal(text) => {
+                for c in text.chars() {
+                    match c {
+                        '{' | '}' | '<' | '>' => {
+                            out.push_str("{\"");
+                            out.push(c);
+                            out.push_str("\"}");
+                        }
+                        c => out.push(c),
+                    }
+                }
+            }
+            Segment::Formatted(segment) => {
+                out.push('{');
+                out.push_str(&unparse_segment(segment));
+                out.push('}');
+            }
+        }
+    }
+    out
+}
+
+/// Convert a dioxus event name like `onmouseenter` into the React name `onMouseEnter`
+pub(crate) fn react_event_name(name: &str) -> String {
+    REACT_EVENTS
+        .iter()
+        .find(|event| event.eq_ignore_ascii_case(name))
+        .map(|event| event.to_string())
+        .unwrap_or_else(|| {
+            let event = name.trim_start_matches("on");
+            let mut chars = event.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+            format!(
+                "on{}{}",
+                first.into_iter().collect::<String>(),
+                chars.as_str()
+            )
+        })
+}
+
+/// Convert an html attribute name into the name React uses
+pub(crate) fn jsx_attribute_name(html: &str) -> String {
+    if let Some((_, jsx)) = JSX_ATTRIBUTES.iter().find(|(name, _)| *name == html) {
+        return jsx.to_string();
+    }
+    if html.starts_with("data-") || html.starts_with("aria-") {
+        return html.to_string();
+    }
+    // Svg attributes like `stroke-width` and `xlink:href` are camelCase in React
+    if html.contains(['-', ':']) {
+        return html.replace(':', "-").to_case(Case::Camel);
+    }
+    html.to_string()
+}
+
+/// Split the body of a component into the statements before the `rsx!` call and the rsx itself
+pub(crate) fn component_body(item_fn: &ItemFn) -> Option<(Vec<Stmt>, CallBody)> {
+    let (last, statements) = item_fn.block.stmts.split_last()?;
+    let mac = match last {
+        Stmt::Expr(syn::Expr::Macro(mac), None) => &mac.mac,
+        Stmt::Macro(mac) => &mac.mac,
+        _ => return None,
+    };
+    if !mac
+        .path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "rsx")
+    {
+        return None;
+    }
+    let body = CallBody::parse_strict.parse2(mac.tokens.clone()).ok()?;
+    Some((statements.to_vec(), body))
+}
+
+struct Prop {
+    /// The camelCase name of the prop in React
+    name: String,
+    /// The name of the argument in rust, which the body of the component still refers to
+    binding: String,
+    ty: String,
+    optional: bool,
+}
+
+fn props_of(item_fn: &ItemFn) -> Vec<Prop> {
+    item_fn
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|input| {
+            let FnArg::Typed(input) = input else {
+                return None;
+            };
+            let Pat::Ident(pat) = input.pat.as_ref() else {
+                return None;
+            };
+            let (ty, optional) = match option_inner(&input.ty) {
+                Some(inner) => (ts_type(inner), true),
+                None => (ts_type(&input.ty), false),
+            };
+            let binding = pat.ident.to_string();
+            Some(Prop {
+                name: binding.to_case(Case::Camel),
+                binding,
+                ty,
+                optional,
+            })
+        })
+        .collect()
+}
+
+fn props_signature(props: &[Prop], typescript: bool) -> String {
+    if props.is_empty() {
+        return String::new();
+    }
+
+    // Rename camelCase props back to their rust names so the expressions in the body still refer to them
+    let names = props
+        .iter()
+        .map(|prop| match prop.name == prop.binding {
+            true => prop.name.clone(),
+            false => format!("{}: {}", prop.name, prop.binding),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    if !typescript {
+        return format!("{{ {names} }}");
+    }
+
+    let types = props
+        .iter()
+        .map(|prop| {
+            let optional = if prop.optional { "?" } else { "" };
+            format!("{}{optional}: {}", prop.name, prop.ty)
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    format!("{{ {names} }}: {{ {types} }}")
+}
+
+/// The type inside of an `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let [inner] = generic_arguments(ty, "Option")?[..] else {
+        return None;
+    };
+    Some(inner)
+}
+
+/// The generic arguments of a type if the last segment of its path is `name`
+fn generic_arguments<'a>(ty: &'a Type, name: &str) -> Option<Vec<&'a Type>> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return Some(vec![]);
+    };
+    Some(
+        arguments
+            .args
+            .iter()
+            .filter_map(|argument| match argument {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+    )
+}
+
+/// The closest TypeScript type to a rust type
+fn ts_type(ty: &Type) -> String {
+    match ty {
+        Type::Reference(reference) => return ts_type(&reference.elem),
+        Type::Slice(slice) => return format!("{}[]", ts_type(&slice.elem)),
+        Type::Array(array) => return format!("{}[]", ts_type(&array.elem)),
+        _ => {}
+    }
+
+    let Type::Path(path) = ty else {
+        return "unknown".to_string();
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return "unknown".to_string();
+    };
+
+    // Signals are read like the value they hold
+    for wrapper in ["Signal", "ReadOnlySignal", "ReadSignal", "Memo", "Vec"] {
+        if let Some([inner]) = generic_arguments(ty, wrapper).as_deref() {
+            return match wrapper {
+                "Vec" => format!("{}[]", ts_type(inner)),
+                _ => ts_type(inner),
+            };
+        }
+    }
+    if let Some([inner]) = generic_arguments(ty, "Option").as_deref() {
+        return format!("{} | undefined", ts_type(inner));
+    }
+
+    match segment.ident.to_string().as_str() {
+        "String" | "str" | "char" => "string",
+        "bool" => "boolean",
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" | "f32" | "f64" => "number",
+        "Element" => "ReactNode",
+        "EventHandler" | "Callback" => "(event?: any) => void",
+        other => return other.to_string(),
+    }
+    .to_string()
+}
@@ -0,0 +1,83 @@
+//! Helpers to write the rust parts of rsx back out as source code
+
+use dioxus_rsx::{FormattedSegment, FormattedSegmentType, PartialClosure, PartialExpr};
+use quote::ToTokens;
+use syn::{parse_quote, Expr, Pat, Stmt};
+
+/// Pretty print a rust expression
+pub(crate) fn unparse_expr(expr: &Expr) -> String {
+    let file: syn::File = parse_quote! { const _: () = #expr; };
+    let unparsed = prettyplease::unparse(&file);
+    let unparsed = unparsed.trim();
+    unparsed
+        .strip_prefix("const _: () = ")
+        .and_then(|expr| expr.strip_suffix(';'))
+        .unwrap_or(unparsed)
+        .to_string()
+}
+
+/// Pretty print a list of statements, one line per entry
+pub(crate) fn unparse_stmts(statements: &[Stmt]) -> Vec<String> {
+    if statements.is_empty() {
+        return vec![];
+    }
+
+    let file: syn::File = syn::parse_quote! { fn __() { #(#statements)* } };
+    let unparsed = prettyplease::unparse(&file);
+    let lines = unparsed.lines().collect::<Vec<_>>();
+    lines[1..lines.len().saturating_sub(1)]
+        .iter()
+        .map(|line| line.strip_prefix("    ").unwrap_or(line).to_string())
+        .collect()
+}
+
+/// Pretty print an expression in rsx that might not have finished parsing
+pub(crate) fn unparse_partial_expr(expr: &PartialExpr) -> String {
+    match expr.as_expr() {
+        Ok(expr) => unparse_expr(&expr),
+        Err(_) => expr.to_token_stream().to_string(),
+    }
+}
+
+/// Pretty print an event handler in rsx
+pub(crate) fn unparse_closure(closure: &PartialClosure) -> String {
+    match closure.as_expr() {
+        Ok(expr) => unparse_expr(&expr),
+        Err(_) => closure.to_token_stream().to_string(),
+    }
+}
+
+/// Write a pattern like `(index, item)` without the extra spaces from the token stream
+pub(crate) fn unparse_pat(pat: &Pat) -> String {
+    pat.to_token_stream()
+        .to_string()
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
+
+/// Write the contents of a formatted segment like `{name}` or `{value:.2}` without the braces
+pub(crate) fn unparse_segment(segment: &FormattedSegment) -> String {
+    let mut out = match &segment.segment {
+        FormattedSegmentType::Ident(ident) => ident.to_string(),
+        FormattedSegmentType::Expr(expr) => unparse_expr(expr),
+    };
+    if !segment.format_args.is_empty() {
+        out.push(':');
+        out.push_str(&segment.format_args);
+    }
+    out
+}
+
+/// Strip a trailing `.iter()` or `.into_iter()` from the expression a loop iterates over
+pub(crate) fn iterated_collection(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::MethodCall(call)
+            if call.args.is_empty() && (call.method == "iter" || call.method == "into_iter") =>
+        {
+            &call.receiver
+        }
+        _ => expr,
+    }
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "jsx")]
+
+#[test]
+fn counter_component() {
+    let jsx = r#"
+export function Counter({ label, initial = 0 }) {
+  const [count, setCount] = useState(initial);
+  return (
+    <div className="counter">
+      <h1>{label}: {count}</h1>
+      <button onClick={() => setCount(count + 1)}>Increment</button>
+      {count > 10 && <p>That's a lot!</p>}
+      <ul>
+        {items.map((item) => <li key={item.id}>{item.name}</li>)}
+      </ul>
+    </div>
+  );
+}
+"#;
+    let out = dioxus_rsx_rosetta::rsx_from_jsx(jsx, false).unwrap();
+
+    for expected in [
+        "use dioxus::prelude::*;",
+        "#[component]\npub fn Counter(label: String, #[props(default = 0.0)] initial: f64) -> Element {",
+        "let mut count = use_signal(|| initial);",
+        r#"class: "counter""#,
+        r#"h1 { "{label}: {count()}" }"#,
+        "count.set(count() + 1)",
+        "if count() > 10 {",
+        "for item in items.iter() {",
+        r#"li { key: "{item.id}", "{item.name}" }"#,
+    ] {
+        assert!(out.contains(expected), "missing {expected:?} in:\n{out}");
+    }
+}
+
+#[test]
+fn typed_props() {
+    let tsx = r#"
+interface ButtonProps {
+  label: string;
+  disabled?: boolean;
+  onClick: (event: React.MouseEvent) => void;
+}
+
+export const Button = ({ label, disabled, onClick }: ButtonProps) => (
+  <button disabled={disabled} onClick={onClick} style={{ marginTop: 4, opacity: 0.5 }}>
+    {disabled ? <span>{label}</span> : label}
+  </button>
+);
+"#;
+    let out = dioxus_rsx_rosetta::rsx_from_jsx(tsx, true).unwrap();
+
+    for expected in [
+        "label: String",
+        "disabled: Option<bool>",
+        "on_click: EventHandler<MouseEvent>",
+        "onclick: move |event| on_click.call(event)",
+        r#"style: "margin-top: 4px; opacity: 0.5;""#,
+        "if disabled {",
+        "} else {",
+    ] {
+        assert!(out.contains(expected), "missing {expected:?} in:\n{out}");
+    }
+}
+
+#[test]
+fn parse_errors() {
+    assert!(dioxus_rsx_rosetta::rsx_from_jsx("const = <div", false).is_err());
+    assert!(matches!(
+        dioxus_rsx_rosetta::rsx_from_jsx("const x = 1;", false),
+        Err(dioxus_rsx_rosetta::JsxError::NoComponents)
+    ));
+}
+
+#[test]
+fn string_escapes() {
+    let jsx = r#"
+export function Paths() {
+  return (
+    <ul>
+      <li>{"C:\\new"}</li>
+      <li>{"it\'s \"quoted\""}</li>
+      <li>{"\x41\u0042\u{43}"}</li>
+    </ul>
+  );
+}
+"#;
+    let out = dioxus_rsx_rosetta::rsx_from_jsx(jsx, false).unwrap();
+
+    for expected in [
+        r#"li { "C:\\new" }"#,
+        r#"li { "it's \"quoted\"" }"#,
+        r#"li { "ABC" }"#,
+    ] {
+        assert!(out.contains(expected), "missing {expected:?} in:\n{out}");
+    }
+}
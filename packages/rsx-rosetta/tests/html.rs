@@ -0,0 +1,65 @@
+use dioxus_rsx::CallBody;
+use syn::parse::Parser;
+
+fn html(rsx: &str) -> String {
+    let body = CallBody::parse_strict.parse_str(rsx).unwrap();
+    dioxus_rsx_rosetta::html_from_rsx(&body)
+}
+
+#[test]
+fn static_elements() {
+    let out = html(
+        r#"
+        div { class: "card", id: "main",
+            h1 { "Hello {name}!" }
+            img { src: "logo.png" }
+            button { disabled: true, onclick: move |_| {}, "Click" }
+        }
+        "#,
+    );
+
+    let expected = r#"<div class="card" id="main">
+  <h1>Hello {name}!</h1>
+  <img src="logo.png">
+  <button disabled>Click</button>
+</div>"#;
+    pretty_assertions::assert_eq!(&out, &expected);
+}
+
+#[test]
+fn style_attributes() {
+    let out = html(r#"div { color: "red", margin_top: "4px", "<hi>" }"#);
+
+    let expected = r#"<div style="color: red; margin-top: 4px;">&lt;hi&gt;</div>"#;
+    pretty_assertions::assert_eq!(&out, &expected);
+}
+
+#[test]
+fn control_flow() {
+    let out = html(
+        r#"
+        ul {
+            for item in items.iter() {
+                li { "{item}" }
+            }
+            if show {
+                p { "shown" }
+            } else {
+                p { "hidden" }
+            }
+        }
+        "#,
+    );
+
+    let expected = r#"<ul>
+  <!-- for item in items -->
+    <li>{item}</li>
+  <!-- /for -->
+  <!-- if show -->
+    <p>shown</p>
+  <!-- else -->
+    <p>hidden</p>
+  <!-- /if -->
+</ul>"#;
+    pretty_assertions::assert_eq!(&out, &expected);
+}
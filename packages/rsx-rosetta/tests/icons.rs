@@ -0,0 +1,67 @@
+use dioxus_rsx_rosetta::{icons_module, icons_sprite, Icon};
+
+const ARROW: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" viewBox="0 0 16 16" fill="#333">
+  <path d="M1 1h14v14H1z"/>
+  <circle cx="8" cy="8" r="4" fill="#333"/>
+</svg>
+"##;
+
+#[test]
+fn icon_component() {
+    let icon = Icon::from_svg("arrow-left", ARROW).unwrap();
+    assert_eq!(icon.name, "ArrowLeft");
+    assert_eq!(icon.id, "arrow-left");
+
+    let out = icons_module(&[icon], None);
+    for expected in [
+        "use dioxus::prelude::*;",
+        "pub fn ArrowLeft(",
+        "#[props(default = 16)] size: u32,",
+        r#"#[props(default = "currentColor".to_string())] fill: String,"#,
+        "#[props(default)] class: String,",
+        r#"view_box: "0 0 16 16","#,
+        r#"width: "{size}","#,
+        r#"fill: "{fill}","#,
+        r#"path { d: "M1 1h14v14H1z" }"#,
+        r#"fill: "currentColor""#,
+    ] {
+        assert!(out.contains(expected), "missing {expected:?} in:\n{out}");
+    }
+
+    // Single color icons follow the text color and the namespaces aren't needed inline
+    assert!(!out.contains("#333"), "{out}");
+    assert!(!out.contains("xmlns"), "{out}");
+}
+
+#[test]
+fn namespace_declarations_are_removed() {
+    let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:svg="http://www.w3.org/2000/svg" viewBox="0 0 24 24" stroke="currentColor">
+  <path d="M2 2h20v20H2z"/>
+</svg>
+"##;
+    let icon = Icon::from_svg("square", svg).unwrap();
+
+    let out = icons_module(&[icon], None);
+    assert!(!out.contains("xmlns"), "{out}");
+    assert!(out.contains(r#"stroke: "currentColor","#), "{out}");
+}
+
+#[test]
+fn icon_sprite() {
+    let icons = [Icon::from_svg("arrow-left", ARROW).unwrap()];
+
+    let sprite = icons_sprite(&icons);
+    assert!(sprite.contains(r#"<symbol id="arrow-left" viewBox="0 0 16 16">"#));
+    assert!(sprite.contains(r#"<path d="M1 1h14v14H1z"></path>"#));
+
+    let out = icons_module(&icons, Some("/assets/icons.svg"));
+    assert!(out.contains(r#"const SPRITE: Asset = asset!("/assets/icons.svg");"#));
+    assert!(out.contains(r#"r#use { href: "{SPRITE}#arrow-left" }"#));
+    assert!(!out.contains("M1 1h14v14H1z"));
+}
+
+#[test]
+fn not_an_svg() {
+    assert!(Icon::from_svg("empty", "<div></div>").is_none());
+}
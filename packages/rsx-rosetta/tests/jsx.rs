@@ -0,0 +1,83 @@
+use dioxus_rsx::CallBody;
+use syn::parse::Parser;
+
+#[test]
+fn rsx_to_jsx() {
+    let body = CallBody::parse_strict
+        .parse_str(
+            r#"
+            div { class: "card", onclick: move |_| count += 1,
+                h1 { "Count: {count}" }
+                for item in items.iter() {
+                    li { key: "{item.id}", "{item.name}" }
+                }
+                if loading {
+                    span { "Loading" }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+    let out = dioxus_rsx_rosetta::jsx_from_rsx(&body);
+
+    let expected = r#"<div className="card" onClick={() => { /* move |_| count += 1 */ }}>
+  <h1>Count: {count}</h1>
+  {items.map((item) => (
+    <li key={item.id}>{item.name}</li>
+  ))}
+  {loading && (
+    <span>Loading</span>
+  )}
+</div>"#;
+    pretty_assertions::assert_eq!(&out, &expected);
+}
+
+#[test]
+fn component_to_tsx() {
+    let file = syn::parse_file(
+        r#"
+        #[component]
+        fn Card(title: String, count: Option<i32>) -> Element {
+            let mut open = use_signal(|| false);
+            rsx! {
+                div { class: "card", "{title}" }
+            }
+        }
+        "#,
+    )
+    .unwrap();
+    let out = dioxus_rsx_rosetta::jsx_from_rust(&file, true);
+
+    let expected = r#"export function Card({ title, count }: { title: string; count?: number }) {
+  // let mut open = use_signal(|| false);
+  return (
+    <div className="card">{title}</div>
+  );
+}"#;
+    pretty_assertions::assert_eq!(&out, &expected);
+}
+
+#[test]
+fn multi_word_props_keep_their_rust_names() {
+    let file = syn::parse_file(
+        r#"
+        fn Card(user_name: String, is_admin: bool) -> Element {
+            rsx! {
+                div { "data-admin": is_admin, "{user_name}" }
+            }
+        }
+        "#,
+    )
+    .unwrap();
+
+    let expected = r#"export function Card({ userName: user_name, isAdmin: is_admin }) {
+  return (
+    <div data-admin={is_admin}>{user_name}</div>
+  );
+}"#;
+    pretty_assertions::assert_eq!(&dioxus_rsx_rosetta::jsx_from_rust(&file, false), &expected);
+
+    let expected = r#"export function Card({ userName: user_name, isAdmin: is_admin }: { userName: string; isAdmin: boolean }) {"#;
+    let out = dioxus_rsx_rosetta::jsx_from_rust(&file, true);
+    pretty_assertions::assert_eq!(out.lines().next().unwrap(), expected);
+}
@@ -349,6 +349,24 @@ impl Component {
     }
 }
 
+/// Check if a function is a component: it either has the `#[component]` attribute or returns an `Element`
+pub fn is_component_fn(item_fn: &syn::ItemFn) -> bool {
+    let has_attribute = item_fn.attrs.iter().any(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "component")
+    });
+    let returns_element = match &item_fn.sig.output {
+        syn::ReturnType::Type(_, ty) => matches!(
+            ty.as_ref(),
+            syn::Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "Element")
+        ),
+        syn::ReturnType::Default => false,
+    };
+    has_attribute || returns_element
+}
+
 /// Normalize the generics of a path
 ///
 /// Ensure there's a `::` after the last segment if there are generics
@@ -143,7 +143,7 @@ async fn serve_server(
                     DevserverMsg::HotReload(hot_reload_msg) => {
                         if hot_reload_msg.for_build_id == Some(dioxus_cli_config::build_id()) {
                             if let Some(table) = hot_reload_msg.jump_table {
-                                unsafe { dioxus_devtools::subsecond::apply_patch(table).unwrap() };
+                                unsafe { dioxus_devtools::apply_patch(table).unwrap() };
 
                                 let mut new_router = axum::Router::new().serve_static_assets();
                                 let new_cfg = ServeConfig::new().unwrap();
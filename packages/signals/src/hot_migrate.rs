@@ -0,0 +1,241 @@
+//! Keep the value of signals across hot patches that change their type.
+//!
+//! Hot patching swaps the code of a running app, but values that are already in memory keep the layout of the old
+//! code. Signals created with [`use_hot_signal`] are serialized before a patch is applied. The next time their
+//! component runs with the new code, every signal is replaced with a new signal that holds the value deserialized by
+//! the new code. A patch can change a type without changing its size or alignment (`u64` to `f64`, reordered enum
+//! variants), so the old value is never read by the new code.
+
+use crate::{Readable, Signal};
+use dioxus_core::use_hook;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
+
+/// A value that can be moved across hot patches by serializing it with the old code and deserializing it with the new
+/// code.
+///
+/// This is implemented for every type that implements [`Serialize`] and [`DeserializeOwned`], so deriving both is
+/// enough to opt into migrations:
+///
+/// ```rust, ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct Todo {
+///     title: String,
+///     done: bool,
+/// }
+/// ```
+pub trait HotMigrate: Serialize + DeserializeOwned + 'static {}
+
+impl<T: Serialize + DeserializeOwned + 'static> HotMigrate for T {}
+
+/// Creates a new [`Signal`] whose value is kept when a hot patch changes `T`.
+///
+/// Without a migration, the app needs to restart when a type held in a signal changes. Fields that are added to `T`
+/// need `#[serde(default)]` to be filled in. If the old value can't be serialized before the patch or deserialized
+/// into the new type, the signal is reset to the value returned by `f`.
+///
+/// ```rust, ignore
+/// fn App() -> Element {
+///     let mut todos = use_hot_signal(Vec::<Todo>::new);
+///
+///     rsx! {
+///         for todo in todos.iter() {
+///             "{todo.title}"
+///         }
+///     }
+/// }
+/// ```
+///
+/// Only the handle returned by the latest run of the component is migrated. Tasks that captured the signal before a
+/// patch keep pointing to the old value.
+#[track_caller]
+#[must_use]
+pub fn use_hot_signal<T: HotMigrate>(f: impl FnOnce() -> T) -> Signal<T> {
+    let mut init = Some(f);
+    let slot = use_hook(|| {
+        let init = init.take().expect("the initializer is only taken once");
+        let slot = Rc::new(HotSignal {
+            id: REGISTRY.with_borrow_mut(|registry| registry.next_id()),
+            signal: Cell::new(Signal::new(init())),
+        });
+        slot.register();
+        slot
+    });
+
+    if let Some(init) = init {
+        slot.restore(init);
+    }
+
+    slot.signal.get()
+}
+
+/// Serialize the value of every hot signal with the current code. This must be called before a patch is applied.
+///
+/// Only the signals created on the current thread are saved, so this needs to run on the thread of the app.
+#[doc(hidden)]
+pub fn save_hot_signals() {
+    REGISTRY.with_borrow_mut(|registry| {
+        registry
+            .live
+            .retain(|_, signal| signal.owner.strong_count() > 0);
+
+        registry.saved = registry
+            .live
+            .iter()
+            .map(|(id, signal)| (*id, (signal.save)()))
+            .collect();
+    })
+}
+
+/// The state of a hot signal stored in the hook. This doesn't store `T` directly, so the layout stays the same if `T`
+/// changes.
+struct HotSignal<T: 'static> {
+    id: usize,
+    signal: Cell<Signal<T>>,
+}
+
+impl<T: HotMigrate> HotSignal<T> {
+    /// Replace the signal with the value saved before the last patch, if a patch was applied since the last time this
+    /// ran
+    fn restore(self: &Rc<Self>, init: impl FnOnce() -> T) {
+        let Some(saved) = REGISTRY.with_borrow_mut(|registry| registry.saved.remove(&self.id))
+        else {
+            return;
+        };
+
+        // The memory of the old value may have a different meaning in the new code even if the layout matches, so
+        // always replace it with the deserialized value
+        let value = match saved.map(|saved| serde_json::from_str::<T>(&saved)) {
+            Some(Ok(value)) => value,
+            Some(Err(err)) => {
+                tracing::warn!(
+                    "Failed to migrate the value of a hot signal to the new version of {}: {err}. Resetting it to the initial value.",
+                    std::any::type_name::<T>()
+                );
+                init()
+            }
+            None => {
+                tracing::warn!(
+                    "Failed to save the value of a hot signal of type {} before the patch. Resetting it to the initial value.",
+                    std::any::type_name::<T>()
+                );
+                init()
+            }
+        };
+        self.signal.set(Signal::new(value));
+
+        // Serialize the value with the new code before the next patch
+        self.register();
+    }
+
+    /// Register the signal with the serializer from the current code, replacing any old version
+    fn register(self: &Rc<Self>) {
+        let weak = Rc::downgrade(self);
+        let save = move || {
+            let slot = weak.upgrade()?;
+            let signal = slot.signal.get();
+            let value = signal.try_peek().ok()?;
+            serde_json::to_string(&*value).ok()
+        };
+
+        let owner: Rc<dyn Any> = self.clone();
+        let live = LiveSignal {
+            owner: Rc::downgrade(&owner),
+            save: Box::new(save),
+        };
+        REGISTRY.with_borrow_mut(|registry| registry.live.insert(self.id, live));
+    }
+}
+
+struct LiveSignal {
+    owner: Weak<dyn Any>,
+    save: Box<dyn Fn() -> Option<String>>,
+}
+
+#[derive(Default)]
+struct Registry {
+    last_id: usize,
+    live: HashMap<usize, LiveSignal>,
+    /// The values serialized before the last patch. `None` if the value couldn't be serialized
+    saved: HashMap<usize, Option<String>>,
+}
+
+impl Registry {
+    fn next_id(&mut self) -> usize {
+        self.last_id += 1;
+        self.last_id
+    }
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry::default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::{ScopeId, VNode, VirtualDom};
+
+    /// Create a hot signal as if its hook ran for the first time
+    fn hot_signal<T: HotMigrate>(id: usize, value: T) -> Rc<HotSignal<T>> {
+        let slot = Rc::new(HotSignal {
+            id,
+            signal: Cell::new(Signal::new(value)),
+        });
+        slot.register();
+        slot
+    }
+
+    fn in_scope(f: impl FnOnce()) {
+        let dom = VirtualDom::new(VNode::empty);
+        dom.in_runtime(|| ScopeId::ROOT.in_runtime(f));
+    }
+
+    #[test]
+    fn same_layout_type_change_is_migrated() {
+        in_scope(|| {
+            let id = REGISTRY.with_borrow_mut(|registry| registry.next_id());
+            let old = hot_signal(id, 5u64);
+            save_hot_signals();
+
+            // Pretend a patch changed the type of the signal from u64 to f64. Both have the same layout, but the
+            // old bits would be read as a different number
+            let new = hot_signal(id, f64::from_bits(5));
+            drop(old);
+            new.restore(|| 0.0);
+
+            assert_eq!(*new.signal.get().peek(), 5.0);
+        });
+    }
+
+    #[test]
+    fn failed_migration_resets_to_initial_value() {
+        in_scope(|| {
+            let id = REGISTRY.with_borrow_mut(|registry| registry.next_id());
+            let old = hot_signal(id, "hello".to_string());
+            save_hot_signals();
+
+            let new = hot_signal(id, 0u64);
+            drop(old);
+            new.restore(|| 42);
+
+            assert_eq!(*new.signal.get().peek(), 42);
+        });
+    }
+
+    #[test]
+    fn signals_are_kept_without_a_patch() {
+        in_scope(|| {
+            let id = REGISTRY.with_borrow_mut(|registry| registry.next_id());
+            let slot = hot_signal(id, 5u64);
+            slot.restore(|| 0);
+
+            assert_eq!(*slot.signal.get().peek(), 5);
+        });
+    }
+}
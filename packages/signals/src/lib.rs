@@ -40,4 +40,9 @@ pub use write::*;
 mod props;
 pub use props::*;
 
+#[cfg(feature = "hot-migrate")]
+mod hot_migrate;
+#[cfg(feature = "hot-migrate")]
+pub use hot_migrate::*;
+
 pub mod warnings;
@@ -0,0 +1,198 @@
+//! Render components to pngs and compare them pixel by pixel.
+
+use crate::SnapshotError;
+use blitz_traits::shell::{ColorScheme, Viewport};
+use dioxus_core::{Element, VirtualDom};
+use dioxus_native_dom::{DioxusDocument, DocumentConfig};
+use parley::{
+    fontique::{Blob, Collection, CollectionOptions, GenericFamily, SourceCache},
+    FontContext,
+};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::Path,
+    sync::Arc,
+};
+
+/// The only font snapshots are rendered with, so the images don't depend on the fonts installed on the machine
+const FONT: &[u8] = include_bytes!("../assets/DejaVuSans.ttf");
+
+/// An 8 bit RGBA image
+pub(crate) struct Image {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) pixels: Vec<u8>,
+}
+
+impl Image {
+    pub(crate) fn read_png(path: &Path) -> Result<Self, SnapshotError> {
+        let decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        let mut reader = decoder.read_info()?;
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels)?;
+
+        if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+            return Err(SnapshotError::UnsupportedPng(path.to_path_buf()));
+        }
+        pixels.truncate(info.buffer_size());
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+
+    pub(crate) fn write_png(&self, path: &Path) -> Result<(), SnapshotError> {
+        let mut encoder =
+            png::Encoder::new(BufWriter::new(File::create(path)?), self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+}
+
+/// Render a component to an image with the CPU renderer
+pub(crate) fn render_image(app: fn() -> Element, width: u32, height: u32, scale: f32) -> Image {
+    let viewport = Viewport::new(width, height, scale, ColorScheme::Light);
+    let mut doc = DioxusDocument::new(
+        VirtualDom::new(app),
+        DocumentConfig {
+            viewport: Some(viewport.clone()),
+            font_ctx: Some(font_context()),
+            ..Default::default()
+        },
+    );
+    doc.initial_build();
+
+    Image {
+        width,
+        height,
+        pixels: doc.render_to_buffer(viewport),
+    }
+}
+
+/// Create a font context without system fonts that resolves every generic family to the bundled font
+fn font_context() -> FontContext {
+    let mut collection = Collection::new(CollectionOptions {
+        shared: false,
+        system_fonts: false,
+    });
+    let families: Vec<_> = collection
+        .register_fonts(Blob::new(Arc::new(FONT)), None)
+        .into_iter()
+        .map(|(family, _)| family)
+        .collect();
+    for generic in GenericFamily::all() {
+        collection.set_generic_families(*generic, families.iter().copied());
+    }
+
+    FontContext {
+        collection,
+        source_cache: SourceCache::default(),
+    }
+}
+
+/// Count the pixels that differ by more than `threshold` in any channel and create an image that highlights them.
+///
+/// The diff is a faded copy of the expected image with the pixels that differ painted red.
+pub(crate) fn diff_images(expected: &Image, actual: &Image, threshold: u8) -> (usize, Image) {
+    let mut different = 0;
+    let mut pixels = Vec::with_capacity(expected.pixels.len());
+
+    for (expected, actual) in expected
+        .pixels
+        .chunks_exact(4)
+        .zip(actual.pixels.chunks_exact(4))
+    {
+        let differs = expected
+            .iter()
+            .zip(actual)
+            .any(|(expected, actual)| expected.abs_diff(*actual) > threshold);
+
+        if differs {
+            different += 1;
+            pixels.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            // Blend the pixel towards white so the highlighted pixels stand out
+            let luma =
+                (expected[0] as u32 * 299 + expected[1] as u32 * 587 + expected[2] as u32 * 114)
+                    / 1000;
+            let faded = (255 - (255 - luma) / 4) as u8;
+            pixels.extend_from_slice(&[faded, faded, faded, 255]);
+        }
+    }
+
+    let diff = Image {
+        width: expected.width,
+        height: expected.height,
+        pixels,
+    };
+
+    (different, diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::prelude::{dioxus_elements, rsx};
+
+    fn image(pixels: &[[u8; 4]]) -> Image {
+        Image {
+            width: pixels.len() as u32,
+            height: 1,
+            pixels: pixels.concat(),
+        }
+    }
+
+    fn text() -> Element {
+        rsx! { p { style: "margin: 4px; font-family: sans-serif;", "Snapshot" } }
+    }
+
+    #[test]
+    fn renders_text_with_the_bundled_font() {
+        let first = render_image(text, 100, 40, 1.0);
+        let second = render_image(text, 100, 40, 1.0);
+
+        assert_eq!((first.width, first.height), (100, 40));
+        assert_eq!(first.pixels.len(), 100 * 40 * 4);
+        assert_eq!(first.pixels, second.pixels);
+
+        // The text is drawn in black on a white page
+        let dark = first
+            .pixels
+            .chunks_exact(4)
+            .filter(|pixel| pixel[0] < 128)
+            .count();
+        assert!(dark > 0, "the text was not rendered");
+    }
+
+    #[test]
+    fn identical_images_have_no_differences() {
+        let expected = image(&[[0, 0, 0, 255], [255, 255, 255, 255]]);
+        let actual = image(&[[0, 0, 0, 255], [255, 255, 255, 255]]);
+
+        let (different, diff) = diff_images(&expected, &actual, 0);
+        assert_eq!(different, 0);
+        assert_eq!(diff.pixels.len(), expected.pixels.len());
+    }
+
+    #[test]
+    fn threshold_ignores_small_changes() {
+        let expected = image(&[[100, 100, 100, 255], [0, 0, 0, 255]]);
+        let actual = image(&[[102, 99, 100, 255], [0, 0, 200, 255]]);
+
+        let (different, diff) = diff_images(&expected, &actual, 2);
+        assert_eq!(different, 1);
+        assert_eq!(&diff.pixels[4..], &[255, 0, 0, 255]);
+
+        let (different, _) = diff_images(&expected, &actual, 0);
+        assert_eq!(different, 2);
+    }
+}
@@ -0,0 +1,433 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+//! Snapshot tests for Dioxus components.
+//!
+//! A [`Snapshot`] renders a component to html with `dioxus-ssr` and to a png with the CPU renderer of
+//! `dioxus-native-dom`. Neither needs a browser or a GPU, so the tests run anywhere `cargo test` does.
+//!
+//! The first run writes the snapshots to `tests/snapshots` in the crate being tested. Later runs compare the output
+//! with the stored snapshots and fail if the html changed or too many pixels differ. When the image doesn't match, a
+//! diff image that highlights the changed pixels is written next to the snapshot. Set the `DIOXUS_UPDATE_SNAPSHOTS`
+//! environment variable to accept the new output. When the `CI` environment variable is set, a missing snapshot fails
+//! the test instead of being written.
+//!
+//! Text in the png is always rendered with DejaVu Sans, which is bundled with this crate, so the images don't depend
+//! on the fonts installed on the machine.
+//!
+//! ```rust, no_run
+//! use dioxus::prelude::*;
+//! use dioxus_snapshot::Snapshot;
+//!
+//! fn app() -> Element {
+//!     rsx! { button { "Click me" } }
+//! }
+//!
+//! #[test]
+//! fn button() {
+//!     Snapshot::new("button", app)
+//!         .with_viewport(200, 100)
+//!         .assert();
+//! }
+//! ```
+//!
+//! ## Feature flags
+//!  - `native` (default): Renders a png of the component in addition to the html.
+
+#[cfg(feature = "native")]
+mod image;
+
+use dioxus_core::{Element, VirtualDom};
+use std::path::{Path, PathBuf};
+
+/// Set this environment variable to overwrite the stored snapshots with the new output instead of comparing them.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "DIOXUS_UPDATE_SNAPSHOTS";
+
+/// Missing snapshots fail instead of being written when this environment variable is set, which most CI providers do.
+pub const CI_ENV: &str = "CI";
+
+/// A snapshot test of a component.
+#[must_use = "A snapshot does nothing until it is checked"]
+pub struct Snapshot {
+    name: String,
+    app: fn() -> Element,
+    dir: PathBuf,
+    width: u32,
+    height: u32,
+    scale: f32,
+    threshold: u8,
+    max_diff_pixels: usize,
+    update: bool,
+    fail_on_missing: bool,
+}
+
+impl Snapshot {
+    /// Create a snapshot test named `name` for the component. The name is used as the file name of the snapshots.
+    pub fn new(name: impl Into<String>, app: fn() -> Element) -> Self {
+        // Cargo sets the manifest dir of the crate being tested when running the tests
+        let crate_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+
+        Self {
+            name: name.into(),
+            app,
+            dir: crate_dir.join("tests").join("snapshots"),
+            width: 800,
+            height: 600,
+            scale: 1.0,
+            threshold: 0,
+            max_diff_pixels: 0,
+            update: std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some(),
+            fail_on_missing: std::env::var_os(CI_ENV).is_some(),
+        }
+    }
+
+    /// Set the size of the viewport the png is rendered at [default: 800x600]
+    pub fn with_viewport(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Set the scale factor the png is rendered at [default: 1.0]
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set how much a color channel of a pixel can change before the pixel counts as different [default: 0]
+    ///
+    /// Text is antialiased slightly differently by different font rasterizers, so a small threshold keeps snapshots
+    /// stable across machines.
+    pub fn with_threshold(mut self, threshold: u8) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set how many pixels can differ from the snapshot before the test fails [default: 0]
+    pub fn with_max_diff_pixels(mut self, max_diff_pixels: usize) -> Self {
+        self.max_diff_pixels = max_diff_pixels;
+        self
+    }
+
+    /// Set the directory the snapshots are stored in [default: `tests/snapshots` in the crate being tested]
+    pub fn with_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = dir.into();
+        self
+    }
+
+    /// Render the component and compare it with the stored snapshots, panicking if they don't match.
+    #[track_caller]
+    pub fn assert(&self) {
+        if let Err(err) = self.check() {
+            panic!("{err}");
+        }
+    }
+
+    /// Render the component and compare it with the stored snapshots.
+    ///
+    /// Both the html and the image are checked even if the html doesn't match. If both fail, the errors are returned
+    /// together in [`SnapshotError::Multiple`].
+    pub fn check(&self) -> Result<(), SnapshotError> {
+        std::fs::create_dir_all(&self.dir)?;
+
+        let mut errors = Vec::new();
+        errors.extend(self.check_html().err());
+
+        #[cfg(feature = "native")]
+        errors.extend(self.check_image().err());
+
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(SnapshotError::Multiple(errors)),
+        }
+    }
+
+    fn check_html(&self) -> Result<(), SnapshotError> {
+        let html = render_html(self.app);
+        let path = self.path("html");
+        let new_path = self.path("new.html");
+
+        let expected = match std::fs::read_to_string(&path) {
+            Ok(expected) => Some(expected),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+
+        match expected {
+            Some(expected) if !self.update && expected != html => {
+                std::fs::write(&new_path, html)?;
+                Err(SnapshotError::HtmlMismatch {
+                    name: self.name.clone(),
+                    new: new_path,
+                })
+            }
+            Some(_) if !self.update => {
+                remove_stale(&new_path)?;
+                Ok(())
+            }
+            None if !self.update && self.fail_on_missing => {
+                std::fs::write(&new_path, html)?;
+                Err(SnapshotError::Missing {
+                    name: self.name.clone(),
+                    path,
+                    new: new_path,
+                })
+            }
+            _ => {
+                std::fs::write(&path, html)?;
+                remove_stale(&new_path)?;
+                Ok(())
+            }
+        }
+    }
+
+    #[cfg(feature = "native")]
+    fn check_image(&self) -> Result<(), SnapshotError> {
+        use crate::image::{diff_images, render_image, Image};
+
+        let actual = render_image(self.app, self.width, self.height, self.scale);
+        let path = self.path("png");
+        let new_path = self.path("new.png");
+        let diff_path = self.path("diff.png");
+
+        if !self.update && !path.exists() && self.fail_on_missing {
+            actual.write_png(&new_path)?;
+            return Err(SnapshotError::Missing {
+                name: self.name.clone(),
+                path,
+                new: new_path,
+            });
+        }
+
+        if self.update || !path.exists() {
+            actual.write_png(&path)?;
+            remove_stale(&new_path)?;
+            remove_stale(&diff_path)?;
+            return Ok(());
+        }
+
+        let expected = Image::read_png(&path)?;
+        if (expected.width, expected.height) != (actual.width, actual.height) {
+            actual.write_png(&new_path)?;
+            return Err(SnapshotError::SizeMismatch {
+                name: self.name.clone(),
+                expected: (expected.width, expected.height),
+                actual: (actual.width, actual.height),
+                new: new_path,
+            });
+        }
+
+        let (different, diff) = diff_images(&expected, &actual, self.threshold);
+        if different > self.max_diff_pixels {
+            actual.write_png(&new_path)?;
+            diff.write_png(&diff_path)?;
+            return Err(SnapshotError::ImageMismatch {
+                name: self.name.clone(),
+                different,
+                allowed: self.max_diff_pixels,
+                diff: diff_path,
+            });
+        }
+
+        remove_stale(&new_path)?;
+        remove_stale(&diff_path)?;
+        Ok(())
+    }
+
+    fn path(&self, extension: &str) -> PathBuf {
+        self.dir.join(format!("{}.{extension}", self.name))
+    }
+}
+
+/// Render a component to html with the same renderer as server side rendering.
+pub fn render_html(app: fn() -> Element) -> String {
+    let mut vdom = VirtualDom::new(app);
+    vdom.rebuild_in_place();
+    dioxus_ssr::render(&vdom)
+}
+
+/// Remove the output of a previous failed run
+fn remove_stale(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// An error that occurred while checking a [`Snapshot`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    /// The snapshot doesn't exist and the tests are running on CI
+    #[error(
+        "The snapshot of `{name}` doesn't exist at {}. Run the tests locally or set DIOXUS_UPDATE_SNAPSHOTS to create it. The new output was written to {}",
+        path.display(),
+        new.display()
+    )]
+    Missing {
+        /// The name of the snapshot
+        name: String,
+        /// The path the snapshot is expected at
+        path: PathBuf,
+        /// The path the new output was written to
+        new: PathBuf,
+    },
+
+    /// The html of the component doesn't match the snapshot
+    #[error(
+        "The html of `{name}` doesn't match the snapshot. The new html was written to {}",
+        new.display()
+    )]
+    HtmlMismatch {
+        /// The name of the snapshot
+        name: String,
+        /// The path the new html was written to
+        new: PathBuf,
+    },
+
+    /// The image of the component has a different size than the snapshot
+    #[error(
+        "The image of `{name}` is {actual:?} but the snapshot is {expected:?}. The new image was written to {}",
+        new.display()
+    )]
+    SizeMismatch {
+        /// The name of the snapshot
+        name: String,
+        /// The width and height of the snapshot
+        expected: (u32, u32),
+        /// The width and height of the new image
+        actual: (u32, u32),
+        /// The path the new image was written to
+        new: PathBuf,
+    },
+
+    /// Too many pixels of the image differ from the snapshot
+    #[error(
+        "{different} pixels of `{name}` differ from the snapshot, but only {allowed} are allowed. The differences were written to {}",
+        diff.display()
+    )]
+    ImageMismatch {
+        /// The name of the snapshot
+        name: String,
+        /// The number of pixels that differ
+        different: usize,
+        /// The number of pixels that were allowed to differ
+        allowed: usize,
+        /// The path the diff image was written to
+        diff: PathBuf,
+    },
+
+    /// More than one part of the snapshot failed, like both the html and the image not matching
+    #[error("{}", display_all(.0))]
+    Multiple(Vec<SnapshotError>),
+
+    /// The snapshot png isn't an 8 bit RGBA image
+    #[error("The png snapshot at {} must be an 8 bit RGBA image", .0.display())]
+    UnsupportedPng(PathBuf),
+
+    /// Failed to decode the png snapshot
+    #[cfg(feature = "native")]
+    #[error("Failed to decode the png snapshot: {0}")]
+    Decode(#[from] png::DecodingError),
+
+    /// Failed to encode the png snapshot
+    #[cfg(feature = "native")]
+    #[error("Failed to encode the png snapshot: {0}")]
+    Encode(#[from] png::EncodingError),
+
+    /// Failed to read or write a snapshot
+    #[error("Failed to read or write a snapshot: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+fn display_all(errors: &[SnapshotError]) -> String {
+    let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+    errors.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::prelude::{dioxus_elements, rsx};
+
+    fn save() -> Element {
+        rsx! { button { "Save" } }
+    }
+
+    fn delete() -> Element {
+        rsx! { button { "Delete everything" } }
+    }
+
+    fn snapshot(dir: &Path, app: fn() -> Element) -> Snapshot {
+        let mut snapshot = Snapshot::new("button", app)
+            .with_viewport(120, 40)
+            .with_dir(dir);
+        snapshot.update = false;
+        snapshot.fail_on_missing = false;
+        snapshot
+    }
+
+    #[test]
+    fn missing_snapshots_are_written_and_then_matched() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = snapshot(dir.path(), save);
+
+        snapshot.check().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("button.html")).unwrap(),
+            render_html(save)
+        );
+        #[cfg(feature = "native")]
+        assert!(dir.path().join("button.png").exists());
+
+        snapshot.check().unwrap();
+        assert!(!dir.path().join("button.new.html").exists());
+    }
+
+    #[test]
+    fn missing_snapshots_fail_on_ci() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut snapshot = snapshot(dir.path(), save);
+        snapshot.fail_on_missing = true;
+
+        let err = snapshot.check().unwrap_err();
+        assert!(!dir.path().join("button.html").exists());
+        assert!(dir.path().join("button.new.html").exists());
+
+        #[cfg(feature = "native")]
+        {
+            let SnapshotError::Multiple(errors) = err else {
+                panic!("expected the html and the image to be missing: {err}");
+            };
+            assert!(errors
+                .iter()
+                .all(|err| matches!(err, SnapshotError::Missing { .. })));
+            assert!(!dir.path().join("button.png").exists());
+        }
+        #[cfg(not(feature = "native"))]
+        assert!(matches!(err, SnapshotError::Missing { .. }));
+    }
+
+    #[test]
+    fn html_and_image_mismatches_are_both_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        snapshot(dir.path(), save).check().unwrap();
+
+        let err = snapshot(dir.path(), delete).check().unwrap_err();
+        assert!(dir.path().join("button.new.html").exists());
+
+        #[cfg(feature = "native")]
+        {
+            let SnapshotError::Multiple(errors) = err else {
+                panic!("expected the html and the image to differ: {err}");
+            };
+            assert!(matches!(errors[0], SnapshotError::HtmlMismatch { .. }));
+            assert!(matches!(errors[1], SnapshotError::ImageMismatch { .. }));
+            assert!(dir.path().join("button.diff.png").exists());
+        }
+        #[cfg(not(feature = "native"))]
+        assert!(matches!(err, SnapshotError::HtmlMismatch { .. }));
+    }
+}
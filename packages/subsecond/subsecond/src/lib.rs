@@ -108,6 +108,10 @@
 //! but for now it's up to the framework authors to handle this. For example, Dioxus apps simply throw
 //! out the old state and rebuild it from scratch.
 //!
+//! Dioxus apps can also opt into migrating state with the `hot-migrate` feature. Signals created with
+//! `use_hot_signal` are serialized with serde before a patch is applied and deserialized by the new code
+//! after it.
+//!
 //! ## Pointer versioning
 //!
 //! Currently, Subsecond does not "version" function pointers. We have plans to provide this metadata
@@ -81,6 +81,21 @@ impl FileEngine for WebFileEngine {
         }
     }
 
+    // read a slice of a file to bytes without reading the rest of the file
+    async fn read_file_chunk(&self, file: &str, offset: u64, len: usize) -> Option<Vec<u8>> {
+        let file = self.find(file)?;
+        let start = offset as f64;
+        let end = (offset.saturating_add(len as u64) as f64).min(file.size());
+        let blob = file.slice_with_f64_and_f64(start, end).ok()?;
+
+        // Every chunk is read through its own promise, so concurrent reads don't share the FileReader and no
+        // callback is leaked per chunk
+        let buffer = wasm_bindgen_futures::JsFuture::from(blob.array_buffer())
+            .await
+            .ok()?;
+        Some(Uint8Array::new(&buffer).to_vec())
+    }
+
     // read a file to string
     async fn read_file_to_string(&self, file: &str) -> Option<String> {
         let file = self.find(file)?;